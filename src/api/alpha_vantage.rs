@@ -0,0 +1,791 @@
+//! Alpha Vantage [`MarketDataProvider`](crate::api::provider::MarketDataProvider) implementation.
+//!
+//! Every call is `GET /query?function=…&apikey=…`. Throttled calls still answer HTTP 200, with a
+//! lone `"Note"` / `"Information"` string instead of data; those map to
+//! [`ProviderError::RateLimited`].
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+use urlencoding::encode;
+
+use crate::api::error::{ProviderError, ProviderResult};
+use crate::api::historical_query::HistoricalQuery;
+use crate::api::polygon::{us_eastern_offset_hours, us_equity_market_state_at};
+use crate::api::provider::MarketDataProvider;
+use crate::api::retry::execute_get_text_with_retry;
+use crate::config::Config;
+use crate::models::historical::{HistoricalData, HistoricalResponse};
+use crate::models::news::{NewsItem, NewsResponse, Publisher};
+use crate::models::search::{SymbolResult, SymbolSearchResponse};
+use crate::models::ticker::{TickerResponse, TickerResult};
+
+const BASE_URL: &str = "https://www.alphavantage.co";
+
+/// `TIME_SERIES_DAILY` without `outputsize=full` returns the latest 100 sessions; `full` is only
+/// requested when the range starts further back than that.
+const COMPACT_DAILY_DAYS: i64 = 140;
+
+fn enc(s: &str) -> String {
+    encode(s).into_owned()
+}
+
+fn alpha_vantage_key(config: &Config) -> ProviderResult<String> {
+    let key = config.effective_alpha_vantage_api_key();
+    if key.is_empty() {
+        return Err(ProviderError::ApiMessage(
+            "Alpha Vantage provider requires non-empty alpha_vantage_api_key in ~/.stockterm.json or STOCKTERM_ALPHA_VANTAGE_API_KEY"
+                .to_string(),
+        ));
+    }
+    Ok(key.into_owned())
+}
+
+/// Maps the in-body status strings Alpha Vantage sends with HTTP 200 to errors.
+fn check_alpha_vantage_body(body: &Value) -> ProviderResult<()> {
+    for field in ["Note", "Information"] {
+        if let Some(msg) = body.get(field).and_then(Value::as_str) {
+            tracing::warn!(target: "stockterm::fetch", provider = "alphavantage", msg, "throttled");
+            return Err(ProviderError::RateLimited { retry_after: None });
+        }
+    }
+    if let Some(msg) = body.get("Error Message").and_then(Value::as_str) {
+        return Err(ProviderError::ApiMessage(msg.to_string()));
+    }
+    Ok(())
+}
+
+/// `{base}/query?function=…&{params}&apikey=…`, status-checked and decoded into `T`.
+async fn fetch_query<T: DeserializeOwned>(
+    base: &str,
+    function: &str,
+    params: &[(&str, &str)],
+    key: &str,
+) -> ProviderResult<T> {
+    let mut url = format!("{base}/query?function={function}");
+    for (name, value) in params {
+        url.push_str(&format!("&{name}={}", enc(value)));
+    }
+    url.push_str(&format!("&apikey={}", enc(key)));
+    let text = execute_get_text_with_retry(&url).await?;
+    let body: Value = serde_json::from_str(&text)?;
+    check_alpha_vantage_body(&body)?;
+    serde_json::from_value(body).map_err(ProviderError::from)
+}
+
+/// Numbers arrive as strings, e.g. `"189.8400"`.
+fn num(s: &Option<String>) -> Option<f64> {
+    s.as_deref().and_then(|s| s.trim().parse().ok())
+}
+
+/// Epoch millis of a US/Eastern wall-clock time (Alpha Vantage series and session dates).
+fn eastern_millis(et: NaiveDateTime) -> u64 {
+    let utc = et - Duration::hours(us_eastern_offset_hours(et.and_utc()));
+    utc.and_utc().timestamp_millis().max(0) as u64
+}
+
+fn parse_series_time(key: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(key, "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .or_else(|| NaiveDate::parse_from_str(key, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0))
+}
+
+#[derive(Deserialize)]
+struct GlobalQuoteWire {
+    #[serde(rename = "Global Quote", default)]
+    quote: GlobalQuote,
+}
+
+#[derive(Deserialize, Default)]
+struct GlobalQuote {
+    #[serde(rename = "01. symbol")]
+    symbol: Option<String>,
+    #[serde(rename = "02. open")]
+    open: Option<String>,
+    #[serde(rename = "03. high")]
+    high: Option<String>,
+    #[serde(rename = "04. low")]
+    low: Option<String>,
+    #[serde(rename = "05. price")]
+    price: Option<String>,
+    #[serde(rename = "06. volume")]
+    volume: Option<String>,
+    #[serde(rename = "07. latest trading day")]
+    latest_trading_day: Option<String>,
+    #[serde(rename = "08. previous close")]
+    previous_close: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ExchangeRateWire {
+    #[serde(rename = "Realtime Currency Exchange Rate")]
+    rate: ExchangeRate,
+}
+
+#[derive(Deserialize)]
+struct ExchangeRate {
+    #[serde(rename = "5. Exchange Rate")]
+    rate: Option<String>,
+    #[serde(rename = "6. Last Refreshed")]
+    last_refreshed: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SeriesBar {
+    #[serde(rename = "1. open")]
+    open: Option<String>,
+    #[serde(rename = "2. high")]
+    high: Option<String>,
+    #[serde(rename = "3. low")]
+    low: Option<String>,
+    #[serde(rename = "4. close")]
+    close: Option<String>,
+    #[serde(rename = "5. volume")]
+    volume: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SymbolSearchWire {
+    #[serde(rename = "bestMatches", default)]
+    best_matches: Vec<SymbolMatch>,
+}
+
+#[derive(Deserialize)]
+struct SymbolMatch {
+    #[serde(rename = "1. symbol")]
+    symbol: String,
+    #[serde(rename = "2. name", default)]
+    name: String,
+    #[serde(rename = "3. type", default)]
+    type_: String,
+    #[serde(rename = "4. region", default)]
+    region: String,
+    #[serde(rename = "8. currency", default)]
+    currency: String,
+}
+
+#[derive(Deserialize)]
+struct NewsSentimentWire {
+    #[serde(default)]
+    feed: Vec<NewsFeedItem>,
+}
+
+#[derive(Deserialize)]
+struct NewsFeedItem {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    url: String,
+    /// `20240710T143000`.
+    #[serde(default)]
+    time_published: String,
+    #[serde(default)]
+    authors: Vec<String>,
+    summary: Option<String>,
+    banner_image: Option<String>,
+    #[serde(default)]
+    source: String,
+    #[serde(default)]
+    source_domain: String,
+    #[serde(default)]
+    topics: Vec<NewsTopic>,
+    #[serde(default)]
+    ticker_sentiment: Vec<NewsTickerSentiment>,
+}
+
+#[derive(Deserialize)]
+struct NewsTopic {
+    topic: String,
+}
+
+#[derive(Deserialize)]
+struct NewsTickerSentiment {
+    ticker: String,
+}
+
+/// `GBP/USD` style symbol from [`AlphaVantageProvider::fx_symbol`].
+fn fx_pair(symbol: &str) -> Option<(&str, &str)> {
+    let (from, to) = symbol.split_once('/')?;
+    (from.len() == 3 && to.len() == 3).then_some((from, to))
+}
+
+fn quote_response(symbol: &str, bar: TickerResult) -> TickerResponse {
+    TickerResponse {
+        ticker: symbol.to_string(),
+        results: vec![bar],
+        status: "OK".to_string(),
+        error: None,
+    }
+}
+
+/// `GLOBAL_QUOTE` (or `CURRENCY_EXCHANGE_RATE` for [`fx_pair`] symbols) against `base`.
+pub(crate) async fn alpha_vantage_quote_at(
+    symbol: &str,
+    key: &str,
+    base: &str,
+) -> ProviderResult<TickerResponse> {
+    if let Some((from, to)) = fx_pair(symbol) {
+        let wire: ExchangeRateWire = fetch_query(
+            base,
+            "CURRENCY_EXCHANGE_RATE",
+            &[("from_currency", from), ("to_currency", to)],
+            key,
+        )
+        .await?;
+        let rate = num(&wire.rate.rate)
+            .ok_or_else(|| ProviderError::ApiMessage(format!("No exchange rate for {symbol}")))?;
+        // `Last Refreshed` is UTC for currency pairs.
+        let t = wire
+            .rate
+            .last_refreshed
+            .as_deref()
+            .and_then(parse_series_time)
+            .map_or_else(|| Utc::now().timestamp_millis(), |d| d.and_utc().timestamp_millis());
+        return Ok(quote_response(
+            symbol,
+            TickerResult {
+                o: rate,
+                h: rate,
+                l: rate,
+                c: rate,
+                v: 0.0,
+                t: t.max(0) as u64,
+                prev_close: None,
+                market_state: None,
+                ext_price: None,
+                week52_low: None,
+                week52_high: None,
+                market_cap: None,
+                currency: Some(to.to_string()),
+            },
+        ));
+    }
+
+    let wire: GlobalQuoteWire = fetch_query(base, "GLOBAL_QUOTE", &[("symbol", symbol)], key).await?;
+    let q = wire.quote;
+    // Unknown tickers come back as `{"Global Quote": {}}`.
+    let close = num(&q.price)
+        .ok_or_else(|| ProviderError::ApiMessage(format!("No quote data for {symbol}")))?;
+    let t = q
+        .latest_trading_day
+        .as_deref()
+        .and_then(parse_series_time)
+        .map_or_else(|| Utc::now().timestamp_millis().max(0) as u64, eastern_millis);
+    Ok(quote_response(
+        q.symbol.as_deref().unwrap_or(symbol),
+        TickerResult {
+            o: num(&q.open).unwrap_or(close),
+            h: num(&q.high).unwrap_or(close),
+            l: num(&q.low).unwrap_or(close),
+            c: close,
+            v: num(&q.volume).unwrap_or(0.0),
+            t,
+            prev_close: num(&q.previous_close),
+            market_state: Some(us_equity_market_state_at(Utc::now())),
+            ext_price: None,
+            week52_low: None,
+            week52_high: None,
+            market_cap: None,
+            currency: None,
+        },
+    ))
+}
+
+/// `TIME_SERIES_INTRADAY` interval for a Polygon-style minute multiplier.
+fn intraday_interval(multiplier: u32) -> &'static str {
+    match multiplier {
+        1 => "1min",
+        15 => "15min",
+        30 => "30min",
+        60 => "60min",
+        _ => "5min",
+    }
+}
+
+fn daily_output_size(from: &str) -> &'static str {
+    let cutoff = (Local::now() - Duration::days(COMPACT_DAILY_DAYS)).format("%Y-%m-%d").to_string();
+    if from < cutoff.as_str() {
+        "full"
+    } else {
+        "compact"
+    }
+}
+
+/// Bars of the response's `Time Series …` object dated `from..=to`, oldest first.
+fn series_bars(body: &HashMap<String, Value>, from: &str, to: &str) -> ProviderResult<Vec<HistoricalData>> {
+    let series = body
+        .iter()
+        .find(|(k, _)| k.contains("Time Series"))
+        .map(|(_, v)| v)
+        .ok_or_else(|| ProviderError::ApiMessage("No time series in response".to_string()))?;
+    let bars: HashMap<String, SeriesBar> = serde_json::from_value(series.clone())?;
+    let mut out: Vec<HistoricalData> = bars
+        .into_iter()
+        .filter(|(when, _)| {
+            let day = when.get(..10).unwrap_or(when);
+            day >= from && day <= to
+        })
+        .filter_map(|(when, bar)| {
+            let c = num(&bar.close)?;
+            let o = num(&bar.open).unwrap_or(c);
+            let h = num(&bar.high).unwrap_or(c);
+            let l = num(&bar.low).unwrap_or(c);
+            Some(HistoricalData {
+                o,
+                h,
+                l,
+                c,
+                v: num(&bar.volume).unwrap_or(0.0),
+                t: eastern_millis(parse_series_time(&when)?),
+                vw: (o + h + l + c) / 4.0,
+                n: None,
+            })
+        })
+        .collect();
+    out.sort_by_key(|b| b.t);
+    Ok(out)
+}
+
+/// `TIME_SERIES_INTRADAY` / `_DAILY` / `_WEEKLY` / `_MONTHLY` by `query.polygon_timespan`.
+pub(crate) async fn alpha_vantage_historical_at(
+    symbol: &str,
+    query: &HistoricalQuery<'_>,
+    key: &str,
+    base: &str,
+) -> ProviderResult<HistoricalResponse> {
+    let (function, extra): (&str, Vec<(&str, &str)>) = match query.polygon_timespan {
+        "minute" | "hour" => {
+            let multiplier = if query.polygon_timespan == "hour" {
+                60
+            } else {
+                query.polygon_multiplier
+            };
+            (
+                "TIME_SERIES_INTRADAY",
+                vec![("interval", intraday_interval(multiplier)), ("outputsize", "full")],
+            )
+        }
+        "week" => ("TIME_SERIES_WEEKLY", Vec::new()),
+        "month" => ("TIME_SERIES_MONTHLY", Vec::new()),
+        _ => ("TIME_SERIES_DAILY", vec![("outputsize", daily_output_size(query.from))]),
+    };
+    let mut params = vec![("symbol", symbol)];
+    params.extend(extra);
+    let body: HashMap<String, Value> = fetch_query(base, function, &params, key).await?;
+    let results = series_bars(&body, query.from, query.to)?;
+    let count = results.len() as u32;
+    Ok(HistoricalResponse {
+        ticker: symbol.to_string(),
+        results,
+        status: "OK".to_string(),
+        request_id: String::new(),
+        count,
+    })
+}
+
+/// `SYMBOL_SEARCH` against `base`.
+pub(crate) async fn alpha_vantage_search_at(
+    query: &str,
+    key: &str,
+    base: &str,
+) -> ProviderResult<SymbolSearchResponse> {
+    let wire: SymbolSearchWire = fetch_query(base, "SYMBOL_SEARCH", &[("keywords", query)], key).await?;
+    let results: Vec<SymbolResult> = wire
+        .best_matches
+        .into_iter()
+        .map(|m| SymbolResult {
+            ticker: m.symbol,
+            name: m.name,
+            locale: if m.region == "United States" { "us" } else { "global" }.to_string(),
+            market: m.region.clone(),
+            primary_exchange: m.region,
+            type_: m.type_,
+            active: true,
+            currency_name: m.currency,
+            cik: None,
+            composite_figi: None,
+            share_class_figi: None,
+            last_updated_utc: String::new(),
+        })
+        .collect();
+    Ok(SymbolSearchResponse {
+        status: "OK".to_string(),
+        count: results.len() as u32,
+        results,
+    })
+}
+
+fn map_news_item(item: NewsFeedItem, symbol: &str) -> Option<NewsItem> {
+    if item.title.is_empty() {
+        return None;
+    }
+    let published_utc = NaiveDateTime::parse_from_str(&item.time_published, "%Y%m%dT%H%M%S")
+        .map(|d| d.and_utc().to_rfc3339())
+        .unwrap_or_default();
+    let mut tickers: Vec<String> = item.ticker_sentiment.into_iter().map(|t| t.ticker).collect();
+    if tickers.is_empty() {
+        tickers.push(symbol.to_uppercase());
+    }
+    Some(NewsItem {
+        id: if item.url.is_empty() {
+            format!("{}{}", item.title, item.time_published)
+        } else {
+            item.url.clone()
+        },
+        publisher: Publisher {
+            name: item.source,
+            homepage_url: if item.source_domain.is_empty() {
+                String::new()
+            } else {
+                format!("https://{}", item.source_domain)
+            },
+            logo_url: String::new(),
+            favicon_url: String::new(),
+        },
+        title: item.title,
+        author: (!item.authors.is_empty()).then(|| item.authors.join(", ")),
+        published_utc,
+        article_url: item.url,
+        tickers,
+        amp_url: None,
+        image_url: item.banner_image.filter(|u| !u.is_empty()),
+        description: item.summary.filter(|s| !s.is_empty()),
+        keywords: item.topics.into_iter().map(|t| t.topic).collect(),
+    })
+}
+
+/// `NEWS_SENTIMENT` for one ticker against `base`.
+pub(crate) async fn alpha_vantage_news_at(
+    symbol: &str,
+    key: &str,
+    base: &str,
+) -> ProviderResult<NewsResponse> {
+    let wire: NewsSentimentWire =
+        fetch_query(base, "NEWS_SENTIMENT", &[("tickers", symbol), ("sort", "LATEST")], key).await?;
+    let results: Vec<NewsItem> = wire
+        .feed
+        .into_iter()
+        .filter_map(|item| map_news_item(item, symbol))
+        .collect();
+    Ok(NewsResponse {
+        status: "OK".to_string(),
+        count: results.len() as u32,
+        results,
+    })
+}
+
+pub struct AlphaVantageProvider;
+
+#[async_trait]
+impl MarketDataProvider for AlphaVantageProvider {
+    /// `GLOBAL_QUOTE`: the latest session's OHLCV plus previous close; the session state comes
+    /// from the US/Eastern clock as with Polygon.
+    async fn get_quote(&self, symbol: &str, config: &Config) -> ProviderResult<TickerResponse> {
+        let key = alpha_vantage_key(config)?;
+        alpha_vantage_quote_at(symbol, &key, BASE_URL).await
+    }
+
+    async fn get_historical(
+        &self,
+        symbol: &str,
+        query: &HistoricalQuery<'_>,
+        config: &Config,
+    ) -> ProviderResult<HistoricalResponse> {
+        let key = alpha_vantage_key(config)?;
+        alpha_vantage_historical_at(symbol, query, &key, BASE_URL).await
+    }
+
+    async fn search_symbols(&self, query: &str, config: &Config) -> ProviderResult<SymbolSearchResponse> {
+        let key = alpha_vantage_key(config)?;
+        alpha_vantage_search_at(query, &key, BASE_URL).await
+    }
+
+    async fn get_news(&self, symbol: &str, config: &Config) -> ProviderResult<NewsResponse> {
+        let key = alpha_vantage_key(config)?;
+        alpha_vantage_news_at(symbol, &key, BASE_URL).await
+    }
+
+    /// `GBP/USD`, quoted through `CURRENCY_EXCHANGE_RATE`.
+    fn fx_symbol(&self, from: &str, to: &str) -> String {
+        format!("{from}/{to}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn throttle_and_error_bodies_map_to_provider_errors() {
+        let note = serde_json::json!({"Note": "Thank you for using Alpha Vantage! Our standard API call frequency is 5 calls per minute"});
+        let info = serde_json::json!({"Information": "We have detected your API key as ... 25 requests per day"});
+        let err = serde_json::json!({"Error Message": "Invalid API call."});
+        assert!(matches!(
+            check_alpha_vantage_body(&note),
+            Err(ProviderError::RateLimited { retry_after: None })
+        ));
+        assert!(matches!(
+            check_alpha_vantage_body(&info),
+            Err(ProviderError::RateLimited { retry_after: None })
+        ));
+        assert!(matches!(
+            check_alpha_vantage_body(&err),
+            Err(ProviderError::ApiMessage(m)) if m == "Invalid API call."
+        ));
+        assert!(check_alpha_vantage_body(&serde_json::json!({"Global Quote": {}})).is_ok());
+    }
+
+    #[test]
+    fn series_times_are_us_eastern() {
+        // 2024-07-10 09:30 EDT = 13:30 UTC; 2024-01-10 00:00 EST = 05:00 UTC.
+        let summer = parse_series_time("2024-07-10 09:30:00").unwrap();
+        let winter = parse_series_time("2024-01-10").unwrap();
+        let ms = |y, m, d, h, min| Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap().timestamp_millis() as u64;
+        assert_eq!(eastern_millis(summer), ms(2024, 7, 10, 13, 30));
+        assert_eq!(eastern_millis(winter), ms(2024, 1, 10, 5, 0));
+    }
+
+    #[test]
+    fn fx_pairs_only_match_iso_codes() {
+        assert_eq!(fx_pair("GBP/USD"), Some(("GBP", "USD")));
+        assert_eq!(fx_pair("BRK/B"), None);
+        assert_eq!(fx_pair("AAPL"), None);
+    }
+}
+
+#[cfg(test)]
+mod wiremock_tests {
+    use super::*;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const KEY: &str = "demo";
+
+    fn ensure_http_client() {
+        crate::api::http::ensure_shared_client_for_tests();
+    }
+
+    async fn mount(srv: &MockServer, function: &str, body: &str) {
+        Mock::given(method("GET"))
+            .and(path("/query"))
+            .and(query_param("function", function))
+            .and(query_param("apikey", KEY))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .expect(1)
+            .mount(srv)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn global_quote_maps_price_and_previous_close() {
+        ensure_http_client();
+        let srv = MockServer::start().await;
+        mount(
+            &srv,
+            "GLOBAL_QUOTE",
+            r#"{"Global Quote": {
+                "01. symbol": "IBM", "02. open": "168.0000", "03. high": "170.5000",
+                "04. low": "167.2500", "05. price": "169.9000", "06. volume": "4123456",
+                "07. latest trading day": "2024-07-10", "08. previous close": "167.7500",
+                "09. change": "2.1500", "10. change percent": "1.2817%"
+            }}"#,
+        )
+        .await;
+
+        let tr = alpha_vantage_quote_at("IBM", KEY, &srv.uri()).await.expect("quote");
+        assert_eq!(tr.ticker, "IBM");
+        let bar = tr.latest_result().expect("bar");
+        assert!((bar.c - 169.9).abs() < 1e-9);
+        assert_eq!(bar.prev_close, Some(167.75));
+        assert!((bar.v - 4_123_456.0).abs() < 1e-9);
+        assert!(bar.market_state.is_some());
+    }
+
+    #[tokio::test]
+    async fn empty_global_quote_is_an_api_message() {
+        ensure_http_client();
+        let srv = MockServer::start().await;
+        mount(&srv, "GLOBAL_QUOTE", r#"{"Global Quote": {}}"#).await;
+
+        let err = alpha_vantage_quote_at("NOPE", KEY, &srv.uri()).await.unwrap_err();
+        assert!(matches!(err, ProviderError::ApiMessage(m) if m.contains("NOPE")));
+    }
+
+    #[tokio::test]
+    async fn note_body_is_rate_limited() {
+        ensure_http_client();
+        let srv = MockServer::start().await;
+        mount(
+            &srv,
+            "GLOBAL_QUOTE",
+            r#"{"Note": "Thank you for using Alpha Vantage! Our standard API call frequency is 5 calls per minute and 500 calls per day."}"#,
+        )
+        .await;
+
+        let err = alpha_vantage_quote_at("IBM", KEY, &srv.uri()).await.unwrap_err();
+        assert!(matches!(err, ProviderError::RateLimited { retry_after: None }));
+    }
+
+    #[tokio::test]
+    async fn information_body_on_search_is_rate_limited() {
+        ensure_http_client();
+        let srv = MockServer::start().await;
+        mount(
+            &srv,
+            "SYMBOL_SEARCH",
+            r#"{"Information": "Thank you for using Alpha Vantage! Our standard API rate limit is 25 requests per day."}"#,
+        )
+        .await;
+
+        let err = alpha_vantage_search_at("ibm", KEY, &srv.uri()).await.unwrap_err();
+        assert!(matches!(err, ProviderError::RateLimited { .. }));
+    }
+
+    #[tokio::test]
+    async fn currency_pair_uses_exchange_rate() {
+        ensure_http_client();
+        let srv = MockServer::start().await;
+        mount(
+            &srv,
+            "CURRENCY_EXCHANGE_RATE",
+            r#"{"Realtime Currency Exchange Rate": {
+                "1. From_Currency Code": "GBP", "3. To_Currency Code": "USD",
+                "5. Exchange Rate": "1.28150000", "6. Last Refreshed": "2024-07-10 14:00:01",
+                "7. Time Zone": "UTC"
+            }}"#,
+        )
+        .await;
+
+        let tr = alpha_vantage_quote_at("GBP/USD", KEY, &srv.uri()).await.expect("fx");
+        let bar = tr.latest_result().expect("bar");
+        assert!((bar.c - 1.2815).abs() < 1e-9);
+        assert_eq!(bar.currency.as_deref(), Some("USD"));
+    }
+
+    #[tokio::test]
+    async fn daily_series_is_filtered_to_range_and_sorted() {
+        ensure_http_client();
+        let srv = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/query"))
+            .and(query_param("function", "TIME_SERIES_DAILY"))
+            .and(query_param("symbol", "IBM"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"Meta Data": {"2. Symbol": "IBM"}, "Time Series (Daily)": {
+                    "2024-07-11": {"1. open": "4", "2. high": "4", "3. low": "4", "4. close": "4", "5. volume": "40"},
+                    "2024-07-10": {"1. open": "3", "2. high": "3", "3. low": "3", "4. close": "3", "5. volume": "30"},
+                    "2024-07-08": {"1. open": "1", "2. high": "1", "3. low": "1", "4. close": "1", "5. volume": "10"},
+                    "2024-07-09": {"1. open": "2", "2. high": "2", "3. low": "2", "4. close": "2", "5. volume": "20"}
+                }}"#,
+            ))
+            .expect(1)
+            .mount(&srv)
+            .await;
+
+        let q = HistoricalQuery {
+            from: "2024-07-09",
+            to: "2024-07-10",
+            bar_interval: "1d",
+            yahoo_range: None,
+            polygon_multiplier: 1,
+            polygon_timespan: "day",
+        };
+        let resp = alpha_vantage_historical_at("IBM", &q, KEY, &srv.uri())
+            .await
+            .expect("series");
+        let closes: Vec<f64> = resp.results.iter().map(|b| b.c).collect();
+        assert_eq!(closes, [2.0, 3.0]);
+        assert_eq!(resp.count, 2);
+    }
+
+    #[tokio::test]
+    async fn intraday_requests_matching_interval() {
+        ensure_http_client();
+        let srv = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/query"))
+            .and(query_param("function", "TIME_SERIES_INTRADAY"))
+            .and(query_param("interval", "30min"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"Time Series (30min)": {
+                    "2024-07-10 10:00:00": {"1. open": "1", "2. high": "2", "3. low": "1", "4. close": "2", "5. volume": "5"},
+                    "2024-07-10 09:30:00": {"1. open": "1", "2. high": "1", "3. low": "1", "4. close": "1", "5. volume": "5"}
+                }}"#,
+            ))
+            .expect(1)
+            .mount(&srv)
+            .await;
+
+        let q = HistoricalQuery {
+            from: "2024-07-10",
+            to: "2024-07-10",
+            bar_interval: "30m",
+            yahoo_range: None,
+            polygon_multiplier: 30,
+            polygon_timespan: "minute",
+        };
+        let resp = alpha_vantage_historical_at("IBM", &q, KEY, &srv.uri())
+            .await
+            .expect("series");
+        assert_eq!(resp.results.len(), 2);
+        assert!(resp.results[0].t < resp.results[1].t);
+    }
+
+    #[tokio::test]
+    async fn symbol_search_maps_best_matches() {
+        ensure_http_client();
+        let srv = MockServer::start().await;
+        mount(
+            &srv,
+            "SYMBOL_SEARCH",
+            r#"{"bestMatches": [
+                {"1. symbol": "TSCO.LON", "2. name": "Tesco PLC", "3. type": "Equity",
+                 "4. region": "United Kingdom", "8. currency": "GBX", "9. matchScore": "0.7273"},
+                {"1. symbol": "TSCDY", "2. name": "Tesco plc", "3. type": "Equity",
+                 "4. region": "United States", "8. currency": "USD", "9. matchScore": "0.7143"}
+            ]}"#,
+        )
+        .await;
+
+        let resp = alpha_vantage_search_at("tesco", KEY, &srv.uri()).await.expect("search");
+        assert_eq!(resp.count, 2);
+        assert_eq!(resp.results[0].ticker, "TSCO.LON");
+        assert_eq!(resp.results[0].locale, "global");
+        assert_eq!(resp.results[1].locale, "us");
+        assert_eq!(resp.results[1].currency_name, "USD");
+    }
+
+    #[tokio::test]
+    async fn news_sentiment_maps_feed_items() {
+        ensure_http_client();
+        let srv = MockServer::start().await;
+        mount(
+            &srv,
+            "NEWS_SENTIMENT",
+            r#"{"items": "2", "feed": [
+                {"title": "IBM beats estimates", "url": "https://example.com/ibm",
+                 "time_published": "20240710T143000", "authors": ["A. Writer"],
+                 "summary": "Quarterly results.", "banner_image": "", "source": "Example News",
+                 "source_domain": "example.com", "topics": [{"topic": "Earnings"}],
+                 "ticker_sentiment": [{"ticker": "IBM"}]},
+                {"title": "", "url": "https://example.com/empty", "time_published": "20240710T120000"}
+            ]}"#,
+        )
+        .await;
+
+        let resp = alpha_vantage_news_at("IBM", KEY, &srv.uri()).await.expect("news");
+        assert_eq!(resp.count, 1);
+        let item = &resp.results[0];
+        assert_eq!(item.publisher.name, "Example News");
+        assert_eq!(item.author.as_deref(), Some("A. Writer"));
+        assert_eq!(item.published_utc, "2024-07-10T14:30:00+00:00");
+        assert_eq!(item.image_url, None);
+        assert_eq!(item.tickers, ["IBM"]);
+        assert_eq!(item.keywords, ["Earnings"]);
+    }
+}
@@ -0,0 +1,565 @@
+//! Ordered provider chain with per-backend circuit breakers (`fallback_providers`).
+//!
+//! A call goes to the first backend whose breaker is closed. Rate limits, timeouts and 5xx
+//! responses move it on to the next backend. Other failures (unknown symbol, bad JSON) come
+//! straight back, because another backend would not do better.
+//!
+//! Which backend answered is reported through a [`ServedBackend`] scope, so each fetch knows
+//! the source of its own data.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_util::future::BoxFuture;
+use tokio::time::Instant;
+
+use crate::api::error::{ProviderError, ProviderResult};
+use crate::api::historical_query::HistoricalQuery;
+use crate::api::provider::MarketDataProvider;
+use crate::config::{Config, MarketProviderKind};
+use crate::models::historical::HistoricalResponse;
+use crate::models::news::NewsResponse;
+use crate::models::search::SymbolSearchResponse;
+use crate::models::ticker::TickerResponse;
+
+/// Consecutive failover errors that open a backend's breaker.
+pub const BREAKER_FAILURE_THRESHOLD: u32 = 3;
+/// How long an open breaker skips its backend before letting a trial call through.
+pub const BREAKER_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Errors worth retrying on the next backend: [`ProviderError::RateLimited`],
+/// [`ProviderError::Timeout`] and 5xx [`ProviderError::Http`]. A 429 that outlived the HTTP
+/// retries arrives as `Http` and counts too.
+pub fn is_failover_error(err: &ProviderError) -> bool {
+    match err {
+        ProviderError::RateLimited { .. } | ProviderError::Timeout => true,
+        ProviderError::Http { status, .. } => *status == 429 || *status >= 500,
+        _ => false,
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Breaker {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+tokio::task_local! {
+    static SERVED: ServedBackend;
+}
+
+/// Backend that answered the latest successful [`FailoverProvider`] call made inside
+/// [`scope`](Self::scope). Clones share the slot, so a fetch that spawns per-symbol tasks
+/// re-enters the scope in each of them.
+#[derive(Debug, Default, Clone)]
+pub struct ServedBackend(Arc<Mutex<Option<MarketProviderKind>>>);
+
+impl ServedBackend {
+    /// Runs `fut` with its failover calls reporting here.
+    pub async fn scope<F: Future>(&self, fut: F) -> F::Output {
+        SERVED.scope(self.clone(), fut).await
+    }
+
+    /// The enclosing scope's slot, or a detached one outside any scope.
+    pub fn current() -> ServedBackend {
+        SERVED.try_with(Clone::clone).unwrap_or_default()
+    }
+
+    /// `None` until a failover call in the scope succeeds (and always for single-backend chains).
+    pub fn get(&self) -> Option<MarketProviderKind> {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn set(&self, kind: MarketProviderKind) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = Some(kind);
+    }
+}
+
+#[derive(Debug, Default)]
+struct HealthState {
+    breakers: HashMap<MarketProviderKind, Breaker>,
+}
+
+/// Circuit breakers per backend.
+///
+/// [`market_provider_for_config`](crate::api::provider::market_provider_for_config) builds a new
+/// [`FailoverProvider`] per fetch, so they all share [`BackendHealth::shared`].
+#[derive(Debug, Default)]
+pub struct BackendHealth {
+    state: Mutex<HealthState>,
+}
+
+impl BackendHealth {
+    /// Process-wide registry.
+    pub fn shared() -> Arc<BackendHealth> {
+        static SHARED: OnceLock<Arc<BackendHealth>> = OnceLock::new();
+        SHARED.get_or_init(Default::default).clone()
+    }
+
+    fn state(&self) -> MutexGuard<'_, HealthState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Closed, or open with the cooldown over (half-open: calls go through as trials, and one more
+    /// failure reopens it).
+    pub fn is_available(&self, kind: MarketProviderKind, now: Instant) -> bool {
+        self.state()
+            .breakers
+            .get(&kind)
+            .and_then(|b| b.open_until)
+            .is_none_or(|until| now >= until)
+    }
+
+    /// Closes `kind`'s breaker.
+    pub fn record_success(&self, kind: MarketProviderKind) {
+        self.state().breakers.insert(kind, Breaker::default());
+    }
+
+    /// Counts a failover error; the [`BREAKER_FAILURE_THRESHOLD`]th in a row opens the breaker.
+    pub fn record_failure(&self, kind: MarketProviderKind, now: Instant) {
+        let mut state = self.state();
+        let breaker = state.breakers.entry(kind).or_default();
+        breaker.consecutive_failures += 1;
+        if breaker.consecutive_failures >= BREAKER_FAILURE_THRESHOLD {
+            breaker.open_until = Some(now + BREAKER_COOLDOWN);
+        }
+    }
+}
+
+/// `(from, to)` ISO codes of an FX quote symbol in any backend's
+/// [`fx_symbol`](MarketDataProvider::fx_symbol) spelling (`GBPUSD=X`, `C:GBPUSD`, `GBP/USD`).
+fn fx_pair_of(symbol: &str) -> Option<(&str, &str)> {
+    let joined = symbol
+        .strip_suffix("=X")
+        .or_else(|| symbol.strip_prefix("C:"));
+    let (from, to) = match joined {
+        Some(pair) if pair.len() == 6 && pair.is_char_boundary(3) => pair.split_at(3),
+        Some(_) => return None,
+        None => symbol.split_once('/')?,
+    };
+    let iso = |s: &str| s.len() == 3 && s.bytes().all(|b| b.is_ascii_uppercase());
+    (iso(from) && iso(to)).then_some((from, to))
+}
+
+/// [`MarketDataProvider`] over an ordered backend list (primary first).
+pub struct FailoverProvider {
+    backends: Vec<(MarketProviderKind, Arc<dyn MarketDataProvider + Send + Sync>)>,
+    health: Arc<BackendHealth>,
+}
+
+impl FailoverProvider {
+    /// `backends` must not be empty; the first entry is the primary.
+    pub fn new(
+        backends: Vec<(MarketProviderKind, Arc<dyn MarketDataProvider + Send + Sync>)>,
+        health: Arc<BackendHealth>,
+    ) -> Self {
+        assert!(!backends.is_empty(), "FailoverProvider needs at least one backend");
+        Self { backends, health }
+    }
+
+    /// `symbol` as backend `idx` spells it: FX symbols in the primary's spelling are rewritten.
+    fn symbol_for(&self, idx: usize, symbol: &str) -> Option<String> {
+        if idx == 0 {
+            return None;
+        }
+        let (from, to) = fx_pair_of(symbol)?;
+        (self.backends[0].1.fx_symbol(from, to) == symbol)
+            .then(|| self.backends[idx].1.fx_symbol(from, to))
+            .filter(|s| s != symbol)
+    }
+
+    /// Runs `op` on each available backend in order until one succeeds or fails with a
+    /// non-failover error. When every breaker is open, all backends are tried anyway. If all
+    /// fail, the primary's error (the first one seen) is returned.
+    async fn call<'a, T: Send>(
+        &'a self,
+        what: &'static str,
+        op: impl Fn(usize, &'a (dyn MarketDataProvider + Send + Sync)) -> BoxFuture<'a, ProviderResult<T>>
+            + Send,
+    ) -> ProviderResult<T> {
+        let now = Instant::now();
+        let mut order: Vec<usize> = (0..self.backends.len())
+            .filter(|&i| self.health.is_available(self.backends[i].0, now))
+            .collect();
+        if order.is_empty() {
+            order = (0..self.backends.len()).collect();
+        }
+
+        let mut first_err = None;
+        for idx in order {
+            let (kind, backend) = &self.backends[idx];
+            match op(idx, backend.as_ref()).await {
+                Ok(v) => {
+                    self.health.record_success(*kind);
+                    let _ = SERVED.try_with(|served| served.set(*kind));
+                    return Ok(v);
+                }
+                Err(e) if is_failover_error(&e) => {
+                    tracing::warn!(
+                        target: "stockterm::fetch",
+                        provider = kind.as_str(),
+                        call = what,
+                        error = %e,
+                        "trying next provider"
+                    );
+                    self.health.record_failure(*kind, Instant::now());
+                    first_err.get_or_insert(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(first_err.expect("at least one backend was tried"))
+    }
+}
+
+#[async_trait]
+impl MarketDataProvider for FailoverProvider {
+    async fn get_quote(&self, symbol: &str, config: &Config) -> ProviderResult<TickerResponse> {
+        self.call("quote", |idx, backend| {
+            let translated = self.symbol_for(idx, symbol);
+            Box::pin(async move {
+                let Some(sym) = translated else {
+                    return backend.get_quote(symbol, config).await;
+                };
+                let mut resp = backend.get_quote(&sym, config).await?;
+                resp.ticker = symbol.to_string();
+                Ok(resp)
+            })
+        })
+        .await
+    }
+
+    async fn get_historical(
+        &self,
+        symbol: &str,
+        query: &HistoricalQuery<'_>,
+        config: &Config,
+    ) -> ProviderResult<HistoricalResponse> {
+        self.call("historical", |_, backend| backend.get_historical(symbol, query, config))
+            .await
+    }
+
+    async fn search_symbols(&self, query: &str, config: &Config) -> ProviderResult<SymbolSearchResponse> {
+        self.call("search", |_, backend| backend.search_symbols(query, config))
+            .await
+    }
+
+    async fn get_news(&self, symbol: &str, config: &Config) -> ProviderResult<NewsResponse> {
+        self.call("news", |_, backend| backend.get_news(symbol, config))
+            .await
+    }
+
+    /// The primary's spelling; [`get_quote`](Self::get_quote) rewrites it for fallbacks.
+    fn fx_symbol(&self, from: &str, to: &str) -> String {
+        self.backends[0].1.fx_symbol(from, to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::models::ticker::TickerResult;
+
+    /// Answers quotes with `price` and news with an empty list, or fails with `err` while it is
+    /// set. History and search are not faked and answer with a non-failover error.
+    struct FakeBackend {
+        price: f64,
+        err: Mutex<Option<ProviderError>>,
+        calls: AtomicUsize,
+        seen: Mutex<Vec<String>>,
+        fx_style: fn(&str, &str) -> String,
+    }
+
+    impl FakeBackend {
+        fn new(price: f64, err: Option<ProviderError>) -> Arc<Self> {
+            Arc::new(Self {
+                price,
+                err: Mutex::new(err),
+                calls: AtomicUsize::new(0),
+                seen: Mutex::new(Vec::new()),
+                fx_style: crate::models::currency::yahoo_fx_symbol,
+            })
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+
+        fn fail(&self) -> ProviderResult<()> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            match self.err.lock().unwrap().clone() {
+                Some(e) => Err(e),
+                None => Ok(()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl MarketDataProvider for FakeBackend {
+        async fn get_quote(&self, symbol: &str, _config: &Config) -> ProviderResult<TickerResponse> {
+            self.seen.lock().unwrap().push(symbol.to_string());
+            self.fail()?;
+            Ok(TickerResponse {
+                ticker: symbol.to_string(),
+                results: vec![TickerResult {
+                    o: self.price,
+                    h: self.price,
+                    l: self.price,
+                    c: self.price,
+                    v: 0.0,
+                    t: 0,
+                    prev_close: None,
+                    market_state: None,
+                    ext_price: None,
+                    week52_low: None,
+                    week52_high: None,
+                    market_cap: None,
+                    currency: None,
+                }],
+                status: "OK".into(),
+                error: None,
+            })
+        }
+
+        async fn get_historical(
+            &self,
+            _symbol: &str,
+            _query: &HistoricalQuery<'_>,
+            _config: &Config,
+        ) -> ProviderResult<HistoricalResponse> {
+            self.fail()?;
+            Err(ProviderError::ApiMessage("FakeBackend has no history".into()))
+        }
+
+        async fn search_symbols(&self, _query: &str, _config: &Config) -> ProviderResult<SymbolSearchResponse> {
+            self.fail()?;
+            Err(ProviderError::ApiMessage("FakeBackend has no search".into()))
+        }
+
+        async fn get_news(&self, _symbol: &str, _config: &Config) -> ProviderResult<NewsResponse> {
+            self.fail()?;
+            Ok(NewsResponse {
+                status: "OK".into(),
+                count: 0,
+                results: Vec::new(),
+            })
+        }
+
+        fn fx_symbol(&self, from: &str, to: &str) -> String {
+            (self.fx_style)(from, to)
+        }
+    }
+
+    fn chain(
+        backends: &[(MarketProviderKind, &Arc<FakeBackend>)],
+    ) -> (FailoverProvider, Arc<BackendHealth>) {
+        let health = Arc::new(BackendHealth::default());
+        let backends = backends
+            .iter()
+            .map(|(k, b)| (*k, Arc::clone(*b) as Arc<dyn MarketDataProvider + Send + Sync>))
+            .collect();
+        (FailoverProvider::new(backends, health.clone()), health)
+    }
+
+    fn http(status: u16) -> ProviderError {
+        ProviderError::Http {
+            status,
+            url: "https://example.test/q".into(),
+            body_snippet: None,
+        }
+    }
+
+    #[test]
+    fn failover_errors_are_rate_limits_timeouts_and_5xx() {
+        assert!(is_failover_error(&ProviderError::RateLimited { retry_after: None }));
+        assert!(is_failover_error(&ProviderError::Timeout));
+        assert!(is_failover_error(&http(503)));
+        assert!(is_failover_error(&http(429)));
+        assert!(!is_failover_error(&http(404)));
+        assert!(!is_failover_error(&ProviderError::ApiMessage("Unknown symbol".into())));
+        assert!(!is_failover_error(&ProviderError::Transport("dns".into())));
+    }
+
+    #[test]
+    fn fx_pairs_parse_every_backend_spelling() {
+        assert_eq!(fx_pair_of("GBPUSD=X"), Some(("GBP", "USD")));
+        assert_eq!(fx_pair_of("C:GBPUSD"), Some(("GBP", "USD")));
+        assert_eq!(fx_pair_of("GBP/USD"), Some(("GBP", "USD")));
+        assert_eq!(fx_pair_of("BRK/B"), None);
+        assert_eq!(fx_pair_of("AAPL"), None);
+    }
+
+    #[tokio::test]
+    async fn retryable_errors_move_to_the_next_backend() {
+        let primary = FakeBackend::new(1.0, Some(ProviderError::RateLimited { retry_after: None }));
+        let second = FakeBackend::new(2.0, Some(http(502)));
+        let third = FakeBackend::new(3.0, None);
+        let (provider, _) = chain(&[
+            (MarketProviderKind::Polygon, &primary),
+            (MarketProviderKind::Finnhub, &second),
+            (MarketProviderKind::Yahoo, &third),
+        ]);
+        let cfg = Config::default();
+
+        let served = ServedBackend::default();
+        let resp = served.scope(provider.get_quote("AAPL", &cfg)).await.unwrap();
+        assert_eq!(resp.latest_result().unwrap().c, 3.0);
+        assert_eq!((primary.calls(), second.calls(), third.calls()), (1, 1, 1));
+        assert_eq!(served.get(), Some(MarketProviderKind::Yahoo));
+
+        *primary.err.lock().unwrap() = None;
+        let served = ServedBackend::default();
+        served.scope(provider.get_quote("AAPL", &cfg)).await.unwrap();
+        assert_eq!(served.get(), Some(MarketProviderKind::Polygon));
+    }
+
+    #[tokio::test]
+    async fn news_fails_over_like_quotes() {
+        let primary = FakeBackend::new(1.0, Some(http(503)));
+        let second = FakeBackend::new(2.0, None);
+        let (provider, _) = chain(&[
+            (MarketProviderKind::Polygon, &primary),
+            (MarketProviderKind::Yahoo, &second),
+        ]);
+
+        let served = ServedBackend::default();
+        let resp = served
+            .scope(provider.get_news("AAPL", &Config::default()))
+            .await
+            .unwrap();
+        assert_eq!(resp.status, "OK");
+        assert_eq!((primary.calls(), second.calls()), (1, 1));
+        assert_eq!(served.get(), Some(MarketProviderKind::Yahoo));
+    }
+
+    #[tokio::test]
+    async fn served_backend_is_per_scope() {
+        let primary = FakeBackend::new(1.0, None);
+        let second = FakeBackend::new(2.0, None);
+        let (provider, _) = chain(&[
+            (MarketProviderKind::Polygon, &primary),
+            (MarketProviderKind::Yahoo, &second),
+        ]);
+        let cfg = Config::default();
+
+        let served = ServedBackend::default();
+        served.scope(provider.get_quote("AAPL", &cfg)).await.unwrap();
+        // A later call outside the scope (another fetch) does not overwrite it.
+        *primary.err.lock().unwrap() = Some(ProviderError::Timeout);
+        provider.get_quote("AAPL", &cfg).await.unwrap();
+        assert_eq!(served.get(), Some(MarketProviderKind::Polygon));
+        // Non-quote calls that fail without failover leave the scope empty.
+        let other = ServedBackend::default();
+        *primary.err.lock().unwrap() = None;
+        assert!(other.scope(provider.search_symbols("app", &cfg)).await.is_err());
+        assert_eq!(second.calls(), 1);
+        assert_eq!(other.get(), None);
+    }
+
+    #[tokio::test]
+    async fn other_errors_return_without_failover() {
+        let primary = FakeBackend::new(1.0, Some(ProviderError::ApiMessage("Unknown symbol".into())));
+        let second = FakeBackend::new(2.0, None);
+        let (provider, _) = chain(&[
+            (MarketProviderKind::Polygon, &primary),
+            (MarketProviderKind::Yahoo, &second),
+        ]);
+
+        let served = ServedBackend::default();
+        let err = served
+            .scope(provider.get_quote("NOPE", &Config::default()))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ProviderError::ApiMessage(_)));
+        assert_eq!(second.calls(), 0);
+        assert_eq!(served.get(), None);
+    }
+
+    #[tokio::test]
+    async fn all_backends_failing_returns_the_primary_error() {
+        let primary = FakeBackend::new(1.0, Some(ProviderError::Timeout));
+        let second = FakeBackend::new(2.0, Some(http(500)));
+        let (provider, _) = chain(&[
+            (MarketProviderKind::Polygon, &primary),
+            (MarketProviderKind::Yahoo, &second),
+        ]);
+
+        let err = provider.get_quote("AAPL", &Config::default()).await.unwrap_err();
+        assert!(matches!(err, ProviderError::Timeout));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn breaker_skips_a_failing_backend_until_the_cooldown_ends() {
+        let primary = FakeBackend::new(1.0, Some(http(503)));
+        let second = FakeBackend::new(2.0, None);
+        let (provider, health) = chain(&[
+            (MarketProviderKind::Polygon, &primary),
+            (MarketProviderKind::Yahoo, &second),
+        ]);
+        let cfg = Config::default();
+
+        for _ in 0..BREAKER_FAILURE_THRESHOLD + 2 {
+            provider.get_quote("AAPL", &cfg).await.unwrap();
+        }
+        assert_eq!(primary.calls(), BREAKER_FAILURE_THRESHOLD as usize);
+        assert!(!health.is_available(MarketProviderKind::Polygon, Instant::now()));
+
+        // Half-open trial fails: the breaker reopens straight away.
+        tokio::time::advance(BREAKER_COOLDOWN).await;
+        provider.get_quote("AAPL", &cfg).await.unwrap();
+        assert_eq!(primary.calls(), BREAKER_FAILURE_THRESHOLD as usize + 1);
+        provider.get_quote("AAPL", &cfg).await.unwrap();
+        assert_eq!(primary.calls(), BREAKER_FAILURE_THRESHOLD as usize + 1);
+
+        // Trial succeeds: the primary serves again.
+        tokio::time::advance(BREAKER_COOLDOWN).await;
+        *primary.err.lock().unwrap() = None;
+        let resp = provider.get_quote("AAPL", &cfg).await.unwrap();
+        assert_eq!(resp.latest_result().unwrap().c, 1.0);
+        assert!(health.is_available(MarketProviderKind::Polygon, Instant::now()));
+    }
+
+    #[tokio::test]
+    async fn all_breakers_open_still_tries_every_backend() {
+        let primary = FakeBackend::new(1.0, None);
+        let second = FakeBackend::new(2.0, None);
+        let (provider, health) = chain(&[
+            (MarketProviderKind::Polygon, &primary),
+            (MarketProviderKind::Yahoo, &second),
+        ]);
+        for _ in 0..BREAKER_FAILURE_THRESHOLD {
+            health.record_failure(MarketProviderKind::Polygon, Instant::now());
+            health.record_failure(MarketProviderKind::Yahoo, Instant::now());
+        }
+
+        let resp = provider.get_quote("AAPL", &Config::default()).await.unwrap();
+        assert_eq!(resp.latest_result().unwrap().c, 1.0);
+    }
+
+    #[tokio::test]
+    async fn fx_symbols_are_respelled_for_fallbacks() {
+        let primary = FakeBackend::new(1.0, Some(ProviderError::Timeout));
+        let second = Arc::new(FakeBackend {
+            fx_style: |from, to| format!("C:{from}{to}"),
+            ..Arc::into_inner(FakeBackend::new(1.27, None)).unwrap()
+        });
+        let (provider, _) = chain(&[
+            (MarketProviderKind::Yahoo, &primary),
+            (MarketProviderKind::Polygon, &second),
+        ]);
+
+        let sym = provider.fx_symbol("GBP", "USD");
+        assert_eq!(sym, "GBPUSD=X");
+        let resp = provider.get_quote(&sym, &Config::default()).await.unwrap();
+        assert_eq!(resp.ticker, "GBPUSD=X");
+        assert_eq!(*second.seen.lock().unwrap(), ["C:GBPUSD"]);
+
+        provider.get_quote("AAPL", &Config::default()).await.unwrap();
+        assert_eq!(second.seen.lock().unwrap().last().unwrap(), "AAPL");
+    }
+}
@@ -0,0 +1,553 @@
+//! Finnhub [`MarketDataProvider`](crate::api::provider::MarketDataProvider) implementation (REST).
+//!
+//! Live trades over Finnhub's websocket live in [`crate::api::finnhub_stream`].
+
+use async_trait::async_trait;
+use chrono::{Duration, Local, NaiveDate, Utc};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+use urlencoding::encode;
+
+use crate::api::error::{ProviderError, ProviderResult};
+use crate::api::historical_query::HistoricalQuery;
+use crate::api::polygon::us_equity_market_state_at;
+use crate::api::provider::MarketDataProvider;
+use crate::api::retry::execute_get_text_with_retry;
+use crate::config::Config;
+use crate::models::historical::{HistoricalData, HistoricalResponse};
+use crate::models::news::{NewsItem, NewsResponse, Publisher};
+use crate::models::search::{SymbolResult, SymbolSearchResponse};
+use crate::models::ticker::{TickerResponse, TickerResult};
+
+const BASE_URL: &str = "https://finnhub.io/api/v1";
+
+/// Company news window ending today.
+const NEWS_LOOKBACK_DAYS: i64 = 7;
+
+fn enc(s: &str) -> String {
+    encode(s).into_owned()
+}
+
+pub(crate) fn finnhub_key(config: &Config) -> ProviderResult<String> {
+    let key = config.effective_finnhub_api_key();
+    if key.is_empty() {
+        return Err(ProviderError::ApiMessage(
+            "Finnhub provider requires non-empty finnhub_api_key in ~/.stockterm.json or STOCKTERM_FINNHUB_API_KEY"
+                .to_string(),
+        ));
+    }
+    Ok(key.into_owned())
+}
+
+/// `{base}{endpoint}?{params}&token=…`; a `{"error": "…"}` body becomes [`ProviderError::ApiMessage`].
+async fn fetch_json<T: DeserializeOwned>(
+    base: &str,
+    endpoint: &str,
+    params: &[(&str, &str)],
+    key: &str,
+) -> ProviderResult<T> {
+    let mut url = format!("{base}{endpoint}?");
+    for (name, value) in params {
+        url.push_str(&format!("{name}={}&", enc(value)));
+    }
+    url.push_str(&format!("token={}", enc(key)));
+    let text = execute_get_text_with_retry(&url).await?;
+    let body: Value = serde_json::from_str(&text)?;
+    if let Some(msg) = body.get("error").and_then(Value::as_str) {
+        return Err(ProviderError::ApiMessage(msg.to_string()));
+    }
+    serde_json::from_value(body).map_err(ProviderError::from)
+}
+
+#[derive(Deserialize)]
+struct QuoteWire {
+    #[serde(default)]
+    c: f64,
+    #[serde(default)]
+    h: f64,
+    #[serde(default)]
+    l: f64,
+    #[serde(default)]
+    o: f64,
+    pc: Option<f64>,
+    /// Unix seconds; `0` for unknown symbols.
+    #[serde(default)]
+    t: i64,
+}
+
+#[derive(Deserialize)]
+struct CandleWire {
+    /// `ok` or `no_data`.
+    s: String,
+    #[serde(default)]
+    t: Vec<i64>,
+    #[serde(default)]
+    o: Vec<f64>,
+    #[serde(default)]
+    h: Vec<f64>,
+    #[serde(default)]
+    l: Vec<f64>,
+    #[serde(default)]
+    c: Vec<f64>,
+    #[serde(default)]
+    v: Vec<f64>,
+}
+
+#[derive(Deserialize)]
+struct SearchWire {
+    #[serde(default)]
+    result: Vec<SearchMatch>,
+}
+
+#[derive(Deserialize)]
+struct SearchMatch {
+    symbol: String,
+    #[serde(default)]
+    description: String,
+    #[serde(rename = "type", default)]
+    type_: String,
+}
+
+#[derive(Deserialize)]
+struct NewsWire {
+    #[serde(default)]
+    headline: String,
+    #[serde(default)]
+    url: String,
+    /// Unix seconds.
+    #[serde(default)]
+    datetime: i64,
+    id: Option<i64>,
+    image: Option<String>,
+    #[serde(default)]
+    related: String,
+    #[serde(default)]
+    source: String,
+    summary: Option<String>,
+}
+
+/// `/quote` against `base`.
+pub(crate) async fn finnhub_quote_at(
+    symbol: &str,
+    key: &str,
+    base: &str,
+) -> ProviderResult<TickerResponse> {
+    let q: QuoteWire = fetch_json(base, "/quote", &[("symbol", symbol)], key).await?;
+    // Unknown tickers answer 200 with every field zeroed.
+    if q.t == 0 && q.c == 0.0 {
+        return Err(ProviderError::ApiMessage(format!("No quote data for {symbol}")));
+    }
+    Ok(TickerResponse {
+        ticker: symbol.to_string(),
+        results: vec![TickerResult {
+            o: q.o,
+            h: q.h,
+            l: q.l,
+            c: q.c,
+            v: 0.0,
+            t: (q.t.max(0) as u64).saturating_mul(1000),
+            prev_close: q.pc.filter(|p| *p > 0.0),
+            market_state: Some(us_equity_market_state_at(Utc::now())),
+            ext_price: None,
+            week52_low: None,
+            week52_high: None,
+            market_cap: None,
+            currency: None,
+        }],
+        status: "OK".to_string(),
+        error: None,
+    })
+}
+
+/// `/stock/candle` resolution for a Polygon-style timespan.
+fn candle_resolution(query: &HistoricalQuery<'_>) -> &'static str {
+    match (query.polygon_timespan, query.polygon_multiplier) {
+        ("minute", 1) => "1",
+        ("minute", 15) => "15",
+        ("minute", 30) => "30",
+        ("minute", 60) | ("hour", _) => "60",
+        ("minute", _) => "5",
+        ("week", _) => "W",
+        ("month", _) => "M",
+        _ => "D",
+    }
+}
+
+/// Unix seconds for the start of `from` and the end of `to` (UTC).
+fn candle_window(from: &str, to: &str) -> ProviderResult<(i64, i64)> {
+    let day = |s: &str| {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .map_err(|e| ProviderError::ApiMessage(format!("Invalid date {s}: {e}")))
+    };
+    let start = day(from)?.and_hms_opt(0, 0, 0).expect("valid time").and_utc();
+    let end = day(to)?.and_hms_opt(23, 59, 59).expect("valid time").and_utc();
+    Ok((start.timestamp(), end.timestamp()))
+}
+
+/// `/stock/candle` against `base`.
+pub(crate) async fn finnhub_historical_at(
+    symbol: &str,
+    query: &HistoricalQuery<'_>,
+    key: &str,
+    base: &str,
+) -> ProviderResult<HistoricalResponse> {
+    let (from, to) = candle_window(query.from, query.to)?;
+    let (from, to) = (from.to_string(), to.to_string());
+    let wire: CandleWire = fetch_json(
+        base,
+        "/stock/candle",
+        &[
+            ("symbol", symbol),
+            ("resolution", candle_resolution(query)),
+            ("from", &from),
+            ("to", &to),
+        ],
+        key,
+    )
+    .await?;
+    let mut results: Vec<HistoricalData> = Vec::new();
+    if wire.s == "ok" {
+        for (i, &t_sec) in wire.t.iter().enumerate() {
+            let Some(&c) = wire.c.get(i) else {
+                continue;
+            };
+            let o = wire.o.get(i).copied().unwrap_or(c);
+            let h = wire.h.get(i).copied().unwrap_or(c);
+            let l = wire.l.get(i).copied().unwrap_or(c);
+            results.push(HistoricalData {
+                o,
+                h,
+                l,
+                c,
+                v: wire.v.get(i).copied().unwrap_or(0.0),
+                t: (t_sec.max(0) as u64).saturating_mul(1000),
+                vw: (o + h + l + c) / 4.0,
+                n: None,
+            });
+        }
+    }
+    results.sort_by_key(|b| b.t);
+    let count = results.len() as u32;
+    Ok(HistoricalResponse {
+        ticker: symbol.to_string(),
+        results,
+        status: "OK".to_string(),
+        request_id: String::new(),
+        count,
+    })
+}
+
+/// `/search` against `base`.
+pub(crate) async fn finnhub_search_at(
+    query: &str,
+    key: &str,
+    base: &str,
+) -> ProviderResult<SymbolSearchResponse> {
+    let wire: SearchWire = fetch_json(base, "/search", &[("q", query)], key).await?;
+    let results: Vec<SymbolResult> = wire
+        .result
+        .into_iter()
+        .map(|m| SymbolResult {
+            // Non-US listings carry an exchange suffix (`TSCO.L`).
+            locale: if m.symbol.contains('.') { "global" } else { "us" }.to_string(),
+            ticker: m.symbol,
+            name: m.description,
+            market: String::new(),
+            primary_exchange: String::new(),
+            type_: m.type_,
+            active: true,
+            currency_name: String::new(),
+            cik: None,
+            composite_figi: None,
+            share_class_figi: None,
+            last_updated_utc: String::new(),
+        })
+        .collect();
+    Ok(SymbolSearchResponse {
+        status: "OK".to_string(),
+        count: results.len() as u32,
+        results,
+    })
+}
+
+fn map_news_item(item: NewsWire, symbol: &str) -> Option<NewsItem> {
+    if item.headline.is_empty() {
+        return None;
+    }
+    let published_utc = chrono::DateTime::from_timestamp(item.datetime, 0)
+        .map(|d| d.to_rfc3339())
+        .unwrap_or_default();
+    let mut tickers: Vec<String> = item
+        .related
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect();
+    if tickers.is_empty() {
+        tickers.push(symbol.to_uppercase());
+    }
+    Some(NewsItem {
+        id: item.id.map_or_else(|| item.url.clone(), |id| id.to_string()),
+        publisher: Publisher {
+            name: item.source,
+            homepage_url: String::new(),
+            logo_url: String::new(),
+            favicon_url: String::new(),
+        },
+        title: item.headline,
+        author: None,
+        published_utc,
+        article_url: item.url,
+        tickers,
+        amp_url: None,
+        image_url: item.image.filter(|u| !u.is_empty()),
+        description: item.summary.filter(|s| !s.is_empty()),
+        keywords: vec![],
+    })
+}
+
+/// `/company-news` for the last [`NEWS_LOOKBACK_DAYS`] against `base`, newest first.
+pub(crate) async fn finnhub_news_at(
+    symbol: &str,
+    key: &str,
+    base: &str,
+) -> ProviderResult<NewsResponse> {
+    let to = Local::now().date_naive();
+    let from = to - Duration::days(NEWS_LOOKBACK_DAYS);
+    let (from, to) = (from.format("%Y-%m-%d").to_string(), to.format("%Y-%m-%d").to_string());
+    let mut wire: Vec<NewsWire> = fetch_json(
+        base,
+        "/company-news",
+        &[("symbol", symbol), ("from", &from), ("to", &to)],
+        key,
+    )
+    .await?;
+    wire.sort_by_key(|n| std::cmp::Reverse(n.datetime));
+    let results: Vec<NewsItem> = wire
+        .into_iter()
+        .filter_map(|item| map_news_item(item, symbol))
+        .collect();
+    Ok(NewsResponse {
+        status: "OK".to_string(),
+        count: results.len() as u32,
+        results,
+    })
+}
+
+pub struct FinnhubProvider;
+
+#[async_trait]
+impl MarketDataProvider for FinnhubProvider {
+    /// `/quote`: current price, day OHLC and previous close; the session state comes from the
+    /// US/Eastern clock as with Polygon.
+    async fn get_quote(&self, symbol: &str, config: &Config) -> ProviderResult<TickerResponse> {
+        let key = finnhub_key(config)?;
+        finnhub_quote_at(symbol, &key, BASE_URL).await
+    }
+
+    async fn get_historical(
+        &self,
+        symbol: &str,
+        query: &HistoricalQuery<'_>,
+        config: &Config,
+    ) -> ProviderResult<HistoricalResponse> {
+        let key = finnhub_key(config)?;
+        finnhub_historical_at(symbol, query, &key, BASE_URL).await
+    }
+
+    async fn search_symbols(&self, query: &str, config: &Config) -> ProviderResult<SymbolSearchResponse> {
+        let key = finnhub_key(config)?;
+        finnhub_search_at(query, &key, BASE_URL).await
+    }
+
+    async fn get_news(&self, symbol: &str, config: &Config) -> ProviderResult<NewsResponse> {
+        let key = finnhub_key(config)?;
+        finnhub_news_at(symbol, &key, BASE_URL).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(timespan: &'static str, multiplier: u32) -> HistoricalQuery<'static> {
+        HistoricalQuery {
+            from: "2024-07-08",
+            to: "2024-07-10",
+            bar_interval: "",
+            yahoo_range: None,
+            polygon_multiplier: multiplier,
+            polygon_timespan: timespan,
+        }
+    }
+
+    #[test]
+    fn candle_resolution_follows_timespan() {
+        assert_eq!(candle_resolution(&query("minute", 5)), "5");
+        assert_eq!(candle_resolution(&query("minute", 30)), "30");
+        assert_eq!(candle_resolution(&query("day", 1)), "D");
+        assert_eq!(candle_resolution(&query("week", 1)), "W");
+        assert_eq!(candle_resolution(&query("month", 1)), "M");
+    }
+
+    #[test]
+    fn candle_window_spans_whole_days() {
+        let (from, to) = candle_window("2024-07-08", "2024-07-10").unwrap();
+        assert_eq!(to - from, 3 * 86_400 - 1);
+        assert!(candle_window("07/08/2024", "2024-07-10").is_err());
+    }
+}
+
+#[cfg(test)]
+mod wiremock_tests {
+    use super::*;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const KEY: &str = "test-token";
+
+    fn ensure_http_client() {
+        crate::api::http::ensure_shared_client_for_tests();
+    }
+
+    fn daily_query() -> HistoricalQuery<'static> {
+        HistoricalQuery {
+            from: "2024-07-08",
+            to: "2024-07-10",
+            bar_interval: "1d",
+            yahoo_range: None,
+            polygon_multiplier: 1,
+            polygon_timespan: "day",
+        }
+    }
+
+    async fn mount(srv: &MockServer, endpoint: &str, body: &str) {
+        Mock::given(method("GET"))
+            .and(path(endpoint))
+            .and(query_param("token", KEY))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .expect(1)
+            .mount(srv)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn quote_maps_price_and_previous_close() {
+        ensure_http_client();
+        let srv = MockServer::start().await;
+        mount(
+            &srv,
+            "/quote",
+            r#"{"c":261.74,"d":2.1,"dp":0.81,"h":263.31,"l":260.68,"o":261.07,"pc":259.64,"t":1720627200}"#,
+        )
+        .await;
+
+        let tr = finnhub_quote_at("AAPL", KEY, &srv.uri()).await.expect("quote");
+        let bar = tr.latest_result().expect("bar");
+        assert!((bar.c - 261.74).abs() < 1e-9);
+        assert_eq!(bar.prev_close, Some(259.64));
+        assert_eq!(bar.t, 1_720_627_200_000);
+    }
+
+    #[tokio::test]
+    async fn zeroed_quote_is_an_api_message() {
+        ensure_http_client();
+        let srv = MockServer::start().await;
+        mount(&srv, "/quote", r#"{"c":0,"d":null,"dp":null,"h":0,"l":0,"o":0,"pc":0,"t":0}"#).await;
+
+        let err = finnhub_quote_at("NOPE", KEY, &srv.uri()).await.unwrap_err();
+        assert!(matches!(err, ProviderError::ApiMessage(m) if m.contains("NOPE")));
+    }
+
+    #[tokio::test]
+    async fn error_body_is_an_api_message() {
+        ensure_http_client();
+        let srv = MockServer::start().await;
+        mount(&srv, "/stock/candle", r#"{"error":"You don't have access to this resource."}"#).await;
+
+        let err = finnhub_historical_at("AAPL", &daily_query(), KEY, &srv.uri())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ProviderError::ApiMessage(m) if m.contains("access")));
+    }
+
+    #[tokio::test]
+    async fn candles_map_to_sorted_bars() {
+        ensure_http_client();
+        let srv = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/stock/candle"))
+            .and(query_param("resolution", "D"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"{"s":"ok","t":[1720569600,1720483200],"o":[2,1],"h":[2,1],"l":[2,1],"c":[2.5,1.5],"v":[200,100]}"#,
+            ))
+            .expect(1)
+            .mount(&srv)
+            .await;
+
+        let resp = finnhub_historical_at("AAPL", &daily_query(), KEY, &srv.uri())
+            .await
+            .expect("candles");
+        let closes: Vec<f64> = resp.results.iter().map(|b| b.c).collect();
+        assert_eq!(closes, [1.5, 2.5]);
+        assert_eq!(resp.count, 2);
+    }
+
+    #[tokio::test]
+    async fn no_data_candles_are_empty() {
+        ensure_http_client();
+        let srv = MockServer::start().await;
+        mount(&srv, "/stock/candle", r#"{"s":"no_data"}"#).await;
+
+        let resp = finnhub_historical_at("AAPL", &daily_query(), KEY, &srv.uri())
+            .await
+            .expect("candles");
+        assert!(resp.results.is_empty());
+    }
+
+    #[tokio::test]
+    async fn search_maps_results() {
+        ensure_http_client();
+        let srv = MockServer::start().await;
+        mount(
+            &srv,
+            "/search",
+            r#"{"count":2,"result":[
+                {"description":"APPLE INC","displaySymbol":"AAPL","symbol":"AAPL","type":"Common Stock"},
+                {"description":"APPLE INC","displaySymbol":"APC.DE","symbol":"APC.DE","type":"Common Stock"}
+            ]}"#,
+        )
+        .await;
+
+        let resp = finnhub_search_at("apple", KEY, &srv.uri()).await.expect("search");
+        assert_eq!(resp.count, 2);
+        assert_eq!(resp.results[0].name, "APPLE INC");
+        assert_eq!(resp.results[0].locale, "us");
+        assert_eq!(resp.results[1].locale, "global");
+    }
+
+    #[tokio::test]
+    async fn company_news_is_newest_first() {
+        ensure_http_client();
+        let srv = MockServer::start().await;
+        mount(
+            &srv,
+            "/company-news",
+            r#"[
+                {"category":"company","datetime":1720600000,"headline":"Older","id":1,"image":"","related":"AAPL","source":"Wire","summary":"","url":"https://example.com/1"},
+                {"category":"company","datetime":1720700000,"headline":"Newer","id":2,"image":"https://example.com/i.png","related":"AAPL,MSFT","source":"Wire","summary":"Body","url":"https://example.com/2"},
+                {"category":"company","datetime":1720650000,"headline":"","id":3,"related":"","source":"Wire","url":""}
+            ]"#,
+        )
+        .await;
+
+        let resp = finnhub_news_at("AAPL", KEY, &srv.uri()).await.expect("news");
+        let titles: Vec<&str> = resp.results.iter().map(|n| n.title.as_str()).collect();
+        assert_eq!(titles, ["Newer", "Older"]);
+        assert_eq!(resp.results[0].tickers, ["AAPL", "MSFT"]);
+        assert_eq!(resp.results[0].id, "2");
+        assert_eq!(resp.results[1].image_url, None);
+    }
+}
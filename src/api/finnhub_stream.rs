@@ -0,0 +1,245 @@
+//! Finnhub websocket trades as a [`QuoteStream`] (`stream_quotes` with the Finnhub provider).
+//!
+//! Trades arrive batched per `{"type":"trade","data":[…]}` frame; the server also sends
+//! `{"type":"ping"}` frames, which count as keep-alives.
+
+use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use urlencoding::encode;
+
+use crate::api::quote_stream::{QuoteStream, QuoteStreamError, QuoteStreamEvent, QuoteTick};
+
+pub const FINNHUB_WS_URL: &str = "wss://ws.finnhub.io";
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum WsMessage {
+    Trade {
+        #[serde(default)]
+        data: Vec<TradeWire>,
+    },
+    Error {
+        #[serde(default)]
+        msg: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+struct TradeWire {
+    s: String,
+    p: f64,
+    #[serde(default)]
+    v: f64,
+    t: u64,
+}
+
+/// Trades in one text frame; pings and unknown types are empty, `{"type":"error"}` is `Err`.
+pub(crate) fn parse_trade_message(text: &str) -> Result<Vec<QuoteTick>, String> {
+    match serde_json::from_str(text).map_err(|e| e.to_string())? {
+        WsMessage::Trade { data } => Ok(data
+            .into_iter()
+            .map(|t| QuoteTick {
+                symbol: t.s,
+                price: t.p,
+                volume: t.v,
+                time_ms: t.t,
+            })
+            .collect()),
+        WsMessage::Error { msg } => Err(msg),
+        WsMessage::Other => Ok(Vec::new()),
+    }
+}
+
+/// Websocket URL for `key`.
+pub fn finnhub_stream_url(key: &str) -> String {
+    format!("{FINNHUB_WS_URL}?token={}", encode(key))
+}
+
+fn socket_err(e: impl std::fmt::Display) -> QuoteStreamError {
+    QuoteStreamError::Socket(e.to_string())
+}
+
+pub struct FinnhubQuoteStream {
+    url: String,
+    ws: Option<WebSocketStream<MaybeTlsStream<TcpStream>>>,
+}
+
+impl FinnhubQuoteStream {
+    pub fn new(url: String) -> Self {
+        Self { url, ws: None }
+    }
+
+    async fn send_text(&mut self, text: String) -> Result<(), QuoteStreamError> {
+        let ws = self.ws.as_mut().ok_or(QuoteStreamError::NotConnected)?;
+        ws.send(Message::Text(text)).await.map_err(socket_err)
+    }
+
+    async fn subscription(&mut self, kind: &str, symbol: &str) -> Result<(), QuoteStreamError> {
+        self.send_text(serde_json::json!({ "type": kind, "symbol": symbol }).to_string())
+            .await
+    }
+}
+
+#[async_trait]
+impl QuoteStream for FinnhubQuoteStream {
+    fn name(&self) -> &'static str {
+        "finnhub"
+    }
+
+    async fn connect(&mut self) -> Result<(), QuoteStreamError> {
+        self.ws = None;
+        let (ws, _) = tokio_tungstenite::connect_async(self.url.as_str())
+            .await
+            .map_err(|e| QuoteStreamError::Connect(e.to_string()))?;
+        self.ws = Some(ws);
+        Ok(())
+    }
+
+    async fn subscribe(&mut self, symbol: &str) -> Result<(), QuoteStreamError> {
+        self.subscription("subscribe", symbol).await
+    }
+
+    async fn unsubscribe(&mut self, symbol: &str) -> Result<(), QuoteStreamError> {
+        self.subscription("unsubscribe", symbol).await
+    }
+
+    async fn next_event(&mut self) -> Result<QuoteStreamEvent, QuoteStreamError> {
+        let ws = self.ws.as_mut().ok_or(QuoteStreamError::NotConnected)?;
+        match ws.next().await {
+            Some(Ok(Message::Text(text))) => match parse_trade_message(&text) {
+                Ok(ticks) if ticks.is_empty() => Ok(QuoteStreamEvent::Alive),
+                Ok(ticks) => Ok(QuoteStreamEvent::Ticks(ticks)),
+                Err(msg) => {
+                    tracing::warn!(target: "stockterm::stream", provider = "finnhub", msg, "server error");
+                    Ok(QuoteStreamEvent::Alive)
+                }
+            },
+            Some(Ok(Message::Close(_))) | None => {
+                self.ws = None;
+                Ok(QuoteStreamEvent::Closed)
+            }
+            Some(Ok(_)) => Ok(QuoteStreamEvent::Alive),
+            Some(Err(e)) => {
+                self.ws = None;
+                Err(socket_err(e))
+            }
+        }
+    }
+
+    async fn heartbeat(&mut self) -> Result<(), QuoteStreamError> {
+        let ws = self.ws.as_mut().ok_or(QuoteStreamError::NotConnected)?;
+        ws.send(Message::Ping(Vec::new())).await.map_err(socket_err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use tokio::net::TcpListener;
+    use tokio::sync::{mpsc, watch};
+    use tokio_tungstenite::accept_async;
+
+    use crate::api::quote_stream::{run_quote_stream, QuoteStreamOptions};
+
+    #[test]
+    fn parses_trades_pings_and_errors() {
+        let ticks = parse_trade_message(
+            r#"{"type":"trade","data":[{"s":"AAPL","p":261.5,"t":1720627200123,"v":100,"c":["1"]}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            ticks,
+            vec![QuoteTick {
+                symbol: "AAPL".into(),
+                price: 261.5,
+                volume: 100.0,
+                time_ms: 1_720_627_200_123,
+            }]
+        );
+        assert!(parse_trade_message(r#"{"type":"ping"}"#).unwrap().is_empty());
+        assert_eq!(
+            parse_trade_message(r#"{"type":"error","msg":"Subscribing to too many symbols"}"#),
+            Err("Subscribing to too many symbols".into())
+        );
+        assert!(parse_trade_message("not json").is_err());
+    }
+
+    async fn next_text<S>(ws: &mut S) -> serde_json::Value
+    where
+        S: StreamExt<Item = Result<Message, tokio_tungstenite::tungstenite::Error>> + Unpin,
+    {
+        loop {
+            if let Message::Text(t) = ws.next().await.expect("frame").expect("ok") {
+                return serde_json::from_str(&t).expect("json");
+            }
+        }
+    }
+
+    /// Local stand-in for `wss://ws.finnhub.io`: drops the first connection, then checks the
+    /// resubscription, pushes one trade and follows a symbol change.
+    #[tokio::test]
+    async fn streams_trades_across_reconnects_and_symbol_changes() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (sym_tx, sym_rx) = watch::channel(vec!["AAPL".to_string()]);
+        let (tick_tx, mut tick_rx) = mpsc::unbounded_channel();
+
+        let server = tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(tcp).await.unwrap();
+            assert_eq!(next_text(&mut ws).await["symbol"], "AAPL");
+            ws.close(None).await.unwrap();
+            drop(ws);
+
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(tcp).await.unwrap();
+            let sub = next_text(&mut ws).await;
+            assert_eq!(sub["type"], "subscribe");
+            assert_eq!(sub["symbol"], "AAPL");
+            ws.send(Message::Text(r#"{"type":"ping"}"#.into())).await.unwrap();
+            ws.send(Message::Text(
+                r#"{"type":"trade","data":[{"s":"AAPL","p":262.25,"t":1720627201000,"v":5}]}"#.into(),
+            ))
+            .await
+            .unwrap();
+            let unsub = next_text(&mut ws).await;
+            assert_eq!(unsub["type"], "unsubscribe");
+            assert_eq!(unsub["symbol"], "AAPL");
+            let sub = next_text(&mut ws).await;
+            assert_eq!(sub["type"], "subscribe");
+            assert_eq!(sub["symbol"], "MSFT");
+        });
+        let opts = QuoteStreamOptions {
+            initial_backoff: Duration::from_millis(10),
+            ..QuoteStreamOptions::default()
+        };
+        let client = tokio::spawn(run_quote_stream(
+            Box::new(FinnhubQuoteStream::new(url)),
+            sym_rx,
+            opts,
+            move |batch| tick_tx.send(batch).is_ok(),
+        ));
+
+        let batch = tick_rx.recv().await.expect("trade batch");
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch[0].symbol, "AAPL");
+        assert!((batch[0].price - 262.25).abs() < 1e-9);
+
+        sym_tx.send(vec!["MSFT".to_string()]).unwrap();
+        server.await.expect("server assertions");
+
+        drop(sym_tx);
+        tokio::time::timeout(Duration::from_secs(5), client)
+            .await
+            .expect("stream stops when the app drops the symbol sender")
+            .unwrap();
+    }
+}
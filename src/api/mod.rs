@@ -1,13 +1,19 @@
+pub mod alpha_vantage;
 pub(crate) mod concurrency;
+pub mod finnhub;
+pub mod finnhub_stream;
 pub mod error;
+pub mod failover;
 pub mod historical_query;
 pub mod http;
 pub(crate) mod http_fetch;
 pub mod polygon;
 pub(crate) mod retry;
 pub mod provider;
+pub mod quote_stream;
+pub mod rate_limit;
 pub mod yahoo;
 
 pub use error::ProviderError;
 pub use historical_query::HistoricalQuery;
-pub use provider::market_provider_for;
+pub use provider::{market_provider_for, market_provider_for_config};
@@ -1,7 +1,7 @@
 //! Polygon.io [`MarketDataProvider`](crate::api::provider::MarketDataProvider) implementation.
 
 use async_trait::async_trait;
-use chrono::{Duration, Local};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Timelike, Utc, Weekday};
 use urlencoding::encode;
 
 use crate::api::error::{ProviderError, ProviderResult};
@@ -12,7 +12,7 @@ use crate::config::Config;
 use crate::models::historical::HistoricalResponse;
 use crate::models::news::NewsResponse;
 use crate::models::search::SymbolSearchResponse;
-use crate::models::ticker::TickerResponse;
+use crate::models::ticker::{MarketState, TickerResponse};
 
 const BASE_URL: &str = "https://api.polygon.io";
 
@@ -37,6 +37,54 @@ async fn fetch_json<T: serde::de::DeserializeOwned>(url: &str) -> ProviderResult
     serde_json::from_str(&text).map_err(ProviderError::from)
 }
 
+/// UTC offset (hours) of US/Eastern at `now`: EDT from the second Sunday of March 02:00 to the
+/// first Sunday of November 02:00 local time, EST otherwise.
+pub(crate) fn us_eastern_offset_hours(now: DateTime<Utc>) -> i64 {
+    let year = now.year();
+    let nth_sunday = |month: u32, n: i64| {
+        let first = NaiveDate::from_ymd_opt(year, month, 1).expect("valid month start");
+        let to_sunday = (7 - first.weekday().num_days_from_sunday() as i64) % 7;
+        first + Duration::days(to_sunday + 7 * (n - 1))
+    };
+    let dst_start = nth_sunday(3, 2).and_hms_opt(7, 0, 0).expect("valid time").and_utc();
+    let dst_end = nth_sunday(11, 1).and_hms_opt(6, 0, 0).expect("valid time").and_utc();
+    if now >= dst_start && now < dst_end {
+        -4
+    } else {
+        -5
+    }
+}
+
+/// Aggregates carry no session flag, so derive it from the US/Eastern wall clock: pre 04:00–09:30,
+/// regular 09:30–16:00, post 16:00–20:00, weekends closed. Exchange holidays are not modelled.
+pub(crate) fn us_equity_market_state_at(now: DateTime<Utc>) -> MarketState {
+    let et = now.naive_utc() + Duration::hours(us_eastern_offset_hours(now));
+    if matches!(et.weekday(), Weekday::Sat | Weekday::Sun) {
+        return MarketState::Closed;
+    }
+    let minute = et.hour() * 60 + et.minute();
+    match minute {
+        240..=569 => MarketState::Pre,
+        570..=959 => MarketState::Regular,
+        960..=1199 => MarketState::Post,
+        _ => MarketState::Closed,
+    }
+}
+
+/// Daily aggregates have no previous-close field: copy the prior bar's close onto the latest bar
+/// (the `sort=desc&limit=5` window always includes it) and stamp the clock-derived session.
+fn annotate_polygon_quote(resp: &mut TickerResponse, now: DateTime<Utc>) {
+    let mut by_time: Vec<usize> = (0..resp.results.len()).collect();
+    by_time.sort_by_key(|&i| std::cmp::Reverse(resp.results[i].t));
+    let Some(&latest) = by_time.first() else {
+        return;
+    };
+    if let Some(&prior) = by_time.get(1) {
+        resp.results[latest].prev_close = Some(resp.results[prior].c);
+    }
+    resp.results[latest].market_state = Some(us_equity_market_state_at(now));
+}
+
 pub struct PolygonProvider;
 
 #[async_trait]
@@ -56,10 +104,11 @@ impl MarketDataProvider for PolygonProvider {
             enc(&to),
             enc(&key)
         );
-        let ticker_data: TickerResponse = fetch_json(&url).await?;
+        let mut ticker_data: TickerResponse = fetch_json(&url).await?;
         if let Some(msg) = ticker_data.api_error_message() {
             return Err(ProviderError::ApiMessage(msg));
         }
+        annotate_polygon_quote(&mut ticker_data, Utc::now());
         Ok(ticker_data)
     }
 
@@ -104,4 +153,46 @@ impl MarketDataProvider for PolygonProvider {
         );
         fetch_json(&url).await
     }
+
+    /// Polygon forex tickers (`C:EURUSD`); the daily aggregate close is the rate.
+    fn fx_symbol(&self, from: &str, to: &str) -> String {
+        format!("C:{from}{to}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn annotate_sets_prev_close_from_prior_bar_any_order() {
+        let json = r#"{"results":[
+            {"o":1.0,"h":1.0,"l":1.0,"c":100.0,"v":1,"t":1000},
+            {"o":1.0,"h":1.0,"l":1.0,"c":104.0,"v":1,"t":3000},
+            {"o":1.0,"h":1.0,"l":1.0,"c":102.0,"v":1,"t":2000}
+        ]}"#;
+        let mut r: TickerResponse = serde_json::from_str(json).expect("parse");
+        annotate_polygon_quote(&mut r, Utc::now());
+        let latest = r.latest_result().expect("bar");
+        assert_eq!(latest.prev_close, Some(102.0));
+        assert!(latest.market_state.is_some());
+    }
+
+    #[test]
+    fn market_state_follows_eastern_clock_across_dst() {
+        // Wed 2024-07-10 (EDT): 13:00 UTC = 09:00 ET pre; 14:00 UTC = 10:00 ET regular.
+        let pre = Utc.with_ymd_and_hms(2024, 7, 10, 13, 0, 0).unwrap();
+        let reg = Utc.with_ymd_and_hms(2024, 7, 10, 14, 0, 0).unwrap();
+        assert_eq!(us_equity_market_state_at(pre), MarketState::Pre);
+        assert_eq!(us_equity_market_state_at(reg), MarketState::Regular);
+        // Wed 2024-01-10 (EST): 14:00 UTC = 09:00 ET pre; 21:30 UTC = 16:30 ET post.
+        let pre_w = Utc.with_ymd_and_hms(2024, 1, 10, 14, 0, 0).unwrap();
+        let post_w = Utc.with_ymd_and_hms(2024, 1, 10, 21, 30, 0).unwrap();
+        assert_eq!(us_equity_market_state_at(pre_w), MarketState::Pre);
+        assert_eq!(us_equity_market_state_at(post_w), MarketState::Post);
+        // Saturday.
+        let sat = Utc.with_ymd_and_hms(2024, 7, 13, 15, 0, 0).unwrap();
+        assert_eq!(us_equity_market_state_at(sat), MarketState::Closed);
+    }
 }
@@ -1,4 +1,4 @@
-//! [`MarketDataProvider`] trait and factory for Yahoo vs Polygon.
+//! [`MarketDataProvider`] trait and factory for Yahoo, Polygon, Alpha Vantage and Finnhub.
 
 use std::sync::Arc;
 
@@ -8,12 +8,17 @@ use crate::config::MarketProviderKind;
 use crate::config::Config;
 use crate::models::historical::HistoricalResponse;
 use crate::models::news::NewsResponse;
+use crate::models::currency::yahoo_fx_symbol;
 use crate::models::search::SymbolSearchResponse;
 use crate::models::ticker::TickerResponse;
 
 use crate::api::error::ProviderResult;
 use crate::api::historical_query::HistoricalQuery;
+use crate::api::alpha_vantage::AlphaVantageProvider;
+use crate::api::failover::{BackendHealth, FailoverProvider};
+use crate::api::finnhub::FinnhubProvider;
 use crate::api::polygon::PolygonProvider;
+use crate::api::rate_limit::RateLimitedProvider;
 use crate::api::yahoo::YahooProvider;
 
 #[async_trait]
@@ -30,12 +35,33 @@ pub trait MarketDataProvider: Send + Sync {
     async fn search_symbols(&self, query: &str, config: &Config) -> ProviderResult<SymbolSearchResponse>;
 
     async fn get_news(&self, symbol: &str, config: &Config) -> ProviderResult<NewsResponse>;
+
+    /// Quote symbol whose last price is the value of one `from` in `to` (ISO codes), fetched
+    /// through [`get_quote`](Self::get_quote) like any ticker. Defaults to Yahoo's `EURUSD=X`.
+    fn fx_symbol(&self, from: &str, to: &str) -> String {
+        yahoo_fx_symbol(from, to)
+    }
 }
 
-/// Shared handle for spawned quote tasks (cheap `Arc` clone).
+/// Shared handle for spawned quote tasks (cheap `Arc` clone), paced by `kind`'s
+/// [`RateLimitedProvider`] limiter.
 pub fn market_provider_for(kind: MarketProviderKind) -> Arc<dyn MarketDataProvider + Send + Sync> {
-    match kind {
+    let inner: Arc<dyn MarketDataProvider + Send + Sync> = match kind {
         MarketProviderKind::Yahoo => Arc::new(YahooProvider),
         MarketProviderKind::Polygon => Arc::new(PolygonProvider),
+        MarketProviderKind::AlphaVantage => Arc::new(AlphaVantageProvider),
+        MarketProviderKind::Finnhub => Arc::new(FinnhubProvider),
+    };
+    Arc::new(RateLimitedProvider::new(kind, inner))
+}
+
+/// Provider for `config.provider`, wrapped in a [`FailoverProvider`] (sharing
+/// [`BackendHealth::shared`]) when [`Config::provider_chain`] has fallbacks.
+pub fn market_provider_for_config(config: &Config) -> Arc<dyn MarketDataProvider + Send + Sync> {
+    let chain = config.provider_chain();
+    if chain.len() == 1 {
+        return market_provider_for(config.provider);
     }
+    let backends = chain.into_iter().map(|kind| (kind, market_provider_for(kind))).collect();
+    Arc::new(FailoverProvider::new(backends, BackendHealth::shared()))
 }
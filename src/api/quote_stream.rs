@@ -0,0 +1,369 @@
+//! [`QuoteStream`] trait, factory and the reconnecting driver shared by every streaming backend.
+//!
+//! A backend only speaks its wire protocol (connect, subscribe, read one event, ping).
+//! [`run_quote_stream`] owns the session: it diffs subscriptions against a [`watch`] channel of
+//! symbols, pings every [`QuoteStreamOptions::heartbeat_interval`], drops connections that stay
+//! silent past [`QuoteStreamOptions::idle_timeout`], and reconnects with exponential backoff.
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use thiserror::Error;
+use tokio::sync::watch;
+use tokio::time::Instant;
+
+use crate::api::finnhub::finnhub_key;
+use crate::api::finnhub_stream::{finnhub_stream_url, FinnhubQuoteStream};
+use crate::config::{Config, MarketProviderKind};
+
+/// One trade or last-price update.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuoteTick {
+    pub symbol: String,
+    pub price: f64,
+    pub volume: f64,
+    /// Epoch millis.
+    pub time_ms: u64,
+}
+
+/// What one read from a [`QuoteStream`] produced.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QuoteStreamEvent {
+    Ticks(Vec<QuoteTick>),
+    /// Server ping, pong or any other frame that proves the connection is alive.
+    Alive,
+    /// Server closed the connection.
+    Closed,
+}
+
+#[derive(Debug, Error)]
+pub enum QuoteStreamError {
+    #[error("connect failed: {0}")]
+    Connect(String),
+    #[error("socket error: {0}")]
+    Socket(String),
+    #[error("not connected")]
+    NotConnected,
+    #[error("no data for {0:?}")]
+    Stale(Duration),
+}
+
+/// Streaming counterpart of [`MarketDataProvider`](crate::api::provider::MarketDataProvider).
+///
+/// Reads must be cancel-safe: [`run_quote_stream`] races [`next_event`](Self::next_event) against
+/// symbol changes and the heartbeat timer.
+#[async_trait]
+pub trait QuoteStream: Send {
+    /// Short backend name for logs (`finnhub`).
+    fn name(&self) -> &'static str;
+
+    /// Opens a fresh connection, dropping any previous one; subscriptions start empty.
+    async fn connect(&mut self) -> Result<(), QuoteStreamError>;
+
+    async fn subscribe(&mut self, symbol: &str) -> Result<(), QuoteStreamError>;
+
+    async fn unsubscribe(&mut self, symbol: &str) -> Result<(), QuoteStreamError>;
+
+    async fn next_event(&mut self) -> Result<QuoteStreamEvent, QuoteStreamError>;
+
+    /// Client keep-alive (websocket ping).
+    async fn heartbeat(&mut self) -> Result<(), QuoteStreamError>;
+}
+
+/// Reconnect and keep-alive timing for [`run_quote_stream`].
+#[derive(Debug, Clone, Copy)]
+pub struct QuoteStreamOptions {
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub heartbeat_interval: Duration,
+    /// Reconnect when nothing (ticks, pings, pongs) arrived for this long.
+    pub idle_timeout: Duration,
+}
+
+impl Default for QuoteStreamOptions {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            heartbeat_interval: Duration::from_secs(20),
+            idle_timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Streaming backend for `config`, when `stream_quotes` is on and the provider has one.
+pub fn quote_stream_for(config: &Config) -> Option<Box<dyn QuoteStream>> {
+    if !config.stream_quotes {
+        return None;
+    }
+    match config.provider {
+        MarketProviderKind::Finnhub => {
+            // A missing key already surfaces through the REST poll.
+            let key = finnhub_key(config).ok()?;
+            Some(Box::new(FinnhubQuoteStream::new(finnhub_stream_url(&key))))
+        }
+        MarketProviderKind::Yahoo | MarketProviderKind::Polygon | MarketProviderKind::AlphaVantage => None,
+    }
+}
+
+enum SessionEnd {
+    /// The symbol sender or the tick consumer is gone.
+    Stopped,
+    Disconnected,
+}
+
+enum Wake {
+    Event(Result<QuoteStreamEvent, QuoteStreamError>),
+    Symbols { open: bool },
+    Heartbeat,
+}
+
+async fn stream_session(
+    stream: &mut dyn QuoteStream,
+    symbols: &mut watch::Receiver<Vec<String>>,
+    opts: &QuoteStreamOptions,
+    deliver: &mut (dyn FnMut(Vec<QuoteTick>) -> bool + Send),
+) -> Result<SessionEnd, QuoteStreamError> {
+    let mut subscribed: HashSet<String> = HashSet::new();
+    let mut heartbeat = tokio::time::interval_at(
+        Instant::now() + opts.heartbeat_interval,
+        opts.heartbeat_interval,
+    );
+    let mut last_seen = Instant::now();
+    loop {
+        let wanted: HashSet<String> = symbols.borrow_and_update().iter().cloned().collect();
+        for sym in subscribed.difference(&wanted) {
+            stream.unsubscribe(sym).await?;
+        }
+        for sym in wanted.difference(&subscribed) {
+            stream.subscribe(sym).await?;
+        }
+        subscribed = wanted;
+
+        loop {
+            let wake = tokio::select! {
+                ev = stream.next_event() => Wake::Event(ev),
+                changed = symbols.changed() => Wake::Symbols { open: changed.is_ok() },
+                _ = heartbeat.tick() => Wake::Heartbeat,
+            };
+            match wake {
+                Wake::Event(ev) => match ev? {
+                    QuoteStreamEvent::Ticks(batch) => {
+                        last_seen = Instant::now();
+                        if !batch.is_empty() && !deliver(batch) {
+                            return Ok(SessionEnd::Stopped);
+                        }
+                    }
+                    QuoteStreamEvent::Alive => last_seen = Instant::now(),
+                    QuoteStreamEvent::Closed => return Ok(SessionEnd::Disconnected),
+                },
+                Wake::Symbols { open: false } => return Ok(SessionEnd::Stopped),
+                Wake::Symbols { open: true } => break,
+                Wake::Heartbeat => {
+                    let silent = last_seen.elapsed();
+                    if silent >= opts.idle_timeout {
+                        return Err(QuoteStreamError::Stale(silent));
+                    }
+                    stream.heartbeat().await?;
+                }
+            }
+        }
+    }
+}
+
+/// Streams ticks for the symbols published on `symbols` into `deliver` until the symbol sender is
+/// dropped or `deliver` returns `false`. Failed connects and dropped sessions retry after a
+/// doubling delay (reset by every successful connect).
+pub async fn run_quote_stream(
+    mut stream: Box<dyn QuoteStream>,
+    mut symbols: watch::Receiver<Vec<String>>,
+    opts: QuoteStreamOptions,
+    mut deliver: impl FnMut(Vec<QuoteTick>) -> bool + Send,
+) {
+    let name = stream.name();
+    let mut backoff = opts.initial_backoff;
+    loop {
+        match stream.connect().await {
+            Ok(()) => {
+                backoff = opts.initial_backoff;
+                match stream_session(stream.as_mut(), &mut symbols, &opts, &mut deliver).await {
+                    Ok(SessionEnd::Stopped) => return,
+                    Ok(SessionEnd::Disconnected) => {
+                        tracing::info!(target: "stockterm::stream", provider = name, "stream closed by server");
+                    }
+                    Err(e) => {
+                        tracing::warn!(target: "stockterm::stream", provider = name, error = %e, "stream dropped");
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::warn!(target: "stockterm::stream", provider = name, error = %e, "stream connect failed");
+            }
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(backoff) => {}
+            changed = symbols.changed() => {
+                if changed.is_err() {
+                    return;
+                }
+            }
+        }
+        backoff = (backoff * 2).min(opts.max_backoff);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+
+    /// Scripted backend: `connects` results in order, then events per connection.
+    struct FakeStream {
+        connects: VecDeque<bool>,
+        sessions: VecDeque<VecDeque<QuoteStreamEvent>>,
+        events: VecDeque<QuoteStreamEvent>,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl QuoteStream for FakeStream {
+        fn name(&self) -> &'static str {
+            "fake"
+        }
+
+        async fn connect(&mut self) -> Result<(), QuoteStreamError> {
+            self.log.lock().unwrap().push(format!("connect@{:?}", Instant::now()));
+            if self.connects.pop_front().unwrap_or(true) {
+                self.events = self.sessions.pop_front().unwrap_or_default();
+                Ok(())
+            } else {
+                Err(QuoteStreamError::Connect("refused".into()))
+            }
+        }
+
+        async fn subscribe(&mut self, symbol: &str) -> Result<(), QuoteStreamError> {
+            self.log.lock().unwrap().push(format!("sub {symbol}"));
+            Ok(())
+        }
+
+        async fn unsubscribe(&mut self, symbol: &str) -> Result<(), QuoteStreamError> {
+            self.log.lock().unwrap().push(format!("unsub {symbol}"));
+            Ok(())
+        }
+
+        async fn next_event(&mut self) -> Result<QuoteStreamEvent, QuoteStreamError> {
+            match self.events.pop_front() {
+                Some(ev) => Ok(ev),
+                // Silent connection: only the heartbeat / idle timeout can end it.
+                None => std::future::pending().await,
+            }
+        }
+
+        async fn heartbeat(&mut self) -> Result<(), QuoteStreamError> {
+            self.log.lock().unwrap().push("ping".into());
+            Ok(())
+        }
+    }
+
+    fn tick(symbol: &str, price: f64) -> QuoteTick {
+        QuoteTick {
+            symbol: symbol.into(),
+            price,
+            volume: 1.0,
+            time_ms: 1,
+        }
+    }
+
+    fn entries(log: &Arc<Mutex<Vec<String>>>, prefix: &str) -> usize {
+        log.lock().unwrap().iter().filter(|l| l.starts_with(prefix)).count()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn reconnects_with_backoff_and_resubscribes() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let stream = FakeStream {
+            connects: VecDeque::from([false, false, true, true]),
+            sessions: VecDeque::from([
+                VecDeque::from([QuoteStreamEvent::Ticks(vec![tick("AAPL", 1.0)]), QuoteStreamEvent::Closed]),
+                VecDeque::from([QuoteStreamEvent::Ticks(vec![tick("AAPL", 2.0)])]),
+            ]),
+            events: VecDeque::new(),
+            log: log.clone(),
+        };
+        let (sym_tx, sym_rx) = watch::channel(vec!["AAPL".to_string()]);
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let started = Instant::now();
+        let task = tokio::spawn(run_quote_stream(
+            Box::new(stream),
+            sym_rx,
+            QuoteStreamOptions::default(),
+            move |batch| tx.send(batch).is_ok(),
+        ));
+
+        assert_eq!(rx.recv().await.unwrap(), vec![tick("AAPL", 1.0)]);
+        // Two refused connects: 1 s then 2 s of backoff before the first session.
+        assert!(started.elapsed() >= Duration::from_secs(3));
+        assert_eq!(rx.recv().await.unwrap(), vec![tick("AAPL", 2.0)]);
+        assert_eq!(entries(&log, "sub AAPL"), 2);
+
+        sym_tx.send(vec!["MSFT".to_string()]).unwrap();
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert_eq!(entries(&log, "unsub AAPL"), 1);
+        assert_eq!(entries(&log, "sub MSFT"), 1);
+
+        drop(sym_tx);
+        task.await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn silent_connection_pings_then_reconnects_after_idle_timeout() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let stream = FakeStream {
+            connects: VecDeque::new(),
+            sessions: VecDeque::new(),
+            events: VecDeque::new(),
+            log: log.clone(),
+        };
+        let (sym_tx, sym_rx) = watch::channel(vec!["AAPL".to_string()]);
+        let opts = QuoteStreamOptions::default();
+        let task = tokio::spawn(run_quote_stream(Box::new(stream), sym_rx, opts, |_| true));
+
+        tokio::time::sleep(opts.idle_timeout - Duration::from_secs(1)).await;
+        assert_eq!(entries(&log, "connect"), 1);
+        assert_eq!(entries(&log, "ping"), 2);
+
+        tokio::time::sleep(Duration::from_secs(2) + opts.initial_backoff).await;
+        assert_eq!(entries(&log, "connect"), 2);
+
+        drop(sym_tx);
+        task.await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stops_when_consumer_is_gone() {
+        let stream = FakeStream {
+            connects: VecDeque::new(),
+            sessions: VecDeque::from([VecDeque::from([QuoteStreamEvent::Ticks(vec![tick("AAPL", 1.0)])])]),
+            events: VecDeque::new(),
+            log: Arc::new(Mutex::new(Vec::new())),
+        };
+        let (_sym_tx, sym_rx) = watch::channel(vec!["AAPL".to_string()]);
+        run_quote_stream(Box::new(stream), sym_rx, QuoteStreamOptions::default(), |_| false).await;
+    }
+
+    #[test]
+    fn factory_needs_stream_quotes_and_a_streaming_provider() {
+        let mut config = Config {
+            provider: MarketProviderKind::Finnhub,
+            finnhub_api_key: "k".into(),
+            ..Config::default()
+        };
+        assert!(quote_stream_for(&config).is_none());
+        config.stream_quotes = true;
+        assert_eq!(quote_stream_for(&config).map(|s| s.name()), Some("finnhub"));
+        config.provider = MarketProviderKind::Yahoo;
+        assert!(quote_stream_for(&config).is_none());
+    }
+}
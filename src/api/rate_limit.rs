@@ -0,0 +1,355 @@
+//! Client-side pacing per provider (`rate_limits`), so free-tier quotas are respected up front
+//! instead of being discovered through 429s.
+//!
+//! Each provider has a one-token bucket refilled every `60 / requests_per_minute` seconds; every
+//! [`MarketDataProvider`] call takes a token. Calls that find the bucket empty wait in a queue
+//! ordered by [`RequestPriority`], then arrival.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::sync::oneshot;
+use tokio::time::Instant;
+
+use crate::api::error::ProviderResult;
+use crate::api::historical_query::HistoricalQuery;
+use crate::api::provider::MarketDataProvider;
+use crate::config::{Config, MarketProviderKind};
+use crate::models::historical::HistoricalResponse;
+use crate::models::news::NewsResponse;
+use crate::models::search::SymbolSearchResponse;
+use crate::models::ticker::TickerResponse;
+
+/// Queue order for calls waiting on a provider's bucket (highest first).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RequestPriority {
+    /// Portfolio, alert and FX symbols, and alert daily closes.
+    Background,
+    /// Watchlist quotes.
+    Watchlist,
+    /// The active symbol and anything the user asked for directly (charts, news, search, CLI).
+    Active,
+}
+
+tokio::task_local! {
+    static PRIORITY: RequestPriority;
+}
+
+/// Runs `fut` with its provider calls queued at `priority`. Calls outside any scope are
+/// [`RequestPriority::Active`].
+pub async fn with_priority<F: Future>(priority: RequestPriority, fut: F) -> F::Output {
+    PRIORITY.scope(priority, fut).await
+}
+
+fn current_priority() -> RequestPriority {
+    PRIORITY.try_with(|p| *p).unwrap_or(RequestPriority::Active)
+}
+
+struct Waiter {
+    priority: RequestPriority,
+    seq: u64,
+    tx: oneshot::Sender<()>,
+}
+
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Waiter {}
+
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Max-heap order: higher priority, then earlier arrival.
+impl Ord for Waiter {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+struct LimiterState {
+    interval: Duration,
+    /// When the bucket holds its token again.
+    next_slot: Instant,
+    queue: BinaryHeap<Waiter>,
+    seq: u64,
+    pumping: bool,
+}
+
+/// Waiting calls and when the next one may go.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueStatus {
+    pub depth: usize,
+    pub next_slot_in: Duration,
+}
+
+/// One provider's token bucket and priority queue.
+pub struct RateLimiter {
+    state: Mutex<LimiterState>,
+}
+
+impl RateLimiter {
+    pub fn new(requests_per_minute: u32) -> Arc<Self> {
+        Arc::new(Self {
+            state: Mutex::new(LimiterState {
+                interval: interval_for(requests_per_minute),
+                next_slot: Instant::now(),
+                queue: BinaryHeap::new(),
+                seq: 0,
+                pumping: false,
+            }),
+        })
+    }
+
+    fn state(&self) -> MutexGuard<'_, LimiterState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Applies a changed `rate_limits` entry from the next refill on.
+    pub fn set_rate(&self, requests_per_minute: u32) {
+        self.state().interval = interval_for(requests_per_minute);
+    }
+
+    /// Waits for a token, queued at `priority` behind higher-priority and earlier callers.
+    pub async fn acquire(self: &Arc<Self>, priority: RequestPriority) {
+        let rx = {
+            let mut state = self.state();
+            let now = Instant::now();
+            if state.queue.is_empty() && now >= state.next_slot {
+                state.next_slot = now + state.interval;
+                return;
+            }
+            let (tx, rx) = oneshot::channel();
+            state.seq += 1;
+            let seq = state.seq;
+            state.queue.push(Waiter { priority, seq, tx });
+            if !state.pumping {
+                state.pumping = true;
+                tokio::spawn(Arc::clone(self).pump());
+            }
+            rx
+        };
+        // The pump only stops early when the runtime shuts down; go ahead rather than hang.
+        let _ = rx.await;
+    }
+
+    /// Hands out tokens to queued callers as they refill; exits when the queue is empty.
+    async fn pump(self: Arc<Self>) {
+        loop {
+            let wait = {
+                let mut state = self.state();
+                let now = Instant::now();
+                if now < state.next_slot {
+                    state.next_slot - now
+                } else {
+                    match state.queue.pop() {
+                        Some(waiter) => {
+                            // A dropped caller does not use up the token.
+                            if waiter.tx.send(()).is_ok() {
+                                state.next_slot = now + state.interval;
+                            }
+                            continue;
+                        }
+                        None => {
+                            state.pumping = false;
+                            return;
+                        }
+                    }
+                }
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// `None` while nothing is queued.
+    pub fn queue_status(&self) -> Option<QueueStatus> {
+        let state = self.state();
+        let depth = state.queue.iter().filter(|w| !w.tx.is_closed()).count();
+        (depth > 0).then(|| QueueStatus {
+            depth,
+            next_slot_in: state.next_slot.saturating_duration_since(Instant::now()),
+        })
+    }
+}
+
+fn interval_for(requests_per_minute: u32) -> Duration {
+    Duration::from_secs(60) / requests_per_minute.max(1)
+}
+
+fn limiters() -> MutexGuard<'static, HashMap<MarketProviderKind, Arc<RateLimiter>>> {
+    static LIMITERS: OnceLock<Mutex<HashMap<MarketProviderKind, Arc<RateLimiter>>>> = OnceLock::new();
+    LIMITERS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Process-wide limiter for `kind`, created at `requests_per_minute` and re-rated on later calls.
+pub fn limiter_for(kind: MarketProviderKind, requests_per_minute: u32) -> Arc<RateLimiter> {
+    let mut map = limiters();
+    match map.get(&kind) {
+        Some(limiter) => {
+            limiter.set_rate(requests_per_minute);
+            Arc::clone(limiter)
+        }
+        None => {
+            let limiter = RateLimiter::new(requests_per_minute);
+            map.insert(kind, Arc::clone(&limiter));
+            limiter
+        }
+    }
+}
+
+/// Calls waiting across every provider, and the soonest next slot among them.
+pub fn queue_status() -> Option<QueueStatus> {
+    limiters()
+        .values()
+        .filter_map(|l| l.queue_status())
+        .reduce(|a, b| QueueStatus {
+            depth: a.depth + b.depth,
+            next_slot_in: a.next_slot_in.min(b.next_slot_in),
+        })
+}
+
+/// Paces every call to `inner` through `kind`'s limiter while
+/// [`Config::requests_per_minute`] has a limit for it.
+pub struct RateLimitedProvider {
+    kind: MarketProviderKind,
+    inner: Arc<dyn MarketDataProvider + Send + Sync>,
+}
+
+impl RateLimitedProvider {
+    pub fn new(kind: MarketProviderKind, inner: Arc<dyn MarketDataProvider + Send + Sync>) -> Self {
+        Self { kind, inner }
+    }
+
+    async fn wait_for_slot(&self, config: &Config) {
+        if let Some(rpm) = config.requests_per_minute(self.kind) {
+            limiter_for(self.kind, rpm).acquire(current_priority()).await;
+        }
+    }
+}
+
+#[async_trait]
+impl MarketDataProvider for RateLimitedProvider {
+    async fn get_quote(&self, symbol: &str, config: &Config) -> ProviderResult<TickerResponse> {
+        self.wait_for_slot(config).await;
+        self.inner.get_quote(symbol, config).await
+    }
+
+    async fn get_historical(
+        &self,
+        symbol: &str,
+        query: &HistoricalQuery<'_>,
+        config: &Config,
+    ) -> ProviderResult<HistoricalResponse> {
+        self.wait_for_slot(config).await;
+        self.inner.get_historical(symbol, query, config).await
+    }
+
+    async fn search_symbols(&self, query: &str, config: &Config) -> ProviderResult<SymbolSearchResponse> {
+        self.wait_for_slot(config).await;
+        self.inner.search_symbols(query, config).await
+    }
+
+    async fn get_news(&self, symbol: &str, config: &Config) -> ProviderResult<NewsResponse> {
+        self.wait_for_slot(config).await;
+        self.inner.get_news(symbol, config).await
+    }
+
+    fn fx_symbol(&self, from: &str, to: &str) -> String {
+        self.inner.fx_symbol(from, to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn bucket_spaces_calls_by_the_configured_rate() {
+        let limiter = RateLimiter::new(5);
+        let start = Instant::now();
+        limiter.acquire(RequestPriority::Active).await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        limiter.acquire(RequestPriority::Active).await;
+        assert_eq!(start.elapsed(), Duration::from_secs(12));
+
+        // The refill already scheduled keeps the old spacing.
+        limiter.set_rate(60);
+        limiter.acquire(RequestPriority::Active).await;
+        assert_eq!(start.elapsed(), Duration::from_secs(24));
+        limiter.acquire(RequestPriority::Active).await;
+        assert_eq!(start.elapsed(), Duration::from_secs(25));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn queue_serves_active_then_watchlist_then_background() {
+        let limiter = RateLimiter::new(60);
+        limiter.acquire(RequestPriority::Active).await;
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        for (name, priority) in [
+            ("alert", RequestPriority::Background),
+            ("watch-1", RequestPriority::Watchlist),
+            ("active", RequestPriority::Active),
+            ("watch-2", RequestPriority::Watchlist),
+        ] {
+            let limiter = Arc::clone(&limiter);
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                limiter.acquire(priority).await;
+                tx.send(name).unwrap();
+            });
+            tokio::task::yield_now().await;
+        }
+        drop(tx);
+
+        let status = limiter.queue_status().unwrap();
+        assert_eq!(status.depth, 4);
+        assert_eq!(status.next_slot_in, Duration::from_secs(1));
+
+        let mut order = Vec::new();
+        while let Some(name) = rx.recv().await {
+            order.push(name);
+        }
+        assert_eq!(order, ["active", "watch-1", "watch-2", "alert"]);
+        assert_eq!(limiter.queue_status(), None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn dropped_waiters_do_not_use_up_a_slot() {
+        let limiter = RateLimiter::new(60);
+        limiter.acquire(RequestPriority::Active).await;
+        let start = Instant::now();
+
+        let abandoned = tokio::spawn({
+            let limiter = Arc::clone(&limiter);
+            async move { limiter.acquire(RequestPriority::Active).await }
+        });
+        tokio::task::yield_now().await;
+        abandoned.abort();
+        let _ = abandoned.await;
+
+        limiter.acquire(RequestPriority::Background).await;
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn priority_scope_reaches_provider_calls() {
+        assert_eq!(current_priority(), RequestPriority::Active);
+        let seen = with_priority(RequestPriority::Background, async { current_priority() }).await;
+        assert_eq!(seen, RequestPriority::Background);
+    }
+}
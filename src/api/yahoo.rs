@@ -19,7 +19,7 @@ use crate::config::Config;
 use crate::models::historical::{HistoricalData, HistoricalResponse};
 use crate::models::news::{NewsItem, NewsResponse, Publisher};
 use crate::models::search::{SymbolResult, SymbolSearchResponse};
-use crate::models::ticker::{TickerResponse, TickerResult};
+use crate::models::ticker::{MarketState, TickerResponse, TickerResult};
 
 const QUERY1: &str = "https://query1.finance.yahoo.com";
 const QUERY2: &str = "https://query2.finance.yahoo.com";
@@ -119,6 +119,20 @@ struct V7QuoteItem {
     regular_market_volume: Option<serde_json::Value>,
     regular_market_time: Option<i64>,
     regular_market_previous_close: Option<f64>,
+    #[serde(default)]
+    market_state: Option<String>,
+    #[serde(default)]
+    pre_market_price: Option<f64>,
+    #[serde(default)]
+    post_market_price: Option<f64>,
+    #[serde(default)]
+    fifty_two_week_low: Option<f64>,
+    #[serde(default)]
+    fifty_two_week_high: Option<f64>,
+    #[serde(default)]
+    market_cap: Option<f64>,
+    #[serde(default)]
+    currency: Option<String>,
 }
 
 fn v7_volume_as_f64(v: Option<&serde_json::Value>) -> f64 {
@@ -168,6 +182,13 @@ fn v7_item_to_ticker_response(q: &V7QuoteItem, requested: &str) -> ProviderResul
         .clone()
         .unwrap_or_else(|| requested.to_uppercase());
 
+    let market_state = q.market_state.as_deref().and_then(MarketState::from_yahoo);
+    let ext_price = match market_state {
+        Some(MarketState::Pre) => q.pre_market_price,
+        Some(MarketState::Post) => q.post_market_price,
+        _ => None,
+    };
+
     Ok(TickerResponse {
         ticker: ticker_name,
         results: vec![TickerResult {
@@ -177,6 +198,13 @@ fn v7_item_to_ticker_response(q: &V7QuoteItem, requested: &str) -> ProviderResul
             c: close,
             v: vol,
             t: t_ms,
+            prev_close: q.regular_market_previous_close,
+            market_state,
+            ext_price,
+            week52_low: q.fifty_two_week_low,
+            week52_high: q.fifty_two_week_high,
+            market_cap: q.market_cap,
+            currency: q.currency.clone(),
         }],
         status: "OK".to_string(),
         error: None,
@@ -215,6 +243,12 @@ fn v7_select_item_for_symbol<'a>(
 /// | `regularMarketPrice` | **`c`** (required for a successful row) |
 /// | `regularMarketVolume` | **`v`** |
 /// | `regularMarketTime` (Unix **seconds**) | **`t`** = ms |
+/// | `regularMarketPreviousClose` | **`prev_close`** |
+/// | `marketState` | **`market_state`** (see [`MarketState::from_yahoo`]) |
+/// | `preMarketPrice` / `postMarketPrice` | **`ext_price`** (only while `market_state` is PRE / POST) |
+/// | `fiftyTwoWeekLow` / `fiftyTwoWeekHigh` | **`week52_low`** / **`week52_high`** |
+/// | `marketCap` | **`market_cap`** |
+/// | `currency` | **`currency`** (as reported, e.g. `GBp` for LSE pence) |
 fn v7_envelope_to_ticker(env: &V7QuoteEnvelope, requested: &str) -> ProviderResult<TickerResponse> {
     if let Some(err) = &env.quote_response.error {
         let msg = err
@@ -541,6 +575,16 @@ fn chart_to_ticker(env: &ChartEnvelope, requested: &str) -> ProviderResult<Ticke
             c: close,
             v: vol,
             t: t_ms,
+            prev_close: meta.previous_close.or(meta.chart_previous_close),
+            market_state: meta
+                .current_trading_period
+                .as_ref()
+                .map(|p| p.state_at(Utc::now().timestamp())),
+            ext_price: None,
+            week52_low: meta.fifty_two_week_low,
+            week52_high: meta.fifty_two_week_high,
+            market_cap: None,
+            currency: meta.currency.clone(),
         }],
         status: "OK".to_string(),
         error: None,
@@ -1304,6 +1348,48 @@ struct ChartMeta {
     regular_market_time: Option<i64>,
     #[serde(default)]
     chart_previous_close: Option<f64>,
+    #[serde(default)]
+    previous_close: Option<f64>,
+    #[serde(default)]
+    fifty_two_week_low: Option<f64>,
+    #[serde(default)]
+    fifty_two_week_high: Option<f64>,
+    #[serde(default)]
+    currency: Option<String>,
+    #[serde(default)]
+    current_trading_period: Option<ChartTradingPeriods>,
+}
+
+/// v8 `meta.currentTradingPeriod` — today's pre / regular / post windows (Unix seconds).
+#[derive(Debug, Deserialize)]
+struct ChartTradingPeriods {
+    pre: Option<ChartTradingWindow>,
+    regular: Option<ChartTradingWindow>,
+    post: Option<ChartTradingWindow>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChartTradingWindow {
+    start: i64,
+    end: i64,
+}
+
+impl ChartTradingPeriods {
+    /// The v8 chart has no `marketState`; infer it from which window contains `now_sec`.
+    fn state_at(&self, now_sec: i64) -> MarketState {
+        let inside = |w: &Option<ChartTradingWindow>| {
+            w.as_ref().is_some_and(|w| w.start <= now_sec && now_sec < w.end)
+        };
+        if inside(&self.regular) {
+            MarketState::Regular
+        } else if inside(&self.pre) {
+            MarketState::Pre
+        } else if inside(&self.post) {
+            MarketState::Post
+        } else {
+            MarketState::Closed
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -1617,7 +1703,13 @@ mod tests {
                     "regularMarketDayHigh": 196.25,
                     "regularMarketDayLow": 193.5,
                     "regularMarketVolume": 52800000,
-                    "regularMarketTime": 1700000000
+                    "regularMarketTime": 1700000000,
+                    "regularMarketPreviousClose": 190.0,
+                    "marketState": "POST",
+                    "fiftyTwoWeekLow": 164.08,
+                    "fiftyTwoWeekHigh": 199.62,
+                    "marketCap": 3040000000000,
+                    "currency": "USD"
                 }],
                 "error": null
             }
@@ -1632,6 +1724,29 @@ mod tests {
         assert!((bar.l - 193.5).abs() < 1e-9);
         assert!((bar.v - 52_800_000.0).abs() < 1.0);
         assert_eq!(bar.t, 1_700_000_000_000u64);
+        assert_eq!(bar.prev_close, Some(190.0));
+        assert_eq!(bar.market_state, Some(MarketState::Post));
+        assert!((bar.day_change() - 5.5).abs() < 1e-9);
+        assert_eq!(bar.ext_price, None);
+        assert_eq!((bar.week52_low, bar.week52_high), (Some(164.08), Some(199.62)));
+        assert_eq!(bar.market_cap, Some(3.04e12));
+        assert_eq!(bar.currency.as_deref(), Some("USD"));
+    }
+
+    #[test]
+    fn v7_envelope_maps_extended_hours_price_for_session() {
+        let json = r#"{"quoteResponse":{"result":[{
+            "symbol":"AAPL","regularMarketPrice":100.0,
+            "preMarketPrice":101.0,"postMarketPrice":98.0,"marketState":"PRE"
+        }],"error":null}}"#;
+        let env: V7QuoteEnvelope = serde_json::from_str(json).expect("parse v7");
+        let bar = v7_envelope_to_ticker(&env, "AAPL").expect("map").results.remove(0);
+        assert_eq!(bar.extended_quote(), Some((MarketState::Pre, 101.0)));
+
+        let json = json.replace("\"PRE\"", "\"POSTPOST\"");
+        let env: V7QuoteEnvelope = serde_json::from_str(&json).expect("parse v7");
+        let bar = v7_envelope_to_ticker(&env, "AAPL").expect("map").results.remove(0);
+        assert_eq!(bar.extended_quote(), Some((MarketState::Post, 98.0)));
     }
 
     #[test]
@@ -1670,6 +1785,20 @@ mod tests {
         let bar = tr.latest_result().expect("bar");
         assert!(bar.c > 0.0);
         assert!(bar.t > 1_000_000_000_000); // ms
+        assert_eq!(bar.prev_close, Some(187.44));
+    }
+
+    #[test]
+    fn chart_trading_periods_state_at() {
+        let p = ChartTradingPeriods {
+            pre: Some(ChartTradingWindow { start: 100, end: 200 }),
+            regular: Some(ChartTradingWindow { start: 200, end: 300 }),
+            post: Some(ChartTradingWindow { start: 300, end: 400 }),
+        };
+        assert_eq!(p.state_at(150), MarketState::Pre);
+        assert_eq!(p.state_at(200), MarketState::Regular);
+        assert_eq!(p.state_at(399), MarketState::Post);
+        assert_eq!(p.state_at(400), MarketState::Closed);
     }
 
     #[test]
@@ -0,0 +1,446 @@
+//! Delivery of fired alerts to the configured [`AlertSink`]s (webhooks and shell commands).
+//!
+//! The TUI spawns [`spawn_alert_deliveries`] and logs failures; `alerts watch` awaits
+//! [`deliver_fired_alerts`] and reports failures with the cycle's errors. Both append one
+//! [`AlertHistoryEntry`] per firing to the alert history file as it fires
+//! ([`firing_entries`]), then one `outcome` line per firing once its desktop toast and sinks
+//! have answered.
+
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::oneshot;
+
+use crate::api::http::shared_client;
+use crate::app::alerts::fired_alert_text;
+use crate::models::alert_history::{
+    append_alert_history, AlertDeliveryError, AlertHistoryEntry, BELL_CHANNEL, DESKTOP_CHANNEL,
+};
+use crate::models::alert_sink::{
+    sinks_for_alert, AlertEvent, AlertSink, AlertSinkTarget, WebhookTemplate,
+};
+use crate::models::alerts::{Alert, AlertQuote, AlertThresholdUnit};
+
+/// Upper bound for a sink command to read its payload and exit.
+pub(crate) const ALERT_COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Error)]
+pub enum AlertSinkError {
+    #[error("webhook request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("webhook returned HTTP {0}")]
+    Status(u16),
+    #[error("could not run command: {0}")]
+    Spawn(#[from] std::io::Error),
+    #[error("command exited with {0}")]
+    Exit(std::process::ExitStatus),
+    #[error("command timed out after {}s", ALERT_COMMAND_TIMEOUT.as_secs())]
+    Timeout,
+}
+
+/// Payload for a newly fired alert, compared against `quotes`.
+pub(crate) fn alert_event(alert: &Alert, quotes: &[AlertQuote], fired_at: DateTime<Utc>) -> AlertEvent {
+    let quote = quotes.iter().find(|q| q.symbol == alert.symbol);
+    let last = quote.map(|q| alert.evaluation_price(q));
+    let message = fired_alert_text(&alert.symbol, alert, quote);
+    let threshold = match (alert.condition.unit(), alert.sma_days) {
+        (AlertThresholdUnit::Days, Some(days)) => f64::from(days),
+        _ => alert.price,
+    };
+    AlertEvent {
+        symbol: alert.symbol.clone(),
+        condition: alert.condition,
+        threshold,
+        last,
+        currency: quote.and_then(|q| q.currency.clone()),
+        message,
+        fired_at,
+    }
+}
+
+/// A newly fired alert and the sinks it selected.
+#[derive(Debug, Clone)]
+pub(crate) struct FiredAlert {
+    pub event: AlertEvent,
+    pub sinks: Vec<AlertSink>,
+}
+
+/// One [`FiredAlert`] per newly fired alert (also when it selects no sinks, for the history).
+pub(crate) fn fired_alerts(
+    sinks: &[AlertSink],
+    alerts: &[Alert],
+    newly: &[usize],
+    quotes: &[AlertQuote],
+) -> Vec<FiredAlert> {
+    newly
+        .iter()
+        .map(|&idx| {
+            let alert = &alerts[idx];
+            let fired_at = alert.last_fired.unwrap_or_else(Utc::now);
+            FiredAlert {
+                event: alert_event(alert, quotes, fired_at),
+                sinks: sinks_for_alert(alert, sinks).cloned().collect(),
+            }
+        })
+        .collect()
+}
+
+/// Result of the coalesced desktop toast for one batch of firings, sent from its thread by
+/// [`fire_alert_notifications`](crate::app::alerts::fire_alert_notifications).
+pub(crate) type DesktopToast = oneshot::Receiver<Result<(), String>>;
+
+/// History entries written as the alerts fire: the bell is delivered; the desktop toast (when
+/// one was shown) and the sinks are pending until [`deliver_fired_alerts`] settles them.
+pub(crate) fn firing_entries(fired: &[FiredAlert], toast: bool) -> Vec<AlertHistoryEntry> {
+    fired
+        .iter()
+        .map(|f| AlertHistoryEntry {
+            event: f.event.clone(),
+            delivered: vec![BELL_CHANNEL.to_string()],
+            errors: Vec::new(),
+            pending: toast
+                .then(|| DESKTOP_CHANNEL.to_string())
+                .into_iter()
+                .chain(f.sinks.iter().map(|s| s.name.clone()))
+                .collect(),
+            outcome: false,
+        })
+        .collect()
+}
+
+/// Waits for the desktop toast and sends every fired alert to its sinks concurrently; one
+/// `outcome` entry per alert that had anything pending.
+pub(crate) async fn deliver_fired_alerts(
+    fired: Vec<FiredAlert>,
+    toast: Option<DesktopToast>,
+) -> Vec<AlertHistoryEntry> {
+    let toast = match toast {
+        Some(rx) => Some(
+            rx.await
+                .unwrap_or_else(|_| Err("notification thread exited".to_string())),
+        ),
+        None => None,
+    };
+    let toast = toast.as_ref();
+    let entries = futures_util::future::join_all(fired.into_iter().map(|f| async move {
+        let results =
+            futures_util::future::join_all(f.sinks.iter().map(|sink| deliver_to_sink(sink, &f.event)))
+                .await;
+        let mut entry = AlertHistoryEntry {
+            event: f.event,
+            delivered: Vec::new(),
+            errors: Vec::new(),
+            pending: Vec::new(),
+            outcome: true,
+        };
+        match toast {
+            Some(Ok(())) => entry.delivered.push(DESKTOP_CHANNEL.to_string()),
+            Some(Err(e)) => entry.errors.push(AlertDeliveryError {
+                channel: DESKTOP_CHANNEL.to_string(),
+                error: e.clone(),
+            }),
+            None => {}
+        }
+        for (sink, result) in f.sinks.iter().zip(results) {
+            match result {
+                Ok(()) => entry.delivered.push(sink.name.clone()),
+                Err(e) => entry.errors.push(AlertDeliveryError {
+                    channel: sink.name.clone(),
+                    error: e.to_string(),
+                }),
+            }
+        }
+        entry
+    }))
+    .await;
+    entries
+        .into_iter()
+        .filter(|e| !e.delivered.is_empty() || !e.errors.is_empty())
+        .collect()
+}
+
+fn append_history_or_log(path: &std::path::Path, entries: &[AlertHistoryEntry]) {
+    if let Err(e) = append_alert_history(path, entries) {
+        tracing::warn!(
+            target: "stockterm::alerts",
+            path = %path.display(),
+            error = %e,
+            "could not append alert history"
+        );
+    }
+}
+
+/// For the TUI: appends every firing to `history` right away, then delivers in the background
+/// ([`deliver_fired_alerts`]); failures go to the log and outcomes to `history`.
+pub(crate) fn spawn_alert_deliveries(
+    fired: Vec<FiredAlert>,
+    toast: Option<DesktopToast>,
+    history: Option<PathBuf>,
+) {
+    if fired.is_empty() {
+        return;
+    }
+    if let Some(path) = &history {
+        append_history_or_log(path, &firing_entries(&fired, toast.is_some()));
+    }
+    tokio::spawn(async move {
+        let entries = deliver_fired_alerts(fired, toast).await;
+        for failure in entries.iter().flat_map(|e| &e.errors) {
+            tracing::warn!(
+                target: "stockterm::alerts",
+                sink = %failure.channel,
+                error = %failure.error,
+                "alert sink delivery failed"
+            );
+        }
+        if let Some(path) = history {
+            append_history_or_log(&path, &entries);
+        }
+    });
+}
+
+pub async fn deliver_to_sink(sink: &AlertSink, event: &AlertEvent) -> Result<(), AlertSinkError> {
+    match &sink.target {
+        AlertSinkTarget::Webhook { url, template } => post_webhook(url, *template, event).await,
+        AlertSinkTarget::Command { command } => run_command(command, event).await,
+    }
+}
+
+async fn post_webhook(
+    url: &str,
+    template: WebhookTemplate,
+    event: &AlertEvent,
+) -> Result<(), AlertSinkError> {
+    let req = shared_client().post(url);
+    let req = match template {
+        WebhookTemplate::Json => req.json(event),
+        WebhookTemplate::Slack => req.json(&serde_json::json!({ "text": event.message })),
+        WebhookTemplate::Discord => req.json(&serde_json::json!({ "content": event.message })),
+        WebhookTemplate::Ntfy => req
+            .header("Title", format!("StockTerm: {}", event.symbol))
+            .body(event.message.clone()),
+    };
+    let status = req.send().await?.status();
+    if !status.is_success() {
+        return Err(AlertSinkError::Status(status.as_u16()));
+    }
+    Ok(())
+}
+
+fn shell_command(command: &str) -> tokio::process::Command {
+    #[cfg(windows)]
+    {
+        let mut cmd = tokio::process::Command::new("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    }
+    #[cfg(not(windows))]
+    {
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    }
+}
+
+/// Runs `command` with the event JSON (one line) on stdin; output is discarded so the TUI stays
+/// intact.
+async fn run_command(command: &str, event: &AlertEvent) -> Result<(), AlertSinkError> {
+    let mut child = shell_command(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()?;
+    let mut payload = serde_json::to_vec(event).unwrap_or_default();
+    payload.push(b'\n');
+    let run = async {
+        if let Some(mut stdin) = child.stdin.take() {
+            // A command that ignores stdin may close it early; its exit status still decides.
+            let _ = stdin.write_all(&payload).await;
+        }
+        child.wait().await
+    };
+    let status = tokio::time::timeout(ALERT_COMMAND_TIMEOUT, run)
+        .await
+        .map_err(|_| AlertSinkError::Timeout)??;
+    if !status.success() {
+        return Err(AlertSinkError::Exit(status));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::alerts::AlertCondition;
+    use wiremock::matchers::{body_json, body_string, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn event() -> AlertEvent {
+        let mut alert = Alert::new("AAPL".into(), AlertCondition::Above, 200.0);
+        alert.last_fired = Some(DateTime::from_timestamp(1_700_000_000, 0).unwrap());
+        let quotes = [AlertQuote::new("AAPL", 201.5)];
+        fired_alerts(&[], &[alert], &[0], &quotes).remove(0).event
+    }
+
+    fn fired(sinks: Vec<AlertSink>) -> Vec<FiredAlert> {
+        vec![FiredAlert { event: event(), sinks }]
+    }
+
+    fn webhook(name: &str, url: String, template: WebhookTemplate) -> AlertSink {
+        AlertSink {
+            name: name.into(),
+            target: AlertSinkTarget::Webhook { url, template },
+        }
+    }
+
+    #[test]
+    fn event_carries_threshold_last_and_message() {
+        let ev = event();
+        assert_eq!(ev.symbol, "AAPL");
+        assert_eq!(ev.threshold, 200.0);
+        assert_eq!(ev.last, Some(201.5));
+        assert!(ev.message.starts_with("AAPL "), "{}", ev.message);
+        assert!(ev.message.ends_with("· last $201.50"), "{}", ev.message);
+        assert_eq!(ev.fired_at.timestamp(), 1_700_000_000);
+    }
+
+    #[test]
+    fn event_message_uses_the_quote_currency() {
+        let alert = Alert::new("VOD.L".into(), AlertCondition::Above, 70.0);
+        let quote = AlertQuote {
+            currency: Some("GBp".into()),
+            ..AlertQuote::new("VOD.L", 71.0)
+        };
+        let ev = alert_event(&alert, &[quote], Utc::now());
+        assert_eq!(ev.message, "VOD.L Above 70.00p · last 71.00p");
+    }
+
+    #[tokio::test]
+    async fn webhook_templates_post_expected_bodies() {
+        crate::api::http::ensure_shared_client_for_tests();
+        let srv = MockServer::start().await;
+        let ev = event();
+        Mock::given(method("POST"))
+            .and(path("/json"))
+            .and(body_json(&ev))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&srv)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/slack"))
+            .and(body_json(serde_json::json!({ "text": ev.message })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&srv)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/discord"))
+            .and(body_json(serde_json::json!({ "content": ev.message })))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&srv)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/ntfy"))
+            .and(header("Title", "StockTerm: AAPL"))
+            .and(body_string(ev.message.clone()))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&srv)
+            .await;
+
+        let sinks = [
+            ("json", WebhookTemplate::Json),
+            ("slack", WebhookTemplate::Slack),
+            ("discord", WebhookTemplate::Discord),
+            ("ntfy", WebhookTemplate::Ntfy),
+        ]
+        .into_iter()
+        .map(|(p, t)| webhook(p, format!("{}/{p}", srv.uri()), t))
+        .collect();
+        let entries = deliver_fired_alerts(fired(sinks), None).await;
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].outcome);
+        assert!(entries[0].errors.is_empty());
+        assert_eq!(entries[0].delivered, ["json", "slack", "discord", "ntfy"]);
+    }
+
+    #[tokio::test]
+    async fn webhook_error_status_is_reported_per_sink() {
+        crate::api::http::ensure_shared_client_for_tests();
+        let srv = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&srv)
+            .await;
+        let entries = deliver_fired_alerts(
+            fired(vec![webhook("team", srv.uri(), WebhookTemplate::Slack)]),
+            None,
+        )
+        .await;
+        assert!(entries[0].delivered.is_empty());
+        assert_eq!(
+            entries[0].errors,
+            [AlertDeliveryError {
+                channel: "team".into(),
+                error: "webhook returned HTTP 500".into(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn firing_is_recorded_before_delivery_and_toast_failure_is_an_error() {
+        let sink = AlertSink {
+            name: "log".into(),
+            target: AlertSinkTarget::Command { command: "exit 0".into() },
+        };
+        let firing = firing_entries(&fired(vec![sink]), true);
+        assert_eq!(firing[0].delivered, [BELL_CHANNEL]);
+        assert_eq!(firing[0].pending, [DESKTOP_CHANNEL, "log"]);
+
+        let (tx, rx) = oneshot::channel();
+        tx.send(Err("no notification daemon".to_string())).unwrap();
+        let entries = deliver_fired_alerts(fired(Vec::new()), Some(rx)).await;
+        assert!(entries[0].delivered.is_empty());
+        assert_eq!(
+            entries[0].errors,
+            [AlertDeliveryError {
+                channel: DESKTOP_CHANNEL.into(),
+                error: "no notification daemon".into(),
+            }]
+        );
+        assert!(deliver_fired_alerts(fired(Vec::new()), None).await.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_receives_event_json_on_stdin() {
+        let out = std::env::temp_dir().join(format!("stockterm-sink-{}.json", std::process::id()));
+        let sink = AlertSink {
+            name: "log".into(),
+            target: AlertSinkTarget::Command {
+                command: format!("cat > '{}'", out.display()),
+            },
+        };
+        let ev = event();
+        deliver_to_sink(&sink, &ev).await.expect("command ok");
+        let written = std::fs::read_to_string(&out).unwrap();
+        let _ = std::fs::remove_file(&out);
+        assert_eq!(serde_json::from_str::<AlertEvent>(written.trim()).unwrap(), ev);
+
+        let failing = AlertSink {
+            name: "bad".into(),
+            target: AlertSinkTarget::Command { command: "exit 3".into() },
+        };
+        assert!(matches!(
+            deliver_to_sink(&failing, &ev).await,
+            Err(AlertSinkError::Exit(_))
+        ));
+    }
+}
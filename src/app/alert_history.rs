@@ -0,0 +1,360 @@
+//! Alerts history sub-view: browse, filter (by symbol) and export the alert history file.
+
+use chrono::Local;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::app::keyboard::letter_key_plain;
+use crate::app::portfolio::{expand_csv_path, MAX_CSV_PATH_LEN};
+use crate::app::styles::ResolvedTheme;
+use crate::app::table_filter::{filter_row_indices, filter_title_suffix};
+use crate::app::{AlertHistoryView, App};
+use crate::app::app::DEFAULT_ALERT_HISTORY_CSV_PATH;
+use crate::config::keymap::{Action, BindingLayer};
+use crate::config::Config;
+use crate::models::alert_history::{read_alert_history, write_alert_history_csv};
+use crate::models::alert_sink::AlertEvent;
+use crate::models::currency::format_money;
+
+/// Entry indices shown for `query` (symbol substring), newest first.
+pub(crate) fn alert_history_filter_indices(view: &AlertHistoryView, query: &str) -> Vec<usize> {
+    filter_row_indices(view.entries.len(), |i| view.entries[i].event.symbol.as_str(), query)
+}
+
+impl App {
+    /// Reads the history file into [`App::alert_history`]; read errors show in the view.
+    pub fn open_alert_history(&mut self) {
+        let mut view = AlertHistoryView::default();
+        match Config::alert_history_path()
+            .map_err(|e| e.to_string())
+            .and_then(|path| read_alert_history(&path).map_err(|e| format!("{}: {e}", path.display())))
+        {
+            Ok(history) => {
+                view.entries = history.entries;
+                view.entries.reverse();
+                view.bad_lines = history.bad_lines;
+            }
+            Err(e) => view.inline_error = Some(format!("Could not read alert history: {e}")),
+        }
+        if !view.entries.is_empty() {
+            view.state.select(Some(0));
+        }
+        self.filter_query.clear();
+        self.filter_input_mode = false;
+        self.alert_history = Some(view);
+    }
+
+    pub fn close_alert_history(&mut self) {
+        self.alert_history = None;
+        self.filter_query.clear();
+        self.filter_input_mode = false;
+    }
+
+    pub(crate) fn clamp_alert_history_selection(&mut self) {
+        let Some(view) = self.alert_history.as_mut() else {
+            return;
+        };
+        let shown = alert_history_filter_indices(view, &self.filter_query).len();
+        if shown == 0 {
+            view.state.select(None);
+        } else {
+            let sel = view.state.selected().unwrap_or(0).min(shown - 1);
+            view.state.select(Some(sel));
+        }
+    }
+}
+
+/// Writes the filtered rows (newest first) to the prompt's path.
+fn submit_alert_history_export(app: &mut App) {
+    let query = app.filter_query.clone();
+    let Some(view) = app.alert_history.as_mut() else {
+        return;
+    };
+    let Some(path) = view.export_path.as_deref().and_then(expand_csv_path) else {
+        view.inline_error = Some("File path required".into());
+        return;
+    };
+    let shown = alert_history_filter_indices(view, &query);
+    let written = std::fs::File::create(&path).map_err(|e| e.to_string()).and_then(|file| {
+        write_alert_history_csv(shown.iter().map(|&i| &view.entries[i]), file)
+            .map_err(|e| e.to_string())
+    });
+    match written {
+        Ok(()) => {
+            view.export_path = None;
+            view.inline_error = None;
+            view.status = Some(format!("Exported {} firings to {}", shown.len(), path.display()));
+        }
+        Err(e) => view.inline_error = Some(format!("Could not write {}: {e}", path.display())),
+    }
+}
+
+/// Export path prompt; owns every key (paths may contain `q`) until Enter / Esc.
+pub(crate) fn handle_alert_history_export_keys(app: &mut App, key: KeyEvent) {
+    use Action::*;
+    let action = app
+        .resolved_keymap
+        .action(BindingLayer::AlertHistoryExportPrompt, &key);
+    let Some(view) = app.alert_history.as_mut() else {
+        return;
+    };
+    let Some(buf) = view.export_path.as_mut() else {
+        return;
+    };
+    match action {
+        Some(AlertHistoryExportEsc) => {
+            view.export_path = None;
+            view.inline_error = None;
+        }
+        Some(AlertHistoryExportEnter) => submit_alert_history_export(app),
+        Some(AlertHistoryExportBackspace) => {
+            buf.pop();
+            view.inline_error = None;
+        }
+        _ => {
+            let KeyCode::Char(c) = key.code else {
+                return;
+            };
+            if letter_key_plain(key.modifiers) && buf.len() < MAX_CSV_PATH_LEN {
+                buf.push(c);
+                view.inline_error = None;
+            }
+        }
+    }
+}
+
+pub(crate) fn handle_alert_history_keys(app: &mut App, key: KeyEvent) {
+    if app.consume_filter_input_key(&key) {
+        app.clamp_alert_history_selection();
+        return;
+    }
+    let Some(action) = app.resolved_keymap.action(BindingLayer::AlertHistory, &key) else {
+        return;
+    };
+    let shown = app
+        .alert_history
+        .as_ref()
+        .map(|v| alert_history_filter_indices(v, &app.filter_query).len())
+        .unwrap_or(0);
+    let Some(view) = app.alert_history.as_mut() else {
+        return;
+    };
+    match action {
+        Action::AlertHistoryClose
+            if key.modifiers == KeyModifiers::NONE || letter_key_plain(key.modifiers) =>
+        {
+            app.close_alert_history();
+        }
+        Action::AlertHistoryFilter if key.modifiers == KeyModifiers::NONE => {
+            app.filter_input_mode = true;
+        }
+        Action::AlertHistoryExport if letter_key_plain(key.modifiers) => {
+            view.export_path = Some(DEFAULT_ALERT_HISTORY_CSV_PATH.to_string());
+            view.inline_error = None;
+            view.status = None;
+        }
+        Action::AlertHistoryRowUp if shown > 0 => {
+            let sel = view.state.selected().map_or(0, |i| i.saturating_sub(1));
+            view.state.select(Some(sel));
+        }
+        Action::AlertHistoryRowDown if shown > 0 => {
+            let sel = view.state.selected().map_or(0, |i| (i + 1).min(shown - 1));
+            view.state.select(Some(sel));
+        }
+        _ => {}
+    }
+}
+
+/// Price column: the compared price in the currency recorded with the firing.
+fn history_price_text(ev: &AlertEvent) -> String {
+    ev.last
+        .map(|p| format_money(p, ev.currency.as_deref().unwrap_or("USD")))
+        .unwrap_or_else(|| "—".to_string())
+}
+
+pub(crate) fn draw_alert_history(f: &mut Frame, app: &mut App, area: Rect, theme: ResolvedTheme) {
+    let border_st = Style::default().fg(theme.border).bg(theme.background);
+    let query = app.filter_query.clone();
+    let filtering = app.filter_input_mode;
+    let Some(view) = app.alert_history.as_mut() else {
+        return;
+    };
+    let shown = alert_history_filter_indices(view, &query);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(area);
+
+    let mut title = format!(
+        "Alert History{} · {} of {} firings",
+        filter_title_suffix(&query),
+        shown.len(),
+        view.entries.len()
+    );
+    if view.bad_lines > 0 {
+        title.push_str(&format!(" · {} unreadable lines skipped", view.bad_lines));
+    }
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .style(theme.canvas())
+        .border_style(border_st);
+
+    if shown.is_empty() {
+        let text = if view.entries.is_empty() {
+            "No alerts have fired yet."
+        } else {
+            "No firings match the filter."
+        };
+        let p = Paragraph::new(Line::from(vec![Span::styled(text, theme.fg_border())])).block(block);
+        f.render_widget(p, chunks[0]);
+    } else {
+        let header = Row::new(
+            ["Fired", "Symbol", "Condition", "Price", "Delivered", "Errors"]
+                .iter()
+                .map(|h| Cell::from(*h).style(theme.fg_foreground())),
+        )
+        .style(theme.canvas().add_modifier(Modifier::BOLD))
+        .height(1);
+        let rows = shown.iter().map(|&i| {
+            let entry = &view.entries[i];
+            let ev = &entry.event;
+            // Channels with no recorded result: delivery was cut short when the app quit.
+            let errors: Vec<String> = entry
+                .errors
+                .iter()
+                .map(|e| format!("{}: {}", e.channel, e.error))
+                .chain(entry.pending.iter().map(|p| format!("{p}: no result")))
+                .collect();
+            Row::new([
+                Cell::from(
+                    ev.fired_at
+                        .with_timezone(&Local)
+                        .format("%Y-%m-%d %H:%M:%S")
+                        .to_string(),
+                ),
+                Cell::from(ev.symbol.clone()),
+                Cell::from(ev.condition.label()),
+                Cell::from(history_price_text(ev)),
+                Cell::from(entry.delivered.join(", ")),
+                Cell::from(errors.join("; ")).style(theme.fg_color(theme.negative)),
+            ])
+            .height(1)
+            .style(theme.canvas())
+        });
+        let selected_style = Style::default()
+            .bg(theme.selection)
+            .fg(theme.foreground)
+            .add_modifier(Modifier::BOLD);
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(20),
+                Constraint::Min(6),
+                Constraint::Length(18),
+                Constraint::Length(11),
+                Constraint::Length(22),
+                Constraint::Min(10),
+            ],
+        )
+        .header(header)
+        .block(block)
+        .highlight_style(selected_style)
+        .highlight_symbol("> ");
+        f.render_stateful_widget(table, chunks[0], &mut view.state);
+    }
+
+    let footer = if let Some(path) = view.export_path.as_deref() {
+        let mut spans = vec![
+            Span::styled("Export CSV to: ", theme.fg_accent()),
+            Span::styled(path, theme.fg_foreground()),
+            Span::styled("  (Enter write · Esc cancel)", theme.fg_muted()),
+        ];
+        if let Some(err) = view.inline_error.as_deref() {
+            spans.push(Span::styled(format!("  {err}"), theme.error_text()));
+        }
+        Line::from(spans)
+    } else if let Some(err) = view.inline_error.as_deref() {
+        Line::from(vec![Span::styled(err, theme.error_text())])
+    } else if filtering {
+        Line::from(vec![Span::styled(
+            "Filter by symbol · Enter keep · Esc clear",
+            theme.fg_muted(),
+        )])
+    } else if let Some(status) = view.status.as_deref() {
+        Line::from(vec![Span::styled(status, theme.fg_foreground())])
+    } else {
+        Line::from(vec![Span::styled(
+            "/ filter · e export CSV · Esc or l back",
+            theme.fg_muted(),
+        )])
+    };
+    f.render_widget(Paragraph::new(footer).style(theme.canvas()), chunks[1]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::alert_history::AlertHistoryEntry;
+    use crate::models::alerts::AlertCondition;
+    use chrono::DateTime;
+
+    fn entry(symbol: &str) -> AlertHistoryEntry {
+        AlertHistoryEntry {
+            event: AlertEvent {
+                symbol: symbol.into(),
+                condition: AlertCondition::Below,
+                threshold: 1.0,
+                last: None,
+                currency: None,
+                message: String::new(),
+                fired_at: DateTime::from_timestamp(0, 0).unwrap(),
+            },
+            delivered: vec!["bell".into()],
+            errors: Vec::new(),
+            pending: Vec::new(),
+            outcome: false,
+        }
+    }
+
+    #[test]
+    fn history_filters_by_symbol_and_clamps_selection() {
+        let mut app = App::new();
+        app.alert_history = Some(AlertHistoryView {
+            entries: vec![entry("AAPL"), entry("MSFT"), entry("AAPL")],
+            ..AlertHistoryView::default()
+        });
+        app.alert_history.as_mut().unwrap().state.select(Some(2));
+
+        app.filter_query = "ms".into();
+        app.clamp_alert_history_selection();
+        let view = app.alert_history.as_ref().unwrap();
+        assert_eq!(alert_history_filter_indices(view, &app.filter_query), vec![1]);
+        assert_eq!(view.state.selected(), Some(0));
+
+        app.filter_query = "zz".into();
+        app.clamp_alert_history_selection();
+        assert_eq!(app.alert_history.as_ref().unwrap().state.selected(), None);
+
+        app.close_alert_history();
+        assert!(app.alert_history.is_none());
+        assert!(app.filter_query.is_empty());
+    }
+
+    #[test]
+    fn history_price_uses_the_recorded_currency() {
+        let mut ev = entry("VOD.L").event;
+        assert_eq!(history_price_text(&ev), "—");
+        ev.last = Some(71.0);
+        assert_eq!(history_price_text(&ev), "$71.00");
+        ev.currency = Some("GBp".into());
+        assert_eq!(history_price_text(&ev), "71.00p");
+    }
+}
@@ -4,9 +4,21 @@ use crate::app::styles::ResolvedTheme;
 use crate::app::app_error::{AppError, ErrorSourceDomain};
 use crate::app::keyboard::letter_key_plain;
 use crate::app::layout::centered_rect;
+use crate::api::rate_limit::{with_priority, RequestPriority};
+use crate::api::{market_provider_for_config, HistoricalQuery, ProviderError};
+use crate::app::charts::compact_volume;
+use crate::app::alert_history::{draw_alert_history, handle_alert_history_keys};
+use crate::app::alert_delivery::{fired_alerts, spawn_alert_deliveries, DesktopToast};
 use crate::app::{AlertAddDialog, AlertAddField, App, Tab};
 use crate::config::keymap::{Action, BindingLayer};
-use crate::models::alerts::{process_alert_crossings, Alert, AlertCondition};
+use crate::config::Config;
+use crate::models::alerts::{
+    evaluate_alerts, take_expired_alerts, Alert, AlertCondition, AlertRepeat, AlertQuote, AlertThresholdUnit,
+    MAX_ALERT_SMA_DAYS, MIN_ALERT_SMA_DAYS,
+};
+use crate::models::currency::format_money;
+use crate::models::ticker::TickerResult;
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -15,6 +27,7 @@ use ratatui::{
     widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table},
     Frame,
 };
+use std::collections::HashMap;
 use std::io::{self, Write};
 
 /// Prefix for `AppError::ConfigSave` / status line when `Config::try_save` fails in `save_alerts` (§18.14.2).
@@ -112,12 +125,248 @@ pub(crate) fn sanitize_alert_notify_display_text(s: &str) -> String {
     }
 }
 
+/// Threshold column text: `$150.00`, `5.00% from £98.20`, `50d SMA`, `1.50M`; prices in
+/// `currency` (see [`format_money`]).
+pub(crate) fn alert_target_text(alert: &Alert, currency: &str) -> String {
+    match alert.condition.unit() {
+        AlertThresholdUnit::Price => format_money(alert.price, currency),
+        AlertThresholdUnit::Percent => match alert.reference_price {
+            Some(r) if alert.condition.uses_reference_price() => {
+                format!("{:.2}% from {}", alert.price, format_money(r, currency))
+            }
+            _ => format!("{:.2}%", alert.price),
+        },
+        AlertThresholdUnit::Days => format!("{}d SMA", alert.sma_days.unwrap_or_default()),
+        AlertThresholdUnit::Shares => compact_volume(alert.price),
+    }
+}
+
+/// `Above $150.00`, `Day down 5.00%`, `Cross above 50d SMA` for notifications and `alerts watch`.
+pub(crate) fn describe_alert(alert: &Alert, currency: &str) -> String {
+    format!("{} {}", alert.condition.label(), alert_target_text(alert, currency))
+}
+
+/// `AAPL Above $150.00 · last $151.20` for a fired alert, with prices in the quote's currency.
+pub(crate) fn fired_alert_text(symbol: &str, alert: &Alert, quote: Option<&AlertQuote>) -> String {
+    let currency = quote.map_or("USD", |q| q.quote_currency());
+    let mut text = format!("{symbol} {}", describe_alert(alert, currency));
+    if let Some(q) = quote {
+        text.push_str(&format!(" · last {}", format_money(alert.evaluation_price(q), currency)));
+    }
+    text
+}
+
+/// Repeat column text: `Once`, `Re-arm ±0.50`, `Every 30m`.
+fn alert_repeat_text(repeat: AlertRepeat) -> String {
+    match repeat {
+        AlertRepeat::Once => "Once".to_string(),
+        AlertRepeat::Rearm { hysteresis } => format!("Re-arm ±{hysteresis:.2}"),
+        AlertRepeat::Cooldown { minutes } => format!("Every {minutes}m"),
+    }
+}
+
+fn alert_last_fired_text(alert: &Alert) -> String {
+    alert
+        .last_fired
+        .map(|t| t.with_timezone(&Local).format("%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "—".to_string())
+}
+
+/// `Price Alerts`, plus the archived count and the key hint once any alert has expired.
+fn alerts_table_title(archived: usize, shown: bool) -> String {
+    match (archived, shown) {
+        (0, _) => "Price Alerts".to_string(),
+        (n, false) => format!("Price Alerts · {n} archived (h to show)"),
+        (n, true) => format!("Price Alerts · {n} archived (h to hide)"),
+    }
+}
+
+/// Status of a fired alert: latched, waiting to move back past the band, or cooling down.
+fn alert_fired_status(alert: &Alert, now: DateTime<Utc>) -> String {
+    match alert.repeat {
+        AlertRepeat::Once => "TRIGGERED".to_string(),
+        AlertRepeat::Rearm { .. } => "Fired · re-arm pending".to_string(),
+        AlertRepeat::Cooldown { minutes } => {
+            let left = alert
+                .last_fired
+                .map(|t| i64::from(minutes) - (now - t).num_minutes())
+                .unwrap_or(0)
+                .max(0);
+            format!("Fired · {left}m cooldown")
+        }
+    }
+}
+
+/// Daily bars behind SMA alerts, fetched at most once per local day per symbol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertDailyCloses {
+    pub fetched_on: NaiveDate,
+    /// Longest SMA period the fetch was sized for.
+    pub sessions: u32,
+    /// `(t ms, close)` oldest first.
+    pub bars: Vec<(u64, f64)>,
+}
+
+impl AlertDailyCloses {
+    /// Closes of sessions before the day of `quote_t` (ms); the whole series when unknown.
+    pub fn completed_before(&self, quote_t: Option<u64>) -> Vec<f64> {
+        let day = |t: u64| DateTime::from_timestamp_millis(t as i64).map(|d| d.date_naive());
+        let today = quote_t.and_then(day);
+        self.bars
+            .iter()
+            .filter(|(t, _)| today.is_none() || day(*t) < today)
+            .map(|&(_, c)| c)
+            .collect()
+    }
+}
+
+/// `(normalized symbol, longest period)` for untriggered SMA alerts whose cached closes are
+/// missing, shorter than the period, or from an earlier day.
+pub(crate) fn stale_alert_daily_closes(
+    alerts: &[Alert],
+    cache: &HashMap<String, AlertDailyCloses>,
+    today: NaiveDate,
+) -> Vec<(String, u32)> {
+    let mut need: Vec<(String, u32)> = Vec::new();
+    for alert in alerts.iter().filter(|a| !a.triggered) {
+        let (Some(days), Some(sym)) = (alert.sma_days, crate::app::normalize_symbol(&alert.symbol))
+        else {
+            continue;
+        };
+        match need.iter_mut().find(|(s, _)| *s == sym) {
+            Some((_, d)) => *d = (*d).max(days),
+            None => need.push((sym, days)),
+        }
+    }
+    need.retain(|(sym, days)| {
+        cache
+            .get(sym)
+            .is_none_or(|c| c.fetched_on != today || c.sessions < *days)
+    });
+    need
+}
+
+/// Daily bars for each `(symbol, sessions)`; the calendar window adds room for weekends and
+/// holidays plus today's partial bar.
+pub(crate) async fn fetch_alert_daily_closes(
+    requests: Vec<(String, u32)>,
+    config: &Config,
+) -> (HashMap<String, AlertDailyCloses>, Vec<(String, ProviderError)>) {
+    let today = Local::now().date_naive();
+    let provider = market_provider_for_config(config);
+    let to = today.format("%Y-%m-%d").to_string();
+    let results = futures_util::future::join_all(requests.into_iter().map(|(sym, sessions)| {
+        let provider = provider.clone();
+        let to = to.clone();
+        async move {
+            let back = chrono::Duration::days(i64::from(sessions) * 7 / 5 + 10);
+            let from = (today - back).format("%Y-%m-%d").to_string();
+            let hq = HistoricalQuery {
+                from: &from,
+                to: &to,
+                bar_interval: "1d",
+                yahoo_range: None,
+                polygon_multiplier: 1,
+                polygon_timespan: "day",
+            };
+            let res =
+                with_priority(RequestPriority::Background, provider.get_historical(&sym, &hq, config))
+                    .await;
+            (sym, sessions, res)
+        }
+    }))
+    .await;
+    let mut closes = HashMap::new();
+    let mut errors = Vec::new();
+    for (sym, sessions, res) in results {
+        match res {
+            Ok(hist) => {
+                let mut bars: Vec<(u64, f64)> = hist.results.iter().map(|b| (b.t, b.c)).collect();
+                bars.sort_by_key(|&(t, _)| t);
+                closes.insert(
+                    sym,
+                    AlertDailyCloses {
+                        fetched_on: today,
+                        sessions,
+                        bars,
+                    },
+                );
+            }
+            Err(e) => errors.push((sym, e)),
+        }
+    }
+    (closes, errors)
+}
+
+/// [`AlertQuote`] for `symbol` from its latest bar and, for SMA alerts, its cached daily closes.
+pub(crate) fn alert_quote_from_bar(
+    symbol: &str,
+    last: f64,
+    bar: Option<&TickerResult>,
+    daily: Option<&AlertDailyCloses>,
+) -> AlertQuote {
+    AlertQuote {
+        symbol: symbol.to_string(),
+        last,
+        extended: bar.and_then(|b| b.extended_quote()).map(|(_, p)| p),
+        prev_close: bar.and_then(|b| b.prev_close),
+        volume: bar.map(|b| b.v),
+        market_state: bar.and_then(|b| b.market_state),
+        daily_closes: daily
+            .map(|d| d.completed_before(bar.map(|b| b.t)))
+            .unwrap_or_default(),
+        currency: bar.and_then(|b| b.currency.clone()),
+    }
+}
+
 fn ring_terminal_bell() {
     let mut out = io::stdout();
     let _ = out.write_all(b"\x07");
     let _ = out.flush();
 }
 
+/// Bell per newly fired alert plus one coalesced desktop toast when `notifications_enabled`
+/// (SPEC §18.5); returns the toast's pending result when one was sent. Shared by
+/// [`App::check_alerts`] and the headless `alerts watch` loop.
+#[cfg_attr(not(feature = "desktop-notify"), allow(unused_variables))]
+pub(crate) fn fire_alert_notifications(
+    alerts: &[Alert],
+    newly: &[usize],
+    quotes: &[AlertQuote],
+    notifications_enabled: bool,
+) -> Option<DesktopToast> {
+    for _ in newly {
+        ring_terminal_bell();
+    }
+
+    if notifications_enabled {
+        #[cfg(feature = "desktop-notify")]
+        {
+            const K: usize = 5;
+            let mut body_lines: Vec<String> = Vec::with_capacity(newly.len().min(K) + 1);
+            for idx in newly {
+                let alert = &alerts[*idx];
+                let quote = quotes.iter().find(|q| q.symbol == alert.symbol);
+                let sym = sanitize_alert_notify_display_text(&alert.symbol);
+                body_lines.push(fired_alert_text(&sym, alert, quote));
+            }
+            let len = body_lines.len();
+            let (summary, lines_for_notify) = if len == 1 {
+                ("StockTerm".to_string(), body_lines)
+            } else {
+                let summary = format!("StockTerm — {len} alerts");
+                let mut body: Vec<String> = body_lines.iter().take(K).cloned().collect();
+                if len > K {
+                    body.push(format!("… and {} more", len - K));
+                }
+                (summary, body)
+            };
+            return Some(spawn_desktop_alert_notifications_batch(summary, lines_for_notify));
+        }
+    }
+    None
+}
+
 pub(crate) fn alerts_tab_banner_active(app: &App) -> bool {
     app.alerts_save_retry_pending
         || app
@@ -127,7 +376,8 @@ pub(crate) fn alerts_tab_banner_active(app: &App) -> bool {
 }
 
 #[cfg(feature = "desktop-notify")]
-fn spawn_desktop_alert_notifications_batch(summary: String, body_lines: Vec<String>) {
+fn spawn_desktop_alert_notifications_batch(summary: String, body_lines: Vec<String>) -> DesktopToast {
+    let (tx, rx) = tokio::sync::oneshot::channel();
     std::thread::spawn(move || {
         let body = assemble_notify_body_capped(&body_lines, NOTIFY_BATCH_BODY_MAX_BYTES);
         let show_result = notify_rust::Notification::new()
@@ -140,7 +390,9 @@ fn spawn_desktop_alert_notifications_batch(summary: String, body_lines: Vec<Stri
         ) {
             eprintln!("stockterm: Notification::show() = {show_result:?}");
         }
+        let _ = tx.send(show_result.map(|_| ()).map_err(|e| e.to_string()));
     });
+    rx
 }
 
 pub fn draw_alerts(f: &mut Frame, app: &mut App, area: Rect, theme: ResolvedTheme) {
@@ -180,9 +432,21 @@ pub fn draw_alerts(f: &mut Frame, app: &mut App, area: Rect, theme: ResolvedThem
         f.render_widget(banner, chunks[0]);
     }
 
-    if app.alerts.is_empty() && app.alert_add_dialog.is_none() {
+    if app.alert_history.is_some() {
+        draw_alert_history(f, app, main, theme);
+        return;
+    }
+
+    let archived: &[Alert] = if app.alerts_show_archived {
+        &app.config.archived_alerts
+    } else {
+        &[]
+    };
+    let title = alerts_table_title(app.config.archived_alerts.len(), app.alerts_show_archived);
+
+    if app.alerts.is_empty() && archived.is_empty() && app.alert_add_dialog.is_none() {
         let block = Block::default()
-            .title("Price Alerts")
+            .title(title.as_str())
             .borders(Borders::ALL)
             .style(theme.canvas())
             .border_style(border_st);
@@ -195,9 +459,9 @@ pub fn draw_alerts(f: &mut Frame, app: &mut App, area: Rect, theme: ResolvedThem
         return;
     }
 
-    if app.alerts.is_empty() {
+    if app.alerts.is_empty() && archived.is_empty() {
         let block = Block::default()
-            .title("Price Alerts")
+            .title(title.as_str())
             .borders(Borders::ALL)
             .style(theme.canvas())
             .border_style(border_st);
@@ -213,7 +477,7 @@ pub fn draw_alerts(f: &mut Frame, app: &mut App, area: Rect, theme: ResolvedThem
             .fg(theme.foreground)
             .add_modifier(Modifier::BOLD);
 
-        let header_cells = ["Symbol", "Condition", "Price", "Current", "Status"]
+        let header_cells = ["Symbol", "Condition", "Target", "Current", "Repeat", "Last fired", "Status"]
             .iter()
             .map(|h| Cell::from(*h).style(theme.fg_foreground()));
 
@@ -221,43 +485,76 @@ pub fn draw_alerts(f: &mut Frame, app: &mut App, area: Rect, theme: ResolvedThem
             .style(theme.canvas().add_modifier(Modifier::BOLD))
             .height(1);
 
+        let now = Utc::now();
+        let local = now.with_timezone(&Local).naive_local();
         let rows = app.alerts.iter().map(|alert| {
-            let current_opt = app.get_current_price(&alert.symbol);
+            let quote = app.alert_quote(&alert.symbol);
+            let current_opt = quote.as_ref().map(|q| alert.evaluation_price(q));
+            let active =
+                alert.is_active_at(now, local, quote.as_ref().and_then(|q| q.market_state));
+            let currency = quote.as_ref().map_or("USD", |q| q.quote_currency());
             let current_cell = current_opt
-                .map(|p| format!("${p:.2}"))
+                .map(|p| format_money(p, currency))
                 .unwrap_or_else(|| "—".to_string());
 
-            let condition_text = match alert.condition {
-                AlertCondition::Above => "Above",
-                AlertCondition::Below => "Below",
-            };
+            let mut condition_text = alert.condition.label().to_string();
+            if alert.extended_hours {
+                condition_text.push_str(" ext");
+            }
 
             let (status_text, status_color) = if alert.triggered {
-                ("TRIGGERED", theme.negative)
+                (alert_fired_status(alert, now), theme.negative)
+            } else if !active {
+                (format!("Inactive · {}", alert.schedule.label()), theme.muted)
             } else if current_opt.is_some() {
-                ("Armed", theme.border)
+                ("Armed".to_string(), theme.border)
             } else {
-                ("No quote", theme.muted)
+                ("No quote".to_string(), theme.muted)
             };
-
             let cells = [
                 Cell::from(alert.symbol.clone()),
                 Cell::from(condition_text),
-                Cell::from(format!("${:.2}", alert.price)),
+                Cell::from(alert_target_text(alert, currency)),
                 Cell::from(current_cell),
+                Cell::from(alert_repeat_text(alert.repeat)),
+                Cell::from(alert_last_fired_text(alert)),
                 Cell::from(status_text).style(theme.fg_color(status_color)),
             ];
 
-            Row::new(cells).height(1).style(theme.canvas())
+            let style = if active { theme.canvas() } else { theme.fg_muted() };
+            Row::new(cells).height(1).style(style)
         });
+        // Archived rows follow the live ones, so selection indices still match `app.alerts`.
+        let archived_rows = archived.iter().map(|alert| {
+            let status = alert
+                .expires_at
+                .map(|t| format!("Expired {}", t.with_timezone(&Local).format("%Y-%m-%d")))
+                .unwrap_or_else(|| "Archived".to_string());
+            let quote = app.alert_quote(&alert.symbol);
+            let currency = quote.as_ref().map_or("USD", |q| q.quote_currency());
+            Row::new([
+                Cell::from(alert.symbol.clone()),
+                Cell::from(alert.condition.label()),
+                Cell::from(alert_target_text(alert, currency)),
+                Cell::from("—"),
+                Cell::from(alert_repeat_text(alert.repeat)),
+                Cell::from(alert_last_fired_text(alert)),
+                Cell::from(status),
+            ])
+            .height(1)
+            .style(theme.fg_muted())
+        });
+        let rows = rows.chain(archived_rows);
 
         let table = Table::new(
             rows,
             [
                 Constraint::Min(6),
-                Constraint::Length(10),
-                Constraint::Length(11),
+                Constraint::Length(18),
+                Constraint::Length(22),
                 Constraint::Length(11),
+                Constraint::Length(13),
+                Constraint::Length(12),
                 Constraint::Min(10),
             ],
         )
@@ -265,7 +562,7 @@ pub fn draw_alerts(f: &mut Frame, app: &mut App, area: Rect, theme: ResolvedThem
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title("Price Alerts")
+                .title(title.as_str())
                 .style(theme.canvas())
                 .border_style(border_st),
         )
@@ -287,7 +584,7 @@ fn draw_alert_add_overlay(f: &mut Frame, app: &App, area: Rect, theme: ResolvedT
 
     f.render_widget(Clear, area);
 
-    let popup = centered_rect(area, 55, 42);
+    let popup = centered_rect(area, 55, 48);
     let border_st = Style::default().fg(theme.border).bg(theme.background);
 
     let sym_style = if dialog.focused == AlertAddField::Symbol {
@@ -300,20 +597,57 @@ fn draw_alert_add_overlay(f: &mut Frame, app: &App, area: Rect, theme: ResolvedT
     } else {
         theme.fg_foreground()
     };
+    let session_style = if dialog.focused == AlertAddField::Session {
+        theme.fg_accent()
+    } else {
+        theme.fg_foreground()
+    };
+    let repeat_style = if dialog.focused == AlertAddField::Repeat {
+        theme.fg_accent()
+    } else {
+        theme.fg_foreground()
+    };
+    let repeat_unit = match dialog.repeat {
+        AlertRepeat::Once => "",
+        AlertRepeat::Rearm { .. } => " band ",
+        AlertRepeat::Cooldown { .. } => " every (min) ",
+    };
+    let active_style = if dialog.focused == AlertAddField::Active {
+        theme.fg_accent()
+    } else {
+        theme.fg_foreground()
+    };
+    let expires_style = if dialog.focused == AlertAddField::Expires {
+        theme.fg_accent()
+    } else {
+        theme.fg_foreground()
+    };
+    let notify_style = if dialog.focused == AlertAddField::Notify {
+        theme.fg_accent()
+    } else {
+        theme.fg_foreground()
+    };
     let thr_style = if dialog.focused == AlertAddField::Threshold {
         theme.fg_accent()
     } else {
         theme.fg_foreground()
     };
+    let session_label = if dialog.extended_hours {
+        "Regular + pre/post-market"
+    } else {
+        "Regular only"
+    };
 
-    let cond_label = match dialog.condition {
-        AlertCondition::Above => "Above",
-        AlertCondition::Below => "Below",
+    let (threshold_prefix, threshold_suffix) = match dialog.condition.unit() {
+        AlertThresholdUnit::Price => ("$", ""),
+        AlertThresholdUnit::Percent => ("", "%"),
+        AlertThresholdUnit::Days => ("", "-day SMA"),
+        AlertThresholdUnit::Shares => ("", " shares"),
     };
 
     let mut lines: Vec<Line> = vec![
         Line::from(vec![Span::styled(
-            "Add price alert — Esc cancel · Tab / Shift+Tab or ; cycle field · ←/→ on Condition (Below/Above) · Enter advances / saves on Threshold",
+            "Add price alert — Esc cancel · Tab / Shift+Tab or ; cycle field · ←/→ on Condition cycle · Enter advances / saves on Threshold",
             theme.canvas(),
         )]),
         Line::from(vec![
@@ -322,15 +656,45 @@ fn draw_alert_add_overlay(f: &mut Frame, app: &App, area: Rect, theme: ResolvedT
         ]),
         Line::from(vec![
             Span::styled("Condition: ", cond_style),
-            Span::styled(cond_label, theme.fg_foreground()),
-            Span::styled("  (; toggles · ← Below · → Above · a/A · b/B)", theme.fg_muted()),
+            Span::styled(dialog.condition.label(), theme.fg_foreground()),
+            Span::styled("  (; or ←/→ cycles · a Above · b Below)", theme.fg_muted()),
+        ]),
+        Line::from(vec![
+            Span::styled("Session:   ", session_style),
+            Span::styled(session_label, theme.fg_foreground()),
+            Span::styled("  (; or ←/→ toggles)", theme.fg_muted()),
+        ]),
+        Line::from(vec![
+            Span::styled("Repeat:    ", repeat_style),
+            Span::styled(dialog.repeat.label(), theme.fg_foreground()),
+            Span::styled(repeat_unit, theme.fg_muted()),
+            Span::styled(dialog.repeat_buffer.as_str(), theme.fg_foreground()),
+            Span::styled("  (; or ←/→ cycles)", theme.fg_muted()),
+        ]),
+        Line::from(vec![
+            Span::styled("Active:    ", active_style),
+            Span::styled(dialog.schedule.label(), theme.fg_foreground()),
+            Span::styled("  (; or ←/→ cycles)", theme.fg_muted()),
         ]),
         Line::from(vec![
-            Span::styled("Threshold: ", thr_style),
-            Span::styled("$", theme.fg_foreground()),
-            Span::styled(dialog.threshold_buffer.as_str(), theme.fg_foreground()),
+            Span::styled("Expires:   ", expires_style),
+            Span::styled(dialog.expires_buffer.as_str(), theme.fg_foreground()),
+            Span::styled("  (YYYY-MM-DD, blank = never)", theme.fg_muted()),
         ]),
     ];
+    if !dialog.sink_names.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled("Notify:    ", notify_style),
+            Span::styled(alert_sinks_label(dialog.sinks.as_deref()), theme.fg_foreground()),
+            Span::styled("  (; or ←/→ cycles)", theme.fg_muted()),
+        ]));
+    }
+    lines.push(Line::from(vec![
+        Span::styled("Threshold: ", thr_style),
+        Span::styled(threshold_prefix, theme.fg_foreground()),
+        Span::styled(dialog.threshold_buffer.as_str(), theme.fg_foreground()),
+        Span::styled(threshold_suffix, theme.fg_foreground()),
+    ]));
 
     if let Some(ref err) = dialog.inline_error {
         lines.push(Line::from(vec![Span::styled(
@@ -353,26 +717,123 @@ pub(crate) fn cycle_alert_dialog_focus(app: &mut App, forward: bool) {
         return;
     };
     use AlertAddField::*;
-    d.focused = match (d.focused, forward) {
-        (Symbol, true) | (Threshold, false) => Condition,
-        (Condition, true) | (Symbol, false) => Threshold,
+    let next = |f: AlertAddField| match (f, forward) {
+        (Symbol, true) | (Session, false) => Condition,
+        (Condition, true) | (Repeat, false) => Session,
+        (Session, true) | (Active, false) => Repeat,
+        (Repeat, true) | (Expires, false) => Active,
+        (Active, true) | (Notify, false) => Expires,
+        (Expires, true) | (Threshold, false) => Notify,
+        (Notify, true) | (Symbol, false) => Threshold,
         (Threshold, true) | (Condition, false) => Symbol,
     };
+    d.focused = next(d.focused);
+    if d.focused == Notify && d.sink_names.is_empty() {
+        d.focused = next(Notify);
+    }
     d.inline_error = None;
 }
 
-fn parse_alert_threshold(s: &str) -> Result<f64, &'static str> {
+/// Dialog / table text for [`Alert::sinks`].
+fn alert_sinks_label(sinks: Option<&[String]>) -> String {
+    match sinks {
+        None => "All sinks".to_string(),
+        Some([]) => "Bell only".to_string(),
+        Some(names) => names.join(", "),
+    }
+}
+
+/// All sinks → bell only → each configured sink on its own.
+fn cycle_alert_sinks(current: Option<Vec<String>>, names: &[String], forward: bool) -> Option<Vec<String>> {
+    let mut choices: Vec<Option<Vec<String>>> = vec![None, Some(Vec::new())];
+    choices.extend(names.iter().map(|n| Some(vec![n.clone()])));
+    let n = choices.len();
+    let next = match choices.iter().position(|c| *c == current) {
+        Some(i) if forward => (i + 1) % n,
+        Some(i) => (i + n - 1) % n,
+        None => 0,
+    };
+    choices.swap_remove(next)
+}
+
+/// ←/→/`;` on a choice field (condition, session, repeat mode, schedule, sinks); `false` on text
+/// fields.
+fn cycle_alert_dialog_choice(d: &mut AlertAddDialog, forward: bool) -> bool {
+    match d.focused {
+        AlertAddField::Condition => d.condition = d.condition.cycle(forward),
+        AlertAddField::Session => d.extended_hours = !d.extended_hours,
+        AlertAddField::Repeat => {
+            d.repeat = d.repeat.cycle(forward);
+            d.repeat_buffer.clear();
+        }
+        AlertAddField::Active => d.schedule = d.schedule.cycle(forward),
+        AlertAddField::Notify => d.sinks = cycle_alert_sinks(d.sinks.take(), &d.sink_names, forward),
+        AlertAddField::Symbol | AlertAddField::Expires | AlertAddField::Threshold => return false,
+    }
+    d.inline_error = None;
+    true
+}
+
+/// Blank → never; otherwise the end of that local day, which must still be ahead.
+fn parse_alert_expiry(s: &str, now: DateTime<Utc>) -> Result<Option<DateTime<Utc>>, &'static str> {
+    let t = s.trim();
+    if t.is_empty() {
+        return Ok(None);
+    }
+    let date = NaiveDate::parse_from_str(t, "%Y-%m-%d").map_err(|_| "Expiry must be YYYY-MM-DD.")?;
+    let end_of_day = date
+        .and_hms_opt(23, 59, 59)
+        .and_then(|ndt| Local.from_local_datetime(&ndt).earliest())
+        .ok_or("Invalid expiry date.")?
+        .with_timezone(&Utc);
+    if end_of_day <= now {
+        return Err("Expiry date has already passed.");
+    }
+    Ok(Some(end_of_day))
+}
+
+fn parse_alert_threshold(s: &str, unit: AlertThresholdUnit) -> Result<f64, String> {
     let t = s.trim();
     if t.is_empty() {
-        return Err("Threshold is required.");
+        return Err("Threshold is required.".into());
     }
     let v: f64 = t.parse().map_err(|_| "Invalid threshold.")?;
     if !v.is_finite() || v <= 0.0 {
-        return Err("Threshold must be a positive number.");
+        return Err("Threshold must be a positive number.".into());
+    }
+    if unit == AlertThresholdUnit::Days
+        && (v.fract() != 0.0 || v < f64::from(MIN_ALERT_SMA_DAYS) || v > f64::from(MAX_ALERT_SMA_DAYS))
+    {
+        return Err(format!(
+            "SMA period must be a whole number of days from {MIN_ALERT_SMA_DAYS} to {MAX_ALERT_SMA_DAYS}."
+        ));
     }
     Ok(v)
 }
 
+/// Repeat mode with its typed value: band for re-arm (blank = 0), whole minutes for cooldown.
+fn parse_alert_repeat(mode: AlertRepeat, s: &str) -> Result<AlertRepeat, &'static str> {
+    let t = s.trim();
+    match mode {
+        AlertRepeat::Once => Ok(AlertRepeat::Once),
+        AlertRepeat::Rearm { .. } => {
+            let hysteresis = if t.is_empty() {
+                0.0
+            } else {
+                t.parse::<f64>().map_err(|_| "Invalid re-arm band.")?
+            };
+            if !hysteresis.is_finite() || hysteresis < 0.0 {
+                return Err("Re-arm band must be zero or more.");
+            }
+            Ok(AlertRepeat::Rearm { hysteresis })
+        }
+        AlertRepeat::Cooldown { .. } => match t.parse::<u32>() {
+            Ok(minutes) if minutes > 0 => Ok(AlertRepeat::Cooldown { minutes }),
+            _ => Err("Cooldown must be a whole number of minutes."),
+        },
+    }
+}
+
 fn try_commit_alert_dialog(app: &mut App) {
     let Some(ref dlg) = app.alert_add_dialog else {
         return;
@@ -383,15 +844,51 @@ fn try_commit_alert_dialog(app: &mut App) {
         }
         return;
     };
-    match parse_alert_threshold(&dlg.threshold_buffer) {
-        Ok(price) => {
-            let condition = dlg.condition;
-            app.alert_add_dialog = None;
-            app.add_alert(sym, condition, price);
+    let repeat = match parse_alert_repeat(dlg.repeat, &dlg.repeat_buffer) {
+        Ok(r) => r,
+        Err(e) => {
+            if let Some(d) = app.alert_add_dialog.as_mut() {
+                d.inline_error = Some(e.into());
+                d.focused = AlertAddField::Repeat;
+            }
+            return;
         }
+    };
+    let expires_at = match parse_alert_expiry(&dlg.expires_buffer, Utc::now()) {
+        Ok(t) => t,
         Err(e) => {
             if let Some(d) = app.alert_add_dialog.as_mut() {
                 d.inline_error = Some(e.into());
+                d.focused = AlertAddField::Expires;
+            }
+            return;
+        }
+    };
+    match parse_alert_threshold(&dlg.threshold_buffer, dlg.condition.unit()) {
+        Ok(threshold) => {
+            let mut alert = if dlg.condition.unit() == AlertThresholdUnit::Days {
+                let mut alert = Alert::new(sym, dlg.condition, 0.0);
+                alert.sma_days = Some(threshold as u32);
+                alert
+            } else {
+                Alert::new(sym, dlg.condition, threshold)
+            };
+            alert.extended_hours = dlg.extended_hours;
+            alert.repeat = repeat;
+            alert.schedule = dlg.schedule;
+            alert.expires_at = expires_at;
+            alert.sinks = dlg.sinks.clone();
+            if alert.condition.uses_reference_price() {
+                alert.reference_price = app
+                    .alert_quote(&alert.symbol)
+                    .map(|q| alert.evaluation_price(&q));
+            }
+            app.alert_add_dialog = None;
+            app.push_alert(alert);
+        }
+        Err(e) => {
+            if let Some(d) = app.alert_add_dialog.as_mut() {
+                d.inline_error = Some(e);
             }
         }
     }
@@ -412,6 +909,14 @@ fn append_symbol_char(buf: &mut String, c: char) -> bool {
     false
 }
 
+fn append_expiry_char(buf: &mut String, c: char) -> bool {
+    if buf.len() >= 10 || !(c.is_ascii_digit() || c == '-') {
+        return false;
+    }
+    buf.push(c);
+    true
+}
+
 fn append_threshold_char(buf: &mut String, c: char) -> bool {
     if buf.len() >= MAX_ALERT_FIELD_LEN {
         return false;
@@ -450,11 +955,15 @@ fn alert_dialog_apply_char(app: &mut App, c: char, effect: AlertDialogCharEffect
     };
     d.inline_error = None;
     match effect {
-        AlertDialogCharEffect::SymbolOnly => {
-            if d.focused == AlertAddField::Symbol {
+        AlertDialogCharEffect::SymbolOnly => match d.focused {
+            AlertAddField::Symbol => {
                 let _ = append_symbol_char(&mut d.symbol_buffer, c);
             }
-        }
+            AlertAddField::Expires if c == '-' => {
+                let _ = append_expiry_char(&mut d.expires_buffer, c);
+            }
+            _ => {}
+        },
         AlertDialogCharEffect::DigitOrDot => match d.focused {
             AlertAddField::Symbol => {
                 let _ = append_symbol_char(&mut d.symbol_buffer, c);
@@ -462,14 +971,29 @@ fn alert_dialog_apply_char(app: &mut App, c: char, effect: AlertDialogCharEffect
             AlertAddField::Threshold => {
                 let _ = append_threshold_char(&mut d.threshold_buffer, c);
             }
-            AlertAddField::Condition => {}
+            AlertAddField::Repeat if !d.repeat.is_once() => {
+                let _ = append_threshold_char(&mut d.repeat_buffer, c);
+            }
+            AlertAddField::Expires => {
+                let _ = append_expiry_char(&mut d.expires_buffer, c);
+            }
+            AlertAddField::Condition
+            | AlertAddField::Session
+            | AlertAddField::Repeat
+            | AlertAddField::Active
+            | AlertAddField::Notify => {}
         },
         AlertDialogCharEffect::Condition(cond) => match d.focused {
             AlertAddField::Symbol => {
                 let _ = append_symbol_char(&mut d.symbol_buffer, c);
             }
             AlertAddField::Condition => d.condition = cond,
-            AlertAddField::Threshold => {}
+            AlertAddField::Session
+            | AlertAddField::Repeat
+            | AlertAddField::Active
+            | AlertAddField::Expires
+            | AlertAddField::Notify
+            | AlertAddField::Threshold => {}
         },
     }
 }
@@ -500,21 +1024,13 @@ fn handle_alert_dialog_keys(app: &mut App, key: KeyEvent) {
                 cycle_alert_dialog_focus(app, false);
             }
             Action::AlertDialogLeft if key.modifiers == KeyModifiers::NONE => {
-                let Some(d) = app.alert_add_dialog.as_mut() else {
-                    return;
-                };
-                if d.focused == AlertAddField::Condition {
-                    d.condition = AlertCondition::Below;
-                    d.inline_error = None;
+                if let Some(d) = app.alert_add_dialog.as_mut() {
+                    cycle_alert_dialog_choice(d, false);
                 }
             }
             Action::AlertDialogRight if key.modifiers == KeyModifiers::NONE => {
-                let Some(d) = app.alert_add_dialog.as_mut() else {
-                    return;
-                };
-                if d.focused == AlertAddField::Condition {
-                    d.condition = AlertCondition::Above;
-                    d.inline_error = None;
+                if let Some(d) = app.alert_add_dialog.as_mut() {
+                    cycle_alert_dialog_choice(d, true);
                 }
             }
             Action::AlertDialogConditionCycleOrFocusNext => {
@@ -524,13 +1040,7 @@ fn handle_alert_dialog_keys(app: &mut App, key: KeyEvent) {
                 let Some(d) = app.alert_add_dialog.as_mut() else {
                     return;
                 };
-                if d.focused == AlertAddField::Condition {
-                    d.condition = match d.condition {
-                        AlertCondition::Above => AlertCondition::Below,
-                        AlertCondition::Below => AlertCondition::Above,
-                    };
-                    d.inline_error = None;
-                } else {
+                if !cycle_alert_dialog_choice(d, true) {
                     cycle_alert_dialog_focus(app, true);
                 }
             }
@@ -541,7 +1051,16 @@ fn handle_alert_dialog_keys(app: &mut App, key: KeyEvent) {
                 d.inline_error = None;
                 match d.focused {
                     AlertAddField::Symbol => d.focused = AlertAddField::Condition,
-                    AlertAddField::Condition => d.focused = AlertAddField::Threshold,
+                    AlertAddField::Condition => d.focused = AlertAddField::Session,
+                    AlertAddField::Session => d.focused = AlertAddField::Repeat,
+                    AlertAddField::Repeat => d.focused = AlertAddField::Active,
+                    AlertAddField::Active => d.focused = AlertAddField::Expires,
+                    AlertAddField::Expires if !d.sink_names.is_empty() => {
+                        d.focused = AlertAddField::Notify
+                    }
+                    AlertAddField::Expires | AlertAddField::Notify => {
+                        d.focused = AlertAddField::Threshold
+                    }
                     AlertAddField::Threshold => try_commit_alert_dialog(app),
                 }
             }
@@ -557,7 +1076,16 @@ fn handle_alert_dialog_keys(app: &mut App, key: KeyEvent) {
                     AlertAddField::Threshold => {
                         d.threshold_buffer.pop();
                     }
-                    AlertAddField::Condition => {}
+                    AlertAddField::Repeat => {
+                        d.repeat_buffer.pop();
+                    }
+                    AlertAddField::Expires => {
+                        d.expires_buffer.pop();
+                    }
+                    AlertAddField::Condition
+                    | AlertAddField::Session
+                    | AlertAddField::Active
+                    | AlertAddField::Notify => {}
                 }
             }
             Action::AlertDialogDigitOrDot => {
@@ -601,6 +1129,10 @@ pub fn handle_alerts_events(app: &mut App, key: KeyEvent) {
         handle_alert_dialog_keys(app, key);
         return;
     }
+    if app.alert_history.is_some() {
+        handle_alert_history_keys(app, key);
+        return;
+    }
 
     if let Some(a) = app.resolved_keymap.action(BindingLayer::Alerts, &key) {
         match a {
@@ -616,6 +1148,16 @@ pub fn handle_alerts_events(app: &mut App, key: KeyEvent) {
                     }
                 }
             }
+            Action::AlertToggleArchived => {
+                if letter_key_plain(key.modifiers) {
+                    app.alerts_show_archived = !app.alerts_show_archived;
+                }
+            }
+            Action::AlertHistoryOpen => {
+                if letter_key_plain(key.modifiers) {
+                    app.open_alert_history();
+                }
+            }
             Action::AlertRowUp => {
                 if app.alerts.is_empty() {
                     return;
@@ -648,12 +1190,12 @@ pub fn handle_alerts_events(app: &mut App, key: KeyEvent) {
 
 impl App {
     pub fn add_alert(&mut self, symbol: String, condition: AlertCondition, price: f64) {
-        self.alerts.push(Alert {
-            symbol,
-            condition,
-            price,
-            triggered: false,
-        });
+        self.push_alert(Alert::new(symbol, condition, price));
+    }
+
+    /// Appends a fully configured alert, persists, and selects it when nothing is selected.
+    pub fn push_alert(&mut self, alert: Alert) {
+        self.alerts.push(alert);
 
         self.save_alerts();
 
@@ -683,64 +1225,54 @@ impl App {
     }
 
     pub fn check_alerts(&mut self) {
-        let prices: Vec<(String, f64)> = self
+        let quotes: Vec<AlertQuote> = self
             .alerts
             .iter()
-            .filter_map(|alert| {
-                self.get_current_price(&alert.symbol)
-                    .map(|price| (alert.symbol.clone(), price))
-            })
+            .filter_map(|alert| self.alert_quote(&alert.symbol))
             .collect();
 
-        let newly = process_alert_crossings(&mut self.alerts, &prices);
+        let now = Utc::now();
+        let archived = self.archive_expired_alerts(now);
+        let pass = evaluate_alerts(&mut self.alerts, &quotes, now);
+        let newly = pass.fired;
         if newly.is_empty() {
+            if pass.changed || archived {
+                self.save_alerts();
+            }
             return;
         }
 
-        for _ in &newly {
-            ring_terminal_bell();
-        }
-
-        if self.config.notifications_enabled {
-            #[cfg(feature = "desktop-notify")]
-            {
-                const K: usize = 5;
-                let mut body_lines: Vec<String> = Vec::with_capacity(newly.len().min(K) + 1);
-                for idx in &newly {
-                    let alert = &self.alerts[*idx];
-                    let last = prices
-                        .iter()
-                        .find(|(s, _)| s == &alert.symbol)
-                        .map(|(_, p)| *p);
-                    let sym = sanitize_alert_notify_display_text(&alert.symbol);
-                    let cond_s = match alert.condition {
-                        AlertCondition::Above => "Above",
-                        AlertCondition::Below => "Below",
-                    };
-                    let mut line = format!("{sym} {cond_s} ${:.2}", alert.price);
-                    if let Some(p) = last {
-                        line.push_str(&format!(" · last ${p:.2}"));
-                    }
-                    body_lines.push(line);
-                }
-                let len = body_lines.len();
-                let (summary, lines_for_notify) = if len == 1 {
-                    ("StockTerm".to_string(), body_lines)
-                } else {
-                    let summary = format!("StockTerm — {len} alerts");
-                    let mut body: Vec<String> = body_lines.iter().take(K).cloned().collect();
-                    if len > K {
-                        body.push(format!("… and {} more", len - K));
-                    }
-                    (summary, body)
-                };
-                spawn_desktop_alert_notifications_batch(summary, lines_for_notify);
-            }
-        }
+        let toast = fire_alert_notifications(
+            &self.alerts,
+            &newly,
+            &quotes,
+            self.config.notifications_enabled,
+        );
+        spawn_alert_deliveries(
+            fired_alerts(&self.config.alert_sinks, &self.alerts, &newly, &quotes),
+            toast,
+            Config::alert_history_path().ok(),
+        );
 
         self.save_alerts();
     }
 
+    /// Moves expired alerts to [`Config::archived_alerts`]; `true` when any moved (caller saves).
+    fn archive_expired_alerts(&mut self, now: DateTime<Utc>) -> bool {
+        let expired = take_expired_alerts(&mut self.alerts, now);
+        if expired.is_empty() {
+            return false;
+        }
+        self.config.archived_alerts.extend(expired);
+        let sel = self.alerts_state.selected();
+        self.alerts_state.select(match (sel, self.alerts.len()) {
+            (_, 0) => None,
+            (Some(i), n) => Some(i.min(n - 1)),
+            (None, _) => None,
+        });
+        true
+    }
+
     pub(crate) fn retry_alerts_save_if_pending(&mut self) {
         if self.alerts_save_retry_pending {
             self.save_alerts();
@@ -770,6 +1302,25 @@ impl App {
     ///
     /// Polygon may omit `TickerResponse.ticker`; empty ticker applies only to [`Self::symbol`].
     pub fn get_current_price(&self, symbol: &str) -> Option<f64> {
+        if let Some(bar) = self.latest_quote_bar(symbol) {
+            return Some(bar.c);
+        }
+
+        if let Some(portfolio_item) = self
+            .portfolios
+            .iter()
+            .flat_map(|p| &p.holdings)
+            .find(|item| item.symbol.eq_ignore_ascii_case(symbol))
+        {
+            return portfolio_item.current_price;
+        }
+
+        None
+    }
+
+    /// Latest quote bar for `symbol` from the active quote or the watchlist cache (same matching
+    /// rules as [`get_current_price`](Self::get_current_price)).
+    pub(crate) fn latest_quote_bar(&self, symbol: &str) -> Option<&TickerResult> {
         if let Some(ticker_data) = &self.ticker_data {
             if crate::models::ticker::ticker_response_matches_symbol_for_session(
                 ticker_data,
@@ -777,28 +1328,26 @@ impl App {
                 &self.symbol,
             ) {
                 if let Some(bar) = ticker_data.latest_result() {
-                    return Some(bar.c);
-                }
-            }
-        }
-
-        if let Some(sym) = crate::app::normalize_symbol(symbol) {
-            if let Some(resp) = self.watchlist_quotes.get(&sym) {
-                if let Some(bar) = resp.latest_result() {
-                    return Some(bar.c);
+                    return Some(bar);
                 }
             }
         }
 
-        if let Some(portfolio_item) = self
-            .portfolio
-            .iter()
-            .find(|item| item.symbol.eq_ignore_ascii_case(symbol))
-        {
-            return portfolio_item.current_price;
-        }
+        let sym = crate::app::normalize_symbol(symbol)?;
+        self.watchlist_quotes.get(&sym)?.latest_result()
+    }
 
-        None
+    /// Prices, previous close, volume and (for SMA alerts) daily closes for alert evaluation.
+    pub(crate) fn alert_quote(&self, symbol: &str) -> Option<AlertQuote> {
+        let last = self.get_current_price(symbol)?;
+        let daily = crate::app::normalize_symbol(symbol)
+            .and_then(|sym| self.alert_daily_closes.get(&sym));
+        Some(alert_quote_from_bar(
+            symbol,
+            last,
+            self.latest_quote_bar(symbol),
+            daily,
+        ))
     }
 }
 
@@ -819,12 +1368,32 @@ mod get_current_price_tests {
                 c: close,
                 v: 1.0,
                 t: 1,
+                prev_close: None,
+                market_state: None,
+                ext_price: None,
+                week52_low: None,
+                week52_high: None,
+                market_cap: None,
+                currency: None,
             }],
             status: "OK".into(),
             error: None,
         }
     }
 
+    #[test]
+    fn alert_quote_carries_extended_hours_price() {
+        use crate::models::ticker::MarketState;
+        let mut app = App::new();
+        let mut resp = quote_response("AAPL", 150.0);
+        resp.results[0].market_state = Some(MarketState::Post);
+        resp.results[0].ext_price = Some(155.0);
+        app.watchlist_quotes.insert("AAPL".into(), resp);
+        let q = app.alert_quote("AAPL").expect("quote");
+        assert_eq!(q.last, 150.0);
+        assert_eq!(q.extended, Some(155.0));
+    }
+
     #[test]
     fn get_current_price_watchlist_cache_case_insensitive() {
         let mut app = App::new();
@@ -846,7 +1415,7 @@ mod get_current_price_tests {
         let mut app = App::new();
         app.symbol = "MSFT".into();
         app.watchlist_quotes.clear();
-        app.portfolio.clear();
+        app.portfolios.iter_mut().for_each(|p| p.holdings.clear());
         app.ticker_data = Some(quote_response("", 300.0));
         assert_eq!(app.get_current_price("AAPL"), None);
         assert_eq!(app.get_current_price("MSFT"), Some(300.0));
@@ -865,14 +1434,9 @@ mod get_current_price_tests {
     #[test]
     fn get_current_price_portfolio_case_insensitive() {
         let mut app = App::new();
-        app.portfolio = vec![PortfolioItem {
-            symbol: "aapl".into(),
-            shares: 1.0,
-            purchase_price: 1.0,
-            current_price: Some(9.0),
-            purchase_date: None,
-            notes: None,
-        }];
+        let mut item = PortfolioItem::new("aapl".into(), 1.0, 1.0);
+        item.current_price = Some(9.0);
+        app.portfolios[0].holdings = vec![item];
         assert_eq!(app.get_current_price("AAPL"), Some(9.0));
     }
 }
@@ -960,3 +1524,95 @@ mod notify_body_cap_tests {
         assert!(!out.contains("SYMBOL39"));
     }
 }
+
+#[cfg(test)]
+mod daily_closes_tests {
+    use super::{stale_alert_daily_closes, AlertDailyCloses};
+    use crate::models::alerts::{Alert, AlertCondition};
+    use chrono::NaiveDate;
+    use std::collections::HashMap;
+
+    #[test]
+    fn refetch_only_missing_short_or_old_history() {
+        let today = NaiveDate::from_ymd_opt(2024, 7, 10).unwrap();
+        let sma = |sym: &str, days: u32| {
+            let mut a = Alert::new(sym.into(), AlertCondition::CrossAboveSma, 0.0);
+            a.sma_days = Some(days);
+            a
+        };
+        let alerts = vec![
+            sma("aapl", 20),
+            sma("AAPL", 50),
+            sma("MSFT", 20),
+            Alert::new("NVDA".into(), AlertCondition::Above, 1.0),
+        ];
+        let cached = |fetched_on, sessions| AlertDailyCloses {
+            fetched_on,
+            sessions,
+            bars: Vec::new(),
+        };
+        let mut cache = HashMap::from([("MSFT".to_string(), cached(today, 20))]);
+        assert_eq!(stale_alert_daily_closes(&alerts, &cache, today), [("AAPL".to_string(), 50)]);
+        cache.insert("AAPL".into(), cached(today, 20));
+        assert_eq!(stale_alert_daily_closes(&alerts, &cache, today), [("AAPL".to_string(), 50)]);
+        let yesterday = today.pred_opt().unwrap();
+        cache.insert("AAPL".into(), cached(yesterday, 50));
+        assert_eq!(stale_alert_daily_closes(&alerts, &cache, today).len(), 1);
+        cache.insert("AAPL".into(), cached(today, 50));
+        assert!(stale_alert_daily_closes(&alerts, &cache, today).is_empty());
+    }
+
+    #[test]
+    fn completed_closes_exclude_the_quote_day() {
+        const DAY_MS: u64 = 86_400_000;
+        let closes = AlertDailyCloses {
+            fetched_on: NaiveDate::from_ymd_opt(2024, 7, 10).unwrap(),
+            sessions: 2,
+            bars: vec![(DAY_MS * 100, 1.0), (DAY_MS * 101, 2.0), (DAY_MS * 102 + 5, 3.0)],
+        };
+        assert_eq!(closes.completed_before(Some(DAY_MS * 102 + 1_000)), [1.0, 2.0]);
+        assert_eq!(closes.completed_before(None), [1.0, 2.0, 3.0]);
+    }
+}
+
+#[cfg(test)]
+mod repeat_tests {
+    use super::parse_alert_repeat;
+    use crate::models::alerts::AlertRepeat;
+
+    #[test]
+    fn repeat_value_parses_per_mode() {
+        let rearm = AlertRepeat::Rearm { hysteresis: 0.0 };
+        let cooldown = AlertRepeat::Cooldown { minutes: 0 };
+        assert_eq!(parse_alert_repeat(AlertRepeat::Once, "junk"), Ok(AlertRepeat::Once));
+        assert_eq!(parse_alert_repeat(rearm, ""), Ok(rearm));
+        assert_eq!(
+            parse_alert_repeat(rearm, "0.5"),
+            Ok(AlertRepeat::Rearm { hysteresis: 0.5 })
+        );
+        assert_eq!(
+            parse_alert_repeat(cooldown, "30"),
+            Ok(AlertRepeat::Cooldown { minutes: 30 })
+        );
+        assert!(parse_alert_repeat(cooldown, "").is_err());
+        assert!(parse_alert_repeat(cooldown, "1.5").is_err());
+    }
+}
+
+#[cfg(test)]
+mod sink_choice_tests {
+    use super::{alert_sinks_label, cycle_alert_sinks};
+
+    #[test]
+    fn sink_choice_cycles_all_bell_only_then_each_sink() {
+        let names = vec!["team".to_string(), "log".to_string()];
+        let mut choice = None;
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            choice = cycle_alert_sinks(choice, &names, true);
+            seen.push(alert_sinks_label(choice.as_deref()));
+        }
+        assert_eq!(seen, ["Bell only", "team", "log", "All sinks"]);
+        assert_eq!(cycle_alert_sinks(None, &names, false), Some(vec!["log".to_string()]));
+    }
+}
@@ -0,0 +1,381 @@
+//! Headless alert evaluation (`stockterm alerts watch`) — no ratatui frontend.
+//!
+//! Each cycle reloads `~/.stockterm.json` (so alerts added or removed in a TUI session are picked up),
+//! fetches quotes for every alert symbol via [`fetch_quote_batch`] (the same batch the TUI poll
+//! uses), applies [`evaluate_alerts`], fires the bell / desktop toast path and the configured
+//! alert sinks (awaited, failures reported like quote errors), and persists
+//! `triggered`; firings are appended to the alert history file. Daily closes for SMA alerts are kept across cycles and refetched once a day.
+//! Alert state is merged into the config as it is on disk after the fetch, so edits made in a TUI
+//! session while a cycle runs are kept.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::{Local, Utc};
+
+use crate::api::rate_limit::RequestPriority;
+use crate::app::alert_delivery::{deliver_fired_alerts, fired_alerts, firing_entries};
+use crate::app::alerts::{
+    alert_quote_from_bar, fetch_alert_daily_closes, fire_alert_notifications, fired_alert_text,
+    stale_alert_daily_closes, AlertDailyCloses,
+};
+use crate::app::app::{data_poll_interval_secs, fetch_quote_batch};
+use crate::app::normalize_symbol;
+use crate::config::{Config, ConfigError};
+use crate::models::alert_history::{append_alert_history, DESKTOP_CHANNEL};
+use crate::models::alerts::{evaluate_alerts, take_expired_alerts, Alert, AlertQuote};
+use crate::models::ticker::TickerResponse;
+
+/// Options for [`watch_alerts`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AlertsWatchOptions {
+    /// Evaluate one cycle and return (cron-friendly).
+    pub once: bool,
+    /// Poll interval in seconds; `None` → [`Config::refresh_rate`] via the TUI poll rules.
+    pub interval_secs: Option<u64>,
+}
+
+/// Outcome of one watch cycle.
+#[derive(Debug, Default)]
+pub struct AlertsWatchCycle {
+    /// Human-readable line per alert that fired this cycle.
+    pub fired: Vec<String>,
+    /// `(symbol, message)` per failed quote.
+    pub errors: Vec<(String, String)>,
+}
+
+/// [`AlertQuote`] per alert symbol from a quote batch and the SMA daily-close cache, keyed by the
+/// alert's own `symbol` spelling.
+pub(crate) fn alert_quotes_from_batch(
+    alerts: &[Alert],
+    quotes: &HashMap<String, TickerResponse>,
+    daily: &HashMap<String, AlertDailyCloses>,
+) -> Vec<AlertQuote> {
+    let mut out: Vec<AlertQuote> = Vec::new();
+    for alert in alerts {
+        if out.iter().any(|q| q.symbol == alert.symbol) {
+            continue;
+        }
+        let Some(sym) = normalize_symbol(&alert.symbol) else {
+            continue;
+        };
+        let Some(bar) = quotes.get(&sym).and_then(|resp| resp.latest_result()) else {
+            continue;
+        };
+        out.push(alert_quote_from_bar(&alert.symbol, bar.c, Some(bar), daily.get(&sym)));
+    }
+    out
+}
+
+fn fired_line(alert: &Alert, quote: Option<&AlertQuote>) -> String {
+    format!(
+        "{} {}",
+        Local::now().format("%Y-%m-%d %H:%M:%S"),
+        fired_alert_text(&alert.symbol, alert, quote)
+    )
+}
+
+/// Writes one cycle's alert changes into `latest`, the config as reloaded after the cycle: each
+/// alert still as it was when the cycle started (`before`) takes its evaluated state from
+/// `after`, and `expired` alerts move to the archive. Alerts edited or removed in the meantime
+/// keep the newer version.
+fn merge_alert_changes(
+    latest: &mut Config,
+    before: &[Alert],
+    after: &[Alert],
+    expired: Vec<Alert>,
+) {
+    for alert in expired {
+        if let Some(i) = latest.alerts.iter().position(|a| *a == alert) {
+            latest.alerts.remove(i);
+            latest.archived_alerts.push(alert);
+        }
+    }
+    let mut taken = vec![false; latest.alerts.len()];
+    for (old, new) in before.iter().zip(after) {
+        if old == new {
+            continue;
+        }
+        let slot = latest
+            .alerts
+            .iter()
+            .enumerate()
+            .position(|(i, a)| !taken[i] && a == old);
+        if let Some(i) = slot {
+            taken[i] = true;
+            latest.alerts[i] = new.clone();
+        }
+    }
+}
+
+/// Runs one fetch → evaluate → notify → persist cycle against `config`. When alert state
+/// changed, the config is reloaded via `reload`, merged with [`merge_alert_changes`] and saved
+/// via `save`. `daily` caches SMA alert history between cycles; firings are appended to `history`.
+pub(crate) async fn run_alerts_watch_cycle(
+    config: &mut Config,
+    daily: &mut HashMap<String, AlertDailyCloses>,
+    history: Option<&Path>,
+    reload: impl FnOnce() -> Result<Config, ConfigError>,
+    save: impl FnOnce(&Config) -> Result<(), ConfigError>,
+) -> Result<AlertsWatchCycle, ConfigError> {
+    let now = Utc::now();
+    let expired = take_expired_alerts(&mut config.alerts, now);
+    let archived = !expired.is_empty();
+    let before = config.alerts.clone();
+    let persist = |config: &Config| {
+        let mut latest = reload()?;
+        merge_alert_changes(&mut latest, &before, &config.alerts, expired);
+        save(&latest)
+    };
+
+    let mut symbols: Vec<String> = config
+        .alerts
+        .iter()
+        .filter_map(|a| normalize_symbol(&a.symbol))
+        .collect();
+    symbols.sort();
+    symbols.dedup();
+    if symbols.is_empty() {
+        if archived {
+            persist(config)?;
+        }
+        return Ok(AlertsWatchCycle::default());
+    }
+
+    let daily_requests = stale_alert_daily_closes(&config.alerts, daily, Local::now().date_naive());
+    let ((quotes, mut errors), (fresh, daily_errors)) = futures_util::future::join(
+        fetch_quote_batch(
+            symbols.into_iter().map(|sym| (sym, RequestPriority::Background)).collect(),
+            config.clone(),
+        ),
+        fetch_alert_daily_closes(daily_requests, config),
+    )
+    .await;
+    errors.extend(daily_errors);
+    daily.extend(fresh);
+
+    let alert_quotes = alert_quotes_from_batch(&config.alerts, &quotes, daily);
+    let pass = evaluate_alerts(&mut config.alerts, &alert_quotes, now);
+    let newly = pass.fired;
+    let mut cycle = AlertsWatchCycle {
+        fired: Vec::new(),
+        errors: errors.into_iter().map(|(s, e)| (s, e.to_string())).collect(),
+    };
+    if newly.is_empty() {
+        // Re-arms, reference prices and archiving must survive the next cycle's config reload.
+        if pass.changed || archived {
+            persist(config)?;
+        }
+        return Ok(cycle);
+    }
+
+    let toast =
+        fire_alert_notifications(&config.alerts, &newly, &alert_quotes, config.notifications_enabled);
+    let fired = fired_alerts(&config.alert_sinks, &config.alerts, &newly, &alert_quotes);
+    let mut history_error = history
+        .and_then(|path| append_alert_history(path, &firing_entries(&fired, toast.is_some())).err());
+    let entries = deliver_fired_alerts(fired, toast).await;
+    // A missing notification daemon is recorded in the history but does not fail `--once`.
+    cycle.errors.extend(
+        entries
+            .iter()
+            .flat_map(|e| &e.errors)
+            .filter(|f| f.channel != DESKTOP_CHANNEL)
+            .map(|f| (format!("alert sink {}", f.channel), f.error.clone())),
+    );
+    if let Some(path) = history {
+        history_error = history_error.or_else(|| append_alert_history(path, &entries).err());
+    }
+    if let Some(e) = history_error {
+        cycle.errors.push(("alert history".to_string(), e.to_string()));
+    }
+    for &idx in &newly {
+        let alert = &config.alerts[idx];
+        let quote = alert_quotes.iter().find(|q| q.symbol == alert.symbol);
+        cycle.fired.push(fired_line(alert, quote));
+    }
+    persist(config)?;
+    Ok(cycle)
+}
+
+/// Polls until Ctrl-C (or after one cycle with [`AlertsWatchOptions::once`]).
+///
+/// Fired alerts print to stdout; quote and config errors print to stderr and the loop continues.
+/// Returns `false` when the last cycle had any error (used for the `--once` exit status).
+pub async fn watch_alerts(opts: AlertsWatchOptions) -> bool {
+    let mut daily = HashMap::new();
+    let history = Config::alert_history_path().ok();
+    loop {
+        let (ok, refresh_rate) = match Config::try_load() {
+            Ok(mut config) => {
+                let refresh_rate = config.refresh_rate;
+                let ok = match run_alerts_watch_cycle(
+                    &mut config,
+                    &mut daily,
+                    history.as_deref(),
+                    Config::try_load,
+                    Config::try_save,
+                )
+                .await {
+                    Ok(cycle) => {
+                        for line in &cycle.fired {
+                            println!("{line}");
+                        }
+                        for (sym, msg) in &cycle.errors {
+                            eprintln!("stockterm: {sym}: {msg}");
+                        }
+                        cycle.errors.is_empty()
+                    }
+                    Err(e) => {
+                        eprintln!("stockterm: could not save alerts: {e}");
+                        false
+                    }
+                };
+                (ok, refresh_rate)
+            }
+            Err(e) => {
+                eprintln!("stockterm: could not load ~/.stockterm.json: {e}");
+                (false, 0)
+            }
+        };
+        if opts.once {
+            return ok;
+        }
+        let secs = opts
+            .interval_secs
+            .map(|s| s.max(1))
+            .unwrap_or_else(|| data_poll_interval_secs(refresh_rate));
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(secs)) => {}
+            _ = tokio::signal::ctrl_c() => return ok,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::alerts::AlertCondition;
+    use crate::models::ticker::TickerResult;
+
+    fn quote(close: f64) -> TickerResponse {
+        TickerResponse {
+            ticker: String::new(),
+            results: vec![TickerResult {
+                o: close,
+                h: close,
+                l: close,
+                c: close,
+                v: 0.0,
+                t: 1,
+                prev_close: None,
+                market_state: None,
+                ext_price: None,
+                week52_low: None,
+                week52_high: None,
+                market_cap: None,
+                currency: None,
+            }],
+            status: "OK".into(),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn alert_quotes_match_normalized_symbols_once() {
+        let alerts = vec![
+            Alert::new("aapl".into(), AlertCondition::Above, 1.0),
+            Alert::new("aapl".into(), AlertCondition::Below, 500.0),
+            Alert::new("MSFT".into(), AlertCondition::Below, 1.0),
+        ];
+        let quotes = HashMap::from([("AAPL".to_string(), quote(150.0))]);
+        let got = alert_quotes_from_batch(&alerts, &quotes, &HashMap::new());
+        let expected = AlertQuote {
+            volume: Some(0.0),
+            ..AlertQuote::new("aapl", 150.0)
+        };
+        assert_eq!(got, vec![expected]);
+    }
+
+    #[tokio::test]
+    async fn cycle_without_alerts_skips_fetch_and_save() {
+        let mut config = Config::default();
+        let cycle = run_alerts_watch_cycle(
+            &mut config,
+            &mut HashMap::new(),
+            None,
+            || panic!("must not reload"),
+            |_| panic!("must not save"),
+        )
+            .await
+            .expect("cycle");
+        assert!(cycle.fired.is_empty());
+        assert!(cycle.errors.is_empty());
+    }
+
+    #[test]
+    fn merge_keeps_edits_made_since_the_cycle_started() {
+        let a = Alert::new("AAPL".into(), AlertCondition::Above, 100.0);
+        let b = Alert::new("MSFT".into(), AlertCondition::Below, 50.0);
+        let now = Utc::now();
+        let fired = |alert: &Alert| Alert {
+            triggered: true,
+            last_fired: Some(now),
+            ..alert.clone()
+        };
+        let edited_b = Alert { price: 40.0, ..b.clone() };
+        let added = Alert::new("TSLA".into(), AlertCondition::Above, 300.0);
+        let mut latest = Config {
+            alerts: vec![a.clone(), edited_b.clone(), added.clone()],
+            ..Default::default()
+        };
+        let before = [a.clone(), b.clone()];
+        merge_alert_changes(&mut latest, &before, &[fired(&a), fired(&b)], Vec::new());
+        assert_eq!(latest.alerts, vec![fired(&a), edited_b, added]);
+    }
+
+    #[tokio::test]
+    async fn cycle_saves_over_the_config_as_reloaded_after_the_fetch() {
+        let expired = Alert {
+            expires_at: Some(Utc::now() - chrono::Duration::hours(1)),
+            ..Alert::new("OLD".into(), AlertCondition::Above, 1.0)
+        };
+        let live = Alert::new("AAPL".into(), AlertCondition::Above, 100.0);
+        // No Polygon key: the quote fails without waiting on the network or the rate limiter.
+        let mut config = Config {
+            provider: crate::config::MarketProviderKind::Polygon,
+            rate_limits: HashMap::from([(crate::config::MarketProviderKind::Polygon, 0)]),
+            alerts: vec![expired.clone(), live.clone()],
+            ..Default::default()
+        };
+        // Written by a TUI session while the quotes were being fetched.
+        let edited = Alert { price: 120.0, ..live };
+        let added = Alert::new("MSFT".into(), AlertCondition::Below, 50.0);
+        let on_disk = Config {
+            refresh_rate: 60,
+            alerts: vec![expired.clone(), edited.clone(), added.clone()],
+            ..config.clone()
+        };
+
+        let mut saved = None;
+        let cycle = run_alerts_watch_cycle(
+            &mut config,
+            &mut HashMap::new(),
+            None,
+            || Ok(on_disk),
+            |c| {
+                saved = Some(c.clone());
+                Ok(())
+            },
+        )
+            .await
+            .expect("cycle");
+        assert_eq!(cycle.errors.len(), 1);
+
+        let saved = saved.expect("archiving is saved");
+        assert_eq!(saved.refresh_rate, 60);
+        assert_eq!(saved.alerts, vec![edited, added]);
+        assert_eq!(saved.archived_alerts, vec![expired]);
+    }
+}
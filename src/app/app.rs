@@ -1,14 +1,23 @@
 use crate::api::concurrency::acquire_quote_permit;
 use crate::api::error::ProviderError;
+use crate::api::failover::ServedBackend;
 use crate::api::http::maybe_debug_http_delay;
-use crate::api::market_provider_for;
+use crate::api::{market_provider_for, market_provider_for_config};
+use crate::api::quote_stream::QuoteTick;
+use crate::api::rate_limit::{queue_status, with_priority, RequestPriority};
 use crate::api::HistoricalQuery;
-use crate::app::alerts::ALERTS_SAVE_ERROR_PREFIX;
+use crate::app::alerts::{
+    fetch_alert_daily_closes, stale_alert_daily_closes, AlertDailyCloses, ALERTS_SAVE_ERROR_PREFIX,
+};
 use crate::app::app_error::{
     push_error_log, persistence_for_app_error, ActiveErrorState, AppError, ErrorLogEntry,
     ErrorPersistence, ErrorSourceDomain, LastFailedFetch, ERROR_TRANSIENT_TTL,
 };
-use crate::app::charts::{viewport_zoom_in, viewport_zoom_out, ChartDisplayMode, ChartViewport};
+use crate::app::charts::{
+    apply_compare_results, cursor_in_viewport, cursor_step_left, cursor_step_right,
+    viewport_zoom_in, viewport_zoom_out, ChartDisplayMode, ChartRangeDialog, ChartViewport,
+    CompareSeries, MAX_COMPARE_SYMBOLS,
+};
 use crate::app::event::{join_event_thread, spawn_event_thread, Event};
 use crate::app::fetch_delivery::deliver_fetch_done;
 use crate::app::handlers::handle_event;
@@ -17,14 +26,23 @@ use crate::config::theme::{PaletteRgb, Theme, ThemePreset};
 use crate::config::keymap::{Action, BindingLayer};
 use crate::config::{
     Config, ConfigError, LayoutPreset, MarketProviderKind, ResolvedKeymap, ResolvedLayout,
+    ALL_PORTFOLIOS_KEY,
 };
-use crate::models::alerts::{Alert, AlertCondition};
+use crate::models::alert_history::AlertHistoryEntry;
+use crate::models::alerts::{Alert, AlertCondition, AlertRepeat, AlertSchedule};
 use crate::models::historical::HistoricalResponse;
 use crate::models::news::NewsResponse;
-use crate::models::portfolio::PortfolioItem;
+use crate::app::portfolio::{merge_trades, MergeTradesError};
+use crate::models::portfolio::{Portfolio, PortfolioItem, TradeSide, Transaction};
+use crate::models::portfolio_csv::{CsvImport, CsvPreset};
 use crate::models::search::SymbolSearchResponse;
 use crate::models::ticker::TickerResponse;
-use crate::models::time_range::TimeRange;
+use crate::models::indicators::Indicator;
+use crate::models::time_range::{CustomRange, TimeRange};
+use crate::models::currency::major_currency;
+use crate::models::watchlist::{
+    sorted_watchlist_indices, validate_watchlist_name, Watchlist, WatchlistSort,
+};
 use ratatui::backend::Backend;
 use ratatui::widgets::{ListState, TableState};
 use ratatui::Terminal;
@@ -87,13 +105,22 @@ pub enum SettingsEdit {
 pub enum PortfolioAddField {
     Shares,
     Price,
+    Fees,
+    Date,
 }
 
-/// In-modal state for adding a portfolio row (Issue #6 / SPEC §13).
+/// In-modal state for recording a buy or sell (Issue #6 / SPEC §13).
 #[derive(Debug, Clone)]
 pub struct PortfolioAddDialog {
+    pub side: TradeSide,
+    /// Holding a sell applies to; `None` → the active [`App::symbol`] (buys).
+    pub symbol: Option<String>,
     pub shares_buffer: String,
     pub price_buffer: String,
+    /// Blank → no fees.
+    pub fees_buffer: String,
+    /// `YYYY-MM-DD`; blank → undated.
+    pub date_buffer: String,
     pub focused: PortfolioAddField,
     pub inline_error: Option<String>,
 }
@@ -101,19 +128,129 @@ pub struct PortfolioAddDialog {
 impl Default for PortfolioAddDialog {
     fn default() -> Self {
         Self {
+            side: TradeSide::Buy,
+            symbol: None,
             shares_buffer: String::new(),
             price_buffer: String::new(),
+            fees_buffer: String::new(),
+            date_buffer: chrono::Local::now().format("%Y-%m-%d").to_string(),
             focused: PortfolioAddField::Shares,
             inline_error: None,
         }
     }
 }
 
+impl PortfolioAddDialog {
+    /// Sell dialog for an existing holding.
+    pub fn sell(symbol: String) -> Self {
+        Self {
+            side: TradeSide::Sell,
+            symbol: Some(symbol),
+            ..Self::default()
+        }
+    }
+}
+
+/// What the Stock View watchlist prompt does on Enter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchlistPromptKind {
+    /// `Ctrl+N`: add a list and switch to it.
+    Create,
+    /// `Ctrl+T`: rename the active list.
+    Rename,
+    /// `Ctrl+X`: confirm deleting the active list (no text input).
+    Delete,
+}
+
+/// Name prompt (or delete confirmation) over the Stock View watchlist.
+#[derive(Debug, Clone)]
+pub struct WatchlistPrompt {
+    pub kind: WatchlistPromptKind,
+    pub buffer: String,
+    pub inline_error: Option<String>,
+}
+
+impl WatchlistPrompt {
+    pub fn new(kind: WatchlistPromptKind, buffer: impl Into<String>) -> Self {
+        Self {
+            kind,
+            buffer: buffer.into(),
+            inline_error: None,
+        }
+    }
+}
+
+/// Portfolio CSV dialog direction (`i` import / `e` export).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortfolioCsvMode {
+    Import,
+    Export,
+}
+
+/// Path prompt for CSV import / export; an import shows its parsed rows before merging.
+#[derive(Debug, Clone)]
+pub struct PortfolioCsvDialog {
+    pub mode: PortfolioCsvMode,
+    /// `~/` expands to the home directory.
+    pub path_buffer: String,
+    /// Import column mapping; Tab cycles.
+    pub preset: CsvPreset,
+    /// Parsed file awaiting Enter (import only); `None` while the path is edited.
+    pub preview: Option<CsvImport>,
+    /// First preview line shown (↑/↓ scroll).
+    pub preview_scroll: usize,
+    pub inline_error: Option<String>,
+}
+
+impl PortfolioCsvDialog {
+    pub fn new(mode: PortfolioCsvMode) -> Self {
+        Self {
+            mode,
+            path_buffer: DEFAULT_PORTFOLIO_CSV_PATH.to_string(),
+            preset: CsvPreset::Auto,
+            preview: None,
+            preview_scroll: 0,
+            inline_error: None,
+        }
+    }
+}
+
+/// Alerts history sub-view: the history file as read when opened, newest first.
+#[derive(Debug, Clone, Default)]
+pub struct AlertHistoryView {
+    pub entries: Vec<AlertHistoryEntry>,
+    /// Unreadable lines skipped while loading.
+    pub bad_lines: usize,
+    /// Selection among the filtered rows.
+    pub state: TableState,
+    /// CSV path being typed after **e** (`~/` expands to the home directory).
+    pub export_path: Option<String>,
+    pub inline_error: Option<String>,
+    /// "Exported …" after a successful export.
+    pub status: Option<String>,
+}
+
+/// Prefilled path in the Alerts history export prompt.
+pub const DEFAULT_ALERT_HISTORY_CSV_PATH: &str = "~/stockterm-alert-history.csv";
+
+/// Prefilled path in the Portfolio CSV dialog.
+pub const DEFAULT_PORTFOLIO_CSV_PATH: &str = "~/stockterm-portfolio.csv";
+
 /// Add-alert modal field focus (SPEC §18.4).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AlertAddField {
     Symbol,
     Condition,
+    /// Regular session only vs. also pre/post-market prices.
+    Session,
+    /// One-shot, re-arm with a hysteresis band, or repeat after a cooldown.
+    Repeat,
+    /// Any time, weekdays, or regular session only.
+    Active,
+    /// Optional `YYYY-MM-DD`; the alert is archived after that local day.
+    Expires,
+    /// All alert sinks, none, or one named sink; skipped when `alert_sinks` is empty.
+    Notify,
     Threshold,
 }
 
@@ -122,6 +259,16 @@ pub struct AlertAddDialog {
     pub symbol_buffer: String,
     pub condition: AlertCondition,
     pub threshold_buffer: String,
+    pub extended_hours: bool,
+    /// Mode only; the band / minutes are typed into `repeat_buffer`.
+    pub repeat: AlertRepeat,
+    pub repeat_buffer: String,
+    pub schedule: AlertSchedule,
+    pub expires_buffer: String,
+    /// [`Alert::sinks`](crate::models::alerts::Alert::sinks) for the new alert.
+    pub sinks: Option<Vec<String>>,
+    /// Names from [`Config::alert_sinks`] when the dialog opened.
+    pub sink_names: Vec<String>,
     pub focused: AlertAddField,
     pub inline_error: Option<String>,
 }
@@ -132,6 +279,13 @@ impl AlertAddDialog {
             symbol_buffer: normalize_symbol(&app.symbol).unwrap_or_default(),
             condition: AlertCondition::Above,
             threshold_buffer: String::new(),
+            extended_hours: false,
+            repeat: AlertRepeat::Once,
+            repeat_buffer: String::new(),
+            schedule: AlertSchedule::default(),
+            expires_buffer: String::new(),
+            sinks: None,
+            sink_names: app.config.alert_sinks.iter().map(|s| s.name.clone()).collect(),
             focused: AlertAddField::Symbol,
             inline_error: None,
         }
@@ -154,22 +308,35 @@ pub enum FetchDone {
     Stock {
         generation: u64,
         quotes: HashMap<String, TickerResponse>,
+        /// SMA alert history fetched alongside stale symbols' quotes.
+        daily_closes: HashMap<String, AlertDailyCloses>,
         errors: Vec<(String, ProviderError)>,
+        /// Backend that answered, when a fallback chain is configured ([`ServedBackend`]).
+        served_by: Option<MarketProviderKind>,
     },
     Historical {
         symbol: String,
         time_range: TimeRange,
+        /// Window used for [`TimeRange::Custom`]; results are dropped if it changed in flight.
+        custom_range: Option<CustomRange>,
         result: Result<HistoricalResponse, ProviderError>,
+        /// Charts compare-mode series fetched with the same query.
+        compare: Vec<(String, Result<HistoricalResponse, ProviderError>)>,
+        served_by: Option<MarketProviderKind>,
     },
     News {
         symbol: String,
         result: Result<NewsResponse, ProviderError>,
+        served_by: Option<MarketProviderKind>,
     },
     Search {
         generation: u64,
         query: String,
         result: Result<SymbolSearchResponse, ProviderError>,
+        served_by: Option<MarketProviderKind>,
     },
+    /// Live prices from the [`QuoteStream`](crate::api::quote_stream::QuoteStream).
+    QuoteTicks(Vec<QuoteTick>),
 }
 
 #[cfg(debug_assertions)]
@@ -251,13 +418,37 @@ pub struct App {
     pub should_quit: bool,
     pub should_fetch_ticker: bool,
     pub symbol: String,
+    /// Symbols of the active named list (mirrors `config.watchlists[active_watchlist]`).
     pub watchlist: Vec<String>,
+    /// Index into [`Config::watchlists`] of the list shown on Stock View.
+    pub active_watchlist: usize,
+    /// Create / rename / delete prompt for named watchlists (`None` = closed).
+    pub watchlist_prompt: Option<WatchlistPrompt>,
+    /// Stock View row order; `None` = insertion order (persisted as `watchlist_sort`).
+    pub watchlist_sort: Option<WatchlistSort>,
     pub watchlist_quotes: HashMap<String, TickerResponse>,
+    /// Daily closes for symbols with SMA alerts, refreshed once per day with the quote poll.
+    pub alert_daily_closes: HashMap<String, AlertDailyCloses>,
     pub watchlist_state: TableState,
+    /// Named accounts (mirrors [`Config::portfolios`] plus runtime prices).
+    pub portfolios: Vec<Portfolio>,
+    /// Portfolio tab account; `None` = All accounts.
+    pub portfolio_account: Option<usize>,
+    /// Rows shown on the Portfolio tab: the active account's holdings, or every account's for All.
     pub portfolio: Vec<PortfolioItem>,
+    /// Account index of each `portfolio` row.
+    pub(crate) portfolio_row_accounts: Vec<usize>,
+    /// Portfolio: new-account name being typed after `n` (`None` = not editing).
+    pub portfolio_account_input: Option<String>,
+    /// Inline error under the new-account prompt (duplicate / blank name).
+    pub portfolio_account_input_error: Option<String>,
     pub portfolio_state: TableState,
     pub alerts: Vec<Alert>,
     pub alerts_state: TableState,
+    /// Alerts tab also lists [`Config::archived_alerts`] (grayed, not selectable).
+    pub alerts_show_archived: bool,
+    /// Alerts tab shows the firing log instead of the alerts table (`l`).
+    pub alert_history: Option<AlertHistoryView>,
     pub active_tab: Tab,
     /// Issue #20 / §20 — surfaced runtime error (status bar); `error_message()` exposes line text.
     pub(crate) active_runtime_error: Option<ActiveErrorState>,
@@ -277,6 +468,9 @@ pub struct App {
     /// terminal size.
     pub error_log_visible_rows: usize,
     pub last_failed_fetch: LastFailedFetch,
+    /// Backend behind the data each fetch last put on screen (from [`FetchDone`]); absent while
+    /// the primary serves or no fallback chain is configured.
+    served_by: HashMap<ErrorSourceDomain, MarketProviderKind>,
     pub search_query: String,
     pub search_table_state: TableState,
     pub search_request_generation: u64,
@@ -298,7 +492,12 @@ pub struct App {
     /// True while a watchlist / quote batch is in flight.
     pub stock_refresh_inflight: bool,
     stock_inflight_since: Option<Instant>,
-    fetch_done_tx: Option<UnboundedSender<FetchDone>>,
+    /// Watchdog window of the running quote batch (see [`stock_batch_stale_after`]).
+    stock_stale_after: Option<Duration>,
+    /// The running quote batch, aborted when stale recovery gives up on it so calls it still has
+    /// queued on a provider's rate limiter leave the queue.
+    stock_batch: Option<tokio::task::AbortHandle>,
+    pub(crate) fetch_done_tx: Option<UnboundedSender<FetchDone>>,
     inflight_recovery_tx: Option<UnboundedSender<InflightRecovery>>,
     stock_fetch_generation: u64,
     stock_refresh_pending: bool,
@@ -308,18 +507,36 @@ pub struct App {
     news_inflight_since: Option<Instant>,
     search_inflight_since: Option<Instant>,
     url_op_tx: Option<UnboundedSender<UrlOpDone>>,
+    /// Symbols followed by the live quote stream while one runs (`stream_quotes`).
+    pub(crate) quote_stream_symbols: Option<tokio::sync::watch::Sender<Vec<String>>>,
     news_url_op_inflight: bool,
     news_url_flash: Option<(crate::app::open_url::NewsUrlFlashHint, Instant)>,
-    /// Charts tab: selected window (Issue #9).
+    /// Charts tab: selected window (Issue #9); restored from [`Config::last_time_range`].
     pub time_range: TimeRange,
+    /// Charts tab: from/to dates used when `time_range` is [`TimeRange::Custom`].
+    pub custom_range: Option<CustomRange>,
+    /// Charts tab: custom from/to dialog opened with `t`.
+    pub chart_range_dialog: Option<ChartRangeDialog>,
     /// Charts tab: pan/zoom indices into `historical_data.results` (Issue #8).
     pub chart_viewport: ChartViewport,
     /// Line vs candlestick rendering (Issue #7).
     pub chart_mode: ChartDisplayMode,
+    /// Charts tab: crosshair bar index into `historical_data.results` (`None` = hidden).
+    pub chart_cursor: Option<usize>,
+    /// Charts compare mode: up to [`MAX_COMPARE_SYMBOLS`] extra series drawn as % change.
+    pub chart_compare: Vec<CompareSeries>,
+    /// Charts compare mode: symbol being typed after `a` (`None` = not editing).
+    pub chart_compare_input: Option<String>,
     /// Issue #6 — add holding (shares / price) modal.
     pub portfolio_dialog: Option<PortfolioAddDialog>,
     /// Issue #6 — first `d` arms; second `d` or `y` confirms remove.
     pub portfolio_remove_armed: bool,
+    /// Portfolio: open-lots panel for the selected holding (`l`).
+    pub portfolio_lots_open: bool,
+    /// Portfolio: CSV import / export dialog (`i` / `e`).
+    pub portfolio_csv: Option<PortfolioCsvDialog>,
+    /// Portfolio: "Exported … / Imported …" status line after a CSV dialog closes.
+    portfolio_csv_flash: Option<(String, Instant)>,
     /// SPEC §18.4 — add price alert dialog.
     pub alert_add_dialog: Option<AlertAddDialog>,
     /// SPEC §18.14.2 — `try_save` failed in `save_alerts`; retry once per stock batch.
@@ -335,6 +552,8 @@ pub struct App {
 }
 
 const MISSING_API_KEY_FOR_POLYGON_MSG: &str = "Polygon provider requires a non-empty `api_key` in ~/.stockterm.json or export STOCKTERM_API_KEY.";
+const MISSING_API_KEY_FOR_ALPHA_VANTAGE_MSG: &str = "Alpha Vantage provider requires a non-empty `alpha_vantage_api_key` in ~/.stockterm.json or export STOCKTERM_ALPHA_VANTAGE_API_KEY.";
+const MISSING_API_KEY_FOR_FINNHUB_MSG: &str = "Finnhub provider requires a non-empty `finnhub_api_key` in ~/.stockterm.json or export STOCKTERM_FINNHUB_API_KEY.";
 
 const MAX_CONCURRENT_QUOTES: usize = 2;
 
@@ -350,6 +569,8 @@ const SETTINGS_SAVED_FLASH: Duration = Duration::from_secs(2);
 
 const NEWS_URL_FLASH: Duration = Duration::from_secs(2);
 
+const PORTFOLIO_CSV_FLASH: Duration = Duration::from_secs(4);
+
 /// Issue #78 / SPEC §39.2 — clear stuck inflight when both channel sends fail.
 const INFLIGHT_STALE_AFTER: Duration = Duration::from_secs(120);
 
@@ -361,6 +582,22 @@ fn inflight_stale_after() -> Duration {
         .unwrap_or(INFLIGHT_STALE_AFTER)
 }
 
+/// Watchdog window for a quote batch of `calls` provider calls: [`inflight_stale_after`] plus the
+/// time the slowest rate-limited backend in the chain needs to pace them and any calls already
+/// queued, so a batch that is slow but healthy is not cleared and restarted.
+fn stock_batch_stale_after(calls: usize, config: &Config) -> Duration {
+    let queued = queue_status().map_or(0, |q| q.depth);
+    let pacing = config
+        .provider_chain()
+        .into_iter()
+        .filter_map(|kind| config.requests_per_minute(kind))
+        .min()
+        .map_or(Duration::ZERO, |rpm| {
+            Duration::from_secs(60) / rpm * (calls + queued) as u32
+        });
+    inflight_stale_after() + pacing
+}
+
 /// Trim and uppercase ticker input; returns `None` if empty after trim.
 pub fn normalize_symbol(s: &str) -> Option<String> {
     let t = s.trim();
@@ -370,6 +607,16 @@ pub fn normalize_symbol(s: &str) -> Option<String> {
     Some(t.to_uppercase())
 }
 
+/// Normalized, de-duplicated tickers of one named watchlist (order kept).
+fn normalized_watchlist_symbols(symbols: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    symbols
+        .iter()
+        .filter_map(|s| normalize_symbol(s))
+        .filter(|s| seen.insert(s.clone()))
+        .collect()
+}
+
 fn quote_error_digest_for_merge(err: &AppError) -> String {
     match err {
         AppError::Provider(pe) => pe.to_string(),
@@ -387,12 +634,44 @@ fn alerts_disk_failure_head_for_quote_merge(full: &str) -> &str {
 
 async fn run_stock_quote_batch(
     generation: u64,
-    symbols: Vec<String>,
+    symbols: Vec<(String, RequestPriority)>,
+    daily_requests: Vec<(String, u32)>,
     config: Config,
 ) -> FetchDone {
+    let served = ServedBackend::default();
+    let ((quotes, mut errors), (daily_closes, daily_errors)) = served
+        .scope(futures_util::future::join(
+            fetch_quote_batch(symbols, config.clone()),
+            fetch_alert_daily_closes(daily_requests, &config),
+        ))
+        .await;
+    errors.extend(daily_errors);
+    FetchDone::Stock {
+        generation,
+        quotes,
+        daily_closes,
+        errors,
+        served_by: served.get(),
+    }
+}
+
+/// Quotes keyed by requested symbol, plus per-symbol failures.
+pub(crate) type QuoteBatch = (HashMap<String, TickerResponse>, Vec<(String, ProviderError)>);
+
+/// One quote batch for the TUI poll loop and the headless `alerts watch` loop: Yahoo batch path
+/// when Yahoo has no fallbacks or rate limit, otherwise per-symbol provider calls queued at each
+/// symbol's [`RequestPriority`] and capped at [`MAX_CONCURRENT_QUOTES`] (uncapped when the
+/// provider's rate limiter already paces them).
+pub(crate) async fn fetch_quote_batch(
+    mut symbols: Vec<(String, RequestPriority)>,
+    config: Config,
+) -> QuoteBatch {
     maybe_debug_http_delay().await;
 
-    if config.provider == MarketProviderKind::Yahoo {
+    if config.provider_chain() == [MarketProviderKind::Yahoo]
+        && config.requests_per_minute(MarketProviderKind::Yahoo).is_none()
+    {
+        let symbols: Vec<String> = symbols.into_iter().map(|(sym, _)| sym).collect();
         let (quotes_raw, mut errors) =
             crate::api::yahoo::yahoo_latest_quotes_for_symbols(&symbols, MAX_CONCURRENT_QUOTES).await;
         let mut quotes = HashMap::new();
@@ -406,26 +685,31 @@ async fn run_stock_quote_batch(
             }
             quotes.insert(sym, data);
         }
-        return FetchDone::Stock {
-            generation,
-            quotes,
-            errors,
-        };
+        return (quotes, errors);
     }
 
-    let provider = market_provider_for(config.provider);
-    let sem = std::sync::Arc::new(Semaphore::new(MAX_CONCURRENT_QUOTES));
+    let provider = market_provider_for_config(&config);
+    let concurrency = match config.requests_per_minute(config.provider) {
+        Some(_) => symbols.len().max(1),
+        None => MAX_CONCURRENT_QUOTES,
+    };
+    let sem = std::sync::Arc::new(Semaphore::new(concurrency));
+    // Highest priority first, so it also reaches the limiter queue first.
+    symbols.sort_by_key(|&(_, priority)| std::cmp::Reverse(priority));
     let mut set = JoinSet::new();
-    for sym in symbols {
+    for (sym, priority) in symbols {
         let sem = sem.clone();
         let cfg = config.clone();
         let provider = provider.clone();
+        let served = ServedBackend::current();
         set.spawn(async move {
-            let _permit = match acquire_quote_permit(&sem, &sym, "polygon").await {
+            let _permit = match acquire_quote_permit(&sem, &sym, config.provider.as_str()).await {
                 Ok(p) => p,
                 Err(e) => return (sym, Err(e)),
             };
-            let res = provider.get_quote(&sym, &cfg).await;
+            let res = served
+                .scope(with_priority(priority, provider.get_quote(&sym, &cfg)))
+                .await;
             (sym, res)
         });
     }
@@ -453,11 +737,7 @@ async fn run_stock_quote_batch(
         }
     }
 
-    FetchDone::Stock {
-        generation,
-        quotes,
-        errors,
-    }
+    (quotes, errors)
 }
 
 impl App {
@@ -479,19 +759,24 @@ impl App {
                 None => AppError::Internal(ke),
             });
         }
-        let portfolio = config.portfolio.clone();
+        let portfolios = config.portfolios.clone();
+        let portfolio_account = match config.last_portfolio.as_deref() {
+            Some(ALL_PORTFOLIOS_KEY) if portfolios.len() > 1 => None,
+            Some(name) => Some(portfolios.iter().position(|p| p.name == name).unwrap_or(0)),
+            None => Some(0),
+        };
         let alerts = config.alerts.clone();
 
-        let watchlist: Vec<String> = config
-            .watchlist
-            .iter()
-            .filter_map(|s| normalize_symbol(s))
-            .collect();
-        let mut seen = HashSet::new();
-        let watchlist: Vec<String> = watchlist
-            .into_iter()
-            .filter(|s| seen.insert(s.clone()))
-            .collect();
+        let active_watchlist = config
+            .last_watchlist
+            .as_deref()
+            .and_then(|name| config.watchlists.iter().position(|w| w.name == name))
+            .unwrap_or(0);
+        let watchlist = config
+            .watchlists
+            .get(active_watchlist)
+            .map(|w| normalized_watchlist_symbols(&w.symbols))
+            .unwrap_or_default();
 
         let symbol = if let Some(first) = watchlist.first() {
             first.clone()
@@ -510,6 +795,12 @@ impl App {
             .and_then(Tab::from_config_str)
             .unwrap_or(Tab::StockView);
 
+        // A `custom` range without saved dates falls back to the default window.
+        let time_range = config
+            .last_time_range
+            .filter(|tr| *tr != TimeRange::Custom || config.custom_range.is_some())
+            .unwrap_or_default();
+
         let mut watchlist_state = TableState::default();
         if !watchlist.is_empty() {
             watchlist_state.select(Some(0));
@@ -533,12 +824,23 @@ impl App {
             should_fetch_ticker: false,
             symbol,
             watchlist,
+            active_watchlist,
+            watchlist_prompt: None,
+            watchlist_sort: config.watchlist_sort,
             watchlist_quotes: HashMap::new(),
+            alert_daily_closes: HashMap::new(),
             watchlist_state,
-            portfolio,
+            portfolios,
+            portfolio_account,
+            portfolio: Vec::new(),
+            portfolio_row_accounts: Vec::new(),
+            portfolio_account_input: None,
+            portfolio_account_input_error: None,
             portfolio_state: TableState::default(),
             alerts,
             alerts_state: TableState::default(),
+            alerts_show_archived: false,
+            alert_history: None,
             active_tab,
             active_runtime_error: None,
             startup_error,
@@ -565,6 +867,9 @@ impl App {
             last_news_network_poll: None,
             stock_refresh_inflight: false,
             stock_inflight_since: None,
+            stock_stale_after: None,
+            served_by: HashMap::new(),
+            stock_batch: None,
             fetch_done_tx: None,
             inflight_recovery_tx: None,
             stock_fetch_generation: 0,
@@ -575,13 +880,22 @@ impl App {
             news_inflight_since: None,
             search_inflight_since: None,
             url_op_tx: None,
+            quote_stream_symbols: None,
             news_url_op_inflight: false,
             news_url_flash: None,
-            time_range: TimeRange::default(),
+            time_range,
+            custom_range: config.custom_range,
+            chart_range_dialog: None,
             chart_viewport: ChartViewport::default(),
             chart_mode: ChartDisplayMode::default(),
+            chart_cursor: None,
+            chart_compare: Vec::new(),
+            chart_compare_input: None,
             portfolio_dialog: None,
             portfolio_remove_armed: false,
+            portfolio_lots_open: false,
+            portfolio_csv: None,
+            portfolio_csv_flash: None,
             alert_add_dialog: None,
             alerts_save_retry_pending: false,
             settings_theme_draft,
@@ -590,6 +904,7 @@ impl App {
             filter_input_mode: false,
         };
 
+        app.rebuild_portfolio_view();
         if !app.portfolio.is_empty() {
             app.portfolio_state.select(Some(0));
         }
@@ -672,10 +987,22 @@ impl App {
         ));
     }
 
-    /// Copy `active_tab` / `symbol` into `config` before any disk write (Issue #19 / §22).
+    /// Copy `active_tab` / `symbol` / chart range into `config` before any disk write (Issue #19 / §22).
     pub(crate) fn sync_session_fields_into_config(&mut self) {
         self.config.last_tab = Some(self.active_tab.as_config_str().to_string());
         self.config.last_symbol = normalize_symbol(&self.symbol);
+        self.config.last_time_range = Some(self.time_range);
+        self.config.custom_range = self.custom_range;
+        self.config.last_portfolio = Some(match self.portfolio_account {
+            Some(i) => self.portfolios.get(i).map_or_else(String::new, |p| p.name.clone()),
+            None => ALL_PORTFOLIOS_KEY.to_string(),
+        });
+        self.config.last_watchlist = self
+            .config
+            .watchlists
+            .get(self.active_watchlist)
+            .map(|w| w.name.clone());
+        self.config.watchlist_sort = self.watchlist_sort;
     }
 
     /// [`Config::try_save`] after refreshing `last_tab` / `last_symbol` from UI state.
@@ -791,9 +1118,41 @@ impl App {
     }
 
     fn provider_ready(&self) -> bool {
+        self.config.has_credentials_for(self.config.provider)
+    }
+
+    /// Fallback backend that served the data on the active tab, when it is not [`Config::provider`].
+    pub(crate) fn served_by_fallback(&self) -> Option<MarketProviderKind> {
+        if self.config.fallback_providers.is_empty() {
+            return None;
+        }
+        let domain = match self.active_tab {
+            Tab::StockView | Tab::Portfolio | Tab::Alerts => ErrorSourceDomain::Stock,
+            Tab::Charts => ErrorSourceDomain::Charts,
+            Tab::News => ErrorSourceDomain::News,
+            Tab::Search => ErrorSourceDomain::Search,
+            Tab::Settings => return None,
+        };
+        self.served_by
+            .get(&domain)
+            .copied()
+            .filter(|&kind| kind != self.config.provider)
+    }
+
+    /// Records the backend of a fetch whose data was just applied to `domain`'s view.
+    fn note_served_by(&mut self, domain: ErrorSourceDomain, served_by: Option<MarketProviderKind>) {
+        match served_by {
+            Some(kind) => self.served_by.insert(domain, kind),
+            None => self.served_by.remove(&domain),
+        };
+    }
+
+    /// Status text when [`provider_ready`](Self::provider_ready) is false.
+    fn missing_api_key_msg(&self) -> &'static str {
         match self.config.provider {
-            MarketProviderKind::Yahoo => true,
-            MarketProviderKind::Polygon => !self.config.effective_api_key().is_empty(),
+            MarketProviderKind::AlphaVantage => MISSING_API_KEY_FOR_ALPHA_VANTAGE_MSG,
+            MarketProviderKind::Finnhub => MISSING_API_KEY_FOR_FINNHUB_MSG,
+            _ => MISSING_API_KEY_FOR_POLYGON_MSG,
         }
     }
 
@@ -808,6 +1167,18 @@ impl App {
         self.last_news_network_poll = None;
     }
 
+    /// Limiter queue priority for a quote of `symbol` (as returned by
+    /// [`collect_symbols_for_quote_fetch`](Self::collect_symbols_for_quote_fetch)).
+    pub(crate) fn quote_fetch_priority(&self, symbol: &str) -> RequestPriority {
+        if normalize_symbol(&self.symbol).as_deref() == Some(symbol) {
+            RequestPriority::Active
+        } else if self.watchlist.iter().any(|s| s == symbol) {
+            RequestPriority::Watchlist
+        } else {
+            RequestPriority::Background
+        }
+    }
+
     pub(crate) fn collect_symbols_for_quote_fetch(&self) -> Vec<String> {
         let mut seen = HashSet::new();
         let mut out = Vec::new();
@@ -821,18 +1192,68 @@ impl App {
                 out.push(sym);
             }
         }
-        for item in &self.portfolio {
-            if let Some(sym) = normalize_symbol(&item.symbol) {
+        let portfolio_symbols = self.portfolios.iter().flat_map(|p| &p.holdings).map(|i| &i.symbol);
+        let alert_symbols = self.alerts.iter().map(|a| &a.symbol);
+        for sym in portfolio_symbols.chain(alert_symbols) {
+            if let Some(sym) = normalize_symbol(sym) {
                 if seen.insert(sym.clone()) {
                     out.push(sym);
                 }
             }
         }
+        for sym in self.portfolio_fx_symbols() {
+            if seen.insert(sym.clone()) {
+                out.push(sym);
+            }
+        }
+        out
+    }
+
+    /// ISO code of [`Config::base_currency`].
+    pub fn base_currency(&self) -> String {
+        major_currency(&self.config.base_currency).0
+    }
+
+    /// FX pair quotes needed to convert every holding into the base currency.
+    fn portfolio_fx_symbols(&self) -> Vec<String> {
+        let base = self.base_currency();
+        let provider = market_provider_for(self.config.provider);
+        let mut out: Vec<String> = Vec::new();
+        for code in self
+            .portfolios
+            .iter()
+            .flat_map(|p| &p.holdings)
+            .filter_map(|i| i.currency.as_deref())
+        {
+            let (iso, _) = major_currency(code);
+            let sym = provider.fx_symbol(&iso, &base);
+            if iso != base && !out.contains(&sym) {
+                out.push(sym);
+            }
+        }
         out
     }
 
+    /// Multiplier from `currency` (as quoted, e.g. `GBp`) to the base currency. `None` = base
+    /// currency. `None` is returned while the FX quote has not arrived.
+    pub fn fx_rate(&self, currency: Option<&str>) -> Option<f64> {
+        let Some(code) = currency else {
+            return Some(1.0);
+        };
+        let base = self.base_currency();
+        let (iso, factor) = major_currency(code);
+        if iso == base {
+            return Some(factor);
+        }
+        let sym = market_provider_for(self.config.provider).fx_symbol(&iso, &base);
+        let rate = self.watchlist_quotes.get(&sym)?.latest_result()?.c;
+        (rate > 0.0).then_some(rate * factor)
+    }
+
     fn clear_portfolio_tab_transient(&mut self) {
         self.portfolio_dialog = None;
+        self.portfolio_csv = None;
+        self.portfolio_account_input = None;
         self.portfolio_remove_armed = false;
     }
 
@@ -842,8 +1263,17 @@ impl App {
         self.filter_input_mode = false;
     }
 
+    /// Watchlist rows as drawn: backing indices in [`watchlist_sort`](Self::watchlist_sort) order,
+    /// narrowed by the table filter.
     pub(crate) fn watchlist_filter_indices(&self) -> Vec<usize> {
-        crate::app::table_filter::filter_symbol_indices(&self.watchlist, &self.filter_query)
+        let order = sorted_watchlist_indices(&self.watchlist, self.watchlist_sort, |sym| {
+            self.watchlist_quotes.get(sym).and_then(|r| r.latest_result())
+        });
+        crate::app::table_filter::filter_ordered_indices(
+            order,
+            |i| self.watchlist[i].as_str(),
+            &self.filter_query,
+        )
     }
 
     pub(crate) fn portfolio_filter_indices(&self) -> Vec<usize> {
@@ -975,7 +1405,7 @@ impl App {
             self.surface_runtime_error(
                 self.active_tab,
                 ErrorSourceDomain::Stock,
-                AppError::Internal(MISSING_API_KEY_FOR_POLYGON_MSG.to_string()),
+                AppError::Internal(self.missing_api_key_msg().to_string()),
                 true,
             );
             self.ticker_data = None;
@@ -987,10 +1417,31 @@ impl App {
         self.stock_fetch_generation += 1;
         let generation = self.stock_fetch_generation;
         let cfg = self.config.clone();
+        let symbols: Vec<(String, RequestPriority)> = symbols
+            .into_iter()
+            .map(|sym| {
+                let priority = self.quote_fetch_priority(&sym);
+                (sym, priority)
+            })
+            .collect();
+        let daily_requests = stale_alert_daily_closes(
+            &self.alerts,
+            &self.alert_daily_closes,
+            chrono::Local::now().date_naive(),
+        );
         let recovery_tx = self.inflight_recovery_tx.clone();
+        self.stock_stale_after = Some(stock_batch_stale_after(
+            symbols.len() + daily_requests.len(),
+            &self.config,
+        ));
 
-        tokio::spawn(async move {
-            let done = match AssertUnwindSafe(run_stock_quote_batch(generation, symbols, cfg))
+        let batch = tokio::spawn(async move {
+            let done = match AssertUnwindSafe(run_stock_quote_batch(
+                generation,
+                symbols,
+                daily_requests,
+                cfg,
+            ))
                 .catch_unwind()
                 .await
             {
@@ -1003,10 +1454,12 @@ impl App {
                     FetchDone::Stock {
                         generation,
                         quotes: HashMap::new(),
+                        daily_closes: HashMap::new(),
                         errors: vec![(
                             String::new(),
                             ProviderError::ApiMessage("quote batch task panicked".into()),
                         )],
+                        served_by: None,
                     }
                 }
             };
@@ -1017,6 +1470,7 @@ impl App {
                 InflightRecovery::Stock,
             );
         });
+        self.stock_batch = Some(batch.abort_handle());
     }
 
     fn apply_stock_fetch_done(
@@ -1033,11 +1487,16 @@ impl App {
 
         self.stock_refresh_inflight = false;
         self.stock_inflight_since = None;
+        self.stock_stale_after = None;
+        self.stock_batch = None;
         self.last_stock_network_poll = Some(Instant::now());
 
+        // New quotes can reorder a sorted watchlist; keep the cursor on the same symbol.
+        let selected = self.selected_watchlist_symbol();
         for (k, v) in quotes {
             self.watchlist_quotes.insert(k, v);
         }
+        self.keep_watchlist_cursor_on(selected);
 
         self.ticker_data = self.watchlist_quotes.get(&self.symbol).cloned();
 
@@ -1102,13 +1561,18 @@ impl App {
             self.last_failed_fetch = LastFailedFetch::None;
         }
 
-        for item in &mut self.portfolio {
+        for item in self.portfolios.iter_mut().flat_map(|p| &mut p.holdings) {
             if let Some(resp) = self.watchlist_quotes.get(&item.symbol) {
                 if let Some(bar) = resp.latest_result() {
                     item.current_price = Some(bar.c);
+                    item.previous_close = Some(bar.change_base());
+                    if bar.currency.is_some() {
+                        item.currency = bar.currency.clone();
+                    }
                 }
             }
         }
+        self.rebuild_portfolio_view();
 
         self.check_alerts();
         self.retry_alerts_save_if_pending();
@@ -1138,7 +1602,7 @@ impl App {
             self.surface_runtime_error(
                 Tab::Charts,
                 ErrorSourceDomain::Charts,
-                AppError::Internal(MISSING_API_KEY_FOR_POLYGON_MSG.to_string()),
+                AppError::Internal(self.missing_api_key_msg().to_string()),
                 true,
             );
             self.historical_data = None;
@@ -1154,9 +1618,12 @@ impl App {
         let sym = self.symbol.clone();
         let cfg = self.config.clone();
         let tr = self.time_range;
+        let custom = self.custom_range;
+        let compare_symbols: Vec<String> =
+            self.chart_compare.iter().map(|c| c.symbol.clone()).collect();
         let recovery_tx = self.inflight_recovery_tx.clone();
         tokio::spawn(async move {
-            let params = tr.historical_params(chrono::Local::now());
+            let params = tr.historical_params(chrono::Local::now(), custom);
             let from = params.from.clone();
             let to = params.to.clone();
             let hq = HistoricalQuery {
@@ -1167,15 +1634,28 @@ impl App {
                 polygon_multiplier: params.polygon_multiplier,
                 polygon_timespan: params.polygon_timespan,
             };
-            let provider = market_provider_for(cfg.provider);
-            let result = provider.get_historical(&sym, &hq, &cfg).await;
+            let provider = market_provider_for_config(&cfg);
+            let served = ServedBackend::default();
+            let (result, compare_results) = served
+                .scope(futures_util::future::join(
+                    provider.get_historical(&sym, &hq, &cfg),
+                    futures_util::future::join_all(
+                        compare_symbols
+                            .iter()
+                            .map(|c| provider.get_historical(c, &hq, &cfg)),
+                    ),
+                ))
+                .await;
             deliver_fetch_done(
                 &tx,
                 recovery_tx.as_ref(),
                 FetchDone::Historical {
                     symbol: sym,
                     time_range: tr,
+                    custom_range: custom,
                     result,
+                    compare: compare_symbols.into_iter().zip(compare_results).collect(),
+                    served_by: served.get(),
                 },
                 InflightRecovery::Historical,
             );
@@ -1201,7 +1681,7 @@ impl App {
             self.surface_runtime_error(
                 Tab::News,
                 ErrorSourceDomain::News,
-                AppError::Internal(MISSING_API_KEY_FOR_POLYGON_MSG.to_string()),
+                AppError::Internal(self.missing_api_key_msg().to_string()),
                 true,
             );
             self.news_data = None;
@@ -1218,12 +1698,17 @@ impl App {
         let cfg = self.config.clone();
         let recovery_tx = self.inflight_recovery_tx.clone();
         tokio::spawn(async move {
-            let provider = market_provider_for(cfg.provider);
-            let result = provider.get_news(&sym, &cfg).await;
+            let provider = market_provider_for_config(&cfg);
+            let served = ServedBackend::default();
+            let result = served.scope(provider.get_news(&sym, &cfg)).await;
             deliver_fetch_done(
                 &tx,
                 recovery_tx.as_ref(),
-                FetchDone::News { symbol: sym, result },
+                FetchDone::News {
+                    symbol: sym,
+                    result,
+                    served_by: served.get(),
+                },
                 InflightRecovery::News,
             );
         });
@@ -1246,7 +1731,9 @@ impl App {
     fn recover_stale_inflight_flags(&mut self) {
         let stale_after = inflight_stale_after();
 
-        if self.stock_refresh_inflight && Self::inflight_is_stale(self.stock_inflight_since, stale_after)
+        let stock_stale_after = self.stock_stale_after.unwrap_or(stale_after);
+        if self.stock_refresh_inflight
+            && Self::inflight_is_stale(self.stock_inflight_since, stock_stale_after)
         {
             tracing::warn!(
                 target: "stockterm::fetch",
@@ -1336,7 +1823,7 @@ impl App {
             self.surface_runtime_error(
                 Tab::Search,
                 ErrorSourceDomain::Search,
-                AppError::Internal(MISSING_API_KEY_FOR_POLYGON_MSG.to_string()),
+                AppError::Internal(self.missing_api_key_msg().to_string()),
                 true,
             );
             return;
@@ -1369,8 +1856,9 @@ impl App {
         self.search_inflight_since = Some(Instant::now());
         let recovery_tx = self.inflight_recovery_tx.clone();
         tokio::spawn(async move {
-            let provider = market_provider_for(cfg.provider);
-            let result = provider.search_symbols(&query, &cfg).await;
+            let provider = market_provider_for_config(&cfg);
+            let served = ServedBackend::default();
+            let result = served.scope(provider.search_symbols(&query, &cfg)).await;
             deliver_fetch_done(
                 &tx,
                 recovery_tx.as_ref(),
@@ -1378,6 +1866,7 @@ impl App {
                     generation,
                     query,
                     result,
+                    served_by: served.get(),
                 },
                 InflightRecovery::Search,
             );
@@ -1388,6 +1877,7 @@ impl App {
     pub fn on_active_symbol_changed_for_charts(&mut self) {
         self.historical_data = None;
         self.chart_viewport = ChartViewport::default();
+        self.chart_cursor = None;
         self.last_charts_network_poll = None;
     }
 
@@ -1602,6 +2092,17 @@ impl App {
         })
     }
 
+    pub(crate) fn set_portfolio_csv_flash(&mut self, text: String) {
+        self.portfolio_csv_flash = Some((text, Instant::now() + PORTFOLIO_CSV_FLASH));
+    }
+
+    pub(crate) fn portfolio_csv_flash_line(&self) -> Option<&str> {
+        self.portfolio_csv_flash
+            .as_ref()
+            .filter(|(_, until)| Instant::now() < *until)
+            .map(|(text, _)| text.as_str())
+    }
+
     pub fn settings_row_prev(&mut self) {
         if self.settings_editing.is_some() {
             return;
@@ -1836,6 +2337,11 @@ impl App {
             InflightRecovery::Stock => {
                 self.stock_refresh_inflight = false;
                 self.stock_inflight_since = None;
+                self.stock_stale_after = None;
+                // A batch still running past its window is stuck; the next poll supersedes it.
+                if let Some(batch) = self.stock_batch.take() {
+                    batch.abort();
+                }
                 // Issue #77 / SPEC §16.3: coalesced refresh must not stick pending when FetchDone send failed.
                 if std::mem::take(&mut self.stock_refresh_pending) {
                     self.spawn_stock_fetch_task();
@@ -1850,22 +2356,39 @@ impl App {
             FetchDone::Stock {
                 generation,
                 quotes,
+                daily_closes,
                 errors,
-            } => self.apply_stock_fetch_done(generation, quotes, errors),
+                served_by,
+            } => {
+                self.alert_daily_closes.extend(daily_closes);
+                if generation == self.stock_fetch_generation && !quotes.is_empty() {
+                    self.note_served_by(ErrorSourceDomain::Stock, served_by);
+                }
+                self.apply_stock_fetch_done(generation, quotes, errors)
+            }
+            FetchDone::QuoteTicks(ticks) => self.apply_quote_ticks(ticks),
             FetchDone::Historical {
                 symbol,
                 time_range,
+                custom_range,
                 result,
+                compare,
+                served_by,
             } => {
                 self.hist_refresh_inflight = false;
                 self.hist_inflight_since = None;
-                if symbol != self.symbol || time_range != self.time_range {
+                if symbol != self.symbol
+                    || time_range != self.time_range
+                    || custom_range != self.custom_range
+                {
                     self.last_charts_network_poll = None;
                     return;
                 }
                 self.last_charts_network_poll = Some(Instant::now());
+                apply_compare_results(&mut self.chart_compare, compare);
                 match result {
                     Ok(data) => {
+                        self.note_served_by(ErrorSourceDomain::Charts, served_by);
                         let prev = self.historical_data.as_ref();
                         self.chart_viewport = crate::app::charts::chart_viewport_after_refresh(
                             prev,
@@ -1897,7 +2420,11 @@ impl App {
                     }
                 }
             }
-            FetchDone::News { symbol, result } => {
+            FetchDone::News {
+                symbol,
+                result,
+                served_by,
+            } => {
                 self.news_refresh_inflight = false;
                 self.news_inflight_since = None;
                 self.last_news_network_poll = Some(Instant::now());
@@ -1906,6 +2433,7 @@ impl App {
                 }
                 match result {
                     Ok(data) => {
+                        self.note_served_by(ErrorSourceDomain::News, served_by);
                         let n = data.results.len();
                         self.news_data = Some(data);
                         if matches!(self.last_failed_fetch, LastFailedFetch::News { .. }) {
@@ -1946,6 +2474,7 @@ impl App {
                 generation,
                 query,
                 result,
+                served_by,
             } => {
                 self.search_refresh_inflight = false;
                 self.search_inflight_since = None;
@@ -1965,6 +2494,7 @@ impl App {
                 }
                 match result {
                     Ok(data) => {
+                        self.note_served_by(ErrorSourceDomain::Search, served_by);
                         self.search_results = Some(data);
                         if matches!(self.last_failed_fetch, LastFailedFetch::Search { .. }) {
                             self.last_failed_fetch = LastFailedFetch::None;
@@ -2012,6 +2542,7 @@ impl App {
     pub async fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
         let (fetch_tx, mut fetch_rx) = tokio::sync::mpsc::unbounded_channel();
         self.fetch_done_tx = Some(fetch_tx);
+        self.start_quote_stream();
         let (recovery_tx, mut recovery_rx) = tokio::sync::mpsc::unbounded_channel();
         self.inflight_recovery_tx = Some(recovery_tx);
         let (url_op_tx, mut url_op_rx) = tokio::sync::mpsc::unbounded_channel();
@@ -2023,6 +2554,7 @@ impl App {
         self.request_immediate_stock_poll();
 
         let run_result = loop {
+            self.sync_quote_stream_symbols();
             draw(terminal, self)?;
 
             if self.should_quit {
@@ -2073,6 +2605,7 @@ impl App {
         self.fetch_done_tx = None;
         self.inflight_recovery_tx = None;
         self.url_op_tx = None;
+        self.quote_stream_symbols = None;
         // Drop the receiver so the event thread's `send` fails and the loop exits (Issue #108).
         drop(event_rx);
         join_event_thread(event_handle);
@@ -2124,19 +2657,7 @@ impl App {
         let same_ticker_case_only = prev_effective.eq_ignore_ascii_case(&sym);
         self.watchlist.push(sym.clone());
         self.symbol = sym;
-        self.config.watchlist = self.watchlist.clone();
-        if let Err(e) = self.try_save_config_with_session() {
-            self.surface_runtime_error(
-                Tab::StockView,
-                ErrorSourceDomain::Portfolio,
-                AppError::ConfigSave(format!("Failed to save watchlist: {e}")),
-                true,
-            );
-        } else if self.active_runtime_error.as_ref().is_some_and(|a| {
-            a.source_domain == ErrorSourceDomain::Portfolio
-        }) {
-            self.active_runtime_error = None;
-        }
+        self.save_watchlists();
         let f = self.watchlist_filter_indices();
         let new_last = self.watchlist.len().saturating_sub(1);
         if f.is_empty() {
@@ -2167,19 +2688,7 @@ impl App {
         }
         self.watchlist.remove(actual);
         self.watchlist_quotes.retain(|k, _| self.watchlist.contains(k));
-        self.config.watchlist = self.watchlist.clone();
-        if let Err(e) = self.try_save_config_with_session() {
-            self.surface_runtime_error(
-                Tab::StockView,
-                ErrorSourceDomain::Portfolio,
-                AppError::ConfigSave(format!("Failed to save watchlist: {e}")),
-                true,
-            );
-        } else if self.active_runtime_error.as_ref().is_some_and(|a| {
-            a.source_domain == ErrorSourceDomain::Portfolio
-        }) {
-            self.active_runtime_error = None;
-        }
+        self.save_watchlists();
 
         if self.watchlist.is_empty() {
             self.watchlist_state.select(None);
@@ -2207,6 +2716,177 @@ impl App {
         self.notify_symbol_changed_for_news();
     }
 
+    /// Copies the active list's symbols into [`Config::watchlists`] and saves. Returns `false`
+    /// (with the error surfaced on Stock View) when the write fails.
+    fn save_watchlists(&mut self) -> bool {
+        if let Some(list) = self.config.watchlists.get_mut(self.active_watchlist) {
+            list.symbols = self.watchlist.clone();
+        }
+        if let Err(e) = self.try_save_config_with_session() {
+            self.surface_runtime_error(
+                Tab::StockView,
+                ErrorSourceDomain::Portfolio,
+                AppError::ConfigSave(format!("Failed to save watchlist: {e}")),
+                true,
+            );
+            return false;
+        }
+        if self
+            .active_runtime_error
+            .as_ref()
+            .is_some_and(|a| a.source_domain == ErrorSourceDomain::Portfolio)
+        {
+            self.active_runtime_error = None;
+        }
+        true
+    }
+
+    /// Name of the active watchlist (shown in the Stock View table title).
+    pub fn watchlist_name(&self) -> &str {
+        self.config
+            .watchlists
+            .get(self.active_watchlist)
+            .map_or("Watchlist", |w| w.name.as_str())
+    }
+
+    /// Loads `config.watchlists[active_watchlist]` into the table, selects its first row, and
+    /// polls so the new list's quotes show without waiting for the refresh interval.
+    fn load_active_watchlist(&mut self) {
+        self.watchlist = self
+            .config
+            .watchlists
+            .get(self.active_watchlist)
+            .map(|w| normalized_watchlist_symbols(&w.symbols))
+            .unwrap_or_default();
+        let f = self.watchlist_filter_indices();
+        match f.first() {
+            Some(&first) => {
+                self.watchlist_state.select(Some(0));
+                if self.symbol != self.watchlist[first] {
+                    self.symbol = self.watchlist[first].clone();
+                    self.ticker_data = self.watchlist_quotes.get(&self.symbol).cloned();
+                    self.on_active_symbol_changed_for_charts();
+                    self.notify_symbol_changed_for_news();
+                }
+            }
+            None => self.watchlist_state.select(None),
+        }
+        self.request_immediate_stock_poll();
+    }
+
+    /// Stock View `]` / `[`: step through the named watchlists (wraps).
+    pub fn watchlist_switch(&mut self, forward: bool) {
+        let n = self.config.watchlists.len();
+        if n <= 1 {
+            return;
+        }
+        let cur = self.active_watchlist.min(n - 1);
+        self.active_watchlist = if forward { (cur + 1) % n } else { (cur + n - 1) % n };
+        self.load_active_watchlist();
+        self.persist_session_to_disk();
+    }
+
+    /// Opens the create / rename / delete prompt; delete is refused while only one list exists.
+    pub fn open_watchlist_prompt(&mut self, kind: WatchlistPromptKind) {
+        if kind == WatchlistPromptKind::Delete && self.config.watchlists.len() <= 1 {
+            self.surface_runtime_error(
+                Tab::StockView,
+                ErrorSourceDomain::Portfolio,
+                AppError::Internal("Cannot delete the only watchlist".to_string()),
+                false,
+            );
+            return;
+        }
+        let buffer = match kind {
+            WatchlistPromptKind::Rename => self.watchlist_name().to_string(),
+            WatchlistPromptKind::Create | WatchlistPromptKind::Delete => String::new(),
+        };
+        self.watchlist_prompt = Some(WatchlistPrompt::new(kind, buffer));
+    }
+
+    /// Enter in the watchlist prompt. `Err` is shown inline and keeps the prompt open.
+    pub fn submit_watchlist_prompt(&mut self) -> Result<(), String> {
+        let Some(prompt) = self.watchlist_prompt.clone() else {
+            return Ok(());
+        };
+        match prompt.kind {
+            WatchlistPromptKind::Create => {
+                let name = validate_watchlist_name(&prompt.buffer, &self.config.watchlists, None)?;
+                self.config.watchlists.push(Watchlist::new(name));
+                self.active_watchlist = self.config.watchlists.len() - 1;
+                self.load_active_watchlist();
+            }
+            WatchlistPromptKind::Rename => {
+                let active = self.active_watchlist;
+                let name =
+                    validate_watchlist_name(&prompt.buffer, &self.config.watchlists, Some(active))?;
+                if let Some(list) = self.config.watchlists.get_mut(active) {
+                    list.name = name;
+                }
+            }
+            WatchlistPromptKind::Delete => {
+                if self.config.watchlists.len() <= 1 {
+                    return Err("Cannot delete the only watchlist".to_string());
+                }
+                let active = self.active_watchlist.min(self.config.watchlists.len() - 1);
+                self.config.watchlists.remove(active);
+                self.active_watchlist = active.min(self.config.watchlists.len() - 1);
+                self.load_active_watchlist();
+            }
+        }
+        self.watchlist_prompt = None;
+        self.save_watchlists();
+        Ok(())
+    }
+
+    /// Backing symbol under the watchlist cursor.
+    pub(crate) fn selected_watchlist_symbol(&self) -> Option<String> {
+        let sel = self.watchlist_state.selected()?;
+        let i = *self.watchlist_filter_indices().get(sel)?;
+        self.watchlist.get(i).cloned()
+    }
+
+    /// Moves the cursor back onto `sym` after the row order changed (new quotes or sort).
+    pub(crate) fn keep_watchlist_cursor_on(&mut self, sym: Option<String>) {
+        let Some(sym) = sym else {
+            return;
+        };
+        let f = self.watchlist_filter_indices();
+        if let Some(pos) = f.iter().position(|&i| self.watchlist[i] == sym) {
+            self.watchlist_state.select(Some(pos));
+        }
+    }
+
+    /// Stock View `.` / `,`: sort by the next / previous visible column (ascending), wrapping
+    /// through insertion order.
+    pub fn watchlist_cycle_sort(&mut self, forward: bool) {
+        let columns = self.config.layout.watchlist_columns();
+        let slots = columns.len() + 1;
+        let cur = self
+            .watchlist_sort
+            .and_then(|s| columns.iter().position(|&c| c == s.column))
+            .map_or(0, |i| i + 1);
+        let next = if forward { (cur + 1) % slots } else { (cur + slots - 1) % slots };
+        let selected = self.selected_watchlist_symbol();
+        self.watchlist_sort = next.checked_sub(1).map(|i| WatchlistSort {
+            column: columns[i],
+            descending: false,
+        });
+        self.keep_watchlist_cursor_on(selected);
+        self.persist_session_to_disk();
+    }
+
+    /// Stock View `-`: flip the active sort direction (no-op while unsorted).
+    pub fn watchlist_reverse_sort(&mut self) {
+        let selected = self.selected_watchlist_symbol();
+        let Some(sort) = self.watchlist_sort.as_mut() else {
+            return;
+        };
+        sort.descending = !sort.descending;
+        self.keep_watchlist_cursor_on(selected);
+        self.persist_session_to_disk();
+    }
+
     pub fn watchlist_select_prev(&mut self) {
         let f = self.watchlist_filter_indices();
         if f.is_empty() {
@@ -2251,7 +2931,7 @@ impl App {
         self.persist_session_to_disk();
     }
 
-    /// Charts tab: switch time range and refetch (keys `1`–`4`).
+    /// Charts tab: switch time range and refetch (keys `1`–`9`).
     ///
     /// Selecting the **same** range again still bypasses the charts throttle and resets the
     /// viewport so e.g. **`3` on default 1M** forces a refresh (auditor: no early-return no-op).
@@ -2259,13 +2939,8 @@ impl App {
         let changed = self.time_range != tr;
         if changed {
             self.time_range = tr;
-            self.historical_data = None;
-            self.chart_viewport = ChartViewport::default();
-            if self.active_runtime_error.as_ref().is_some_and(|a| {
-                a.source_domain == ErrorSourceDomain::Charts
-            }) {
-                self.active_runtime_error = None;
-            }
+            self.clear_charts_series();
+            self.persist_session_to_disk();
         }
         self.request_immediate_charts_poll();
         if !changed {
@@ -2273,6 +2948,54 @@ impl App {
         }
     }
 
+    /// Drop loaded bars, viewport, crosshair, and compare data after the chart window changes.
+    fn clear_charts_series(&mut self) {
+        self.historical_data = None;
+        self.chart_viewport = ChartViewport::default();
+        self.chart_cursor = None;
+        for c in &mut self.chart_compare {
+            c.data = None;
+            c.error = None;
+        }
+        if self.active_runtime_error.as_ref().is_some_and(|a| {
+            a.source_domain == ErrorSourceDomain::Charts
+        }) {
+            self.active_runtime_error = None;
+        }
+    }
+
+    /// Charts: open the custom from/to dialog, prefilled with the last custom window.
+    pub fn charts_open_range_dialog(&mut self) {
+        let today = chrono::Local::now().date_naive();
+        self.chart_range_dialog = Some(ChartRangeDialog::new(self.custom_range, today));
+    }
+
+    /// Validate the dialog and switch to [`TimeRange::Custom`]; errors stay inline.
+    pub fn charts_commit_range_dialog(&mut self) {
+        let Some(dialog) = self.chart_range_dialog.as_mut() else {
+            return;
+        };
+        let today = chrono::Local::now().date_naive();
+        let range = match CustomRange::parse(&dialog.from_buffer, &dialog.to_buffer, today) {
+            Ok(r) => r,
+            Err(e) => {
+                dialog.inline_error = Some(e);
+                return;
+            }
+        };
+        self.chart_range_dialog = None;
+        if self.time_range == TimeRange::Custom && self.custom_range == Some(range) {
+            self.request_immediate_charts_poll();
+            self.charts_reset_viewport();
+            return;
+        }
+        self.custom_range = Some(range);
+        self.time_range = TimeRange::Custom;
+        self.clear_charts_series();
+        self.request_immediate_charts_poll();
+        self.persist_session_to_disk();
+    }
+
     /// Bypass charts throttle (e.g. after changing `time_range`).
     pub fn request_immediate_charts_poll(&mut self) {
         self.last_charts_network_poll = None;
@@ -2317,6 +3040,97 @@ impl App {
         self.chart_mode = self.chart_mode.toggle();
     }
 
+    /// Charts: start typing a compare symbol (no-op once [`MAX_COMPARE_SYMBOLS`] are shown).
+    pub fn charts_open_compare_input(&mut self) {
+        if self.chart_compare.len() < MAX_COMPARE_SYMBOLS {
+            self.chart_compare_input = Some(String::new());
+        }
+    }
+
+    /// Add the typed symbol to compare mode and refetch. Blank, duplicate, or active-symbol input
+    /// just closes the prompt.
+    pub fn charts_commit_compare_input(&mut self) {
+        let Some(input) = self.chart_compare_input.take() else {
+            return;
+        };
+        let Some(sym) = normalize_symbol(&input) else {
+            return;
+        };
+        let duplicate = self.chart_compare.iter().any(|c| c.symbol == sym)
+            || normalize_symbol(&self.symbol).as_deref() == Some(sym.as_str());
+        if duplicate || self.chart_compare.len() >= MAX_COMPARE_SYMBOLS {
+            return;
+        }
+        self.chart_compare.push(CompareSeries::new(sym));
+        self.request_immediate_charts_poll();
+    }
+
+    /// Charts: drop the most recently added compare symbol.
+    pub fn charts_remove_last_compare(&mut self) {
+        self.chart_compare.pop();
+    }
+
+    /// Crosshair bar inside the current viewport, if shown.
+    pub fn chart_cursor_index(&self) -> Option<usize> {
+        let h = self.historical_data.as_ref()?;
+        cursor_in_viewport(self.chart_cursor, h.results.len(), &self.chart_viewport)
+    }
+
+    /// Show the crosshair on the last visible bar, or hide it.
+    pub fn charts_toggle_cursor(&mut self) {
+        if self.chart_cursor.take().is_some() {
+            return;
+        }
+        let Some(h) = self.historical_data.as_ref() else {
+            return;
+        };
+        self.chart_cursor = cursor_in_viewport(Some(usize::MAX), h.results.len(), &self.chart_viewport);
+    }
+
+    pub fn charts_cursor_left(&mut self) {
+        let Some(h) = self.historical_data.as_ref() else {
+            return;
+        };
+        self.chart_cursor =
+            cursor_step_left(self.chart_cursor, &mut self.chart_viewport, h.results.len());
+    }
+
+    pub fn charts_cursor_right(&mut self) {
+        let Some(h) = self.historical_data.as_ref() else {
+            return;
+        };
+        self.chart_cursor =
+            cursor_step_right(self.chart_cursor, &mut self.chart_viewport, h.results.len());
+    }
+
+    /// Indicators enabled for the current [`Self::time_range`] (`Config.chart_indicators`).
+    pub fn chart_indicators(&self) -> &[Indicator] {
+        self.config
+            .chart_indicators
+            .get(&self.time_range)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Charts tab: show/hide `indicator` for the current range and persist the set (debounced).
+    pub fn charts_toggle_indicator(&mut self, indicator: Indicator) {
+        let set = self
+            .config
+            .chart_indicators
+            .entry(self.time_range)
+            .or_default();
+        if let Some(i) = set.iter().position(|&x| x == indicator) {
+            set.remove(i);
+        } else {
+            set.push(indicator);
+            set.sort_by_key(|x| Indicator::ALL.iter().position(|a| a == x));
+        }
+        if set.is_empty() {
+            self.config.chart_indicators.remove(&self.time_range);
+        }
+        self.persist_session_to_disk();
+    }
+
     pub fn next_tab(&mut self) {
         let from = self.active_tab;
         self.active_tab = match self.active_tab {
@@ -2353,43 +3167,55 @@ impl App {
         self.persist_session_to_disk();
     }
 
-    /// Adds or merges a holding for the active [`Self::symbol`] and persists config.
+    /// Appends `tx` to the ledger for `symbol` (creating the holding on a first buy) and persists
+    /// config.
     ///
     /// # Returns
     ///
-    /// - `true` if the holding was applied and [`Self::try_save_config_with_session`] succeeded.
+    /// - `true` if the trade was applied and [`Self::try_save_config_with_session`] succeeded.
     /// - `false` if:
-    ///   1. [`crate::app::normalize_symbol`] on [`Self::symbol`] is `None` — does **not** set
+    ///   1. [`crate::app::normalize_symbol`] on `symbol` is `None` — does **not** set
     ///      `error_message`; caller ([`crate::app::portfolio::try_commit_portfolio_dialog`]) must set
     ///      `portfolio_dialog.inline_error`.
     ///   2. config save fails — sets runtime error via [`Self::surface_runtime_error`]; caller must
     ///      **not** overwrite with `inline_error`.
     ///
-    /// Any new `false` branch must either set `error_message` or extend the contract in SPEC §36.3.
-    pub fn add_to_portfolio(&mut self, shares: f64, purchase_price: f64) -> bool {
-        let Some(sym) = normalize_symbol(&self.symbol) else {
+    /// The dialog checks sells against the open quantity first (inline error); [`merge_trades`]
+    /// rejects them again for every other caller. Any new `false` branch must either set
+    /// `error_message` or extend the contract in SPEC §36.3.
+    pub fn add_to_portfolio(&mut self, symbol: &str, tx: Transaction) -> bool {
+        self.add_trades_to_portfolio([(symbol.to_string(), tx)])
+    }
+
+    /// Batch form of [`Self::add_to_portfolio`] (CSV import): every trade is merged into the active
+    /// account through [`merge_trades`], then config is saved once. Same `false` contract; nothing
+    /// is applied when any symbol fails to normalize or the save fails. A sell beyond the shares
+    /// held is also `false` and surfaces a runtime error. Also `false` (without an error) on the
+    /// All accounts view, which callers keep read-only. The selection follows the last trade's
+    /// symbol.
+    pub fn add_trades_to_portfolio(
+        &mut self,
+        trades: impl IntoIterator<Item = (String, Transaction)>,
+    ) -> bool {
+        let Some(account) = self.portfolio_account.filter(|&i| i < self.portfolios.len()) else {
             return false;
         };
+        let backup = self.portfolios.clone();
+        let sym = match merge_trades(&mut self.portfolios[account].holdings, trades) {
+            Ok(Some(sym)) => sym,
+            Ok(None) | Err(MergeTradesError::InvalidSymbol(_)) => return false,
+            Err(e @ MergeTradesError::Oversell { .. }) => {
+                self.surface_runtime_error(
+                    Tab::Portfolio,
+                    ErrorSourceDomain::Portfolio,
+                    AppError::Internal(e.to_string()),
+                    true,
+                );
+                return false;
+            }
+        };
 
-        let backup = self.portfolio.clone();
-
-        if let Some(item) = self
-            .portfolio
-            .iter_mut()
-            .find(|i| normalize_symbol(&i.symbol).as_deref() == Some(sym.as_str()))
-        {
-            item.shares += shares;
-            let total_shares = item.shares;
-            let existing_cost = (total_shares - shares) * item.purchase_price;
-            let new_cost = shares * purchase_price;
-            item.purchase_price = (existing_cost + new_cost) / total_shares;
-        } else {
-            self.portfolio
-                .push(PortfolioItem::new(sym.clone(), shares, purchase_price));
-        }
-
-        self.config.portfolio = self.portfolio.clone();
-        match self.try_save_config_with_session() {
+        match self.save_portfolios(backup) {
             Ok(()) => {
                 if !self.portfolio.is_empty() {
                     let f = self.portfolio_filter_indices();
@@ -2408,71 +3234,186 @@ impl App {
                 }
                 true
             }
-            Err(e) => {
-                self.portfolio = backup;
-                self.config.portfolio = self.portfolio.clone();
-                self.surface_runtime_error(
-                    Tab::Portfolio,
-                    ErrorSourceDomain::Portfolio,
-                    AppError::ConfigSave(e.to_string()),
-                    true,
-                );
-                false
-            }
+            Err(()) => false,
         }
     }
 
-    /// Returns `false` if index invalid or `try_save` failed.
+    /// Removes view row `index` from its account. Returns `false` if index invalid or `try_save` failed.
     pub fn remove_from_portfolio(&mut self, index: usize) -> bool {
-        if index >= self.portfolio.len() {
+        let (Some(item), Some(&account)) = (self.portfolio.get(index), self.portfolio_row_accounts.get(index)) else {
             return false;
-        }
-
-        let backup = self.portfolio.clone();
-        self.portfolio.remove(index);
-        self.config.portfolio = self.portfolio.clone();
-        match self.try_save_config_with_session() {
+        };
+        let symbol = item.symbol.clone();
+        let backup = self.portfolios.clone();
+        let Some(holdings) = self.portfolios.get_mut(account).map(|p| &mut p.holdings) else {
+            return false;
+        };
+        holdings.retain(|h| h.symbol != symbol);
+        match self.save_portfolios(backup) {
             Ok(()) => {
                 self.clamp_portfolio_filter_selection();
                 true
             }
+            Err(()) => false,
+        }
+    }
+
+    /// Rebuilds the Portfolio tab rows from `portfolios` for the selected account.
+    pub(crate) fn rebuild_portfolio_view(&mut self) {
+        if self.portfolio_account.is_some_and(|i| i >= self.portfolios.len()) {
+            self.portfolio_account = Some(0);
+        }
+        self.portfolio.clear();
+        self.portfolio_row_accounts.clear();
+        for (i, account) in self.portfolios.iter().enumerate() {
+            if self.portfolio_account.is_none_or(|a| a == i) {
+                self.portfolio.extend(account.holdings.iter().cloned());
+                self.portfolio_row_accounts.extend(std::iter::repeat_n(i, account.holdings.len()));
+            }
+        }
+    }
+
+    /// Mirrors `portfolios` into config and saves; on failure restores `backup` and surfaces the error.
+    fn save_portfolios(&mut self, backup: Vec<Portfolio>) -> Result<(), ()> {
+        self.config.portfolios = self.portfolios.clone();
+        self.rebuild_portfolio_view();
+        match self.try_save_config_with_session() {
+            Ok(()) => Ok(()),
             Err(e) => {
-                self.portfolio = backup;
-                self.config.portfolio = self.portfolio.clone();
+                self.portfolios = backup;
+                self.config.portfolios = self.portfolios.clone();
+                self.rebuild_portfolio_view();
                 self.surface_runtime_error(
                     Tab::Portfolio,
                     ErrorSourceDomain::Portfolio,
                     AppError::ConfigSave(e.to_string()),
                     true,
                 );
-                false
+                Err(())
             }
         }
     }
 
-    pub fn calculate_portfolio_value(&self) -> f64 {
-        self.portfolio
+    /// Name of the selected account, or "All accounts".
+    pub fn portfolio_account_label(&self) -> &str {
+        match self.portfolio_account.and_then(|i| self.portfolios.get(i)) {
+            Some(p) => &p.name,
+            None => "All accounts",
+        }
+    }
+
+    /// Portfolio `]` / `[`: step through the accounts, then All accounts (when there are two or more).
+    pub fn portfolio_switch_account(&mut self, forward: bool) {
+        let n = self.portfolios.len();
+        let slots = if n > 1 { n + 1 } else { n };
+        if slots <= 1 {
+            return;
+        }
+        let cur = self.portfolio_account.unwrap_or(n);
+        let next = if forward { (cur + 1) % slots } else { (cur + slots - 1) % slots };
+        self.portfolio_account = (next < n).then_some(next);
+        self.portfolio_remove_armed = false;
+        self.rebuild_portfolio_view();
+        self.portfolio_state
+            .select((!self.portfolio_filter_indices().is_empty()).then_some(0));
+        self.persist_session_to_disk();
+    }
+
+    /// Portfolio `n`: adds an empty account and selects it. `Err` is shown inline in the prompt.
+    pub fn portfolio_create_account(&mut self, name: &str) -> Result<(), String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Name required".into());
+        }
+        if name == ALL_PORTFOLIOS_KEY {
+            return Err(format!("{ALL_PORTFOLIOS_KEY:?} is reserved"));
+        }
+        if self.portfolios.iter().any(|p| p.name.eq_ignore_ascii_case(name)) {
+            return Err(format!("Account {name:?} already exists"));
+        }
+        let backup = self.portfolios.clone();
+        let previous = self.portfolio_account;
+        self.portfolios.push(Portfolio::new(name));
+        self.portfolio_account = Some(self.portfolios.len() - 1);
+        if self.save_portfolios(backup).is_err() {
+            self.portfolio_account = previous;
+            self.rebuild_portfolio_view();
+            return Ok(());
+        }
+        self.portfolio_state.select(None);
+        Ok(())
+    }
+
+    /// Holdings of `account`, or of every account for `None`.
+    fn account_holdings(&self, account: Option<usize>) -> impl Iterator<Item = &PortfolioItem> {
+        self.portfolios
             .iter()
-            .filter_map(|item| item.market_value())
+            .enumerate()
+            .filter(move |(i, _)| account.is_none_or(|a| a == *i))
+            .flat_map(|(_, p)| &p.holdings)
+    }
+
+    /// Sum of `value` over `account`'s holdings in the base currency; holdings whose FX rate is
+    /// not known yet are left out (see [`portfolio_fx_pending`](Self::portfolio_fx_pending)).
+    fn sum_in_base_currency(
+        &self,
+        account: Option<usize>,
+        value: impl Fn(&PortfolioItem) -> Option<f64>,
+    ) -> f64 {
+        self.account_holdings(account)
+            .filter_map(|item| Some(value(item)? * self.fx_rate(item.currency.as_deref())?))
             .sum()
     }
 
-    pub fn calculate_portfolio_cost(&self) -> f64 {
-        self.portfolio.iter().map(|item| item.cost_basis()).sum()
+    /// Holdings of `account` left out of the totals while their FX quote is missing.
+    pub fn portfolio_fx_pending(&self, account: Option<usize>) -> usize {
+        self.account_holdings(account)
+            .filter(|item| self.fx_rate(item.currency.as_deref()).is_none())
+            .count()
     }
 
-    pub fn calculate_portfolio_profit_loss(&self) -> f64 {
-        self.calculate_portfolio_value() - self.calculate_portfolio_cost()
+    /// Market value of `account` (`None` = all accounts combined) in the base currency.
+    pub fn calculate_portfolio_value(&self, account: Option<usize>) -> f64 {
+        self.sum_in_base_currency(account, PortfolioItem::market_value)
+    }
+
+    /// Cost basis of the open lots under [`Config::cost_basis_method`] (`None` = all accounts).
+    pub fn calculate_portfolio_cost(&self, account: Option<usize>) -> f64 {
+        let method = self.config.cost_basis_method;
+        self.sum_in_base_currency(account, |item| Some(item.cost_basis(method)))
+    }
+
+    /// Realized P/L converted at today's rate (trades carry no historical FX).
+    pub fn calculate_portfolio_realized(&self, account: Option<usize>) -> f64 {
+        let method = self.config.cost_basis_method;
+        self.sum_in_base_currency(account, |item| Some(item.realized_profit_loss(method)))
+    }
+
+    /// Portfolio: switch FIFO → LIFO → average cost (`m`) and persist it.
+    pub fn portfolio_cycle_cost_basis(&mut self) {
+        self.config.cost_basis_method = self.config.cost_basis_method.next();
+        self.persist_session_to_disk();
+    }
+
+    pub fn calculate_portfolio_profit_loss(&self, account: Option<usize>) -> f64 {
+        self.calculate_portfolio_value(account) - self.calculate_portfolio_cost(account)
+    }
+
+    /// Sum of per-holding day P/L versus previous close (holdings without both prices are skipped).
+    pub fn calculate_portfolio_day_change(&self, account: Option<usize>) -> f64 {
+        self.sum_in_base_currency(account, PortfolioItem::day_change)
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::items_after_test_module)]
 mod tests {
     use super::{
         data_poll_interval_secs, normalize_symbol, search_result_matches_current, App,
     };
     use crate::app::app_error::{push_error_log, ErrorLogEntry, UiErrorCategory, ERROR_LOG_CAP};
     use crate::app::Tab;
+    use crate::models::portfolio::PortfolioItem;
     use std::collections::VecDeque;
     use std::time::{Duration, Instant};
 
@@ -2859,7 +3800,7 @@ mod tests {
         let mut app = App::new();
         app.watchlist.clear();
         app.symbol = "AAPL".to_string();
-        app.portfolio = vec![PortfolioItem::new("IBM".to_string(), 1.0, 100.0)];
+        app.portfolios[0].holdings = vec![PortfolioItem::new("IBM".to_string(), 1.0, 100.0)];
         let syms = app.collect_symbols_for_quote_fetch();
         assert!(syms.contains(&"AAPL".to_string()));
         assert!(syms.contains(&"IBM".to_string()));
@@ -2879,8 +3820,8 @@ mod tests {
         app.portfolio_dialog = Some(PortfolioAddDialog {
             shares_buffer: "1".into(),
             price_buffer: "1".into(),
-            focused: PortfolioAddField::Price,
-            inline_error: None,
+            focused: PortfolioAddField::Date,
+            ..PortfolioAddDialog::default()
         });
         try_commit_portfolio_dialog(&mut app);
         assert!(app.portfolio_dialog.is_some());
@@ -2893,6 +3834,255 @@ mod tests {
         assert!(app.active_runtime_error.is_none());
     }
 
+    #[test]
+    fn portfolio_sell_cannot_exceed_open_quantity() {
+        use crate::app::portfolio::try_commit_portfolio_dialog;
+        use crate::app::PortfolioAddDialog;
+        use crate::models::portfolio::PortfolioItem;
+
+        let mut app = App::new();
+        app.portfolio_account = Some(0);
+        app.portfolios[0].holdings = vec![PortfolioItem::new("IBM".into(), 2.0, 100.0)];
+        app.rebuild_portfolio_view();
+        let mut dialog = PortfolioAddDialog::sell("IBM".into());
+        dialog.shares_buffer = "5".into();
+        dialog.price_buffer = "120".into();
+        app.portfolio_dialog = Some(dialog);
+        try_commit_portfolio_dialog(&mut app);
+        assert_eq!(
+            app.portfolio_dialog
+                .as_ref()
+                .and_then(|d| d.inline_error.as_deref()),
+            Some("Only 2.00 shares are held")
+        );
+        assert_eq!(app.portfolio[0].transactions.len(), 1);
+    }
+
+    #[test]
+    fn portfolio_accounts_switch_and_total_per_account() {
+        use crate::models::portfolio::{Portfolio, PortfolioItem, Transaction};
+
+        let mut app = App::new();
+        let priced = |sym: &str, shares: f64, cost: f64, price: f64| {
+            let mut item = PortfolioItem::new(sym.into(), shares, cost);
+            item.current_price = Some(price);
+            item
+        };
+        app.portfolios = vec![
+            Portfolio {
+                name: "Taxable".into(),
+                holdings: vec![priced("AAPL", 10.0, 100.0, 150.0)],
+            },
+            Portfolio {
+                name: "IRA".into(),
+                holdings: vec![priced("AAPL", 5.0, 200.0, 150.0), priced("VTI", 2.0, 50.0, 60.0)],
+            },
+        ];
+        app.portfolio_account = Some(0);
+        app.rebuild_portfolio_view();
+        assert_eq!(app.portfolio.len(), 1);
+        assert!((app.calculate_portfolio_value(Some(1)) - 870.0).abs() < 1e-9);
+        assert!((app.calculate_portfolio_profit_loss(Some(1)) - (870.0 - 1100.0)).abs() < 1e-9);
+        assert!((app.calculate_portfolio_cost(None) - 2100.0).abs() < 1e-9);
+
+        app.portfolio_switch_account(true);
+        assert_eq!(app.portfolio_account_label(), "IRA");
+        app.portfolio_switch_account(true);
+        assert_eq!(app.portfolio_account, None);
+        assert_eq!(app.portfolio.len(), 3, "All accounts keeps one row per account and symbol");
+        assert_eq!(app.portfolio_row_accounts, vec![0, 1, 1]);
+        app.portfolio_switch_account(true);
+        assert_eq!(app.portfolio_account, Some(0));
+        app.portfolio_switch_account(false);
+        assert_eq!(app.portfolio_account, None);
+
+        assert!(!app.add_to_portfolio("MSFT", Transaction::buy(None, 1.0, 1.0, 0.0)));
+        assert!(app.portfolio_create_account(" ira ").is_err());
+        assert!(app.portfolio_create_account("*").is_err());
+    }
+
+    #[test]
+    fn foreign_holdings_convert_into_base_currency() {
+        use crate::config::MarketProviderKind;
+
+        let mut app = App::new();
+        app.config.provider = MarketProviderKind::Yahoo;
+        app.config.base_currency = "usd".into();
+        app.watchlist.clear();
+        app.symbol.clear();
+        app.alerts.clear();
+        app.portfolios.truncate(1);
+        let mut lse = PortfolioItem::new("VOD.L".into(), 100.0, 70.0);
+        lse.currency = Some("GBp".into());
+        lse.current_price = Some(80.0);
+        let mut tsx = PortfolioItem::new("SHOP.TO".into(), 2.0, 100.0);
+        tsx.currency = Some("CAD".into());
+        tsx.current_price = Some(110.0);
+        let mut us = PortfolioItem::new("AAPL".into(), 1.0, 150.0);
+        us.current_price = Some(200.0);
+        app.portfolios[0].holdings = vec![lse, tsx, us];
+
+        assert_eq!(
+            app.collect_symbols_for_quote_fetch(),
+            ["VOD.L", "SHOP.TO", "AAPL", "GBPUSD=X", "CADUSD=X"]
+        );
+        assert_eq!(app.portfolio_fx_pending(None), 2);
+        assert!((app.calculate_portfolio_value(None) - 200.0).abs() < 1e-9);
+
+        let mut fx = |sym: &str, rate: f64| {
+            let mut r = crate::models::ticker::TickerResponse {
+                ticker: sym.into(),
+                results: vec![],
+                status: "OK".into(),
+                error: None,
+            };
+            r.results.push(crate::models::ticker::TickerResult {
+                o: rate,
+                h: rate,
+                l: rate,
+                c: rate,
+                v: 0.0,
+                t: 1,
+                prev_close: None,
+                market_state: None,
+                ext_price: None,
+                week52_low: None,
+                week52_high: None,
+                market_cap: None,
+                currency: None,
+            });
+            app.watchlist_quotes.insert(sym.into(), r);
+        };
+        fx("GBPUSD=X", 1.25);
+        fx("CADUSD=X", 0.75);
+        assert_eq!(app.portfolio_fx_pending(None), 0);
+        // 100 × 80p × 1.25 + 2 × C$110 × 0.75 + $200
+        assert!((app.calculate_portfolio_value(None) - 465.0).abs() < 1e-9);
+        // 100 × 70p × 1.25 + 2 × C$100 × 0.75 + $150
+        assert!((app.calculate_portfolio_cost(None) - 387.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn quote_fetch_priority_ranks_active_then_watchlist_then_the_rest() {
+        use crate::api::rate_limit::RequestPriority;
+
+        let mut app = App::new();
+        app.watchlist = vec!["AAPL".into(), "MSFT".into()];
+        app.symbol = "msft".into();
+        assert_eq!(app.quote_fetch_priority("MSFT"), RequestPriority::Active);
+        assert_eq!(app.quote_fetch_priority("AAPL"), RequestPriority::Watchlist);
+        assert_eq!(app.quote_fetch_priority("NVDA"), RequestPriority::Background);
+    }
+
+    #[test]
+    fn named_watchlists_switch_and_fetch_only_active_list() {
+        use crate::app::WatchlistPromptKind;
+        use crate::models::alerts::{Alert, AlertCondition};
+        use crate::models::watchlist::Watchlist;
+
+        let mut app = App::new();
+        app.config.watchlists = vec![
+            Watchlist {
+                name: "Tech".into(),
+                symbols: vec!["AAPL".into(), "MSFT".into()],
+            },
+            Watchlist {
+                name: "Energy".into(),
+                symbols: vec!["xom".into(), "CVX".into(), "XOM".into()],
+            },
+        ];
+        app.active_watchlist = 0;
+        app.watchlist = vec!["AAPL".into(), "MSFT".into()];
+        app.symbol = "AAPL".into();
+        app.portfolios[0].holdings.clear();
+        app.alerts = vec![Alert::new("NVDA".into(), AlertCondition::Above, 100.0)];
+
+        app.watchlist_switch(true);
+        assert_eq!(app.watchlist_name(), "Energy");
+        assert_eq!(app.watchlist, ["XOM", "CVX"]);
+        assert_eq!(app.symbol, "XOM");
+        assert_eq!(app.collect_symbols_for_quote_fetch(), ["XOM", "CVX", "NVDA"]);
+
+        app.sync_session_fields_into_config();
+        assert_eq!(app.config.last_watchlist.as_deref(), Some("Energy"));
+
+        app.watchlist_switch(false);
+        assert_eq!(app.watchlist_name(), "Tech");
+        assert_eq!(app.watchlist_state.selected(), Some(0));
+
+        app.config.watchlists.truncate(1);
+        app.open_watchlist_prompt(WatchlistPromptKind::Delete);
+        assert!(app.watchlist_prompt.is_none(), "the last list cannot be deleted");
+        app.open_watchlist_prompt(WatchlistPromptKind::Rename);
+        assert_eq!(app.watchlist_prompt.as_ref().map(|p| p.buffer.as_str()), Some("Tech"));
+    }
+
+    #[test]
+    fn watchlist_sort_cycles_columns_and_filter_keeps_sorted_order() {
+        use crate::models::watchlist::{WatchlistColumn, WatchlistSort};
+
+        let mut app = App::new();
+        app.config.layout.watchlist_columns =
+            Some(vec![WatchlistColumn::Last, WatchlistColumn::ChangePercent]);
+        app.watchlist = vec!["MSFT".into(), "AAPL".into(), "AMZN".into()];
+        app.watchlist_sort = None;
+        app.clear_table_filter();
+        let mut quote = |sym: &str, close: f64| {
+            let mut r = crate::models::ticker::TickerResponse {
+                ticker: sym.into(),
+                results: vec![],
+                status: "OK".into(),
+                error: None,
+            };
+            r.results.push(crate::models::ticker::TickerResult {
+                o: close,
+                h: close,
+                l: close,
+                c: close,
+                v: 0.0,
+                t: 1,
+                prev_close: None,
+                market_state: None,
+                ext_price: None,
+                week52_low: None,
+                week52_high: None,
+                market_cap: None,
+                currency: None,
+            });
+            app.watchlist_quotes.insert(sym.into(), r);
+        };
+        quote("MSFT", 400.0);
+        quote("AAPL", 190.0);
+        quote("AMZN", 180.0);
+        app.watchlist_state.select(Some(0));
+
+        app.watchlist_cycle_sort(true);
+        assert_eq!(app.watchlist_sort.map(|s| s.column), Some(WatchlistColumn::Symbol));
+        assert_eq!(app.watchlist_filter_indices(), [1, 2, 0]);
+        assert_eq!(app.watchlist_state.selected(), Some(2), "cursor stays on MSFT");
+
+        app.watchlist_cycle_sort(true);
+        app.watchlist_reverse_sort();
+        assert_eq!(
+            app.watchlist_sort,
+            Some(WatchlistSort {
+                column: WatchlistColumn::Last,
+                descending: true
+            })
+        );
+        assert_eq!(app.watchlist_filter_indices(), [0, 1, 2]);
+        app.filter_query = "a".into();
+        assert_eq!(app.watchlist_filter_indices(), [1, 2]);
+
+        app.watchlist_cycle_sort(false);
+        app.watchlist_cycle_sort(false);
+        assert_eq!(app.watchlist_sort, None);
+        app.watchlist_cycle_sort(false);
+        assert_eq!(app.watchlist_sort.map(|s| s.column), Some(WatchlistColumn::ChangePercent));
+        app.sync_session_fields_into_config();
+        assert_eq!(app.config.watchlist_sort, app.watchlist_sort);
+    }
+
     /// Issue #4 / SPEC §35.4 — JSON default `refresh_rate: 0` maps to 30 s effective poll.
     #[test]
     fn data_poll_interval_zero_means_thirty_seconds() {
@@ -2925,6 +4115,178 @@ mod tests {
         assert!(app.stock_inflight_since.is_none());
     }
 
+    /// 15 quotes at 5 requests/min take almost three minutes, past the 120 s stale window; the
+    /// watchdog must leave the batch running so its quotes are applied.
+    #[tokio::test(start_paused = true)]
+    async fn rate_limited_batch_longer_than_the_stale_window_delivers() {
+        use super::{stock_batch_stale_after, FetchDone, INFLIGHT_STALE_AFTER};
+        use crate::api::rate_limit::{RateLimiter, RequestPriority};
+        use crate::config::MarketProviderKind;
+        use crate::models::ticker::TickerResponse;
+        use std::collections::HashMap;
+
+        let mut app = App::new();
+        app.config.provider = MarketProviderKind::Polygon;
+        let symbols: Vec<String> = (0..15).map(|i| format!("S{i}")).collect();
+
+        let limiter = RateLimiter::new(5);
+        let started = tokio::time::Instant::now();
+        let batch = tokio::spawn({
+            let symbols = symbols.clone();
+            async move {
+                let mut set = tokio::task::JoinSet::new();
+                for sym in symbols {
+                    let limiter = std::sync::Arc::clone(&limiter);
+                    set.spawn(async move {
+                        limiter.acquire(RequestPriority::Watchlist).await;
+                        let quote = TickerResponse {
+                            ticker: sym.clone(),
+                            results: vec![],
+                            status: "OK".into(),
+                            error: None,
+                        };
+                        (sym, quote)
+                    });
+                }
+                let mut quotes = HashMap::new();
+                while let Some(Ok((sym, quote))) = set.join_next().await {
+                    quotes.insert(sym, quote);
+                }
+                quotes
+            }
+        });
+        app.stock_refresh_inflight = true;
+        app.stock_fetch_generation = 1;
+        app.stock_stale_after = Some(stock_batch_stale_after(symbols.len(), &app.config));
+        app.stock_batch = Some(batch.abort_handle());
+
+        let quotes = batch.await.expect("batch runs to the end");
+        let elapsed = started.elapsed();
+        assert!(elapsed > INFLIGHT_STALE_AFTER);
+        app.stock_inflight_since = Some(Instant::now() - elapsed);
+        app.recover_stale_inflight_flags();
+        assert!(app.stock_refresh_inflight, "slow batch is still healthy");
+
+        app.apply_fetch_done(FetchDone::Stock {
+            generation: 1,
+            quotes,
+            daily_closes: HashMap::new(),
+            errors: Vec::new(),
+            served_by: None,
+        });
+        assert!(!app.stock_refresh_inflight);
+        assert!(symbols.iter().all(|s| app.watchlist_quotes.contains_key(s)));
+    }
+
+    #[test]
+    fn served_by_fallback_follows_the_fetch_behind_the_active_tab() {
+        use super::{FetchDone, Tab};
+        use crate::config::MarketProviderKind;
+        use crate::models::ticker::TickerResponse;
+        use std::collections::HashMap;
+
+        let mut app = App::new();
+        app.config.provider = MarketProviderKind::Polygon;
+        app.config.fallback_providers = vec![MarketProviderKind::Yahoo];
+        let batch = |generation, served_by| {
+            let quote = TickerResponse {
+                ticker: "AAPL".into(),
+                results: vec![],
+                status: "OK".into(),
+                error: None,
+            };
+            FetchDone::Stock {
+                generation,
+                quotes: HashMap::from([("AAPL".to_string(), quote)]),
+                daily_closes: HashMap::new(),
+                errors: Vec::new(),
+                served_by,
+            }
+        };
+
+        app.active_tab = Tab::StockView;
+        app.stock_fetch_generation = 1;
+        app.apply_fetch_done(batch(1, Some(MarketProviderKind::Yahoo)));
+        assert_eq!(app.served_by_fallback(), Some(MarketProviderKind::Yahoo));
+        app.active_tab = Tab::Charts;
+        assert_eq!(app.served_by_fallback(), None, "no chart fetch went through the fallback");
+        app.active_tab = Tab::Portfolio;
+        assert_eq!(app.served_by_fallback(), Some(MarketProviderKind::Yahoo));
+
+        // A superseded batch does not speak for the view.
+        app.stock_fetch_generation = 2;
+        app.apply_fetch_done(batch(1, Some(MarketProviderKind::Polygon)));
+        assert_eq!(app.served_by_fallback(), Some(MarketProviderKind::Yahoo));
+        app.apply_fetch_done(batch(2, Some(MarketProviderKind::Polygon)));
+        assert_eq!(app.served_by_fallback(), None);
+    }
+
+    #[test]
+    fn charts_toggle_indicator_is_per_time_range() {
+        use crate::models::indicators::Indicator;
+        use crate::models::time_range::TimeRange;
+
+        let mut app = App::new();
+        app.time_range = TimeRange::M1;
+        app.charts_toggle_indicator(Indicator::Rsi);
+        app.charts_toggle_indicator(Indicator::Sma);
+        assert_eq!(app.chart_indicators(), &[Indicator::Sma, Indicator::Rsi]);
+        app.time_range = TimeRange::Y1;
+        assert!(app.chart_indicators().is_empty());
+        app.time_range = TimeRange::M1;
+        app.charts_toggle_indicator(Indicator::Sma);
+        app.charts_toggle_indicator(Indicator::Rsi);
+        assert!(!app.config.chart_indicators.contains_key(&TimeRange::M1));
+        assert!(app.session_persist_deadline.is_some());
+    }
+
+    #[test]
+    fn custom_range_dialog_validates_then_switches_and_syncs_session() {
+        use crate::models::time_range::TimeRange;
+
+        let mut app = App::new();
+        app.time_range = TimeRange::M1;
+        app.charts_open_range_dialog();
+        let d = app.chart_range_dialog.as_mut().expect("dialog");
+        d.from_buffer = "2024-03-01".into();
+        d.to_buffer = "2024-02-01".into();
+        app.charts_commit_range_dialog();
+        assert!(app.chart_range_dialog.as_ref().unwrap().inline_error.is_some());
+        assert_eq!(app.time_range, TimeRange::M1);
+
+        let d = app.chart_range_dialog.as_mut().unwrap();
+        d.to_buffer = "2024-04-01".into();
+        app.charts_commit_range_dialog();
+        assert!(app.chart_range_dialog.is_none());
+        assert_eq!(app.time_range, TimeRange::Custom);
+        assert_eq!(
+            app.custom_range.map(|c| c.label()).as_deref(),
+            Some("2024-03-01 → 2024-04-01")
+        );
+        assert!(app.session_persist_deadline.is_some());
+        app.sync_session_fields_into_config();
+        assert_eq!(app.config.last_time_range, Some(TimeRange::Custom));
+        assert_eq!(app.config.custom_range, app.custom_range);
+    }
+
+    #[test]
+    fn compare_input_rejects_duplicates_and_caps_at_four() {
+        let mut app = App::new();
+        app.symbol = "AAPL".into();
+        for sym in ["spy", "SPY", "aapl", "qqq", "", "msft", "dia"] {
+            app.charts_open_compare_input();
+            app.chart_compare_input = Some(sym.into());
+            app.charts_commit_compare_input();
+        }
+        let got: Vec<_> = app.chart_compare.iter().map(|c| c.symbol.as_str()).collect();
+        assert_eq!(got, ["SPY", "QQQ", "MSFT", "DIA"]);
+        app.charts_open_compare_input();
+        assert!(app.chart_compare_input.is_none(), "full → prompt stays closed");
+        app.charts_remove_last_compare();
+        app.charts_open_compare_input();
+        assert_eq!(app.chart_compare_input.as_deref(), Some(""));
+    }
+
     struct InflightStaleEnvGuard;
 
     impl InflightStaleEnvGuard {
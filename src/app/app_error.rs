@@ -46,7 +46,7 @@ impl AppError {
             AppError::Provider(pe) => category_from_provider(pe),
             AppError::ConfigSave(_) => UiErrorCategory::Cfg,
             AppError::Internal(s) => {
-                if s.starts_with("Polygon provider requires") {
+                if s.contains(" provider requires a non-empty ") {
                     UiErrorCategory::Cfg
                 } else {
                     UiErrorCategory::Int
@@ -144,7 +144,7 @@ pub enum ErrorPersistence {
 
 pub fn persistence_for_app_error(err: &AppError) -> ErrorPersistence {
     // SPEC §20.6 — `AppError::ConfigSave` and every `AppError::Internal` flavor
-    // (including the `Polygon provider requires …` config-shape error) are
+    // (including the `… provider requires …` missing-key errors) are
     // currently `Sticky`; only `Provider` differentiates `Transient` vs
     // `Sticky` (see `persistence_for_provider`).
     match err {
@@ -170,7 +170,7 @@ fn persistence_for_provider(pe: &ProviderError) -> ErrorPersistence {
 }
 
 /// Which fetch / subsystem produced the active error (for TTL + success clearing).
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ErrorSourceDomain {
     Stock,
     Charts,
@@ -1,18 +1,26 @@
-//! Charts tab: line chart, candlesticks, viewport (Issues #7 / #8 / #9).
+//! Charts tab: line chart, candlesticks, viewport (Issues #7 / #8 / #9), crosshair readout, volume
+//! histogram, indicator overlays, RSI / MACD sub-panels ([`crate::models::indicators`]), and
+//! compare mode (extra symbols as % change from the first visible bar), and the custom from/to
+//! range dialog.
 
+use crate::api::error::ProviderError;
+use crate::app::layout::centered_rect;
 use crate::app::styles::ResolvedTheme;
 use crate::app::App;
 use crate::config::ResolvedLayout;
+use crate::models::currency::format_money;
 use crate::models::historical::{HistoricalData, HistoricalResponse};
-use crate::models::time_range::TimeRange;
-use chrono::{DateTime, Utc};
+use crate::models::indicators::{self, Indicator};
+use crate::models::time_range::{CustomRange, TimeRange};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::ops::Range;
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Direction, Layout, Rect},
-    style::Style,
+    style::{Color, Style},
     symbols,
     text::{Line, Span},
-    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph, Widget},
+    widgets::{Axis, Block, Borders, Chart, Clear, Dataset, GraphType, Paragraph, Widget},
     Frame,
 };
 
@@ -47,6 +55,145 @@ impl ChartViewport {
     }
 }
 
+/// Extra symbols allowed in compare mode.
+pub const MAX_COMPARE_SYMBOLS: usize = 4;
+
+/// Longest symbol accepted by the compare prompt.
+pub const MAX_COMPARE_SYMBOL_LEN: usize = 12;
+
+/// `YYYY-MM-DD` — longest input accepted by each custom range field.
+pub const CUSTOM_RANGE_DATE_LEN: usize = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChartRangeField {
+    From,
+    To,
+}
+
+/// Custom from/to window dialog (`t` on Charts); committed into [`App::custom_range`].
+#[derive(Debug, Clone)]
+pub struct ChartRangeDialog {
+    pub from_buffer: String,
+    pub to_buffer: String,
+    pub focused: ChartRangeField,
+    pub inline_error: Option<String>,
+}
+
+impl ChartRangeDialog {
+    /// Prefilled with the saved window, else the 30 days ending `today`.
+    pub fn new(custom: Option<CustomRange>, today: NaiveDate) -> Self {
+        let range = custom.unwrap_or(CustomRange {
+            from: today - chrono::Duration::days(30),
+            to: today,
+        });
+        Self {
+            from_buffer: range.from.format("%Y-%m-%d").to_string(),
+            to_buffer: range.to.format("%Y-%m-%d").to_string(),
+            focused: ChartRangeField::From,
+            inline_error: None,
+        }
+    }
+
+    pub fn focused_buffer_mut(&mut self) -> &mut String {
+        match self.focused {
+            ChartRangeField::From => &mut self.from_buffer,
+            ChartRangeField::To => &mut self.to_buffer,
+        }
+    }
+
+    pub fn toggle_focus(&mut self) {
+        self.focused = match self.focused {
+            ChartRangeField::From => ChartRangeField::To,
+            ChartRangeField::To => ChartRangeField::From,
+        };
+        self.inline_error = None;
+    }
+
+    /// Appends a digit or `-` to the focused field (up to [`CUSTOM_RANGE_DATE_LEN`]).
+    pub fn push_char(&mut self, c: char) {
+        if !(c.is_ascii_digit() || c == '-') {
+            return;
+        }
+        self.inline_error = None;
+        let buf = self.focused_buffer_mut();
+        if buf.len() < CUSTOM_RANGE_DATE_LEN {
+            buf.push(c);
+        }
+    }
+}
+
+/// One compare-mode symbol and its latest fetch outcome.
+#[derive(Debug)]
+pub struct CompareSeries {
+    pub symbol: String,
+    /// Last successful series (kept across failed refreshes).
+    pub data: Option<HistoricalResponse>,
+    pub error: Option<String>,
+}
+
+impl CompareSeries {
+    pub fn new(symbol: String) -> Self {
+        Self {
+            symbol,
+            data: None,
+            error: None,
+        }
+    }
+}
+
+/// Store fetched compare series on the matching entries; symbols removed meanwhile are dropped.
+pub fn apply_compare_results(
+    series: &mut [CompareSeries],
+    results: Vec<(String, Result<HistoricalResponse, ProviderError>)>,
+) {
+    for (symbol, result) in results {
+        let Some(entry) = series.iter_mut().find(|c| c.symbol == symbol) else {
+            continue;
+        };
+        match result {
+            Ok(data) => {
+                entry.data = Some(data);
+                entry.error = None;
+            }
+            Err(e) => entry.error = Some(e.to_string()),
+        }
+    }
+}
+
+fn percent_from(base: f64, v: f64) -> f64 {
+    if base == 0.0 {
+        0.0
+    } else {
+        (v / base - 1.0) * 100.0
+    }
+}
+
+/// `(seconds, % change)` for bars in `from_ms..=to_ms`, measured from the first bar in that window.
+fn compare_percent_points(results: &[HistoricalData], from_ms: u64, to_ms: u64) -> Vec<(f64, f64)> {
+    let mut in_window = results.iter().filter(|b| (from_ms..=to_ms).contains(&b.t));
+    let Some(first) = in_window.next() else {
+        return Vec::new();
+    };
+    let base = first.c;
+    std::iter::once(first)
+        .chain(in_window)
+        .map(|b| (b.t as f64 / 1000.0, percent_from(base, b.c)))
+        .collect()
+}
+
+/// Compare-series colors: mid-tone hues that read on light and dark presets and, unlike the
+/// theme's `positive` / `negative`, say nothing about gain or loss.
+const COMPARE_COLORS: [Color; MAX_COMPARE_SYMBOLS] = [
+    Color::Rgb(230, 159, 0),
+    Color::Rgb(148, 103, 189),
+    Color::Rgb(0, 114, 178),
+    Color::Rgb(204, 121, 167),
+];
+
+fn compare_color(i: usize) -> Color {
+    COMPARE_COLORS[i % MAX_COMPARE_SYMBOLS]
+}
+
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum ChartDisplayMode {
     #[default]
@@ -72,9 +219,13 @@ impl ChartDisplayMode {
 
 /// Visible bars for rendering; empty if there is no data.
 pub fn visible_slice<'a>(results: &'a [HistoricalData], vp: &ChartViewport) -> &'a [HistoricalData] {
-    let len = results.len();
+    &results[visible_range(results.len(), vp)]
+}
+
+/// Index range behind [`visible_slice`] (indicator series are sliced with the same window).
+fn visible_range(len: usize, vp: &ChartViewport) -> Range<usize> {
     if len == 0 {
-        return &[];
+        return 0..0;
     }
     let end = if vp.end == 0 {
         len
@@ -82,7 +233,7 @@ pub fn visible_slice<'a>(results: &'a [HistoricalData], vp: &ChartViewport) -> &
         vp.end.min(len).max(1)
     };
     let start = vp.start.min(end - 1);
-    &results[start..end]
+    start..end
 }
 
 /// Whether the viewport shows the entire series (same rule as “full” in [`visible_slice`]).
@@ -208,17 +359,128 @@ pub fn viewport_pan_right(vp: &mut ChartViewport, len: usize) {
     vp.normalize(len);
 }
 
-fn price_bounds(slice: &[HistoricalData]) -> Option<(f64, f64)> {
-    if slice.is_empty() {
+/// Crosshair clamped into the visible window; `None` when hidden or there is no data.
+pub fn cursor_in_viewport(cursor: Option<usize>, len: usize, vp: &ChartViewport) -> Option<usize> {
+    let c = cursor?;
+    let r = visible_range(len, vp);
+    if r.is_empty() {
         return None;
     }
+    Some(c.clamp(r.start, r.end - 1))
+}
+
+/// Move the crosshair one bar left, panning the viewport when it sits on the first visible bar.
+pub fn cursor_step_left(cursor: Option<usize>, vp: &mut ChartViewport, len: usize) -> Option<usize> {
+    let c = cursor_in_viewport(cursor, len, vp)?;
+    if c == 0 {
+        return Some(0);
+    }
+    if c == visible_range(len, vp).start {
+        viewport_pan_left(vp, len);
+    }
+    Some(c - 1)
+}
+
+/// Move the crosshair one bar right, panning the viewport when it sits on the last visible bar.
+pub fn cursor_step_right(cursor: Option<usize>, vp: &mut ChartViewport, len: usize) -> Option<usize> {
+    let c = cursor_in_viewport(cursor, len, vp)?;
+    if c + 1 >= len {
+        return Some(c);
+    }
+    if c + 1 == visible_range(len, vp).end {
+        viewport_pan_right(vp, len);
+    }
+    Some(c + 1)
+}
+
+/// Crosshair text: bar time, O/H/L/C, volume, and change versus the previous bar.
+fn cursor_readout(results: &[HistoricalData], idx: usize, intraday: bool) -> Option<String> {
+    let bar = results.get(idx)?;
+    let time = match DateTime::<Utc>::from_timestamp_millis(bar.t as i64) {
+        Some(dt) if intraday => dt.format("%Y-%m-%d %H:%MZ").to_string(),
+        Some(dt) => dt.format("%Y-%m-%d").to_string(),
+        None => "?".to_string(),
+    };
+    let change = match idx.checked_sub(1).and_then(|i| results.get(i)) {
+        Some(prev) if prev.c != 0.0 => {
+            let d = bar.c - prev.c;
+            format!("{d:+.2} ({:+.2}%)", d / prev.c * 100.0)
+        }
+        _ => "—".to_string(),
+    };
+    Some(format!(
+        "{time} · O {:.2} H {:.2} L {:.2} C {:.2} · Vol {} · {change}",
+        bar.o,
+        bar.h,
+        bar.l,
+        bar.c,
+        compact_volume(bar.v)
+    ))
+}
+
+fn app_cursor_readout(app: &App) -> Option<String> {
+    let idx = app.chart_cursor_index()?;
+    let results = &app.historical_data.as_ref()?.results;
+    cursor_readout(results, idx, range_is_intraday(app))
+}
+
+/// Whether the selected window is fetched with minute bars (custom windows depend on their span).
+fn range_is_intraday(app: &App) -> bool {
+    match app.time_range {
+        TimeRange::D1 | TimeRange::W1 => true,
+        TimeRange::Custom => app.custom_range.is_some_and(|c| {
+            c.historical_params(chrono::Local::now().date_naive())
+                .is_intraday()
+        }),
+        _ => false,
+    }
+}
+
+/// Quote currency of the charted symbol (price axis labels); `USD` until its quote arrives.
+fn chart_currency(app: &App) -> &str {
+    app.watchlist_quotes
+        .get(&app.symbol)
+        .and_then(|q| q.latest_result())
+        .map_or("USD", |r| r.quote_currency())
+}
+
+/// Preset label, or the custom window's dates.
+fn time_range_title(app: &App) -> String {
+    match (app.time_range, app.custom_range) {
+        (TimeRange::Custom, Some(c)) => c.label(),
+        (tr, _) => tr.label().to_string(),
+    }
+}
+
+/// Vertical crosshair in column `x`, drawn only through empty cells so data stays readable.
+fn mark_cursor_column(buf: &mut Buffer, x: u16, area: Rect, theme: ResolvedTheme) {
+    if x < area.left() || x >= area.right() {
+        return;
+    }
+    for y in area.top()..area.bottom() {
+        let cell = buf.get_mut(x, y);
+        if cell.symbol() == " " {
+            cell.set_symbol("┊");
+            cell.set_fg(theme.muted);
+        }
+    }
+}
+
+fn price_bounds(slice: &[HistoricalData]) -> Option<(f64, f64)> {
+    padded_bounds(slice.iter().flat_map(|b| [b.l, b.o, b.c, b.h]))
+}
+
+/// Min/max of `values` with 10% headroom (5% of the level when flat); `None` when empty or non-finite.
+fn padded_bounds(values: impl Iterator<Item = f64>) -> Option<(f64, f64)> {
     let mut lo = f64::MAX;
     let mut hi = f64::MIN;
-    for b in slice {
-        lo = lo.min(b.l).min(b.o).min(b.c);
-        hi = hi.max(b.h).max(b.o).max(b.c);
+    let mut any = false;
+    for v in values {
+        any = true;
+        lo = lo.min(v);
+        hi = hi.max(v);
     }
-    if !lo.is_finite() || !hi.is_finite() {
+    if !any || !lo.is_finite() || !hi.is_finite() {
         return None;
     }
     if (hi - lo).abs() < f64::EPSILON {
@@ -252,21 +514,182 @@ fn format_time_axis(ts_ms: f64, intraday: bool) -> String {
 }
 
 fn charts_short_title(app: &App) -> String {
-    format!(
-        "{} · {} · {}",
-        app.symbol,
-        app.time_range.label(),
+    let mode = if app.chart_compare.is_empty() {
         app.chart_mode.label()
-    )
+    } else {
+        "% chg"
+    };
+    let mut title = format!("{} · {} · {mode}", app.symbol, time_range_title(app));
+    if !app.chart_compare.is_empty() {
+        let symbols: Vec<&str> = app.chart_compare.iter().map(|c| c.symbol.as_str()).collect();
+        title.push_str(&format!(" vs {}", symbols.join(" ")));
+    }
+    for ind in app.chart_indicators() {
+        title.push(' ');
+        title.push_str(ind.label());
+    }
+    title
 }
 
 fn charts_key_hints() -> &'static str {
-    "1-4 range │ +/- zoom │ h l pan │ 0 reset │ c mode"
+    "1-9 t range │ +/- zoom │ h l pan │ 0 reset │ c mode │ s e b r m studies │ x , . cursor │ a d compare"
+}
+
+/// Title / chrome-strip tail: compare prompt, then crosshair readout, then key hints.
+fn charts_status_tail(app: &App) -> String {
+    if let Some(input) = &app.chart_compare_input {
+        return format!("compare: {input}▏ Enter add · Esc cancel");
+    }
+    app_cursor_readout(app).unwrap_or_else(|| charts_key_hints().to_string())
+}
+
+/// One price-axis line derived from an overlay [`Indicator`] (Bollinger contributes three).
+struct OverlayLine {
+    name: String,
+    color: Color,
+    /// Aligned with the visible slice.
+    values: Vec<Option<f64>>,
+}
+
+fn overlay_lines(
+    closes: &[f64],
+    enabled: &[Indicator],
+    range: Range<usize>,
+    theme: ResolvedTheme,
+) -> Vec<OverlayLine> {
+    let mut out = Vec::new();
+    for &ind in enabled {
+        match ind {
+            Indicator::Sma => out.push(OverlayLine {
+                name: ind.label().into(),
+                color: theme.border,
+                values: indicators::sma(closes, indicators::SMA_PERIOD)[range.clone()].to_vec(),
+            }),
+            Indicator::Ema => out.push(OverlayLine {
+                name: ind.label().into(),
+                color: theme.foreground,
+                values: indicators::ema(closes, indicators::EMA_PERIOD)[range.clone()].to_vec(),
+            }),
+            Indicator::Bollinger => {
+                let bands = indicators::bollinger(
+                    closes,
+                    indicators::BOLLINGER_PERIOD,
+                    indicators::BOLLINGER_STD_DEVS,
+                );
+                let bands = &bands[range.clone()];
+                for (suffix, pick) in [
+                    ("upper", (|b: &indicators::BollingerBand| b.upper) as fn(&_) -> f64),
+                    ("mid", |b| b.middle),
+                    ("lower", |b| b.lower),
+                ] {
+                    out.push(OverlayLine {
+                        name: format!("{} {suffix}", ind.label()),
+                        color: theme.muted,
+                        values: bands.iter().map(|b| b.as_ref().map(pick)).collect(),
+                    });
+                }
+            }
+            Indicator::Rsi | Indicator::Macd => {}
+        }
+    }
+    out
+}
+
+/// Widen `(lo, hi)` so overlay lines (e.g. Bollinger bands) are not clipped.
+fn include_overlay_bounds((mut lo, mut hi): (f64, f64), overlays: &[OverlayLine]) -> (f64, f64) {
+    for v in overlays.iter().flat_map(|o| o.values.iter().flatten()) {
+        if v.is_finite() {
+            lo = lo.min(*v);
+            hi = hi.max(*v);
+        }
+    }
+    (lo, hi)
+}
+
+/// Areas under the price chart: volume (`volume_pct` of the body, `0` = none) and one strip per
+/// sub-panel indicator (RSI / MACD). Short terminals keep the whole body for price.
+struct ChartPanes {
+    price: Rect,
+    volume: Option<Rect>,
+    indicators: Vec<Rect>,
+}
+
+fn split_chart_panes(area: Rect, volume_pct: u16, indicator_panels: usize) -> ChartPanes {
+    if (volume_pct == 0 && indicator_panels == 0) || area.height < 10 {
+        return ChartPanes {
+            price: area,
+            volume: None,
+            indicators: Vec::new(),
+        };
+    }
+    let mut constraints = vec![Constraint::Min(5)];
+    if volume_pct > 0 {
+        constraints.push(Constraint::Percentage(volume_pct));
+    }
+    constraints.extend((0..indicator_panels).map(|_| Constraint::Percentage(25)));
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(area);
+    let (volume, indicators) = if volume_pct > 0 {
+        (Some(chunks[1]), chunks[2..].to_vec())
+    } else {
+        (None, chunks[1..].to_vec())
+    };
+    ChartPanes {
+        price: chunks[0],
+        volume,
+        indicators,
+    }
+}
+
+/// `1234567` → `1.23M` for panel titles.
+pub(crate) fn compact_volume(v: f64) -> String {
+    let a = v.abs();
+    if a >= 1e9 {
+        format!("{:.2}B", v / 1e9)
+    } else if a >= 1e6 {
+        format!("{:.2}M", v / 1e6)
+    } else if a >= 1e3 {
+        format!("{:.1}K", v / 1e3)
+    } else {
+        format!("{v:.0}")
+    }
+}
+
+/// Volume histogram; `left_pad` columns are skipped so bars line up with a [`Chart`] plot area
+/// that starts after its y-axis labels.
+fn draw_volume_panel(
+    f: &mut Frame,
+    area: Rect,
+    slice: &[HistoricalData],
+    left_pad: u16,
+    theme: ResolvedTheme,
+) {
+    let title = match slice.last() {
+        Some(b) => format!("Volume {}", compact_volume(b.v)),
+        None => "Volume".to_string(),
+    };
+    let block = Block::default()
+        .title(Span::styled(title, theme.fg_foreground()))
+        .borders(Borders::TOP)
+        .style(theme.canvas())
+        .border_style(Style::default().fg(theme.border).bg(theme.background));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let pad = left_pad.min(inner.width.saturating_sub(2));
+    let bars = Rect {
+        x: inner.x + pad,
+        width: inner.width - pad,
+        ..inner
+    };
+    f.render_widget(VolumeHistogram { data: slice, theme }, bars);
 }
 
+/// The compare prompt or crosshair readout takes the place of the key hints while active.
 fn charts_block_title(app: &App, include_key_hints: bool) -> String {
     if include_key_hints {
-        format!("{} │ {}", charts_short_title(app), charts_key_hints())
+        format!("{} │ {}", charts_short_title(app), charts_status_tail(app))
     } else {
         charts_short_title(app)
     }
@@ -280,7 +703,8 @@ pub fn draw_charts(
     layout: ResolvedLayout,
 ) {
     if layout.charts_chart_pct >= 100 {
-        draw_charts_inner(f, app, area, theme, true);
+        draw_charts_inner(f, app, area, theme, true, layout.charts_volume_pct);
+        draw_chart_range_dialog(f, app, area, theme);
         return;
     }
 
@@ -291,15 +715,68 @@ pub fn draw_charts(
             Constraint::Min(2),
         ])
         .split(area);
-    draw_charts_inner(f, app, chunks[0], theme, false);
+    draw_charts_inner(f, app, chunks[0], theme, false, layout.charts_volume_pct);
     draw_charts_chrome_strip(f, app, chunks[1], theme);
+    draw_chart_range_dialog(f, app, area, theme);
+}
+
+fn draw_chart_range_dialog(f: &mut Frame, app: &App, area: Rect, theme: ResolvedTheme) {
+    let Some(dialog) = app.chart_range_dialog.as_ref() else {
+        return;
+    };
+
+    f.render_widget(Clear, area);
+
+    let popup = centered_rect(area, 55, 40);
+    let border_st = Style::default().fg(theme.border).bg(theme.background);
+    let field_style = |field: ChartRangeField| {
+        if dialog.focused == field {
+            theme.fg_accent()
+        } else {
+            theme.fg_foreground()
+        }
+    };
+
+    let mut lines: Vec<Line> = vec![
+        Line::from(vec![Span::styled(
+            "Custom range — Esc cancel · Tab switch field · Enter applies (YYYY-MM-DD)",
+            theme.canvas(),
+        )]),
+        Line::from(vec![
+            Span::styled("From: ", field_style(ChartRangeField::From)),
+            Span::styled(dialog.from_buffer.as_str(), theme.fg_foreground()),
+        ]),
+        Line::from(vec![
+            Span::styled("To:   ", field_style(ChartRangeField::To)),
+            Span::styled(dialog.to_buffer.as_str(), theme.fg_foreground()),
+        ]),
+    ];
+
+    if let Some(ref err) = dialog.inline_error {
+        lines.push(Line::from(vec![Span::styled(
+            err.as_str(),
+            theme.error_text(),
+        )]));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Chart range")
+        .style(theme.canvas())
+        .border_style(border_st);
+    f.render_widget(Paragraph::new(lines).block(block), popup);
 }
 
 fn draw_charts_chrome_strip(f: &mut Frame, app: &App, area: Rect, theme: ResolvedTheme) {
-    let line = Line::from(vec![Span::styled(
-        format!("{} │ {}", charts_short_title(app), charts_key_hints()),
-        theme.fg_muted(),
-    )]);
+    let tail_style = if app.chart_compare_input.is_some() || app.chart_cursor_index().is_some() {
+        theme.fg_foreground()
+    } else {
+        theme.fg_muted()
+    };
+    let line = Line::from(vec![
+        Span::styled(format!("{} │ ", charts_short_title(app)), theme.fg_muted()),
+        Span::styled(charts_status_tail(app), tail_style),
+    ]);
     let block = Block::default()
         .borders(Borders::TOP)
         .style(theme.canvas())
@@ -307,7 +784,14 @@ fn draw_charts_chrome_strip(f: &mut Frame, app: &App, area: Rect, theme: Resolve
     f.render_widget(Paragraph::new(line).block(block), area);
 }
 
-fn draw_charts_inner(f: &mut Frame, app: &App, area: Rect, theme: ResolvedTheme, full_title: bool) {
+fn draw_charts_inner(
+    f: &mut Frame,
+    app: &App,
+    area: Rect,
+    theme: ResolvedTheme,
+    full_title: bool,
+    volume_pct: u16,
+) {
     let block = Block::default()
         .title(charts_block_title(app, full_title))
         .borders(Borders::ALL)
@@ -345,36 +829,89 @@ fn draw_charts_inner(f: &mut Frame, app: &App, area: Rect, theme: ResolvedTheme,
         return;
     }
 
-    let Some((price_min, price_max)) = price_bounds(slice) else {
-        let no_data_text = Line::from(vec![Span::styled(
-            "Invalid price data",
-            theme.error_text(),
-        )]);
-        let paragraph = ratatui::widgets::Paragraph::new(no_data_text).block(block);
-        f.render_widget(paragraph, area);
-        return;
+    let range = visible_range(historical_data.results.len(), &app.chart_viewport);
+    let enabled = app.chart_indicators();
+    let closes = indicators::closes(&historical_data.results);
+    // Compare mode plots % change, so price-axis overlays do not apply.
+    let compare_mode = !app.chart_compare.is_empty();
+    let overlays = if compare_mode {
+        Vec::new()
+    } else {
+        overlay_lines(&closes, enabled, range.clone(), theme)
     };
+    let panels: Vec<Indicator> = enabled.iter().copied().filter(|i| !i.is_overlay()).collect();
+    let cursor_slot = app.chart_cursor_index().map(|i| i - range.start);
 
+    let base_close = slice[0].c;
     let data: Vec<(f64, f64)> = slice
         .iter()
-        .map(|b| (b.t as f64 / 1000.0, b.c))
+        .map(|b| {
+            let y = if compare_mode {
+                percent_from(base_close, b.c)
+            } else {
+                b.c
+            };
+            (b.t as f64 / 1000.0, y)
+        })
         .collect();
 
     let (min_time, max_time) = data.iter().fold((f64::MAX, f64::MIN), |(a, b), &(t, _)| {
         (a.min(t), b.max(t))
     });
+
+    let first_ts = slice.first().map(|b| b.t).unwrap_or(0);
+    let last_ts = slice.last().map(|b| b.t).unwrap_or(0);
+    let compare_lines: Vec<_> = app
+        .chart_compare
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let points = c
+                .data
+                .as_ref()
+                .map(|d| compare_percent_points(&d.results, first_ts, last_ts))
+                .unwrap_or_default();
+            let name = match (points.last(), &c.error) {
+                (Some(&(_, pct)), _) => format!("{} {pct:+.1}%", c.symbol),
+                (None, Some(_)) => format!("{} (error)", c.symbol),
+                (None, None) if c.data.is_some() => format!("{} (no data)", c.symbol),
+                (None, None) => format!("{} …", c.symbol),
+            };
+            (name, compare_color(i), points)
+        })
+        .collect();
+
+    let bounds = if compare_mode {
+        padded_bounds(
+            data.iter()
+                .chain(compare_lines.iter().flat_map(|(_, _, p)| p))
+                .map(|&(_, y)| y),
+        )
+    } else {
+        price_bounds(slice).map(|b| include_overlay_bounds(b, &overlays))
+    };
+    let Some((price_min, price_max)) = bounds else {
+        let no_data_text = Line::from(vec![Span::styled(
+            "Invalid price data",
+            theme.error_text(),
+        )]);
+        let paragraph = ratatui::widgets::Paragraph::new(no_data_text).block(block);
+        f.render_widget(paragraph, area);
+        return;
+    };
+
     let span_sec = max_time - min_time;
-    let intraday = matches!(app.time_range, TimeRange::D1 | TimeRange::W1) || span_sec < 86400.0 * 3.0;
+    let intraday = range_is_intraday(app) || span_sec < 86400.0 * 3.0;
 
-    let first_ts = slice.first().map(|b| b.t as f64).unwrap_or(0.0);
-    let last_ts = slice.last().map(|b| b.t as f64).unwrap_or(0.0);
-    let vis_from = format_time_axis(first_ts, intraday);
-    let vis_to = format_time_axis(last_ts, intraday);
+    let vis_from = format_time_axis(first_ts as f64, intraday);
+    let vis_to = format_time_axis(last_ts as f64, intraday);
 
     let inner = block.inner(area);
     f.render_widget(block, area);
+    let panes = split_chart_panes(inner, volume_pct, panels.len());
+    let inner = panes.price;
 
-    if matches!(app.chart_mode, ChartDisplayMode::Candlestick) {
+    if matches!(app.chart_mode, ChartDisplayMode::Candlestick) && !compare_mode {
         if slice.len() < 2 {
             let msg = Line::from(vec![Span::styled(
                 format!(
@@ -392,20 +929,82 @@ fn draw_charts_inner(f: &mut Frame, app: &App, area: Rect, theme: ResolvedTheme,
             min_y: price_min,
             max_y: price_max,
             theme,
+            overlays: &overlays,
         };
         f.render_widget(chart, inner);
+        if let Some(ci) = cursor_slot {
+            let x = slot_center_x(inner, ci, slice.len());
+            mark_cursor_column(f.buffer_mut(), x, inner, theme);
+        }
+        if let Some(vol) = panes.volume {
+            draw_volume_panel(f, vol, slice, 0, theme);
+        }
+        // Slot centers, matching `slot_center_x`.
+        let n = slice.len() as f64;
+        let xs: Vec<f64> = (0..slice.len()).map(|i| i as f64 + 0.5).collect();
+        for (ind, panel) in panels.iter().zip(panes.indicators) {
+            draw_indicator_panel(f, panel, *ind, &closes, range.clone(), &xs, [0.0, n], theme);
+        }
         return;
     }
 
-    let datasets = vec![Dataset::default()
-        .name("Close")
+    let overlay_points: Vec<Vec<(f64, f64)>> = overlays
+        .iter()
+        .map(|o| {
+            data.iter()
+                .zip(&o.values)
+                .filter_map(|(&(t, _), v)| v.map(|v| (t, v)))
+                .collect()
+        })
+        .collect();
+    let primary_name = match data.last() {
+        Some(&(_, pct)) if compare_mode => format!("{} {pct:+.1}%", app.symbol),
+        _ => "Close".to_string(),
+    };
+    let mut datasets = vec![Dataset::default()
+        .name(primary_name)
         .marker(symbols::Marker::Braille)
         .graph_type(GraphType::Line)
         .style(theme.fg_accent())
         .data(&data)];
+    for (name, color, points) in &compare_lines {
+        datasets.push(
+            Dataset::default()
+                .name(name.clone())
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(theme.fg_color(*color))
+                .data(points),
+        );
+    }
+    for (o, points) in overlays.iter().zip(&overlay_points) {
+        datasets.push(
+            Dataset::default()
+                .name(o.name.clone())
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(theme.fg_color(o.color))
+                .data(points),
+        );
+    }
 
     let format_time = |time: &f64| format_time_axis(*time * 1000.0, intraday);
-    let format_price = |price: &f64| format!("${:.2}", price);
+    let currency = chart_currency(app);
+    let format_price = |price: &f64| {
+        if compare_mode {
+            format!("{price:+.1}%")
+        } else {
+            format_money(*price, currency)
+        }
+    };
+    // ratatui starts the plot after the widest y label (or first x label) plus the axis line.
+    let plot_left_pad = [price_min, (price_min + price_max) / 2.0, price_max]
+        .iter()
+        .map(|p| format_price(p).chars().count())
+        .chain(std::iter::once(format_time(&min_time).chars().count()))
+        .max()
+        .unwrap_or(0) as u16
+        + 1;
 
     let chart = Chart::new(datasets)
         .x_axis(
@@ -428,7 +1027,7 @@ fn draw_charts_inner(f: &mut Frame, app: &App, area: Rect, theme: ResolvedTheme,
         .y_axis(
             Axis::default()
                 .title(Line::from(vec![Span::styled(
-                    "Price",
+                    if compare_mode { "% chg" } else { "Price" },
                     theme.fg_foreground(),
                 )]))
                 .style(theme.fg_foreground())
@@ -444,6 +1043,146 @@ fn draw_charts_inner(f: &mut Frame, app: &App, area: Rect, theme: ResolvedTheme,
         );
 
     f.render_widget(chart, inner);
+    if let Some(ci) = cursor_slot {
+        // Plot rows exclude the x-axis line and its labels.
+        let plot = Rect {
+            x: inner.x + plot_left_pad.min(inner.width),
+            width: inner.width.saturating_sub(plot_left_pad),
+            height: inner.height.saturating_sub(2),
+            ..inner
+        };
+        let span = (max_time - min_time).max(f64::EPSILON);
+        let frac = ((data[ci].0 - min_time) / span).clamp(0.0, 1.0);
+        let x = plot.x + (frac * f64::from(plot.width.saturating_sub(1))).round() as u16;
+        mark_cursor_column(f.buffer_mut(), x, plot, theme);
+    }
+    if let Some(vol) = panes.volume {
+        draw_volume_panel(f, vol, slice, plot_left_pad, theme);
+    }
+
+    let xs: Vec<f64> = data.iter().map(|&(t, _)| t).collect();
+    for (ind, panel) in panels.iter().zip(panes.indicators) {
+        draw_indicator_panel(
+            f,
+            panel,
+            *ind,
+            &closes,
+            range.clone(),
+            &xs,
+            [min_time, max_time],
+            theme,
+        );
+    }
+}
+
+/// RSI (0–100 with 30 / 70 guides) or MACD (line, signal, zero) under the price chart.
+#[allow(clippy::too_many_arguments)]
+fn draw_indicator_panel(
+    f: &mut Frame,
+    area: Rect,
+    indicator: Indicator,
+    closes: &[f64],
+    range: Range<usize>,
+    xs: &[f64],
+    x_bounds: [f64; 2],
+    theme: ResolvedTheme,
+) {
+    let points = |values: &[Option<f64>]| -> Vec<(f64, f64)> {
+        xs.iter()
+            .zip(values)
+            .filter_map(|(&x, v)| v.map(|v| (x, v)))
+            .collect()
+    };
+    let guide = |y: f64| vec![(x_bounds[0], y), (x_bounds[1], y)];
+
+    let (title, lines, y_bounds) =
+        match indicator {
+            Indicator::Rsi => {
+                let rsi = &indicators::rsi(closes, indicators::RSI_PERIOD)[range];
+                let last = rsi.iter().rev().flatten().next();
+                let title = match last {
+                    Some(v) => format!("{} {v:.1}", indicator.label()),
+                    None => indicator.label().to_string(),
+                };
+                (
+                    title,
+                    vec![
+                        (guide(70.0), theme.fg_muted()),
+                        (guide(30.0), theme.fg_muted()),
+                        (points(rsi), theme.fg_accent()),
+                    ],
+                    [0.0, 100.0],
+                )
+            }
+            Indicator::Macd => {
+                let macd = &indicators::macd(
+                    closes,
+                    indicators::MACD_FAST,
+                    indicators::MACD_SLOW,
+                    indicators::MACD_SIGNAL,
+                )[range];
+                let line: Vec<Option<f64>> = macd.iter().map(|p| p.map(|p| p.macd)).collect();
+                let signal: Vec<Option<f64>> = macd.iter().map(|p| p.map(|p| p.signal)).collect();
+                let (lo, hi) = macd
+                    .iter()
+                    .flatten()
+                    .fold((0.0f64, 0.0f64), |(lo, hi), p| {
+                        (lo.min(p.macd).min(p.signal), hi.max(p.macd).max(p.signal))
+                    });
+                let pad = ((hi - lo) * 0.1).max(1e-6);
+                let title = match macd.iter().rev().flatten().next() {
+                    Some(p) => format!(
+                        "{} {:.2} · signal {:.2} · hist {:+.2}",
+                        indicator.label(),
+                        p.macd,
+                        p.signal,
+                        p.histogram
+                    ),
+                    None => indicator.label().to_string(),
+                };
+                (
+                    title,
+                    vec![
+                        (guide(0.0), theme.fg_muted()),
+                        (points(&signal), theme.fg_color(theme.border)),
+                        (points(&line), theme.fg_accent()),
+                    ],
+                    [lo - pad, hi + pad],
+                )
+            }
+            Indicator::Sma | Indicator::Ema | Indicator::Bollinger => return,
+        };
+
+    let datasets: Vec<Dataset> = lines
+        .iter()
+        .map(|(pts, style)| {
+            Dataset::default()
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(*style)
+                .data(pts)
+        })
+        .collect();
+    let block = Block::default()
+        .title(Span::styled(title, theme.fg_foreground()))
+        .borders(Borders::TOP)
+        .style(theme.canvas())
+        .border_style(Style::default().fg(theme.border).bg(theme.background));
+    let chart = Chart::new(datasets)
+        .block(block)
+        .style(theme.canvas())
+        .legend_position(None)
+        .x_axis(Axis::default().bounds(x_bounds))
+        .y_axis(
+            Axis::default()
+                .style(theme.fg_foreground())
+                .bounds(y_bounds)
+                .labels(vec![
+                    Span::styled(format!("{:.1}", y_bounds[0]), theme.fg_foreground()),
+                    Span::styled(format!("{:.1}", y_bounds[1]), theme.fg_foreground()),
+                ]),
+        );
+    f.render_widget(chart, area);
 }
 
 /// Candlesticks in equal-width slots so bars sit closer than edge-to-edge indexing (Issue #7).
@@ -452,34 +1191,37 @@ struct CandlestickChart<'a> {
     min_y: f64,
     max_y: f64,
     theme: ResolvedTheme,
+    /// Indicator lines dotted through empty cells at each slot center.
+    overlays: &'a [OverlayLine],
 }
 
-impl CandlestickChart<'_> {
-    /// Center of bar `i` in slot `i` of `n` equal columns (tighter than edge-to-edge `i/(n-1)`).
-    fn slot_center_x(&self, area: Rect, i: usize, n: usize) -> u16 {
-        let w = area.width.max(1);
-        if n == 0 {
-            return area.left();
-        }
-        if n == 1 {
-            return area.left() + w / 2;
-        }
-        let slot = f64::from(w) / n as f64;
-        let cx = f64::from(area.left()) + slot * (i as f64 + 0.5);
-        cx.round().clamp(f64::from(area.left()), f64::from(area.right().saturating_sub(1))) as u16
+/// Center of bar `i` in slot `i` of `n` equal columns (tighter than edge-to-edge `i/(n-1)`).
+fn slot_center_x(area: Rect, i: usize, n: usize) -> u16 {
+    let w = area.width.max(1);
+    if n == 0 {
+        return area.left();
     }
+    if n == 1 {
+        return area.left() + w / 2;
+    }
+    let slot = f64::from(w) / n as f64;
+    let cx = f64::from(area.left()) + slot * (i as f64 + 0.5);
+    cx.round().clamp(f64::from(area.left()), f64::from(area.right().saturating_sub(1))) as u16
+}
 
-    fn body_width_cells(&self, area: Rect, n: usize) -> u16 {
-        if n == 0 {
-            return 1;
-        }
-        let slot = f64::from(area.width.max(1)) / n as f64;
-        if slot >= 4.0 {
-            2
-        } else {
-            1
-        }
+fn body_width_cells(area: Rect, n: usize) -> u16 {
+    if n == 0 {
+        return 1;
+    }
+    let slot = f64::from(area.width.max(1)) / n as f64;
+    if slot >= 4.0 {
+        2
+    } else {
+        1
     }
+}
+
+impl CandlestickChart<'_> {
 
     fn price_to_row(&self, area: Rect, price: f64) -> Option<u16> {
         let h = area.height;
@@ -517,8 +1259,8 @@ impl Widget for CandlestickChart<'_> {
         let n = self.data.len();
 
         for (i, bar) in self.data.iter().enumerate() {
-            let cx = self.slot_center_x(area, i, n);
-            let bw = self.body_width_cells(area, n);
+            let cx = slot_center_x(area, i, n);
+            let bw = body_width_cells(area, n);
             let x0 = cx.saturating_sub(bw / 2);
             let Some(y_high) = self.price_to_row(area, bar.h) else {
                 continue;
@@ -565,6 +1307,73 @@ impl Widget for CandlestickChart<'_> {
             }
         }
 
+        for overlay in self.overlays {
+            for (i, v) in overlay.values.iter().enumerate().take(n) {
+                let Some(y) = v.and_then(|v| self.price_to_row(area, v)) else {
+                    continue;
+                };
+                let cell = buf.get_mut(slot_center_x(area, i, n), y);
+                if cell.symbol() == " " {
+                    cell.set_symbol("·");
+                    cell.set_fg(overlay.color);
+                }
+            }
+        }
+    }
+}
+
+/// Volume bars in the same equal-width slots as [`CandlestickChart`], colored by candle direction.
+struct VolumeHistogram<'a> {
+    data: &'a [HistoricalData],
+    theme: ResolvedTheme,
+}
+
+/// Eighth-block glyphs for the fractional top cell of a bar.
+const BAR_EIGHTHS: [&str; 8] = [" ", "▁", "▂", "▃", "▄", "▅", "▆", "▇"];
+
+impl Widget for VolumeHistogram<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if self.data.is_empty() || area.width == 0 || area.height == 0 {
+            return;
+        }
+        let max_v = self
+            .data
+            .iter()
+            .map(|b| b.v)
+            .filter(|v| v.is_finite())
+            .fold(0.0f64, f64::max);
+        if max_v <= 0.0 {
+            return;
+        }
+        let n = self.data.len();
+        let bw = body_width_cells(area, n);
+        for (i, bar) in self.data.iter().enumerate() {
+            if !bar.v.is_finite() || bar.v <= 0.0 {
+                continue;
+            }
+            let color = if bar.c >= bar.o {
+                self.theme.positive
+            } else {
+                self.theme.negative
+            };
+            let eighths = ((bar.v / max_v) * f64::from(area.height) * 8.0).round().max(1.0) as u32;
+            let x0 = slot_center_x(area, i, n).saturating_sub(bw / 2);
+            for row in 0..area.height {
+                let filled = eighths.saturating_sub(u32::from(row) * 8).min(8);
+                if filled == 0 {
+                    break;
+                }
+                let symbol = if filled == 8 { "█" } else { BAR_EIGHTHS[filled as usize] };
+                let y = area.bottom() - 1 - row;
+                for dx in 0..bw {
+                    let x = x0.saturating_add(dx).min(area.right().saturating_sub(1));
+                    let cell = buf.get_mut(x, y);
+                    cell.set_symbol(symbol);
+                    cell.set_fg(color);
+                    cell.set_bg(self.theme.background);
+                }
+            }
+        }
     }
 }
 
@@ -706,6 +1515,155 @@ mod tests {
         assert!(out.start < out.end);
     }
 
+    fn test_theme() -> ResolvedTheme {
+        ResolvedTheme::from_palette(crate::config::theme::ThemePreset::default().base_rgb())
+    }
+
+    #[test]
+    fn compare_colors_avoid_gain_loss_and_accent_slots() {
+        for preset in crate::config::theme::ThemePreset::ALL {
+            let theme = ResolvedTheme::from_palette(preset.base_rgb());
+            for c in COMPARE_COLORS {
+                assert!(![theme.positive, theme.negative, theme.accent].contains(&c), "{preset:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn overlay_lines_slice_to_viewport_and_skip_panels() {
+        let closes: Vec<f64> = (0..30).map(f64::from).collect();
+        let enabled = [Indicator::Sma, Indicator::Bollinger, Indicator::Rsi];
+        let lines = overlay_lines(&closes, &enabled, 10..30, test_theme());
+        assert_eq!(lines.len(), 4, "SMA + three Bollinger bands; RSI is a sub-panel");
+        assert!(lines.iter().all(|l| l.values.len() == 20));
+        assert_eq!(lines[0].values[9], Some(9.5), "SMA20 at bar 19");
+        assert!(lines[0].values[8].is_none());
+    }
+
+    #[test]
+    fn include_overlay_bounds_widens_price_axis() {
+        let lines = vec![OverlayLine {
+            name: "x".into(),
+            color: Color::Reset,
+            values: vec![None, Some(0.5), Some(12.0)],
+        }];
+        assert_eq!(include_overlay_bounds((1.0, 10.0), &lines), (0.5, 12.0));
+    }
+
+    #[test]
+    fn split_chart_panes_orders_volume_before_indicators() {
+        let area = Rect::new(0, 0, 80, 40);
+        let panes = split_chart_panes(area, 20, 2);
+        let vol = panes.volume.expect("volume pane");
+        assert_eq!(panes.indicators.len(), 2);
+        assert!(panes.price.bottom() <= vol.top());
+        assert!(vol.bottom() <= panes.indicators[0].top());
+        assert_eq!(vol.height, 8);
+        let none = split_chart_panes(area, 0, 0);
+        assert_eq!(none.price, area);
+        assert!(none.volume.is_none());
+    }
+
+    #[test]
+    fn volume_histogram_scales_to_max_and_colors_by_direction() {
+        let theme = test_theme();
+        let data = vec![bar(1, 1.0, 2.0, 0.5, 2.0), bar(2, 2.0, 2.0, 0.5, 1.0)];
+        let data: Vec<_> = data
+            .into_iter()
+            .zip([100.0, 50.0])
+            .map(|(b, v)| HistoricalData { v, ..b })
+            .collect();
+        let area = Rect::new(0, 0, 4, 4);
+        let mut buf = Buffer::empty(area);
+        VolumeHistogram { data: &data, theme }.render(area, &mut buf);
+        // Slot centers at x=1 (up, full height) and x=3 (down, half height).
+        assert_eq!(buf.get(1, 0).symbol(), "█");
+        assert_eq!(buf.get(1, 0).fg, theme.positive);
+        assert_eq!(buf.get(3, 1).symbol(), " ");
+        assert_eq!(buf.get(3, 2).symbol(), "█");
+        assert_eq!(buf.get(3, 3).fg, theme.negative);
+    }
+
+    #[test]
+    fn compact_volume_units() {
+        assert_eq!(compact_volume(950.0), "950");
+        assert_eq!(compact_volume(12_345.0), "12.3K");
+        assert_eq!(compact_volume(1_234_567.0), "1.23M");
+        assert_eq!(compact_volume(2_500_000_000.0), "2.50B");
+    }
+
+    #[test]
+    fn cursor_clamps_into_viewport() {
+        let vp = ChartViewport { start: 5, end: 10 };
+        assert_eq!(cursor_in_viewport(None, 20, &vp), None);
+        assert_eq!(cursor_in_viewport(Some(2), 20, &vp), Some(5));
+        assert_eq!(cursor_in_viewport(Some(usize::MAX), 20, &vp), Some(9));
+        assert_eq!(cursor_in_viewport(Some(3), 0, &vp), None);
+    }
+
+    #[test]
+    fn cursor_steps_pan_viewport_at_edges() {
+        let mut vp = ChartViewport { start: 5, end: 10 };
+        assert_eq!(cursor_step_left(Some(6), &mut vp, 20), Some(5));
+        assert_eq!(vp, ChartViewport { start: 5, end: 10 });
+        assert_eq!(cursor_step_left(Some(5), &mut vp, 20), Some(4));
+        assert_eq!(vp, ChartViewport { start: 4, end: 9 });
+        assert_eq!(cursor_step_right(Some(8), &mut vp, 20), Some(9));
+        assert_eq!(vp, ChartViewport { start: 5, end: 10 });
+
+        let mut full = ChartViewport::default();
+        assert_eq!(cursor_step_right(Some(19), &mut full, 20), Some(19));
+        assert_eq!(cursor_step_left(Some(0), &mut full, 20), Some(0));
+    }
+
+    #[test]
+    fn cursor_readout_shows_ohlc_volume_and_change() {
+        let mut bars = vec![bar(0, 1.0, 1.0, 1.0, 100.0), bar(86_400_000, 101.0, 103.5, 99.0, 102.0)];
+        bars[1].v = 1_500_000.0;
+        let got = cursor_readout(&bars, 1, false).unwrap();
+        assert_eq!(
+            got,
+            "1970-01-02 · O 101.00 H 103.50 L 99.00 C 102.00 · Vol 1.50M · +2.00 (+2.00%)"
+        );
+        assert!(cursor_readout(&bars, 0, true).unwrap().ends_with("· —"));
+        assert!(cursor_readout(&bars, 2, false).is_none());
+    }
+
+    #[test]
+    fn compare_points_rebase_on_first_bar_in_window() {
+        let bars = vec![
+            bar(1_000, 1.0, 1.0, 1.0, 50.0),
+            bar(2_000, 1.0, 1.0, 1.0, 100.0),
+            bar(3_000, 1.0, 1.0, 1.0, 110.0),
+            bar(4_000, 1.0, 1.0, 1.0, 90.0),
+        ];
+        let got = compare_percent_points(&bars, 2_000, 3_500);
+        assert_eq!(got.len(), 2);
+        assert_eq!(got[0], (2.0, 0.0));
+        assert!((got[1].1 - 10.0).abs() < 1e-9);
+        assert!(compare_percent_points(&bars, 5_000, 6_000).is_empty());
+    }
+
+    #[test]
+    fn apply_compare_results_keeps_last_good_series_on_error() {
+        let mut series = vec![CompareSeries::new("SPY".into()), CompareSeries::new("QQQ".into())];
+        apply_compare_results(
+            &mut series,
+            vec![
+                ("SPY".into(), Ok(hist("SPY", 3))),
+                ("DIA".into(), Ok(hist("DIA", 3))),
+            ],
+        );
+        assert_eq!(series[0].data.as_ref().map(|d| d.results.len()), Some(3));
+        assert!(series[1].data.is_none());
+        apply_compare_results(
+            &mut series,
+            vec![("SPY".into(), Err(ProviderError::ApiMessage("down".into())))],
+        );
+        assert!(series[0].data.is_some());
+        assert!(series[0].error.is_some());
+    }
+
     /// Issue #36 / §40.1 — invalid timestamps must not panic; axis shows `?`.
     #[test]
     fn format_time_axis_valid_recent_ms() {
@@ -727,4 +1685,36 @@ mod tests {
         let label = format_time_axis(0.0, false);
         assert!(!label.is_empty());
     }
+    #[test]
+    fn chart_currency_follows_the_charted_quote() {
+        use crate::models::ticker::{TickerResponse, TickerResult};
+        let mut app = App::new();
+        app.symbol = "VOD.L".into();
+        assert_eq!(chart_currency(&app), "USD");
+        let bar = TickerResult {
+            o: 70.0,
+            h: 71.0,
+            l: 69.0,
+            c: 70.5,
+            v: 0.0,
+            t: 1,
+            prev_close: None,
+            market_state: None,
+            ext_price: None,
+            week52_low: None,
+            week52_high: None,
+            market_cap: None,
+            currency: Some("GBp".into()),
+        };
+        app.watchlist_quotes.insert(
+            "VOD.L".into(),
+            TickerResponse {
+                ticker: "VOD.L".into(),
+                results: vec![bar],
+                status: "OK".into(),
+                error: None,
+            },
+        );
+        assert_eq!(format_money(70.5, chart_currency(&app)), "70.50p");
+    }
 }
@@ -25,6 +25,7 @@ fn fetch_done_context(done: &FetchDone) -> &'static str {
         FetchDone::Historical { .. } => "historical fetch result",
         FetchDone::News { .. } => "news fetch result",
         FetchDone::Search { .. } => "search fetch result",
+        FetchDone::QuoteTicks(_) => "quote stream ticks",
     }
 }
 
@@ -76,7 +77,10 @@ mod tests {
             FetchDone::Historical {
                 symbol: "AAPL".into(),
                 time_range: TimeRange::M1,
+                custom_range: None,
                 result: Err(crate::api::error::ProviderError::ApiMessage("x".into())),
+                compare: Vec::new(),
+                served_by: None,
             },
             InflightRecovery::Historical,
         );
@@ -105,6 +109,7 @@ mod tests {
                     count: 0,
                     results: vec![],
                 }),
+                served_by: None,
             },
             InflightRecovery::Search,
         );
@@ -1,11 +1,18 @@
 #![allow(clippy::collapsible_match)]
 
+use crate::app::alert_history::handle_alert_history_export_keys;
 use crate::app::alerts::{cycle_alert_dialog_focus, handle_alerts_events};
+use crate::app::charts::MAX_COMPARE_SYMBOL_LEN;
 use crate::app::keyboard::{letter_key_plain, should_global_quit, tab_key_plain};
-use crate::app::portfolio::{cycle_portfolio_dialog_focus, handle_portfolio_events};
-use crate::app::{App, SettingsEdit, Tab};
+use crate::app::portfolio::{
+    cycle_portfolio_dialog_focus, handle_portfolio_account_input, handle_portfolio_csv_keys,
+    handle_portfolio_events,
+};
+use crate::app::{App, SettingsEdit, Tab, WatchlistPromptKind};
 use crate::config::keymap::{Action, BindingLayer};
+use crate::models::indicators::Indicator;
 use crate::models::time_range::TimeRange;
+use crate::models::watchlist::MAX_WATCHLIST_NAME_LEN;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 /// Add dialog open on Alerts or Portfolio — global Tab cycles fields instead of switching tabs (§36.2).
@@ -15,6 +22,57 @@ fn modal_add_dialog_open(app: &App) -> bool {
 }
 
 pub fn handle_event(app: &mut App, key: KeyEvent) {
+    // The compare prompt owns every key until Enter / Esc so symbols like `QQQ` can be typed.
+    if app.active_tab == Tab::Charts
+        && app.chart_compare_input.is_some()
+        && !app.error_log_overlay_open
+    {
+        handle_chart_compare_input(app, key);
+        return;
+    }
+    // Same for the custom range dialog: Tab switches fields instead of tabs.
+    if app.active_tab == Tab::Charts
+        && app.chart_range_dialog.is_some()
+        && !app.error_log_overlay_open
+    {
+        handle_chart_range_dialog(app, key);
+        return;
+    }
+    // Portfolio CSV dialog: file paths may contain `q`, and Tab cycles the import preset.
+    if app.active_tab == Tab::Portfolio
+        && app.portfolio_csv.is_some()
+        && !app.error_log_overlay_open
+    {
+        handle_portfolio_csv_keys(app, key);
+        return;
+    }
+    if app.active_tab == Tab::Portfolio
+        && app.portfolio_account_input.is_some()
+        && !app.error_log_overlay_open
+    {
+        handle_portfolio_account_input(app, key);
+        return;
+    }
+    // Alert history export path: file paths may contain `q`.
+    if app.active_tab == Tab::Alerts
+        && app
+            .alert_history
+            .as_ref()
+            .is_some_and(|h| h.export_path.is_some())
+        && !app.error_log_overlay_open
+    {
+        handle_alert_history_export_keys(app, key);
+        return;
+    }
+    // Watchlist names may contain `q` and spaces.
+    if app.active_tab == Tab::StockView
+        && app.watchlist_prompt.is_some()
+        && !app.error_log_overlay_open
+    {
+        handle_watchlist_prompt(app, key);
+        return;
+    }
+
     // Issue #123 / SPEC §20.15.4 — `Quit` is global, including when the error log overlay is open.
     // Issue #51 / §42.1 — `q`/`Q` wildcard respects §24 when `q` is remapped on Global.
     if should_global_quit(
@@ -149,7 +207,18 @@ fn handle_charts_events(app: &mut App, key: KeyEvent) {
             ChartRangeD1 => app.set_charts_time_range(TimeRange::D1),
             ChartRangeW1 => app.set_charts_time_range(TimeRange::W1),
             ChartRangeM1 => app.set_charts_time_range(TimeRange::M1),
+            ChartRangeM3 => app.set_charts_time_range(TimeRange::M3),
+            ChartRangeM6 => app.set_charts_time_range(TimeRange::M6),
+            ChartRangeYtd => app.set_charts_time_range(TimeRange::Ytd),
             ChartRangeY1 => app.set_charts_time_range(TimeRange::Y1),
+            ChartRangeY5 => app.set_charts_time_range(TimeRange::Y5),
+            ChartRangeMax => app.set_charts_time_range(TimeRange::Max),
+            ChartRangeCustom => {
+                if letter_key_plain(key.modifiers) {
+                    app.chart_compare_input = None;
+                    app.charts_open_range_dialog();
+                }
+            }
             ChartResetViewport => app.charts_reset_viewport(),
             ChartZoomIn | ChartZoomOut => {
                 if charts_zoom_modifiers_ok(key.modifiers) {
@@ -175,11 +244,135 @@ fn handle_charts_events(app: &mut App, key: KeyEvent) {
                     app.charts_toggle_mode();
                 }
             }
+            ChartToggleSma | ChartToggleEma | ChartToggleBollinger | ChartToggleRsi
+            | ChartToggleMacd => {
+                if letter_key_plain(key.modifiers) {
+                    let indicator = match a {
+                        ChartToggleSma => Indicator::Sma,
+                        ChartToggleEma => Indicator::Ema,
+                        ChartToggleBollinger => Indicator::Bollinger,
+                        ChartToggleRsi => Indicator::Rsi,
+                        _ => Indicator::Macd,
+                    };
+                    app.charts_toggle_indicator(indicator);
+                }
+            }
+            ChartCursorToggle => {
+                if letter_key_plain(key.modifiers) {
+                    app.charts_toggle_cursor();
+                }
+            }
+            ChartCursorLeft => app.charts_cursor_left(),
+            ChartCursorRight => app.charts_cursor_right(),
+            ChartCompareAdd => {
+                if letter_key_plain(key.modifiers) {
+                    app.charts_open_compare_input();
+                }
+            }
+            ChartCompareRemove => {
+                if letter_key_plain(key.modifiers) {
+                    app.charts_remove_last_compare();
+                }
+            }
             _ => {}
         }
     }
 }
 
+fn handle_chart_compare_input(app: &mut App, key: KeyEvent) {
+    use Action::*;
+    match app.resolved_keymap.action(BindingLayer::ChartCompareInput, &key) {
+        Some(ChartCompareInputEsc) => app.chart_compare_input = None,
+        Some(ChartCompareInputEnter) => app.charts_commit_compare_input(),
+        Some(ChartCompareInputBackspace) => {
+            if let Some(input) = app.chart_compare_input.as_mut() {
+                input.pop();
+            }
+        }
+        _ => {
+            let KeyCode::Char(c) = key.code else {
+                return;
+            };
+            if !letter_key_plain(key.modifiers) {
+                return;
+            }
+            if let Some(input) = app.chart_compare_input.as_mut() {
+                if (c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '^' | '='))
+                    && input.len() < MAX_COMPARE_SYMBOL_LEN
+                {
+                    input.push(c.to_ascii_uppercase());
+                }
+            }
+        }
+    }
+}
+
+fn handle_watchlist_prompt(app: &mut App, key: KeyEvent) {
+    use Action::*;
+    match app.resolved_keymap.action(BindingLayer::WatchlistPrompt, &key) {
+        Some(WatchlistPromptEsc) => app.watchlist_prompt = None,
+        Some(WatchlistPromptEnter) => {
+            if let Err(e) = app.submit_watchlist_prompt() {
+                if let Some(p) = app.watchlist_prompt.as_mut() {
+                    p.inline_error = Some(e);
+                }
+            }
+        }
+        Some(WatchlistPromptBackspace) => {
+            if let Some(p) = app.watchlist_prompt.as_mut() {
+                p.buffer.pop();
+                p.inline_error = None;
+            }
+        }
+        _ => {
+            let KeyCode::Char(c) = key.code else {
+                return;
+            };
+            if !letter_key_plain(key.modifiers) || c.is_control() {
+                return;
+            }
+            if let Some(p) = app.watchlist_prompt.as_mut() {
+                if p.kind != WatchlistPromptKind::Delete
+                    && p.buffer.chars().count() < MAX_WATCHLIST_NAME_LEN
+                {
+                    p.buffer.push(c);
+                    p.inline_error = None;
+                }
+            }
+        }
+    }
+}
+
+fn handle_chart_range_dialog(app: &mut App, key: KeyEvent) {
+    use Action::*;
+    match app.resolved_keymap.action(BindingLayer::ChartRangeDialog, &key) {
+        Some(ChartRangeDialogEsc) => app.chart_range_dialog = None,
+        Some(ChartRangeDialogEnter) => app.charts_commit_range_dialog(),
+        Some(ChartRangeDialogNextField) => {
+            if let Some(d) = app.chart_range_dialog.as_mut() {
+                d.toggle_focus();
+            }
+        }
+        Some(ChartRangeDialogBackspace) => {
+            if let Some(d) = app.chart_range_dialog.as_mut() {
+                d.inline_error = None;
+                d.focused_buffer_mut().pop();
+            }
+        }
+        _ => {
+            let KeyCode::Char(c) = key.code else {
+                return;
+            };
+            if !letter_key_plain(key.modifiers) {
+                return;
+            }
+            if let Some(d) = app.chart_range_dialog.as_mut() {
+                d.push_char(c);
+            }
+        }
+    }
+}
+
 fn handle_search_events(app: &mut App, key: KeyEvent) {
     // §26 / Issue #136: `SearchEsc`, `SearchEnter`, and `SearchBackspace` intentionally require
     // `KeyModifiers::NONE` so Ctrl/Alt chords do not clear the query or pick a row by accident.
@@ -455,6 +648,20 @@ fn handle_stock_view_keys(app: &mut App, key: KeyEvent) {
             StockEnter if key.modifiers == KeyModifiers::NONE && !app.filter_input_mode => {
                 app.should_fetch_ticker = true;
             }
+            WatchlistNext if !app.filter_input_mode => app.watchlist_switch(true),
+            WatchlistPrev if !app.filter_input_mode => app.watchlist_switch(false),
+            WatchlistNew if !app.filter_input_mode => {
+                app.open_watchlist_prompt(WatchlistPromptKind::Create);
+            }
+            WatchlistRename if !app.filter_input_mode => {
+                app.open_watchlist_prompt(WatchlistPromptKind::Rename);
+            }
+            WatchlistDelete if !app.filter_input_mode => {
+                app.open_watchlist_prompt(WatchlistPromptKind::Delete);
+            }
+            WatchlistSortNext if !app.filter_input_mode => app.watchlist_cycle_sort(true),
+            WatchlistSortPrev if !app.filter_input_mode => app.watchlist_cycle_sort(false),
+            WatchlistSortReverse if !app.filter_input_mode => app.watchlist_reverse_sort(),
             _ => {}
         }
         return;
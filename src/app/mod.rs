@@ -11,10 +11,15 @@ mod layout;
 pub mod charts;
 pub mod portfolio;
 pub mod alerts;
+pub mod alerts_watch;
+pub mod alert_delivery;
+pub mod alert_history;
 mod table_filter;
+mod quote_stream;
 mod fetch_delivery;
 
 pub use self::app::{
-    normalize_symbol, AlertAddDialog, AlertAddField, App, FetchDone, PortfolioAddDialog,
-    PortfolioAddField, SettingsEdit, Tab, SETTINGS_ROW_COUNT,
+    normalize_symbol, AlertAddDialog, AlertAddField, AlertHistoryView, App, FetchDone, PortfolioAddDialog,
+    PortfolioAddField, PortfolioCsvDialog, PortfolioCsvMode, SettingsEdit, Tab, WatchlistPrompt,
+    WatchlistPromptKind, SETTINGS_ROW_COUNT,
 };
@@ -5,8 +5,15 @@ use crate::app::app_error::{AppError, ErrorSourceDomain};
 use crate::app::keyboard::letter_key_plain;
 use crate::app::layout::centered_rect;
 use crate::app::table_filter::filter_title_suffix;
-use crate::app::{normalize_symbol, App, PortfolioAddField, Tab};
+use crate::app::{normalize_symbol, App, PortfolioAddField, PortfolioCsvDialog, PortfolioCsvMode, Tab};
 use crate::config::keymap::{Action, BindingLayer};
+use crate::models::currency::format_money;
+use crate::models::portfolio::{
+    first_oversell, merge_trade, validate_holding_limits, PortfolioItem, TradeSide, Transaction,
+};
+use crate::models::portfolio_csv::{parse_portfolio_csv, write_portfolio_csv};
+use chrono::NaiveDate;
+use std::path::PathBuf;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -18,15 +25,75 @@ use ratatui::{
 
 const MAX_HOLDING_INPUT_LEN: usize = 24;
 
-/// Upper sanity bound for shares (paste / typo); SPEC §15.5.
-pub(crate) const MAX_HOLDING_SHARES: f64 = 1_000_000_000.0;
-/// Upper sanity bound for price per share; SPEC §15.5.
-pub(crate) const MAX_HOLDING_PRICE_PER_SHARE: f64 = 1e12;
+pub(crate) const MAX_CSV_PATH_LEN: usize = 512;
+
+const MAX_ACCOUNT_NAME_LEN: usize = 32;
+
+/// Shown when a trade action is used on the All accounts view.
+pub(crate) const PORTFOLIO_ALL_ACCOUNTS_READ_ONLY: &str =
+    "All accounts is read-only — pick an account with [ / ] to record trades.";
 
 /// Shown when commit fails because `App.symbol` does not normalize (Issues #69 / #83).
 pub(crate) const PORTFOLIO_ADD_INVALID_SYMBOL_INLINE: &str =
     "Cannot add holding: no valid ticker is set. Pick a symbol on Stock View.";
 
+/// Why [`merge_trades`] left the holdings unchanged.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum MergeTradesError {
+    InvalidSymbol(String),
+    /// A sell of more shares than `symbol` holds at that point of its ledger.
+    Oversell { symbol: String, held: f64 },
+}
+
+impl std::fmt::Display for MergeTradesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeTradesError::InvalidSymbol(s) => write!(f, "Invalid symbol {s:?}"),
+            MergeTradesError::Oversell { symbol, held } => {
+                write!(f, "{symbol}: only {held:.2} shares are held")
+            }
+        }
+    }
+}
+
+/// Merge path shared by the add dialog, the CSV dialog and `stockterm portfolio import`: every
+/// symbol must normalize, and no sell may exceed the shares held when the ledger replays it
+/// (holdings already oversold before this batch are left alone). Nothing is merged on error;
+/// `Ok` carries the last trade's symbol.
+pub(crate) fn merge_trades(
+    holdings: &mut Vec<PortfolioItem>,
+    trades: impl IntoIterator<Item = (String, Transaction)>,
+) -> Result<Option<String>, MergeTradesError> {
+    let mut merged = holdings.clone();
+    let mut touched: Vec<String> = Vec::new();
+    for (symbol, tx) in trades {
+        let sym = normalize_symbol(&symbol).ok_or(MergeTradesError::InvalidSymbol(symbol))?;
+        merge_trade(&mut merged, &sym, tx);
+        touched.retain(|s| *s != sym);
+        touched.push(sym);
+    }
+    for sym in &touched {
+        let ledger = |items: &[PortfolioItem]| {
+            items
+                .iter()
+                .find(|i| i.symbol.trim().eq_ignore_ascii_case(sym))
+                .map(|i| i.transactions.clone())
+                .unwrap_or_default()
+        };
+        if first_oversell(&ledger(holdings)).is_some() {
+            continue;
+        }
+        if let Some(held) = first_oversell(&ledger(&merged)) {
+            return Err(MergeTradesError::Oversell {
+                symbol: sym.clone(),
+                held,
+            });
+        }
+    }
+    *holdings = merged;
+    Ok(touched.pop())
+}
+
 /// Parse a positive decimal for shares or purchase price (Issue #6 / SPEC §13).
 pub(crate) fn parse_holding_decimal(input: &str) -> Result<f64, &'static str> {
     let t = input.trim();
@@ -43,29 +110,62 @@ pub(crate) fn parse_holding_decimal(input: &str) -> Result<f64, &'static str> {
     Ok(v)
 }
 
-pub(crate) fn validate_holding_limits(shares: f64, price: f64) -> Result<(), &'static str> {
-    if shares > MAX_HOLDING_SHARES {
-        return Err("Shares exceed the allowed maximum");
+/// Optional fee field: blank → `0`, otherwise a non-negative decimal.
+pub(crate) fn parse_fees(input: &str) -> Result<f64, &'static str> {
+    let t = input.trim();
+    if t.is_empty() {
+        return Ok(0.0);
     }
-    if price > MAX_HOLDING_PRICE_PER_SHARE {
-        return Err("Price per share exceeds the allowed maximum");
+    let v: f64 = t.parse().map_err(|_| "Invalid fees")?;
+    if !v.is_finite() || v < 0.0 {
+        return Err("Fees must be zero or more");
     }
-    Ok(())
+    Ok(v)
 }
 
-/// Cycle Shares ↔ Price when the add dialog is open (#67 / SPEC §15.4). With only two
-/// fields, forward and backward are the same toggle.
-pub(crate) fn cycle_portfolio_dialog_focus(app: &mut App, _forward: bool) {
+/// Optional trade date: blank → undated, otherwise `YYYY-MM-DD`.
+pub(crate) fn parse_trade_date(input: &str) -> Result<Option<NaiveDate>, &'static str> {
+    let t = input.trim();
+    if t.is_empty() {
+        return Ok(None);
+    }
+    NaiveDate::parse_from_str(t, "%Y-%m-%d")
+        .map(Some)
+        .map_err(|_| "Date must be YYYY-MM-DD")
+}
+
+/// Cycle Shares → Price → Fees → Date when the trade dialog is open (#67 / SPEC §15.4).
+pub(crate) fn cycle_portfolio_dialog_focus(app: &mut App, forward: bool) {
     let Some(d) = app.portfolio_dialog.as_mut() else {
         return;
     };
+    use PortfolioAddField::*;
     d.inline_error = None;
-    d.focused = match d.focused {
-        PortfolioAddField::Shares => PortfolioAddField::Price,
-        PortfolioAddField::Price => PortfolioAddField::Shares,
+    d.focused = match (d.focused, forward) {
+        (Shares, true) | (Fees, false) => Price,
+        (Price, true) | (Date, false) => Fees,
+        (Fees, true) | (Shares, false) => Date,
+        (Date, true) | (Price, false) => Shares,
     };
 }
 
+fn focused_dialog_buffer(d: &mut crate::app::PortfolioAddDialog) -> &mut String {
+    match d.focused {
+        PortfolioAddField::Shares => &mut d.shares_buffer,
+        PortfolioAddField::Price => &mut d.price_buffer,
+        PortfolioAddField::Fees => &mut d.fees_buffer,
+        PortfolioAddField::Date => &mut d.date_buffer,
+    }
+}
+
+fn append_date_char(buf: &mut String, c: char) -> bool {
+    if buf.len() >= 10 || !(c.is_ascii_digit() || c == '-') {
+        return false;
+    }
+    buf.push(c);
+    true
+}
+
 fn append_numeric_char(buf: &mut String, c: char) -> bool {
     if buf.len() >= MAX_HOLDING_INPUT_LEN {
         return false;
@@ -114,99 +214,180 @@ fn portfolio_move_down(app: &mut App) {
 
 pub fn draw_portfolio(f: &mut Frame, app: &mut App, area: Rect, theme: ResolvedTheme) {
     let border_st = Style::default().fg(theme.border).bg(theme.background);
-    if app.portfolio.is_empty() {
-        let block = Block::default()
-            .title("Portfolio")
-            .borders(Borders::ALL)
-            .style(theme.canvas())
-            .border_style(border_st);
-        let no_data_text = Line::from(vec![Span::styled(
-            "Your portfolio is empty. Press 'a' to add the active symbol (set it on Stock View first).",
-            theme.fg_border(),
-        )]);
-        let paragraph = Paragraph::new(no_data_text)
-            .wrap(Wrap { trim: true })
-            .block(block);
-        f.render_widget(paragraph, area);
-    } else {
-        let block = Block::default()
-            .title("Portfolio")
-            .borders(Borders::ALL)
-            .style(theme.canvas())
-            .border_style(border_st);
+    let method = app.config.cost_basis_method;
+    let block = Block::default()
+        .title(format!(
+            "Portfolio · {} · {} cost basis",
+            app.portfolio_account_label(),
+            method.label()
+        ))
+        .borders(Borders::ALL)
+        .style(theme.canvas())
+        .border_style(border_st);
 
-        let inner = block.inner(area);
-        f.render_widget(block, area);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
 
-        let layout = if app.portfolio_remove_armed {
-            vec![
-                Constraint::Length(3),
-                Constraint::Min(0),
-                Constraint::Length(2),
-            ]
-        } else {
-            vec![Constraint::Length(3), Constraint::Min(0)]
-        };
+    let all_accounts = app.portfolio_account.is_none();
+    let mut layout = vec![Constraint::Length(1), Constraint::Length(3)];
+    if all_accounts {
+        // Header, one row per account, combined row, borders.
+        layout.push(Constraint::Length(app.portfolios.len() as u16 + 4));
+    }
+    layout.push(Constraint::Min(0));
+    if app.portfolio_remove_armed {
+        layout.push(Constraint::Length(2));
+    }
 
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(layout)
-            .split(inner);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(layout)
+        .split(inner);
+    let body = chunks[if all_accounts { 3 } else { 2 }];
 
-        let total_value = app.calculate_portfolio_value();
-        let total_cost = app.calculate_portfolio_cost();
-        let total_profit_loss = total_value - total_cost;
-        let profit_loss_percent = if total_cost > 0.0 {
-            (total_profit_loss / total_cost) * 100.0
-        } else {
-            0.0
-        };
+    draw_portfolio_account_selector(f, app, chunks[0], theme);
 
-        let pl_color = if total_profit_loss >= 0.0 {
-            theme.positive
-        } else {
-            theme.negative
-        };
+    let account = app.portfolio_account;
+    let total_value = app.calculate_portfolio_value(account);
+    let total_cost = app.calculate_portfolio_cost(account);
+    let total_profit_loss = app.calculate_portfolio_profit_loss(account);
+    let profit_loss_percent = if total_cost > 0.0 {
+        (total_profit_loss / total_cost) * 100.0
+    } else {
+        0.0
+    };
 
-        let summary_text = vec![Line::from(vec![
-            Span::styled("Total Value: ", theme.canvas()),
-            Span::styled(
-                format!("${:.2}", total_value),
-                theme.fg_accent(),
-            ),
-            Span::styled("  |  Cost Basis: ", theme.canvas()),
-            Span::styled(
-                format!("${:.2}", total_cost),
-                theme.fg_foreground(),
-            ),
-            Span::styled("  |  P/L: ", theme.canvas()),
-            Span::styled(
-                format!("${:.2} ({:.2}%)", total_profit_loss, profit_loss_percent),
-                theme.fg_color(pl_color),
+    let pl_color = if total_profit_loss >= 0.0 {
+        theme.positive
+    } else {
+        theme.negative
+    };
+
+    let realized = app.calculate_portfolio_realized(account);
+    let realized_color = if realized >= 0.0 {
+        theme.positive
+    } else {
+        theme.negative
+    };
+
+    let day_change = app.calculate_portfolio_day_change(account);
+    let day_base: f64 = app
+        .portfolio
+        .iter()
+        .filter(|item| item.day_change().is_some())
+        .filter_map(|item| {
+            let rate = app.fx_rate(item.currency.as_deref())?;
+            item.previous_close.map(|p| p * item.shares() * rate)
+        })
+        .sum();
+    let day_percent = if day_base.abs() > f64::EPSILON {
+        (day_change / day_base) * 100.0
+    } else {
+        0.0
+    };
+    let day_color = if day_change >= 0.0 {
+        theme.positive
+    } else {
+        theme.negative
+    };
+
+    // Totals are in the base currency; rows stay in each holding's quote currency.
+    let base = app.base_currency();
+    let mut summary_spans = vec![
+        Span::styled("Total Value: ", theme.canvas()),
+        Span::styled(format_money(total_value, &base), theme.fg_accent()),
+        Span::styled("  |  Cost Basis: ", theme.canvas()),
+        Span::styled(format_money(total_cost, &base), theme.fg_foreground()),
+        Span::styled("  |  P/L: ", theme.canvas()),
+        Span::styled(
+            format!(
+                "{} ({:.2}%)",
+                format_money(total_profit_loss, &base),
+                profit_loss_percent
             ),
-        ])];
+            theme.fg_color(pl_color),
+        ),
+        Span::styled("  |  Realized: ", theme.canvas()),
+        Span::styled(format_money(realized, &base), theme.fg_color(realized_color)),
+        Span::styled("  |  Day: ", theme.canvas()),
+        Span::styled(
+            format!("{} ({:.2}%)", format_money(day_change, &base), day_percent),
+            theme.fg_color(day_color),
+        ),
+    ];
+    let fx_pending = app.portfolio_fx_pending(account);
+    if fx_pending > 0 {
+        summary_spans.push(Span::styled(
+            format!("  |  {fx_pending} awaiting FX rate"),
+            theme.fg_muted(),
+        ));
+    }
+    let summary_text = vec![Line::from(summary_spans)];
+
+    let summary = Paragraph::new(summary_text)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Summary")
+                .style(theme.canvas())
+                .border_style(border_st),
+        );
+
+    f.render_widget(summary, chunks[1]);
+
+    if all_accounts {
+        draw_portfolio_accounts(f, app, chunks[2], theme);
+    }
 
-        let summary = Paragraph::new(summary_text)
+    if app.portfolio.is_empty() {
+        let no_data_text = Line::from(vec![Span::styled(
+            if all_accounts {
+                "No holdings in any account. Pick an account with [ / ] to add trades."
+            } else {
+                "This account is empty. Press 'a' to add the active symbol (set it on Stock View first) or 'i' to import a CSV."
+            },
+            theme.fg_border(),
+        )]);
+        let paragraph = Paragraph::new(no_data_text)
+            .wrap(Wrap { trim: true })
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title("Summary")
+                    .title("Holdings")
                     .style(theme.canvas())
                     .border_style(border_st),
             );
-
-        f.render_widget(summary, chunks[0]);
-
-        let table_chunk = chunks[1];
+        f.render_widget(paragraph, body);
+    } else {
+        let filtered_idx = app.portfolio_filter_indices();
+        let lots_item = app
+            .portfolio_lots_open
+            .then(|| app.portfolio_state.selected())
+            .flatten()
+            .and_then(|i| filtered_idx.get(i))
+            .map(|&i| app.portfolio[i].clone());
+        let (table_chunk, lots_chunk) = match &lots_item {
+            Some(item) => {
+                let lots = item.position(method).open_lots.len() as u16;
+                let split = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Min(5), Constraint::Length((lots + 3).clamp(4, 12))])
+                    .split(body);
+                (split[0], Some(split[1]))
+            }
+            None => (body, None),
+        };
 
         let selected_style = Style::default()
             .bg(theme.selection)
             .fg(theme.foreground)
             .add_modifier(Modifier::BOLD);
 
-        let header_cells = ["Symbol", "Shares", "Avg Price", "Current", "Value", "P/L", "P/L %"]
-            .iter()
-            .map(|h| Cell::from(*h).style(theme.fg_foreground()));
+        let header_cells = all_accounts
+            .then_some("Account")
+            .into_iter()
+            .chain(["Symbol", "Shares", "Avg Cost", "Current", "Value", "Day P/L", "P/L", "P/L %", "Realized"])
+            .map(|h| Cell::from(h).style(theme.fg_foreground()));
 
         let header = Row::new(header_cells)
             .style(theme.canvas().add_modifier(Modifier::BOLD))
@@ -214,7 +395,6 @@ pub fn draw_portfolio(f: &mut Frame, app: &mut App, area: Rect, theme: ResolvedT
 
         let holdings_title = format!("Holdings{}", filter_title_suffix(&app.filter_query));
 
-        let filtered_idx = app.portfolio_filter_indices();
         if filtered_idx.is_empty() {
             let empty = Line::from(vec![Span::styled(
                 "No symbols match filter — press Esc to clear.",
@@ -231,37 +411,64 @@ pub fn draw_portfolio(f: &mut Frame, app: &mut App, area: Rect, theme: ResolvedT
         } else {
             let rows = filtered_idx.iter().map(|&idx| {
                 let item = &app.portfolio[idx];
+                let pos = item.position(method);
+                let shares = pos.shares();
+                let cost = pos.cost_basis();
                 let current_price = item.current_price.unwrap_or(0.0);
-                let market_value = current_price * item.shares;
-                let profit_loss = market_value - (item.purchase_price * item.shares);
-                let pl_percent = if item.purchase_price > 0.0 {
-                    (profit_loss / (item.purchase_price * item.shares)) * 100.0
+                let market_value = current_price * shares;
+                let profit_loss = market_value - cost;
+                let pl_percent = if cost > 0.0 {
+                    (profit_loss / cost) * 100.0
                 } else {
                     0.0
                 };
+                let realized_color = if pos.realized >= 0.0 {
+                    theme.positive
+                } else {
+                    theme.negative
+                };
 
                 let pl_color = if profit_loss >= 0.0 {
                     theme.positive
                 } else {
                     theme.negative
                 };
+                let ccy = item.currency.as_deref().unwrap_or(&base);
+                let (day_s, day_color) = match item.day_change() {
+                    Some(d) if d >= 0.0 => (format_money(d, ccy), theme.positive),
+                    Some(d) => (format_money(d, ccy), theme.negative),
+                    None => ("—".to_string(), theme.muted),
+                };
 
+                let account_cell = all_accounts.then(|| {
+                    let name = app
+                        .portfolio_row_accounts
+                        .get(idx)
+                        .and_then(|&a| app.portfolios.get(a))
+                        .map_or("", |p| p.name.as_str());
+                    Cell::from(name.to_string()).style(theme.fg_muted())
+                });
                 let cells = [
                     Cell::from(item.symbol.clone()),
-                    Cell::from(format!("{:.2}", item.shares)),
-                    Cell::from(format!("${:.2}", item.purchase_price)),
-                    Cell::from(format!("${:.2}", current_price)),
-                    Cell::from(format!("${:.2}", market_value)),
-                    Cell::from(format!("${:.2}", profit_loss)).style(theme.fg_color(pl_color)),
+                    Cell::from(format!("{:.2}", shares)),
+                    Cell::from(format_money(pos.average_cost().unwrap_or(0.0), ccy)),
+                    Cell::from(format_money(current_price, ccy)),
+                    Cell::from(format_money(market_value, ccy)),
+                    Cell::from(day_s).style(theme.fg_color(day_color)),
+                    Cell::from(format_money(profit_loss, ccy)).style(theme.fg_color(pl_color)),
                     Cell::from(format!("{:.2}%", pl_percent)).style(theme.fg_color(pl_color)),
+                    Cell::from(format_money(pos.realized, ccy)).style(theme.fg_color(realized_color)),
                 ];
 
-                Row::new(cells).height(1).style(theme.canvas())
+                Row::new(account_cell.into_iter().chain(cells))
+                    .height(1)
+                    .style(theme.canvas())
             });
 
-            let table = Table::new(
-                rows,
-                [
+            let widths: Vec<Constraint> = all_accounts
+                .then_some(Constraint::Length(12))
+                .into_iter()
+                .chain([
                     Constraint::Length(8),
                     Constraint::Length(8),
                     Constraint::Length(10),
@@ -269,35 +476,232 @@ pub fn draw_portfolio(f: &mut Frame, app: &mut App, area: Rect, theme: ResolvedT
                     Constraint::Length(10),
                     Constraint::Length(10),
                     Constraint::Length(10),
-                ],
-            )
-            .header(header)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title(holdings_title)
-                    .style(theme.canvas())
-                    .border_style(border_st),
-            )
-            .highlight_style(selected_style)
-            .highlight_symbol("> ");
+                    Constraint::Length(10),
+                    Constraint::Length(10),
+                ])
+                .collect();
+            let table = Table::new(rows, widths)
+                .header(header)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(holdings_title)
+                        .style(theme.canvas())
+                        .border_style(border_st),
+                )
+                .highlight_style(selected_style)
+                .highlight_symbol("> ");
 
             f.render_stateful_widget(table, table_chunk, &mut app.portfolio_state);
         }
 
-        if app.portfolio_remove_armed {
-            let hint = Paragraph::new(Line::from(vec![Span::styled(
-                "Remove armed — confirm: d or y  |  cancel: Esc or n",
-                theme.fg_border(),
-            )]))
-            .style(theme.canvas());
-            f.render_widget(hint, chunks[2]);
+        if let (Some(item), Some(lots_chunk)) = (lots_item.as_ref(), lots_chunk) {
+            draw_portfolio_lots(f, item, method, &base, lots_chunk, theme);
         }
     }
 
+    if app.portfolio_remove_armed {
+        let hint = Paragraph::new(Line::from(vec![Span::styled(
+            "Remove armed — confirm: d or y  |  cancel: Esc or n",
+            theme.fg_border(),
+        )]))
+        .style(theme.canvas());
+        f.render_widget(hint, chunks[chunks.len() - 1]);
+    }
+
     if app.portfolio_dialog.is_some() {
         draw_portfolio_add_overlay(f, app, area, theme);
     }
+    if app.portfolio_csv.is_some() {
+        draw_portfolio_csv_overlay(f, app, area, theme);
+    }
+    if app.portfolio_account_input.is_some() {
+        draw_portfolio_account_input(f, app, area, theme);
+    }
+}
+
+/// One line of account names; the selected one (or "All accounts") is highlighted.
+fn draw_portfolio_account_selector(f: &mut Frame, app: &App, area: Rect, theme: ResolvedTheme) {
+    let mut spans = vec![Span::styled("Accounts: ", theme.fg_muted())];
+    let names = app
+        .portfolios
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (Some(i), p.name.as_str()))
+        .chain((app.portfolios.len() > 1).then_some((None, "All accounts")));
+    for (i, (account, name)) in names.enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(" · ", theme.fg_muted()));
+        }
+        let style = if account == app.portfolio_account {
+            theme.fg_accent().add_modifier(Modifier::BOLD)
+        } else {
+            theme.fg_foreground()
+        };
+        spans.push(Span::styled(name.to_string(), style));
+    }
+    spans.push(Span::styled("   [ / ] switch · n new", theme.fg_muted()));
+    f.render_widget(Paragraph::new(Line::from(spans)).style(theme.canvas()), area);
+}
+
+/// All accounts view: value, cost, P/L and realized per account plus the combined row.
+fn draw_portfolio_accounts(f: &mut Frame, app: &App, area: Rect, theme: ResolvedTheme) {
+    let border_st = Style::default().fg(theme.border).bg(theme.background);
+    let base = app.base_currency();
+    let signed = |v: f64| {
+        let color = if v >= 0.0 { theme.positive } else { theme.negative };
+        Cell::from(format_money(v, &base)).style(theme.fg_color(color))
+    };
+    let row = |name: String, account: Option<usize>| {
+        Row::new([
+            Cell::from(name),
+            Cell::from(format_money(app.calculate_portfolio_value(account), &base)),
+            Cell::from(format_money(app.calculate_portfolio_cost(account), &base)),
+            signed(app.calculate_portfolio_profit_loss(account)),
+            signed(app.calculate_portfolio_realized(account)),
+            signed(app.calculate_portfolio_day_change(account)),
+        ])
+        .style(theme.canvas())
+    };
+    let mut rows: Vec<Row> = app
+        .portfolios
+        .iter()
+        .enumerate()
+        .map(|(i, p)| row(p.name.clone(), Some(i)))
+        .collect();
+    rows.push(row("Combined".to_string(), None).style(theme.canvas().add_modifier(Modifier::BOLD)));
+    let header = Row::new(
+        ["Account", "Value", "Cost Basis", "P/L", "Realized", "Day P/L"]
+            .iter()
+            .map(|h| Cell::from(*h).style(theme.fg_foreground())),
+    )
+    .style(theme.canvas().add_modifier(Modifier::BOLD));
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(16),
+            Constraint::Length(14),
+            Constraint::Length(14),
+            Constraint::Length(14),
+            Constraint::Length(14),
+            Constraint::Length(14),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Accounts")
+            .style(theme.canvas())
+            .border_style(border_st),
+    );
+    f.render_widget(table, area);
+}
+
+fn draw_portfolio_account_input(f: &mut Frame, app: &App, area: Rect, theme: ResolvedTheme) {
+    let Some(input) = app.portfolio_account_input.as_ref() else {
+        return;
+    };
+    let popup = centered_rect(area, 40, 20);
+    f.render_widget(Clear, popup);
+    let mut lines = vec![
+        Line::from(vec![Span::styled("Enter create · Esc cancel", theme.canvas())]),
+        Line::from(vec![
+            Span::styled("Name: ", theme.fg_accent()),
+            Span::styled(input.as_str(), theme.fg_foreground()),
+        ]),
+    ];
+    if let Some(ref err) = app.portfolio_account_input_error {
+        lines.push(Line::from(vec![Span::styled(err.as_str(), theme.error_text())]));
+    }
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("New account")
+        .style(theme.canvas())
+        .border_style(Style::default().fg(theme.border).bg(theme.background));
+    f.render_widget(Paragraph::new(lines).block(block), popup);
+}
+
+/// Open lots of one holding under `method`, oldest first.
+fn draw_portfolio_lots(
+    f: &mut Frame,
+    item: &PortfolioItem,
+    method: crate::models::portfolio::CostBasisMethod,
+    base_currency: &str,
+    area: Rect,
+    theme: ResolvedTheme,
+) {
+    let border_st = Style::default().fg(theme.border).bg(theme.background);
+    let pos = item.position(method);
+    let ccy = item.currency.as_deref().unwrap_or(base_currency);
+    let title = format!(
+        "Lots — {} · {} · {} trades · realized {}",
+        item.symbol,
+        method.label(),
+        item.transactions.len(),
+        format_money(pos.realized, ccy)
+    );
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .style(theme.canvas())
+        .border_style(border_st);
+    if pos.open_lots.is_empty() {
+        let p = Paragraph::new(Line::from(vec![Span::styled(
+            "No open lots — position fully sold.",
+            theme.fg_muted(),
+        )]))
+        .block(block);
+        f.render_widget(p, area);
+        return;
+    }
+    let header = Row::new(
+        ["Date", "Shares", "Cost/Sh", "Cost", "Value", "P/L"]
+            .iter()
+            .map(|h| Cell::from(*h).style(theme.fg_foreground())),
+    )
+    .style(theme.canvas().add_modifier(Modifier::BOLD));
+    let rows = pos.open_lots.iter().map(|lot| {
+        let date = lot
+            .date
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| "—".to_string());
+        let (value, pl) = match item.current_price {
+            Some(p) => {
+                let value = p * lot.quantity;
+                (format_money(value, ccy), Some(value - lot.cost()))
+            }
+            None => ("—".to_string(), None),
+        };
+        let (pl_s, pl_color) = match pl {
+            Some(v) if v >= 0.0 => (format_money(v, ccy), theme.positive),
+            Some(v) => (format_money(v, ccy), theme.negative),
+            None => ("—".to_string(), theme.muted),
+        };
+        Row::new([
+            Cell::from(date),
+            Cell::from(format!("{:.2}", lot.quantity)),
+            Cell::from(format_money(lot.unit_cost, ccy)),
+            Cell::from(format_money(lot.cost(), ccy)),
+            Cell::from(value),
+            Cell::from(pl_s).style(theme.fg_color(pl_color)),
+        ])
+        .style(theme.canvas())
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(11),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(12),
+        ],
+    )
+    .header(header)
+    .block(block);
+    f.render_widget(table, area);
 }
 
 fn draw_portfolio_add_overlay(f: &mut Frame, app: &App, area: Rect, theme: ResolvedTheme) {
@@ -307,40 +711,58 @@ fn draw_portfolio_add_overlay(f: &mut Frame, app: &App, area: Rect, theme: Resol
 
     f.render_widget(Clear, area);
 
-    let popup = centered_rect(area, 55, 40);
+    let popup = centered_rect(area, 55, 50);
     let border_st = Style::default().fg(theme.border).bg(theme.background);
-    let sym_label = normalize_symbol(&app.symbol).unwrap_or_default();
-
-    let shares_style = if dialog.focused == PortfolioAddField::Shares {
-        theme.fg_accent()
-    } else {
-        theme.fg_foreground()
+    let sym_label = dialog
+        .symbol
+        .clone()
+        .or_else(|| normalize_symbol(&app.symbol))
+        .unwrap_or_default();
+    let field_style = |field: PortfolioAddField| {
+        if dialog.focused == field {
+            theme.fg_accent()
+        } else {
+            theme.fg_foreground()
+        }
     };
-    let price_style = if dialog.focused == PortfolioAddField::Price {
-        theme.fg_accent()
-    } else {
-        theme.fg_foreground()
+    let held = match dialog.side {
+        TradeSide::Sell => app
+            .portfolio
+            .iter()
+            .find(|i| normalize_symbol(&i.symbol).as_deref() == Some(sym_label.as_str()))
+            .map(|i| format!("  ({:.2} held)", i.shares()))
+            .unwrap_or_default(),
+        TradeSide::Buy => String::new(),
     };
 
     let mut lines: Vec<Line> = vec![
         Line::from(vec![Span::styled(
-            "Add holding — Esc cancel · Tab / Shift+Tab or ; cycle field · Enter on Price saves",
+            "Record trade — Esc cancel · Tab / Shift+Tab or ; cycle field · Enter on Date saves",
             theme.canvas(),
         )]),
         Line::from(vec![
-            Span::styled("Symbol: ", theme.canvas()),
+            Span::styled(format!("{}: ", dialog.side.label()), theme.canvas()),
             Span::styled(sym_label, theme.fg_accent().add_modifier(Modifier::BOLD)),
+            Span::styled(held, theme.fg_muted()),
         ]),
         Line::from(vec![
-            Span::styled("Shares:  ", shares_style),
+            Span::styled("Shares:  ", field_style(PortfolioAddField::Shares)),
             Span::styled(dialog.shares_buffer.as_str(), theme.fg_foreground()),
         ]),
         Line::from(vec![
-            Span::styled("Price:   ", price_style),
+            Span::styled("Price:   ", field_style(PortfolioAddField::Price)),
             Span::styled(dialog.price_buffer.as_str(), theme.fg_foreground()),
         ]),
+        Line::from(vec![
+            Span::styled("Fees:    ", field_style(PortfolioAddField::Fees)),
+            Span::styled(dialog.fees_buffer.as_str(), theme.fg_foreground()),
+        ]),
+        Line::from(vec![
+            Span::styled("Date:    ", field_style(PortfolioAddField::Date)),
+            Span::styled(dialog.date_buffer.as_str(), theme.fg_foreground()),
+        ]),
         Line::from(vec![Span::styled(
-            "Enter on Shares → Price | Enter on Price → save",
+            "Fees and Date may be blank · Enter advances a field",
             theme.fg_muted(),
         )]),
     ];
@@ -354,14 +776,260 @@ fn draw_portfolio_add_overlay(f: &mut Frame, app: &App, area: Rect, theme: Resol
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .title("Add to portfolio")
+        .title(match dialog.side {
+            TradeSide::Buy => "Buy",
+            TradeSide::Sell => "Sell",
+        })
         .style(theme.canvas())
         .border_style(border_st);
     let p = Paragraph::new(lines).block(block);
     f.render_widget(p, popup);
 }
 
-/// Commits the portfolio add dialog when both fields parse.
+fn draw_portfolio_csv_overlay(f: &mut Frame, app: &App, area: Rect, theme: ResolvedTheme) {
+    let Some(dialog) = app.portfolio_csv.as_ref() else {
+        return;
+    };
+    f.render_widget(Clear, area);
+    let border_st = Style::default().fg(theme.border).bg(theme.background);
+
+    let Some(preview) = dialog.preview.as_ref() else {
+        let popup = centered_rect(area, 60, 40);
+        let (title, verb) = match dialog.mode {
+            PortfolioCsvMode::Import => ("Import CSV", "preview"),
+            PortfolioCsvMode::Export => ("Export CSV", "write"),
+        };
+        let mut lines = vec![
+            Line::from(vec![Span::styled(
+                format!("Enter {verb} · Esc cancel · Backspace edit"),
+                theme.canvas(),
+            )]),
+            Line::from(vec![
+                Span::styled("File:    ", theme.fg_accent()),
+                Span::styled(dialog.path_buffer.as_str(), theme.fg_foreground()),
+            ]),
+        ];
+        if dialog.mode == PortfolioCsvMode::Import {
+            lines.push(Line::from(vec![
+                Span::styled("Preset:  ", theme.fg_foreground()),
+                Span::styled(dialog.preset.label(), theme.fg_accent().add_modifier(Modifier::BOLD)),
+                Span::styled("  (Tab cycles)", theme.fg_muted()),
+            ]));
+            lines.push(Line::from(vec![Span::styled(
+                "auto · stockterm · fidelity · schwab · ibkr · robinhood",
+                theme.fg_muted(),
+            )]));
+        } else {
+            lines.push(Line::from(vec![Span::styled(
+                format!(
+                    "{} trades · columns symbol, side, date, quantity, price, fees",
+                    app.portfolio.iter().map(|i| i.transactions.len()).sum::<usize>()
+                ),
+                theme.fg_muted(),
+            )]));
+        }
+        if let Some(ref err) = dialog.inline_error {
+            lines.push(Line::from(vec![Span::styled(err.as_str(), theme.error_text())]));
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .style(theme.canvas())
+            .border_style(border_st);
+        f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }).block(block), popup);
+        return;
+    };
+
+    let popup = centered_rect(area, 80, 70);
+    let mut rows: Vec<(u64, Line)> = preview
+        .trades
+        .iter()
+        .map(|t| {
+            let tx = &t.transaction;
+            let date = tx
+                .date
+                .map(|d| d.format("%Y-%m-%d").to_string())
+                .unwrap_or_else(|| "—".to_string());
+            let text = format!(
+                "{:>5}  {:<8} {:<4}  {:<10}  {:>12.4}  {:>12.4}  {:>8.2}",
+                t.line,
+                t.symbol,
+                tx.side.label(),
+                date,
+                tx.quantity,
+                tx.price,
+                tx.fees
+            );
+            (t.line, Line::from(vec![Span::styled(text, theme.fg_foreground())]))
+        })
+        .collect();
+    rows.extend(preview.errors.iter().map(|e| {
+        let text = format!("{:>5}  {}", e.line, e.message);
+        (e.line, Line::from(vec![Span::styled(text, theme.error_text())]))
+    }));
+    rows.sort_by_key(|(line, _)| *line);
+
+    let mut lines = vec![
+        Line::from(vec![Span::styled(
+            format!(
+                "Enter merges {} trades · Esc back · ↑/↓ scroll",
+                preview.trades.len()
+            ),
+            theme.canvas(),
+        )]),
+        Line::from(vec![Span::styled(
+            format!(
+                "{:>5}  {:<8} {:<4}  {:<10}  {:>12}  {:>12}  {:>8}",
+                "Line", "Symbol", "Side", "Date", "Quantity", "Price", "Fees"
+            ),
+            theme.fg_foreground().add_modifier(Modifier::BOLD),
+        )]),
+    ];
+    lines.extend(rows.into_iter().skip(dialog.preview_scroll).map(|(_, l)| l));
+    if let Some(ref err) = dialog.inline_error {
+        lines.insert(1, Line::from(vec![Span::styled(err.as_str(), theme.error_text())]));
+    }
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            "Import preview — {} · {} trades · {} bad rows",
+            preview.preset.label(),
+            preview.trades.len(),
+            preview.errors.len()
+        ))
+        .style(theme.canvas())
+        .border_style(border_st);
+    f.render_widget(Paragraph::new(lines).block(block), popup);
+}
+
+/// `~/` → home directory; blank → `None`.
+pub(crate) fn expand_csv_path(input: &str) -> Option<PathBuf> {
+    let t = input.trim();
+    if t.is_empty() {
+        return None;
+    }
+    match t.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().map(|h| h.join(rest)),
+        None => Some(PathBuf::from(t)),
+    }
+}
+
+/// Enter in the CSV dialog: export writes the file; import parses into a preview, then a second
+/// Enter merges the good rows through [`App::add_trades_to_portfolio`].
+pub(crate) fn submit_portfolio_csv_dialog(app: &mut App) {
+    let Some(dlg) = app.portfolio_csv.as_mut() else {
+        return;
+    };
+    dlg.inline_error = None;
+    if let Some(preview) = dlg.preview.as_ref() {
+        if preview.trades.is_empty() {
+            dlg.inline_error = Some("No valid rows to import".into());
+            return;
+        }
+        let (count, skipped) = (preview.trades.len(), preview.errors.len());
+        let trades: Vec<(String, Transaction)> = preview
+            .trades
+            .iter()
+            .map(|t| (t.symbol.clone(), t.transaction.clone()))
+            .collect();
+        if app.add_trades_to_portfolio(trades) {
+            app.portfolio_csv = None;
+            app.set_portfolio_csv_flash(if skipped == 0 {
+                format!("Imported {count} trades")
+            } else {
+                format!("Imported {count} trades ({skipped} bad rows skipped)")
+            });
+            app.request_immediate_stock_poll();
+        }
+        return;
+    }
+    let Some(path) = expand_csv_path(&dlg.path_buffer) else {
+        dlg.inline_error = Some("File path required".into());
+        return;
+    };
+    match dlg.mode {
+        PortfolioCsvMode::Import => {
+            let parsed = std::fs::read_to_string(&path)
+                .map_err(|e| format!("Could not read {}: {e}", path.display()))
+                .and_then(|text| parse_portfolio_csv(&text, dlg.preset));
+            match parsed {
+                Ok(import) => {
+                    dlg.preview = Some(import);
+                    dlg.preview_scroll = 0;
+                }
+                Err(e) => dlg.inline_error = Some(e),
+            }
+        }
+        PortfolioCsvMode::Export => {
+            let written = std::fs::File::create(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|file| write_portfolio_csv(&app.portfolio, file).map_err(|e| e.to_string()));
+            match written {
+                Ok(()) => {
+                    let trades: usize = app.portfolio.iter().map(|i| i.transactions.len()).sum();
+                    app.portfolio_csv = None;
+                    app.set_portfolio_csv_flash(format!("Exported {trades} trades to {}", path.display()));
+                }
+                Err(e) => {
+                    if let Some(d) = app.portfolio_csv.as_mut() {
+                        d.inline_error = Some(format!("Could not write {}: {e}", path.display()));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Keys while the CSV dialog is open (routed before global Tab / quit so paths can be typed).
+pub(crate) fn handle_portfolio_csv_keys(app: &mut App, key: KeyEvent) {
+    use Action::*;
+    let Some(dlg) = app.portfolio_csv.as_mut() else {
+        return;
+    };
+    match app.resolved_keymap.action(BindingLayer::PortfolioCsvDialog, &key) {
+        Some(PortfolioCsvEsc) => {
+            if dlg.preview.take().is_none() {
+                app.portfolio_csv = None;
+            } else {
+                dlg.inline_error = None;
+            }
+        }
+        Some(PortfolioCsvEnter) => submit_portfolio_csv_dialog(app),
+        Some(PortfolioCsvNextPreset) => {
+            if dlg.mode == PortfolioCsvMode::Import && dlg.preview.is_none() {
+                dlg.preset = dlg.preset.next();
+                dlg.inline_error = None;
+            }
+        }
+        Some(PortfolioCsvScrollDown) => {
+            if let Some(p) = dlg.preview.as_ref() {
+                let rows = p.trades.len() + p.errors.len();
+                dlg.preview_scroll = (dlg.preview_scroll + 1).min(rows.saturating_sub(1));
+            }
+        }
+        Some(PortfolioCsvScrollUp) => dlg.preview_scroll = dlg.preview_scroll.saturating_sub(1),
+        Some(PortfolioCsvBackspace) => {
+            if dlg.preview.is_none() {
+                dlg.inline_error = None;
+                dlg.path_buffer.pop();
+            }
+        }
+        _ => {
+            let KeyCode::Char(c) = key.code else {
+                return;
+            };
+            if !letter_key_plain(key.modifiers) || dlg.preview.is_some() {
+                return;
+            }
+            if dlg.path_buffer.len() < MAX_CSV_PATH_LEN {
+                dlg.inline_error = None;
+                dlg.path_buffer.push(c);
+            }
+        }
+    }
+}
+
+/// Commits the trade dialog when every field parses (sells may not exceed the open quantity).
 ///
 /// # `add_to_portfolio` contract (Issue #83 / SPEC §36.3.3)
 ///
@@ -374,30 +1042,44 @@ pub(crate) fn try_commit_portfolio_dialog(app: &mut App) {
     let Some(ref dlg) = app.portfolio_dialog else {
         return;
     };
-    let shares_r = parse_holding_decimal(&dlg.shares_buffer);
-    let price_r = parse_holding_decimal(&dlg.price_buffer);
-
-    match (shares_r, price_r) {
-        (Ok(shares), Ok(price)) => {
-            if let Err(e) = validate_holding_limits(shares, price) {
-                if let Some(d) = app.portfolio_dialog.as_mut() {
-                    d.inline_error = Some(e.to_string());
-                }
-                return;
+    let parsed = parse_holding_decimal(&dlg.shares_buffer).and_then(|shares| {
+        let price = parse_holding_decimal(&dlg.price_buffer)?;
+        validate_holding_limits(shares, price)?;
+        Ok((shares, price, parse_fees(&dlg.fees_buffer)?, parse_trade_date(&dlg.date_buffer)?))
+    });
+    let (shares, price, fees, date) = match parsed {
+        Ok(v) => v,
+        Err(e) => {
+            if let Some(d) = app.portfolio_dialog.as_mut() {
+                d.inline_error = Some(e.to_string());
             }
-            if app.add_to_portfolio(shares, price) {
-                app.portfolio_dialog = None;
-                app.request_immediate_stock_poll();
-            } else if app.error_message().is_none() {
+            return;
+        }
+    };
+    let symbol = dlg.symbol.clone().unwrap_or_else(|| app.symbol.clone());
+    let tx = match dlg.side {
+        TradeSide::Buy => Transaction::buy(date, shares, price, fees),
+        TradeSide::Sell => {
+            let held = app
+                .portfolio
+                .iter()
+                .find(|i| normalize_symbol(&i.symbol) == normalize_symbol(&symbol))
+                .map_or(0.0, PortfolioItem::shares);
+            if shares > held + f64::EPSILON {
                 if let Some(d) = app.portfolio_dialog.as_mut() {
-                    d.inline_error = Some(PORTFOLIO_ADD_INVALID_SYMBOL_INLINE.into());
+                    d.inline_error = Some(format!("Only {held:.2} shares are held"));
                 }
+                return;
             }
+            Transaction::sell(date, shares, price, fees)
         }
-        (Err(e), _) | (_, Err(e)) => {
-            if let Some(d) = app.portfolio_dialog.as_mut() {
-                d.inline_error = Some(e.to_string());
-            }
+    };
+    if app.add_to_portfolio(&symbol, tx) {
+        app.portfolio_dialog = None;
+        app.request_immediate_stock_poll();
+    } else if app.error_message().is_none() {
+        if let Some(d) = app.portfolio_dialog.as_mut() {
+            d.inline_error = Some(PORTFOLIO_ADD_INVALID_SYMBOL_INLINE.into());
         }
     }
 }
@@ -419,11 +1101,7 @@ fn handle_portfolio_dialog_keys(app: &mut App, key: KeyEvent) {
             Action::PortfolioDialogBackspace if key.modifiers == KeyModifiers::NONE => {
                 if let Some(d) = app.portfolio_dialog.as_mut() {
                     d.inline_error = None;
-                    let buf = match d.focused {
-                        PortfolioAddField::Shares => &mut d.shares_buffer,
-                        PortfolioAddField::Price => &mut d.price_buffer,
-                    };
-                    buf.pop();
+                    focused_dialog_buffer(d).pop();
                 }
                 return;
             }
@@ -432,7 +1110,9 @@ fn handle_portfolio_dialog_keys(app: &mut App, key: KeyEvent) {
                     d.inline_error = None;
                     match d.focused {
                         PortfolioAddField::Shares => d.focused = PortfolioAddField::Price,
-                        PortfolioAddField::Price => try_commit_portfolio_dialog(app),
+                        PortfolioAddField::Price => d.focused = PortfolioAddField::Fees,
+                        PortfolioAddField::Fees => d.focused = PortfolioAddField::Date,
+                        PortfolioAddField::Date => try_commit_portfolio_dialog(app),
                     }
                 }
                 return;
@@ -446,11 +1126,13 @@ fn handle_portfolio_dialog_keys(app: &mut App, key: KeyEvent) {
                 };
                 if let Some(d) = app.portfolio_dialog.as_mut() {
                     d.inline_error = None;
-                    let buf = match d.focused {
-                        PortfolioAddField::Shares => &mut d.shares_buffer,
-                        PortfolioAddField::Price => &mut d.price_buffer,
+                    let date = d.focused == PortfolioAddField::Date;
+                    let buf = focused_dialog_buffer(d);
+                    let _ = if date {
+                        append_date_char(buf, c)
+                    } else {
+                        append_numeric_char(buf, c)
                     };
-                    let _ = append_numeric_char(buf, c);
                 }
                 return;
             }
@@ -502,6 +1184,67 @@ fn handle_portfolio_remove_armed_keys(app: &mut App, key: KeyEvent) {
     }
 }
 
+/// Keys while the new-account prompt is open (routed before global quit so names may contain `q`).
+pub(crate) fn handle_portfolio_account_input(app: &mut App, key: KeyEvent) {
+    use Action::*;
+    match app.resolved_keymap.action(BindingLayer::PortfolioAccountInput, &key) {
+        Some(PortfolioAccountInputEsc) => {
+            app.portfolio_account_input = None;
+            app.portfolio_account_input_error = None;
+        }
+        Some(PortfolioAccountInputEnter) => {
+            let name = app.portfolio_account_input.clone().unwrap_or_default();
+            match app.portfolio_create_account(&name) {
+                Ok(()) => {
+                    app.portfolio_account_input = None;
+                    app.portfolio_account_input_error = None;
+                }
+                Err(e) => app.portfolio_account_input_error = Some(e),
+            }
+        }
+        Some(PortfolioAccountInputBackspace) => {
+            if let Some(input) = app.portfolio_account_input.as_mut() {
+                input.pop();
+            }
+            app.portfolio_account_input_error = None;
+        }
+        _ => {
+            let KeyCode::Char(c) = key.code else {
+                return;
+            };
+            if !letter_key_plain(key.modifiers) || c.is_control() {
+                return;
+            }
+            if let Some(input) = app.portfolio_account_input.as_mut() {
+                if input.chars().count() < MAX_ACCOUNT_NAME_LEN {
+                    input.push(c);
+                }
+            }
+            app.portfolio_account_input_error = None;
+        }
+    }
+}
+
+/// Surfaces [`PORTFOLIO_ALL_ACCOUNTS_READ_ONLY`] and returns `true` on the All accounts view.
+fn block_on_all_accounts(app: &mut App) -> bool {
+    if app.portfolio_account.is_some() {
+        return false;
+    }
+    app.surface_runtime_error(
+        Tab::Portfolio,
+        ErrorSourceDomain::Portfolio,
+        AppError::Internal(PORTFOLIO_ALL_ACCOUNTS_READ_ONLY.to_string()),
+        true,
+    );
+    true
+}
+
+fn selected_portfolio_item(app: &App) -> Option<&PortfolioItem> {
+    let selected = app.portfolio_state.selected()?;
+    let idx = *app.portfolio_filter_indices().get(selected)?;
+    app.portfolio.get(idx)
+}
+
 pub fn handle_portfolio_events(app: &mut App, key: KeyEvent) {
     if app.portfolio_dialog.is_some() {
         handle_portfolio_dialog_keys(app, key);
@@ -523,6 +1266,9 @@ pub fn handle_portfolio_events(app: &mut App, key: KeyEvent) {
                 app.filter_input_mode = true;
             }
             Action::PortfolioAdd if letter_key_plain(key.modifiers) => {
+                if block_on_all_accounts(app) {
+                    return;
+                }
                 if normalize_symbol(&app.symbol).is_none() {
                     app.surface_runtime_error(
                         Tab::Portfolio,
@@ -539,6 +1285,57 @@ pub fn handle_portfolio_events(app: &mut App, key: KeyEvent) {
                 app.portfolio_dialog = Some(crate::app::PortfolioAddDialog::default());
                 app.clear_active_runtime_unless_alerts_save();
             }
+            Action::PortfolioSell if letter_key_plain(key.modifiers) => {
+                if block_on_all_accounts(app) {
+                    return;
+                }
+                let Some(item) = selected_portfolio_item(app) else {
+                    return;
+                };
+                if item.shares() <= f64::EPSILON {
+                    return;
+                }
+                let symbol = item.symbol.clone();
+                app.portfolio_remove_armed = false;
+                app.portfolio_dialog = Some(crate::app::PortfolioAddDialog::sell(symbol));
+                app.clear_active_runtime_unless_alerts_save();
+            }
+            Action::PortfolioToggleLots if letter_key_plain(key.modifiers) => {
+                app.portfolio_lots_open = !app.portfolio_lots_open;
+                if app.portfolio_lots_open && app.portfolio_state.selected().is_none() {
+                    app.portfolio_state.select(Some(0));
+                }
+            }
+            Action::PortfolioCycleCostBasis if letter_key_plain(key.modifiers) => {
+                app.portfolio_cycle_cost_basis();
+            }
+            Action::PortfolioImportCsv if letter_key_plain(key.modifiers) => {
+                if block_on_all_accounts(app) {
+                    return;
+                }
+                app.portfolio_remove_armed = false;
+                app.portfolio_csv = Some(PortfolioCsvDialog::new(PortfolioCsvMode::Import));
+                app.clear_active_runtime_unless_alerts_save();
+            }
+            Action::PortfolioExportCsv if letter_key_plain(key.modifiers) => {
+                if app.portfolio.is_empty() {
+                    return;
+                }
+                app.portfolio_remove_armed = false;
+                app.portfolio_csv = Some(PortfolioCsvDialog::new(PortfolioCsvMode::Export));
+                app.clear_active_runtime_unless_alerts_save();
+            }
+            Action::PortfolioAccountNext if letter_key_plain(key.modifiers) => {
+                app.portfolio_switch_account(true);
+            }
+            Action::PortfolioAccountPrev if letter_key_plain(key.modifiers) => {
+                app.portfolio_switch_account(false);
+            }
+            Action::PortfolioAccountNew if letter_key_plain(key.modifiers) => {
+                app.portfolio_remove_armed = false;
+                app.portfolio_account_input = Some(String::new());
+                app.portfolio_account_input_error = None;
+            }
             Action::PortfolioRemoveArm if letter_key_plain(key.modifiers) => {
                 if app.portfolio.is_empty() {
                     return;
@@ -577,9 +1374,10 @@ pub fn handle_portfolio_events(app: &mut App, key: KeyEvent) {
 #[cfg(test)]
 mod tests {
     use super::{
-        parse_holding_decimal, validate_holding_limits, MAX_HOLDING_PRICE_PER_SHARE,
-        MAX_HOLDING_SHARES,
+        merge_trades, parse_fees, parse_holding_decimal, parse_trade_date, validate_holding_limits,
+        MergeTradesError,
     };
+    use crate::models::portfolio::{PortfolioItem, Transaction, MAX_HOLDING_PRICE_PER_SHARE, MAX_HOLDING_SHARES};
 
     #[test]
     fn parse_holding_decimal_accepts_positive() {
@@ -607,4 +1405,66 @@ mod tests {
         assert!(validate_holding_limits(MAX_HOLDING_SHARES * 2.0, 1.0).is_err());
         assert!(validate_holding_limits(1.0, MAX_HOLDING_PRICE_PER_SHARE * 2.0).is_err());
     }
+
+    #[test]
+    fn optional_fees_and_date_fields() {
+        assert_eq!(parse_fees(""), Ok(0.0));
+        assert_eq!(parse_fees("1.25"), Ok(1.25));
+        assert!(parse_fees("-1").is_err());
+        assert_eq!(parse_trade_date(" "), Ok(None));
+        assert_eq!(
+            parse_trade_date("2024-02-29").map(|d| d.map(|d| d.to_string())),
+            Ok(Some("2024-02-29".to_string()))
+        );
+        assert!(parse_trade_date("2024-02-30").is_err());
+    }
+
+    #[test]
+    fn csv_import_previews_rows_before_merging() {
+        use crate::app::{App, PortfolioCsvDialog, PortfolioCsvMode};
+        let path = std::env::temp_dir().join(format!("stockterm-import-{}.csv", std::process::id()));
+        std::fs::write(&path, "symbol,side,date,quantity,price,fees\nNVDA,buy,2024-05-01,3,900,0\nNVDA,hold,,1,1,0\n")
+            .unwrap();
+        let mut app = App::new();
+        let before = app.portfolio.len();
+        let mut dialog = PortfolioCsvDialog::new(PortfolioCsvMode::Import);
+        dialog.path_buffer = path.display().to_string();
+        app.portfolio_csv = Some(dialog);
+        super::submit_portfolio_csv_dialog(&mut app);
+        std::fs::remove_file(&path).ok();
+        let preview = app
+            .portfolio_csv
+            .as_ref()
+            .and_then(|d| d.preview.as_ref())
+            .expect("preview");
+        assert_eq!(preview.trades.len(), 1);
+        assert_eq!(preview.errors[0].line, 3);
+        assert_eq!(app.portfolio.len(), before, "nothing merges until the preview is confirmed");
+    }
+
+    #[test]
+    fn merge_trades_rejects_oversells_and_bad_symbols_without_merging() {
+        let mut holdings = vec![PortfolioItem::with_transactions(
+            "AAPL".into(),
+            vec![Transaction::buy(None, 2.0, 10.0, 0.0)],
+        )];
+        let sell = |n| ("aapl".to_string(), Transaction::sell(None, n, 12.0, 0.0));
+
+        let err = merge_trades(&mut holdings, [sell(1.0), sell(1.5)]).unwrap_err();
+        assert_eq!(
+            err,
+            MergeTradesError::Oversell {
+                symbol: "AAPL".into(),
+                held: 1.0
+            }
+        );
+        assert_eq!(
+            merge_trades(&mut holdings, [("  ".to_string(), Transaction::buy(None, 1.0, 1.0, 0.0))]),
+            Err(MergeTradesError::InvalidSymbol("  ".into()))
+        );
+        assert_eq!(holdings[0].transactions.len(), 1);
+
+        assert_eq!(merge_trades(&mut holdings, [sell(2.0)]), Ok(Some("AAPL".into())));
+        assert!(holdings[0].shares().abs() < 1e-9);
+    }
 }
@@ -0,0 +1,179 @@
+//! Live prices between REST polls (`stream_quotes`).
+//!
+//! [`App::run`] starts the provider's [`QuoteStream`](crate::api::quote_stream::QuoteStream),
+//! republishes the followed symbols every loop and applies [`FetchDone::QuoteTicks`] batches to
+//! [`App::watchlist_quotes`]; alerts on a ticked symbol are evaluated per batch instead of waiting
+//! for the next poll. The regular poll still refreshes everything else.
+
+use tokio::sync::watch;
+
+use crate::api::quote_stream::{quote_stream_for, run_quote_stream, QuoteStreamOptions, QuoteTick};
+use crate::app::{normalize_symbol, App, FetchDone};
+
+impl App {
+    /// Watchlist, active symbol and alert symbols, normalized and de-duplicated.
+    fn quote_stream_wanted_symbols(&self) -> Vec<String> {
+        let mut out: Vec<String> = Vec::new();
+        let alert_symbols = self.alerts.iter().map(|a| &a.symbol);
+        for sym in self.watchlist.iter().chain(std::iter::once(&self.symbol)).chain(alert_symbols) {
+            if let Some(s) = normalize_symbol(sym) {
+                if !out.contains(&s) {
+                    out.push(s);
+                }
+            }
+        }
+        out
+    }
+
+    /// Spawns the quote stream when configured; ticks come back through the fetch channel.
+    pub(crate) fn start_quote_stream(&mut self) {
+        let Some(tx) = self.fetch_done_tx.clone() else {
+            return;
+        };
+        let Some(stream) = quote_stream_for(&self.config) else {
+            return;
+        };
+        let (symbols_tx, symbols_rx) = watch::channel(self.quote_stream_wanted_symbols());
+        tokio::spawn(run_quote_stream(
+            stream,
+            symbols_rx,
+            QuoteStreamOptions::default(),
+            move |ticks| tx.send(FetchDone::QuoteTicks(ticks)).is_ok(),
+        ));
+        self.quote_stream_symbols = Some(symbols_tx);
+    }
+
+    /// Publishes the followed symbols when the watchlist, active symbol or alerts changed.
+    pub(crate) fn sync_quote_stream_symbols(&mut self) {
+        if self.quote_stream_symbols.is_none() {
+            return;
+        }
+        let wanted = self.quote_stream_wanted_symbols();
+        if let Some(tx) = self.quote_stream_symbols.as_ref() {
+            tx.send_if_modified(|current| {
+                if *current == wanted {
+                    return false;
+                }
+                *current = wanted;
+                true
+            });
+        }
+    }
+
+    /// Moves each symbol's latest bar to its newest tick, then re-evaluates alerts when a ticked
+    /// symbol has one. Symbols without a polled quote yet, and ticks older than that quote, are
+    /// ignored.
+    pub(crate) fn apply_quote_ticks(&mut self, ticks: Vec<QuoteTick>) {
+        let selected = self.selected_watchlist_symbol();
+        let mut active_changed = false;
+        let mut alert_changed = false;
+        for tick in ticks {
+            let Some(bar) = self
+                .watchlist_quotes
+                .get_mut(&tick.symbol)
+                .and_then(|resp| resp.latest_result_mut())
+            else {
+                continue;
+            };
+            if tick.time_ms < bar.t {
+                continue;
+            }
+            bar.c = tick.price;
+            bar.h = bar.h.max(tick.price);
+            bar.l = if bar.l > 0.0 { bar.l.min(tick.price) } else { tick.price };
+            bar.t = tick.time_ms;
+            active_changed |= tick.symbol == self.symbol;
+            alert_changed |= self
+                .alerts
+                .iter()
+                .any(|a| normalize_symbol(&a.symbol).as_deref() == Some(tick.symbol.as_str()));
+        }
+        self.keep_watchlist_cursor_on(selected);
+        if active_changed {
+            self.ticker_data = self.watchlist_quotes.get(&self.symbol).cloned();
+        }
+        if alert_changed {
+            self.check_alerts();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::alerts::{Alert, AlertCondition};
+    use crate::models::ticker::{TickerResponse, TickerResult};
+
+    fn quote(close: f64, t: u64) -> TickerResponse {
+        TickerResponse {
+            ticker: String::new(),
+            results: vec![TickerResult {
+                o: close,
+                h: close,
+                l: close,
+                c: close,
+                v: 0.0,
+                t,
+                prev_close: Some(close),
+                market_state: None,
+                ext_price: None,
+                week52_low: None,
+                week52_high: None,
+                market_cap: None,
+                currency: None,
+            }],
+            status: "OK".into(),
+            error: None,
+        }
+    }
+
+    fn tick(symbol: &str, price: f64, time_ms: u64) -> QuoteTick {
+        QuoteTick {
+            symbol: symbol.into(),
+            price,
+            volume: 1.0,
+            time_ms,
+        }
+    }
+
+    #[test]
+    fn ticks_move_latest_bar_and_skip_stale_or_unknown_symbols() {
+        let mut app = App::new();
+        app.watchlist = vec!["AAPL".into(), "MSFT".into()];
+        app.symbol = "AAPL".into();
+        app.alerts.clear();
+        app.watchlist_quotes.insert("AAPL".into(), quote(100.0, 1_000));
+        app.watchlist_quotes.insert("MSFT".into(), quote(300.0, 5_000));
+
+        app.apply_quote_ticks(vec![
+            tick("AAPL", 102.5, 2_000),
+            tick("AAPL", 99.0, 3_000),
+            tick("MSFT", 310.0, 4_000),
+            tick("TSLA", 1.0, 9_000),
+        ]);
+
+        let aapl = app.watchlist_quotes["AAPL"].latest_result().unwrap();
+        assert_eq!((aapl.c, aapl.h, aapl.l, aapl.t), (99.0, 102.5, 99.0, 3_000));
+        assert_eq!(aapl.prev_close, Some(100.0));
+        assert_eq!(app.watchlist_quotes["MSFT"].latest_result().unwrap().c, 300.0);
+        assert!(!app.watchlist_quotes.contains_key("TSLA"));
+        assert_eq!(app.ticker_data.as_ref().and_then(|r| r.latest_result()).map(|b| b.c), Some(99.0));
+    }
+
+    #[test]
+    fn stream_symbols_follow_watchlist_active_symbol_and_alerts() {
+        let mut app = App::new();
+        app.watchlist = vec!["aapl".into(), "MSFT".into()];
+        app.symbol = "TSLA".into();
+        app.alerts = vec![Alert::new("nvda".into(), AlertCondition::Above, 1.0)];
+        let (tx, mut rx) = watch::channel(Vec::new());
+        app.quote_stream_symbols = Some(tx);
+
+        app.sync_quote_stream_symbols();
+        assert!(rx.has_changed().unwrap());
+        assert_eq!(*rx.borrow_and_update(), ["AAPL", "MSFT", "TSLA", "NVDA"]);
+
+        app.sync_quote_stream_symbols();
+        assert!(!rx.has_changed().unwrap());
+    }
+}
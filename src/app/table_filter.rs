@@ -10,20 +10,32 @@ pub const MAX_FILTER_QUERY_LEN: usize = 64;
 /// expected ASCII; non-ASCII tickers may not match intuitively).
 pub(crate) fn filter_row_indices<'a>(
     len: usize,
+    symbol_at: impl FnMut(usize) -> &'a str,
+    query: &str,
+) -> Vec<usize> {
+    filter_ordered_indices((0..len).collect(), symbol_at, query)
+}
+
+/// Same matching as [`filter_row_indices`], over backing indices already in display `order`
+/// (e.g. a sorted watchlist); the result keeps that order.
+pub(crate) fn filter_ordered_indices<'a>(
+    order: Vec<usize>,
     mut symbol_at: impl FnMut(usize) -> &'a str,
     query: &str,
 ) -> Vec<usize> {
     if query.is_empty() {
-        return (0..len).collect();
+        return order;
     }
     let needle = query.to_ascii_lowercase();
-    (0..len)
+    order
+        .into_iter()
         .filter(|&i| symbol_at(i).to_ascii_lowercase().contains(needle.as_str()))
         .collect()
 }
 
 /// Returns backing indices `0..symbols.len()` whose symbol contains `query` (ASCII case-insensitive).
 /// Empty `query` yields all indices in order.
+#[cfg(test)]
 pub fn filter_symbol_indices(symbols: &[impl AsRef<str>], query: &str) -> Vec<usize> {
     filter_row_indices(symbols.len(), |i| symbols[i].as_ref(), query)
 }
@@ -86,6 +98,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn filter_ordered_indices_keeps_sorted_order() {
+        let s = ["AAPL", "MSFT", "AMZN"];
+        assert_eq!(filter_ordered_indices(vec![2, 1, 0], |i| s[i], "a"), vec![2, 0]);
+        assert_eq!(filter_ordered_indices(vec![2, 1, 0], |i| s[i], ""), vec![2, 1, 0]);
+    }
+
     #[test]
     fn filter_title_suffix_strips_control_chars() {
         let s = filter_title_suffix("A\u{7}B");
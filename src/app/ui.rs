@@ -1,13 +1,15 @@
+use crate::api::rate_limit::{queue_status, QueueStatus};
 use crate::app::alerts::draw_alerts;
 use crate::app::charts::draw_charts;
 use crate::app::layout::{centered_rect, shell_vertical_constraints};
-use crate::config::ResolvedLayout;
+use crate::config::{MarketProviderKind, ResolvedLayout};
 use crate::app::portfolio::draw_portfolio;
 use crate::app::styles::ResolvedTheme;
 use crate::app::table_filter::filter_title_suffix;
-use crate::app::{App, SettingsEdit, Tab};
-use crate::config::MarketProviderKind;
-use crate::models::ticker::{ticker_response_matches_symbol_for_session, TickerResponse};
+use crate::app::{App, SettingsEdit, Tab, WatchlistPromptKind};
+use crate::models::ticker::{ticker_response_matches_symbol_for_session, TickerResponse, TickerResult};
+use crate::models::currency::format_money;
+use crate::models::watchlist::WatchlistColumn;
 use ratatui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
@@ -215,6 +217,66 @@ fn draw_stock_view(f: &mut Frame, app: &mut App, area: Rect, rt: ResolvedTheme,
 
     draw_watchlist_table(f, app, chunks[0], rt);
     draw_stock_detail(f, app, chunks[1], rt);
+    if app.watchlist_prompt.is_some() {
+        draw_watchlist_prompt(f, app, area, rt);
+    }
+}
+
+/// `Watchlist: Tech (2/3)` — the position only appears once there is more than one list.
+fn watchlist_block_title(app: &App) -> String {
+    let n = app.config.watchlists.len();
+    if n > 1 {
+        format!("Watchlist: {} ({}/{n})", app.watchlist_name(), app.active_watchlist + 1)
+    } else {
+        format!("Watchlist: {}", app.watchlist_name())
+    }
+}
+
+fn draw_watchlist_prompt(f: &mut Frame, app: &App, area: Rect, rt: ResolvedTheme) {
+    let Some(prompt) = app.watchlist_prompt.as_ref() else {
+        return;
+    };
+    let popup = centered_rect(area, 44, 22);
+    f.render_widget(Clear, popup);
+    let (title, mut lines) = match prompt.kind {
+        WatchlistPromptKind::Delete => (
+            "Delete watchlist",
+            vec![
+                Line::from(vec![Span::styled("Enter delete · Esc cancel", rt.canvas())]),
+                Line::from(vec![Span::styled(
+                    format!(
+                        "Delete {:?} ({} symbols)?",
+                        app.watchlist_name(),
+                        app.watchlist.len()
+                    ),
+                    rt.fg_foreground(),
+                )]),
+            ],
+        ),
+        WatchlistPromptKind::Create | WatchlistPromptKind::Rename => (
+            if prompt.kind == WatchlistPromptKind::Create {
+                "New watchlist"
+            } else {
+                "Rename watchlist"
+            },
+            vec![
+                Line::from(vec![Span::styled("Enter save · Esc cancel", rt.canvas())]),
+                Line::from(vec![
+                    Span::styled("Name: ", rt.fg_accent()),
+                    Span::styled(prompt.buffer.as_str(), rt.fg_foreground()),
+                ]),
+            ],
+        ),
+    };
+    if let Some(ref err) = prompt.inline_error {
+        lines.push(Line::from(vec![Span::styled(err.as_str(), rt.error_text())]));
+    }
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .style(rt.canvas())
+        .border_style(Style::default().fg(rt.border).bg(rt.background));
+    f.render_widget(Paragraph::new(lines).block(block), popup);
 }
 
 fn draw_watchlist_table(f: &mut Frame, app: &mut App, area: Rect, rt: ResolvedTheme) {
@@ -241,7 +303,7 @@ fn draw_watchlist_table(f: &mut Frame, app: &mut App, area: Rect, rt: ResolvedTh
             ]),
         ];
         let block = Block::default()
-            .title("Watchlist")
+            .title(watchlist_block_title(app))
             .borders(Borders::ALL)
             .style(rt.canvas())
             .border_style(Style::default().fg(rt.border).bg(rt.background));
@@ -250,7 +312,8 @@ fn draw_watchlist_table(f: &mut Frame, app: &mut App, area: Rect, rt: ResolvedTh
     }
 
     let wl_title = format!(
-        "Watchlist (w add, x/D remove, j/k navigate){}",
+        "{} (w add, x/D remove, j/k navigate, [/] lists){}",
+        watchlist_block_title(app),
         filter_title_suffix(&app.filter_query)
     );
 
@@ -269,9 +332,16 @@ fn draw_watchlist_table(f: &mut Frame, app: &mut App, area: Rect, rt: ResolvedTh
         return;
     }
 
-    let header_cells = ["Symbol", "Last", "Change", "%Chg", "Volume"]
-        .iter()
-        .map(|h| Cell::from(*h).style(rt.fg_foreground()));
+    let columns = app.config.layout.watchlist_columns();
+    let header_cells = columns.iter().map(|&col| {
+        let label = match app.watchlist_sort {
+            Some(sort) if sort.column == col => {
+                format!("{} {}", col.label(), if sort.descending { "▼" } else { "▲" })
+            }
+            _ => col.label().to_string(),
+        };
+        Cell::from(label).style(rt.fg_foreground())
+    });
 
     let header = Row::new(header_cells)
         .style(rt.canvas().add_modifier(Modifier::BOLD))
@@ -279,80 +349,124 @@ fn draw_watchlist_table(f: &mut Frame, app: &mut App, area: Rect, rt: ResolvedTh
 
     let rows = filtered_idx.iter().map(|&idx| {
         let sym = &app.watchlist[idx];
-        let row_style = rt.canvas();
-        let (last_s, chg_s, pct_s, vol_s, chg_color) =
-            match app.watchlist_quotes.get(sym).and_then(|r| r.latest_result()) {
-                Some(bar) => {
-                    let price_change = bar.c - bar.o;
-                    let pct = if bar.o.abs() > f64::EPSILON {
-                        (price_change / bar.o) * 100.0
-                    } else {
-                        0.0
-                    };
-                    let chg_color = if price_change >= 0.0 {
-                        rt.positive
-                    } else {
-                        rt.negative
-                    };
-                    (
-                        format!("${:.2}", bar.c),
-                        format!(
-                            "{}{:.2}",
-                            if price_change >= 0.0 { "+" } else { "" },
-                            price_change
-                        ),
-                        format!(
-                            "{}{:.2}%",
-                            if price_change >= 0.0 { "+" } else { "" },
-                            pct
-                        ),
-                        format!("{:.0}", bar.v),
-                        chg_color,
-                    )
-                }
-                _ => (
-                    "—".to_string(),
-                    "—".to_string(),
-                    "—".to_string(),
-                    "—".to_string(),
-                    rt.muted,
-                ),
-            };
-
-        let cells = [
-            Cell::from(sym.as_str()),
-            Cell::from(last_s),
-            Cell::from(chg_s).style(rt.fg_color(chg_color)),
-            Cell::from(pct_s).style(rt.fg_color(chg_color)),
-            Cell::from(vol_s),
-        ];
-        Row::new(cells).height(1).style(row_style)
+        let bar_opt = app.watchlist_quotes.get(sym).and_then(|r| r.latest_result());
+        let cells = columns
+            .iter()
+            .map(|&col| watchlist_cell(col, sym, bar_opt, rt));
+        Row::new(cells).height(1).style(rt.canvas())
     });
 
-    let table = Table::new(
-        rows,
-        [
-            Constraint::Min(6),
-            Constraint::Length(10),
-            Constraint::Length(10),
-            Constraint::Length(9),
-            Constraint::Min(8),
-        ],
-    )
-    .header(header)
-    .block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title(wl_title)
-            .style(rt.canvas())
-            .border_style(Style::default().fg(rt.border).bg(rt.background)),
-    )
-    .highlight_style(selected_style)
-    .highlight_symbol("> ");
+    let widths: Vec<Constraint> = columns.iter().map(|&c| watchlist_column_width(c)).collect();
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(wl_title)
+                .style(rt.canvas())
+                .border_style(Style::default().fg(rt.border).bg(rt.background)),
+        )
+        .highlight_style(selected_style)
+        .highlight_symbol("> ");
 
     f.render_stateful_widget(table, area, &mut app.watchlist_state);
 }
 
+fn watchlist_column_width(col: WatchlistColumn) -> Constraint {
+    match col {
+        WatchlistColumn::Symbol => Constraint::Min(6),
+        WatchlistColumn::Last | WatchlistColumn::Change => Constraint::Length(10),
+        WatchlistColumn::ChangePercent => Constraint::Length(9),
+        WatchlistColumn::Volume => Constraint::Min(8),
+        WatchlistColumn::DayRange | WatchlistColumn::Week52Range => Constraint::Length(17),
+        WatchlistColumn::MarketCap => Constraint::Length(10),
+        WatchlistColumn::PrevClose => Constraint::Length(12),
+        WatchlistColumn::ExtHours => Constraint::Length(20),
+    }
+}
+
+/// One watchlist cell; quote columns show `—` until a quote arrives.
+fn watchlist_cell<'a>(
+    col: WatchlistColumn,
+    sym: &'a str,
+    bar: Option<&TickerResult>,
+    rt: ResolvedTheme,
+) -> Cell<'a> {
+    if col == WatchlistColumn::Symbol {
+        return Cell::from(sym);
+    }
+    let Some(bar) = bar else {
+        let placeholder = if col == WatchlistColumn::ExtHours { "" } else { "—" };
+        return Cell::from(placeholder).style(rt.fg_color(rt.muted));
+    };
+    let change = bar.day_change();
+    let sign = if change >= 0.0 { "+" } else { "" };
+    let chg_color = if change >= 0.0 { rt.positive } else { rt.negative };
+    let range = |low: Option<f64>, high: Option<f64>| match (low, high) {
+        (Some(l), Some(h)) => format!("{l:.2}–{h:.2}"),
+        _ => "—".to_string(),
+    };
+    match col {
+        WatchlistColumn::Symbol => Cell::from(sym),
+        WatchlistColumn::Last => Cell::from(format_money(bar.c, bar.quote_currency())),
+        WatchlistColumn::Change => {
+            Cell::from(format!("{sign}{change:.2}")).style(rt.fg_color(chg_color))
+        }
+        WatchlistColumn::ChangePercent => Cell::from(format!(
+            "{sign}{:.2}%",
+            bar.day_change_percent()
+        ))
+        .style(rt.fg_color(chg_color)),
+        WatchlistColumn::Volume => Cell::from(format!("{:.0}", bar.v)),
+        WatchlistColumn::DayRange => Cell::from(range(Some(bar.l), Some(bar.h))),
+        WatchlistColumn::Week52Range => Cell::from(range(bar.week52_low, bar.week52_high)),
+        WatchlistColumn::MarketCap => {
+            Cell::from(bar.market_cap.map_or_else(
+                || "—".to_string(),
+                |cap| format_market_cap(cap, bar.quote_currency()),
+            ))
+        }
+        WatchlistColumn::PrevClose => Cell::from(
+            bar.prev_close
+                .map_or_else(|| "—".to_string(), |p| format_money(p, bar.quote_currency())),
+        ),
+        WatchlistColumn::ExtHours => match extended_hours_cell(bar) {
+            Some((text, up)) => {
+                Cell::from(text).style(rt.fg_color(if up { rt.positive } else { rt.negative }))
+            }
+            None => Cell::from(""),
+        },
+    }
+}
+
+/// `$3.04T`, `£512.30B`, `C$87.10M` (market cap is in major units even for `GBp` listings).
+fn format_market_cap(cap: f64, currency: &str) -> String {
+    const UNITS: [(f64, &str); 3] = [(1e12, "T"), (1e9, "B"), (1e6, "M")];
+    let iso = crate::models::currency::major_currency(currency).0;
+    for (scale, suffix) in UNITS {
+        if cap.abs() >= scale {
+            return format!("{}{suffix}", format_money(cap / scale, &iso));
+        }
+    }
+    format_money(cap, &iso)
+}
+
+/// `"POST 196.10 +0.31%"` and whether the move is non-negative, when an extended-hours trade is known.
+fn extended_hours_cell(bar: &TickerResult) -> Option<(String, bool)> {
+    let (state, price) = bar.extended_quote()?;
+    let (change, pct) = bar.extended_change()?;
+    Some((
+        format!(
+            "{} {:.2} {}{:.2}%",
+            state.label(),
+            price,
+            if change >= 0.0 { "+" } else { "" },
+            pct
+        ),
+        change >= 0.0,
+    ))
+}
+
 fn draw_stock_detail(f: &mut Frame, app: &App, area: Rect, rt: ResolvedTheme) {
     let block = Block::default()
         .title(format!("Detail: {}", app.symbol))
@@ -378,29 +492,23 @@ fn draw_stock_detail(f: &mut Frame, app: &App, area: Rect, rt: ResolvedTheme) {
             f.render_widget(Paragraph::new(text).block(block), area);
             return;
         };
-        let price_change = result.c - result.o;
-        let percent_change = if result.o.abs() > f64::EPSILON {
-            (price_change / result.o) * 100.0
-        } else {
-            0.0
-        };
+        let price_change = result.day_change();
+        let percent_change = result.day_change_percent();
+        let ccy = result.quote_currency();
         let change_color = if price_change >= 0.0 {
             rt.positive
         } else {
             rt.negative
         };
 
-        let text = vec![
+        let mut text = vec![
             Line::from(vec![
                 Span::styled("Symbol: ", rt.canvas()),
                 Span::styled(&app.symbol, rt.fg_accent()),
             ]),
             Line::from(vec![
                 Span::styled("Price: ", rt.canvas()),
-                Span::styled(
-                    format!("${:.2}", result.c),
-                    rt.fg_foreground(),
-                ),
+                Span::styled(format_money(result.c, ccy), rt.fg_foreground()),
             ]),
             Line::from(vec![
                 Span::styled("Change: ", rt.canvas()),
@@ -415,25 +523,26 @@ fn draw_stock_detail(f: &mut Frame, app: &App, area: Rect, rt: ResolvedTheme) {
                 ),
             ]),
             Line::from(vec![
-                Span::styled("Open: ", rt.canvas()),
+                Span::styled("Prev Close: ", rt.canvas()),
                 Span::styled(
-                    format!("${:.2}", result.o),
+                    result
+                        .prev_close
+                        .map(|p| format_money(p, ccy))
+                        .unwrap_or_else(|| "—".to_string()),
                     rt.fg_foreground(),
                 ),
             ]),
+            Line::from(vec![
+                Span::styled("Open: ", rt.canvas()),
+                Span::styled(format_money(result.o, ccy), rt.fg_foreground()),
+            ]),
             Line::from(vec![
                 Span::styled("High: ", rt.canvas()),
-                Span::styled(
-                    format!("${:.2}", result.h),
-                    rt.fg_foreground(),
-                ),
+                Span::styled(format_money(result.h, ccy), rt.fg_foreground()),
             ]),
             Line::from(vec![
                 Span::styled("Low: ", rt.canvas()),
-                Span::styled(
-                    format!("${:.2}", result.l),
-                    rt.fg_foreground(),
-                ),
+                Span::styled(format_money(result.l, ccy), rt.fg_foreground()),
             ]),
             Line::from(vec![
                 Span::styled("Volume: ", rt.canvas()),
@@ -442,8 +551,44 @@ fn draw_stock_detail(f: &mut Frame, app: &App, area: Rect, rt: ResolvedTheme) {
                     rt.fg_foreground(),
                 ),
             ]),
+            Line::from(vec![
+                Span::styled("Session: ", rt.canvas()),
+                Span::styled(
+                    result.market_state.map(|m| m.label()).unwrap_or("—"),
+                    rt.fg_muted(),
+                ),
+            ]),
         ];
 
+        if let (Some((state, price)), Some((change, pct))) =
+            (result.extended_quote(), result.extended_change())
+        {
+            let ext_color = if change >= 0.0 {
+                rt.positive
+            } else {
+                rt.negative
+            };
+            let sign = if change >= 0.0 { "+" } else { "" };
+            // Insert under Price/Change so the extended move reads next to the regular one.
+            text.insert(
+                3,
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {} ", state.label()),
+                        Style::default()
+                            .fg(rt.background)
+                            .bg(rt.accent)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(format!(" {} ", format_money(price, ccy)), rt.fg_foreground()),
+                    Span::styled(
+                        format!("{sign}{change:.2} ({sign}{pct:.2}%)"),
+                        rt.fg_color(ext_color),
+                    ),
+                ]),
+            );
+        }
+
         f.render_widget(Paragraph::new(text).block(block), area);
     } else if let Some(error) = app.error_message().as_deref() {
         let text = vec![Line::from(vec![Span::styled(
@@ -724,10 +869,7 @@ fn draw_settings(f: &mut Frame, app: &mut App, area: Rect, rt: ResolvedTheme) {
     let inner = block.inner(area);
     f.render_widget(block, area);
 
-    let provider_s = match app.config.provider {
-        MarketProviderKind::Yahoo => "yahoo",
-        MarketProviderKind::Polygon => "polygon",
-    };
+    let provider_s = app.config.provider.as_str();
     let theme_s = theme_row_summary(app);
 
     let flash = app
@@ -892,8 +1034,28 @@ pub(crate) fn stock_view_status_lines(width: u16, rt: ResolvedTheme) -> Vec<Line
     }
 }
 
+/// Appends `via <backend>` to the last status line while a fallback provider serves the data.
+pub(crate) fn push_served_by(lines: &mut [Line<'_>], backend: MarketProviderKind, rt: ResolvedTheme) {
+    if let Some(line) = lines.last_mut() {
+        line.spans.push(Span::styled(" · ", rt.canvas()));
+        line.spans.push(Span::styled(format!("via {}", backend.as_str()), rt.warning_text()));
+    }
+}
+
+/// Appends the rate-limiter queue (`3 queued · next 9s`) to the last status line.
+pub(crate) fn push_request_queue(lines: &mut [Line<'_>], status: QueueStatus, rt: ResolvedTheme) {
+    if let Some(line) = lines.last_mut() {
+        let eta = status.next_slot_in.as_secs_f64().ceil() as u64;
+        line.spans.push(Span::styled(" · ", rt.canvas()));
+        line.spans.push(Span::styled(
+            format!("{} queued · next {eta}s", status.depth),
+            rt.fg_muted(),
+        ));
+    }
+}
+
 fn draw_status_bar(f: &mut Frame, app: &App, area: Rect, rt: ResolvedTheme) {
-    let lines: Vec<Line> = if let Some(error) = app.error_message() {
+    let mut lines: Vec<Line> = if let Some(error) = app.error_message() {
         vec![Line::from(vec![Span::styled(error, rt.error_text())])]
     } else if app.active_tab == Tab::Search && app.search_refresh_inflight {
         vec![Line::from(vec![Span::styled(
@@ -902,6 +1064,8 @@ fn draw_status_bar(f: &mut Frame, app: &App, area: Rect, rt: ResolvedTheme) {
         )])]
     } else if let Some(flash) = app.news_url_flash_line() {
         vec![Line::from(vec![Span::styled(flash, rt.success_text())])]
+    } else if let Some(flash) = app.portfolio_csv_flash_line() {
+        vec![Line::from(vec![Span::styled(flash, rt.success_text())])]
     } else if app.active_tab == Tab::News && app.news_refresh_inflight {
         vec![Line::from(vec![Span::styled(
             "Loading news…",
@@ -947,6 +1111,14 @@ fn draw_status_bar(f: &mut Frame, app: &App, area: Rect, rt: ResolvedTheme) {
         line.spans.extend(status_bar_global_suffix(rt));
         vec![line]
     };
+    if app.error_message().is_none() {
+        if let Some(backend) = app.served_by_fallback() {
+            push_served_by(&mut lines, backend, rt);
+        }
+        if let Some(status) = queue_status() {
+            push_request_queue(&mut lines, status, rt);
+        }
+    }
 
     let paragraph = Paragraph::new(lines).style(rt.canvas());
     f.render_widget(paragraph, area);
@@ -986,6 +1158,34 @@ mod status_bar_tests {
         assert!(text.contains("Shift"));
     }
 
+    #[test]
+    fn served_by_goes_on_the_last_status_line() {
+        let mut lines = stock_view_status_lines(80, test_rt());
+        push_served_by(&mut lines, MarketProviderKind::Finnhub, test_rt());
+        let text: String = lines[1]
+            .spans
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert!(text.ends_with(" · via finnhub"));
+    }
+
+    #[test]
+    fn request_queue_shows_depth_and_rounded_up_eta() {
+        let mut lines = stock_view_status_lines(120, test_rt());
+        let status = QueueStatus {
+            depth: 7,
+            next_slot_in: std::time::Duration::from_millis(8_200),
+        };
+        push_request_queue(&mut lines, status, test_rt());
+        let text: String = lines[0]
+            .spans
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert!(text.ends_with(" · 7 queued · next 9s"));
+    }
+
     #[test]
     fn status_bar_row_count_stock_view_narrow_is_two() {
         let mut app = App::new();
@@ -0,0 +1,802 @@
+//! Non-interactive subcommands for scripts and cron (`stockterm quote|history|search|news`,
+//! `stockterm alerts watch`, `stockterm portfolio import|export`).
+//!
+//! Each command loads `~/.stockterm.json`, calls [`market_provider_for_config`] (the configured
+//! provider plus any `fallback_providers`), and prints a plain-text table to stdout (or JSON with `--json`). Provider errors go to stderr
+//! and make the process exit non-zero; partial `quote` batches still print the symbols that succeeded.
+
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use chrono::{Local, NaiveDate, TimeZone};
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+
+use crate::api::{market_provider_for_config, HistoricalQuery};
+use crate::app::alerts_watch::{watch_alerts, AlertsWatchOptions};
+use crate::app::normalize_symbol;
+use crate::app::portfolio::merge_trades;
+use crate::config::Config;
+use crate::models::historical::HistoricalData;
+use crate::models::portfolio::Portfolio;
+use crate::models::portfolio_csv::{parse_portfolio_csv, write_portfolio_csv, CsvPreset, ImportedTrade};
+use crate::models::ticker::{MarketState, TickerResponse};
+use crate::models::time_range::{CustomRange, TimeRange};
+
+/// Exit status when any provider request failed.
+pub const EXIT_PROVIDER_ERROR: i32 = 1;
+/// Exit status when `~/.stockterm.json` cannot be read or parsed.
+pub const EXIT_CONFIG_ERROR: i32 = 2;
+/// Exit status when the input is unusable: a `history` `--from`/`--to` range that is reversed or
+/// in the future, or `portfolio` input (unreadable CSV, bad rows, unknown account, a sell beyond
+/// the shares held).
+pub const EXIT_DATA_ERROR: i32 = 3;
+
+/// Top-level arguments. No subcommand → interactive TUI.
+#[derive(Debug, Parser)]
+#[command(name = "stockterm", version, about = "Terminal UI for stock quotes, watchlists, charts, and alerts")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Print JSON instead of a table (subcommands only).
+    #[arg(long, global = true)]
+    pub json: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Latest quote for one or more symbols.
+    Quote {
+        #[arg(required = true)]
+        symbols: Vec<String>,
+    },
+    /// Historical bars for one symbol.
+    History {
+        symbol: String,
+        /// Chart window: 1d, 1w, 1m, 3m, 6m, ytd, 1y, 5y or max.
+        #[arg(long, default_value = "1m")]
+        range: TimeRange,
+        /// Custom window start (YYYY-MM-DD); replaces `--range`.
+        #[arg(long, conflicts_with = "range")]
+        from: Option<NaiveDate>,
+        /// Custom window end (YYYY-MM-DD). Default: today.
+        #[arg(long, requires = "from")]
+        to: Option<NaiveDate>,
+    },
+    /// Symbol search (ticker or company name).
+    Search {
+        #[arg(required = true)]
+        query: Vec<String>,
+    },
+    /// Recent headlines for one symbol.
+    News { symbol: String },
+    /// Price alerts without the TUI.
+    Alerts {
+        #[command(subcommand)]
+        command: AlertsCommand,
+    },
+    /// Portfolio ledger CSV import / export.
+    Portfolio {
+        #[command(subcommand)]
+        command: PortfolioCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum PortfolioCommand {
+    /// Write every buy and sell of one account as `symbol,side,date,quantity,price,fees`.
+    Export {
+        /// Output file (default: stdout).
+        file: Option<PathBuf>,
+        /// Account name (default: the first account).
+        #[arg(long)]
+        account: Option<String>,
+    },
+    /// Merge trades or broker positions from a CSV file into an account in ~/.stockterm.json.
+    ///
+    /// Prints the parsed rows; bad rows go to stderr with their line numbers and make the exit
+    /// status non-zero (the good rows are still merged). A sell beyond the shares held aborts
+    /// the whole import.
+    Import {
+        file: PathBuf,
+        /// Column mapping: auto, stockterm, fidelity, schwab, ibkr or robinhood.
+        #[arg(long, default_value = "auto")]
+        preset: CsvPreset,
+        /// Print the parsed rows without saving.
+        #[arg(long)]
+        dry_run: bool,
+        /// Account name (default: the first account; created when missing).
+        #[arg(long)]
+        account: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AlertsCommand {
+    /// Poll quotes for `alerts` in ~/.stockterm.json, fire bell/desktop notifications, persist
+    /// `triggered` (runs until Ctrl-C).
+    Watch {
+        /// Evaluate once and exit (non-zero exit on any quote or save error).
+        #[arg(long)]
+        once: bool,
+        /// Poll interval in seconds (default: `refresh_rate` from the config, 30 s when unset).
+        #[arg(long)]
+        interval: Option<u64>,
+    },
+}
+
+/// One `quote` row; `change` is last minus previous close (open when the provider has none),
+/// same as the Stock View watchlist.
+#[derive(Debug, Serialize)]
+pub(crate) struct QuoteRow {
+    pub symbol: String,
+    pub last: f64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub volume: f64,
+    pub prev_close: Option<f64>,
+    pub change: f64,
+    pub change_percent: f64,
+    pub market_state: Option<MarketState>,
+    /// Bar timestamp, Unix milliseconds.
+    pub time: u64,
+}
+
+impl QuoteRow {
+    fn from_response(symbol: &str, resp: &TickerResponse) -> Option<Self> {
+        let bar = resp.latest_result()?;
+        Some(Self {
+            symbol: resp.symbol_or(symbol).to_uppercase(),
+            last: bar.c,
+            open: bar.o,
+            high: bar.h,
+            low: bar.l,
+            volume: bar.v,
+            prev_close: bar.prev_close,
+            change: bar.day_change(),
+            change_percent: bar.day_change_percent(),
+            market_state: bar.market_state,
+            time: bar.t,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct HistoryBar {
+    t: u64,
+    o: f64,
+    h: f64,
+    l: f64,
+    c: f64,
+    v: f64,
+}
+
+impl From<&HistoricalData> for HistoryBar {
+    fn from(d: &HistoricalData) -> Self {
+        Self {
+            t: d.t,
+            o: d.o,
+            h: d.h,
+            l: d.l,
+            c: d.c,
+            v: d.v,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct HistoryOut<'a> {
+    symbol: &'a str,
+    range: &'static str,
+    bars: Vec<HistoryBar>,
+}
+
+#[derive(Debug, Serialize)]
+struct SearchRow<'a> {
+    symbol: &'a str,
+    name: &'a str,
+    #[serde(rename = "type")]
+    kind: &'a str,
+    exchange: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct NewsRow<'a> {
+    title: &'a str,
+    publisher: &'a str,
+    published_utc: &'a str,
+    url: &'a str,
+}
+
+/// Runs `command` to completion and returns the process exit status.
+pub async fn run(command: Command, json: bool) -> i32 {
+    match command {
+        Command::Alerts {
+            command: AlertsCommand::Watch { once, interval },
+        } => {
+            let opts = AlertsWatchOptions {
+                once,
+                interval_secs: interval,
+            };
+            if watch_alerts(opts).await {
+                0
+            } else {
+                EXIT_PROVIDER_ERROR
+            }
+        }
+        Command::Portfolio { command } => match load_config() {
+            Some(config) => run_portfolio(config, command, json),
+            None => EXIT_CONFIG_ERROR,
+        },
+        Command::Quote { symbols } => {
+            let Some(config) = load_config() else {
+                return EXIT_CONFIG_ERROR;
+            };
+            exit_status(run_quote(&mut io::stdout().lock(), &config, &symbols, json).await)
+        }
+        Command::History {
+            symbol,
+            range,
+            from,
+            to,
+        } => {
+            let custom = match custom_range_arg(from, to, Local::now().date_naive()) {
+                Ok(c) => c,
+                Err(code) => return code,
+            };
+            let Some(config) = load_config() else {
+                return EXIT_CONFIG_ERROR;
+            };
+            let mut out = io::stdout().lock();
+            exit_status(run_history(&mut out, &config, &symbol, range, custom, json).await)
+        }
+        Command::Search { query } => {
+            let Some(config) = load_config() else {
+                return EXIT_CONFIG_ERROR;
+            };
+            exit_status(run_search(&mut io::stdout().lock(), &config, &query.join(" "), json).await)
+        }
+        Command::News { symbol } => {
+            let Some(config) = load_config() else {
+                return EXIT_CONFIG_ERROR;
+            };
+            exit_status(run_news(&mut io::stdout().lock(), &config, &symbol, json).await)
+        }
+    }
+}
+
+/// `~/.stockterm.json`, or `None` after reporting why it could not be read.
+fn load_config() -> Option<Config> {
+    Config::try_load()
+        .inspect_err(|e| eprintln!("stockterm: could not load ~/.stockterm.json: {e}"))
+        .ok()
+}
+
+/// Exit status for a command that wrote to stdout.
+/// `history --from/--to` as a [`CustomRange`] (`--to` defaults to today); a bad range is reported
+/// on stderr as [`EXIT_DATA_ERROR`].
+fn custom_range_arg(from: Option<NaiveDate>, to: Option<NaiveDate>, today: NaiveDate) -> Result<Option<CustomRange>, i32> {
+    let Some(from) = from else {
+        return Ok(None);
+    };
+    CustomRange::new(from, to.unwrap_or(today), today).map(Some).map_err(|e| {
+        eprintln!("stockterm: {e}");
+        EXIT_DATA_ERROR
+    })
+}
+
+fn exit_status(res: io::Result<i32>) -> i32 {
+    match res {
+        Ok(code) => code,
+        // Broken pipe (`| head`) is not worth a message.
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => 0,
+        Err(e) => {
+            eprintln!("stockterm: {e}");
+            EXIT_PROVIDER_ERROR
+        }
+    }
+}
+
+async fn run_quote(out: &mut impl Write, config: &Config, symbols: &[String], json: bool) -> io::Result<i32> {
+    let provider = market_provider_for_config(config);
+    let mut rows = Vec::new();
+    let mut failed = false;
+    for sym in symbols.iter().filter_map(|s| normalize_symbol(s)) {
+        match provider.get_quote(&sym, config).await {
+            Ok(resp) => {
+                if let Some(msg) = resp.api_error_message() {
+                    eprintln!("stockterm: {sym}: {msg}");
+                    failed = true;
+                } else if let Some(row) = QuoteRow::from_response(&sym, &resp) {
+                    rows.push(row);
+                } else {
+                    eprintln!("stockterm: {sym}: no quote data returned");
+                    failed = true;
+                }
+            }
+            Err(e) => {
+                eprintln!("stockterm: {sym}: {e}");
+                failed = true;
+            }
+        }
+    }
+    if json {
+        write_json(out, &rows)?;
+    } else {
+        let table: Vec<Vec<String>> = rows
+            .iter()
+            .map(|r| {
+                vec![
+                    r.symbol.clone(),
+                    format!("{:.2}", r.last),
+                    signed(r.change, ""),
+                    signed(r.change_percent, "%"),
+                    r.prev_close.map(|p| format!("{p:.2}")).unwrap_or_else(|| "-".into()),
+                    format!("{:.2}", r.open),
+                    format!("{:.2}", r.high),
+                    format!("{:.2}", r.low),
+                    format!("{:.0}", r.volume),
+                    r.market_state.map(|m| m.label()).unwrap_or("-").to_string(),
+                ]
+            })
+            .collect();
+        out.write_all(
+            render_table(
+                &["SYMBOL", "LAST", "CHANGE", "%CHG", "PREV", "OPEN", "HIGH", "LOW", "VOLUME", "SESSION"],
+                &[false, true, true, true, true, true, true, true, true, false],
+                &table,
+            )
+            .as_bytes(),
+        )?;
+    }
+    Ok(if failed { EXIT_PROVIDER_ERROR } else { 0 })
+}
+
+async fn run_history(
+    out: &mut impl Write,
+    config: &Config,
+    symbol: &str,
+    range: TimeRange,
+    custom: Option<CustomRange>,
+    json: bool,
+) -> io::Result<i32> {
+    let Some(sym) = normalize_symbol(symbol) else {
+        eprintln!("stockterm: empty symbol");
+        return Ok(EXIT_PROVIDER_ERROR);
+    };
+    let range = if custom.is_some() { TimeRange::Custom } else { range };
+    let params = range.historical_params(Local::now(), custom);
+    let hq = HistoricalQuery {
+        from: &params.from,
+        to: &params.to,
+        bar_interval: params.bar_interval,
+        yahoo_range: params.yahoo_range,
+        polygon_multiplier: params.polygon_multiplier,
+        polygon_timespan: params.polygon_timespan,
+    };
+    let provider = market_provider_for_config(config);
+    let resp = match provider.get_historical(&sym, &hq, config).await {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("stockterm: {sym}: {e}");
+            return Ok(EXIT_PROVIDER_ERROR);
+        }
+    };
+    if json {
+        write_json(
+            out,
+            &HistoryOut {
+                symbol: &sym,
+                range: range.label(),
+                bars: resp.results.iter().map(HistoryBar::from).collect(),
+            },
+        )?;
+    } else {
+        let intraday = params.bar_interval.ends_with('m');
+        let table: Vec<Vec<String>> = resp
+            .results
+            .iter()
+            .map(|d| {
+                vec![
+                    format_bar_time(d.t, intraday),
+                    format!("{:.2}", d.o),
+                    format!("{:.2}", d.h),
+                    format!("{:.2}", d.l),
+                    format!("{:.2}", d.c),
+                    format!("{:.0}", d.v),
+                ]
+            })
+            .collect();
+        out.write_all(
+            render_table(
+                &["TIME", "OPEN", "HIGH", "LOW", "CLOSE", "VOLUME"],
+                &[false, true, true, true, true, true],
+                &table,
+            )
+            .as_bytes(),
+        )?;
+    }
+    Ok(0)
+}
+
+async fn run_search(out: &mut impl Write, config: &Config, query: &str, json: bool) -> io::Result<i32> {
+    let provider = market_provider_for_config(config);
+    let resp = match provider.search_symbols(query.trim(), config).await {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("stockterm: search {query:?}: {e}");
+            return Ok(EXIT_PROVIDER_ERROR);
+        }
+    };
+    let rows: Vec<SearchRow<'_>> = resp
+        .results
+        .iter()
+        .map(|r| SearchRow {
+            symbol: &r.ticker,
+            name: &r.name,
+            kind: &r.type_,
+            exchange: &r.primary_exchange,
+        })
+        .collect();
+    if json {
+        write_json(out, &rows)?;
+    } else {
+        let table: Vec<Vec<String>> = rows
+            .iter()
+            .map(|r| vec![r.symbol.into(), r.name.into(), r.kind.into(), r.exchange.into()])
+            .collect();
+        out.write_all(render_table(&["SYMBOL", "NAME", "TYPE", "EXCHANGE"], &[false; 4], &table).as_bytes())?;
+    }
+    Ok(0)
+}
+
+async fn run_news(out: &mut impl Write, config: &Config, symbol: &str, json: bool) -> io::Result<i32> {
+    let Some(sym) = normalize_symbol(symbol) else {
+        eprintln!("stockterm: empty symbol");
+        return Ok(EXIT_PROVIDER_ERROR);
+    };
+    let provider = market_provider_for_config(config);
+    let resp = match provider.get_news(&sym, config).await {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("stockterm: {sym}: {e}");
+            return Ok(EXIT_PROVIDER_ERROR);
+        }
+    };
+    let rows: Vec<NewsRow<'_>> = resp
+        .results
+        .iter()
+        .map(|n| NewsRow {
+            title: &n.title,
+            publisher: &n.publisher.name,
+            published_utc: &n.published_utc,
+            url: &n.article_url,
+        })
+        .collect();
+    if json {
+        write_json(out, &rows)?;
+    } else {
+        let table: Vec<Vec<String>> = rows
+            .iter()
+            .map(|r| vec![r.published_utc.into(), r.publisher.into(), r.title.into(), r.url.into()])
+            .collect();
+        out.write_all(render_table(&["PUBLISHED", "PUBLISHER", "TITLE", "URL"], &[false; 4], &table).as_bytes())?;
+    }
+    Ok(0)
+}
+
+fn run_portfolio(mut config: Config, command: PortfolioCommand, json: bool) -> i32 {
+    match command {
+        PortfolioCommand::Export { file, account } => {
+            let found = match account.as_deref() {
+                None => config.portfolios.first(),
+                Some(name) => config.portfolios.iter().find(|p| p.name == name),
+            };
+            let Some(holdings) = found.map(|p| &p.holdings[..]) else {
+                eprintln!("stockterm: no account named {:?}", account.unwrap_or_default());
+                return EXIT_DATA_ERROR;
+            };
+            let res = match file {
+                Some(path) => std::fs::File::create(&path)
+                    .map_err(|e| format!("{}: {e}", path.display()))
+                    .and_then(|f| write_portfolio_csv(holdings, f).map_err(|e| e.to_string())),
+                None => write_portfolio_csv(holdings, io::stdout().lock()).map_err(|e| e.to_string()),
+            };
+            match res {
+                Ok(()) => 0,
+                Err(e) => {
+                    eprintln!("stockterm: {e}");
+                    EXIT_DATA_ERROR
+                }
+            }
+        }
+        PortfolioCommand::Import {
+            file,
+            preset,
+            dry_run,
+            account,
+        } => {
+            let parsed = std::fs::read_to_string(&file)
+                .map_err(|e| format!("{}: {e}", file.display()))
+                .and_then(|text| parse_portfolio_csv(&text, preset));
+            let import = match parsed {
+                Ok(i) => i,
+                Err(e) => {
+                    eprintln!("stockterm: {e}");
+                    return EXIT_DATA_ERROR;
+                }
+            };
+            for e in &import.errors {
+                eprintln!("stockterm: {}:{}: {}", file.display(), e.line, e.message);
+            }
+            let res = write_import_preview(&mut io::stdout().lock(), &import.trades, json);
+            if let Err(e) = res {
+                if e.kind() != io::ErrorKind::BrokenPipe {
+                    eprintln!("stockterm: {e}");
+                    return EXIT_DATA_ERROR;
+                }
+            }
+            if !import.trades.is_empty() {
+                let idx = match account {
+                    None => 0,
+                    Some(name) => match config.portfolios.iter().position(|p| p.name == name) {
+                        Some(i) => i,
+                        None => {
+                            config.portfolios.push(Portfolio::new(name));
+                            config.portfolios.len() - 1
+                        }
+                    },
+                };
+                let Some(target) = config.portfolios.get_mut(idx) else {
+                    eprintln!("stockterm: no portfolio account to import into");
+                    return EXIT_CONFIG_ERROR;
+                };
+                let trades = import.trades.into_iter().map(|t| (t.symbol, t.transaction));
+                if let Err(e) = merge_trades(&mut target.holdings, trades) {
+                    eprintln!("stockterm: {}: {e}; nothing was imported", file.display());
+                    return EXIT_DATA_ERROR;
+                }
+                if !dry_run {
+                    if let Err(e) = config.try_save() {
+                        eprintln!("stockterm: could not save ~/.stockterm.json: {e}");
+                        return EXIT_CONFIG_ERROR;
+                    }
+                }
+            }
+            if import.errors.is_empty() {
+                0
+            } else {
+                EXIT_DATA_ERROR
+            }
+        }
+    }
+}
+
+fn write_import_preview(out: &mut impl Write, trades: &[ImportedTrade], json: bool) -> io::Result<()> {
+    if json {
+        return write_json(out, trades);
+    }
+    let table: Vec<Vec<String>> = trades
+        .iter()
+        .map(|t| {
+            let tx = &t.transaction;
+            vec![
+                t.line.to_string(),
+                t.symbol.clone(),
+                tx.side.label().to_uppercase(),
+                tx.date.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_else(|| "-".into()),
+                format!("{}", tx.quantity),
+                format!("{:.4}", tx.price),
+                format!("{:.2}", tx.fees),
+            ]
+        })
+        .collect();
+    out.write_all(
+        render_table(
+            &["LINE", "SYMBOL", "SIDE", "DATE", "QUANTITY", "PRICE", "FEES"],
+            &[true, false, false, false, true, true, true],
+            &table,
+        )
+        .as_bytes(),
+    )
+}
+
+fn write_json<T: Serialize + ?Sized>(out: &mut impl Write, value: &T) -> io::Result<()> {
+    serde_json::to_writer_pretty(&mut *out, value).map_err(io::Error::other)?;
+    writeln!(out)
+}
+
+fn signed(v: f64, suffix: &str) -> String {
+    format!("{}{:.2}{suffix}", if v >= 0.0 { "+" } else { "" }, v)
+}
+
+fn format_bar_time(t_ms: u64, intraday: bool) -> String {
+    let Some(dt) = Local.timestamp_millis_opt(t_ms as i64).single() else {
+        return t_ms.to_string();
+    };
+    if intraday {
+        dt.format("%Y-%m-%d %H:%M").to_string()
+    } else {
+        dt.format("%Y-%m-%d").to_string()
+    }
+}
+
+/// Space-padded columns sized to the widest cell (by `char` count); `right_align[i]` for numbers.
+pub(crate) fn render_table(headers: &[&str], right_align: &[bool], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (w, cell) in widths.iter_mut().zip(row) {
+            *w = (*w).max(cell.chars().count());
+        }
+    }
+    let fmt_row = |cells: &mut dyn Iterator<Item = &str>| -> String {
+        let line: Vec<String> = cells
+            .zip(widths.iter().zip(right_align))
+            .map(|(cell, (&w, &right))| {
+                if right {
+                    format!("{cell:>w$}")
+                } else {
+                    format!("{cell:<w$}")
+                }
+            })
+            .collect();
+        let mut s = line.join("  ").trim_end().to_string();
+        s.push('\n');
+        s
+    };
+    let mut out = fmt_row(&mut headers.iter().copied());
+    for row in rows {
+        out.push_str(&fmt_row(&mut row.iter().map(String::as_str)));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ticker::TickerResult;
+    use clap::CommandFactory;
+
+    #[test]
+    fn cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn no_subcommand_means_tui() {
+        let cli = Cli::try_parse_from(["stockterm"]).expect("parse");
+        assert!(cli.command.is_none());
+    }
+
+    #[test]
+    fn history_parses_range_and_trailing_json() {
+        let cli = Cli::try_parse_from(["stockterm", "history", "AAPL", "--range", "1y", "--json"]).expect("parse");
+        assert!(cli.json);
+        assert!(matches!(
+            cli.command,
+            Some(Command::History { ref symbol, range: TimeRange::Y1, from: None, .. }) if symbol == "AAPL"
+        ));
+        assert!(Cli::try_parse_from(["stockterm", "history", "AAPL", "--range", "7y"]).is_err());
+    }
+
+    #[test]
+    fn history_parses_custom_from_to() {
+        let cli = Cli::try_parse_from([
+            "stockterm", "history", "AAPL", "--from", "2024-01-02", "--to", "2024-03-28",
+        ])
+        .expect("parse");
+        let Some(Command::History { from, to, .. }) = cli.command else {
+            panic!("expected history");
+        };
+        assert_eq!(from.map(|d| d.to_string()).as_deref(), Some("2024-01-02"));
+        assert_eq!(to.map(|d| d.to_string()).as_deref(), Some("2024-03-28"));
+        assert!(Cli::try_parse_from(["stockterm", "history", "AAPL", "--to", "2024-03-28"]).is_err());
+        assert!(Cli::try_parse_from([
+            "stockterm", "history", "AAPL", "--range", "1y", "--from", "2024-01-02",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn history_rejects_a_bad_custom_range_as_a_data_error() {
+        let d = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        let today = d("2024-06-01");
+        assert_eq!(custom_range_arg(None, None, today), Ok(None));
+        assert!(custom_range_arg(Some(d("2024-01-02")), None, today).unwrap().is_some());
+        assert_eq!(custom_range_arg(Some(d("2024-03-28")), Some(d("2024-01-02")), today), Err(EXIT_DATA_ERROR));
+        assert_eq!(custom_range_arg(Some(d("2024-07-01")), None, today), Err(EXIT_DATA_ERROR));
+    }
+
+    #[test]
+    fn quote_requires_a_symbol() {
+        assert!(Cli::try_parse_from(["stockterm", "quote"]).is_err());
+        let cli = Cli::try_parse_from(["stockterm", "quote", "AAPL", "MSFT"]).expect("parse");
+        assert!(matches!(cli.command, Some(Command::Quote { ref symbols }) if symbols.len() == 2));
+    }
+
+    #[test]
+    fn alerts_watch_parses_once_and_interval() {
+        let cli = Cli::try_parse_from(["stockterm", "alerts", "watch", "--once", "--interval", "60"]).expect("parse");
+        assert!(matches!(
+            cli.command,
+            Some(Command::Alerts {
+                command: AlertsCommand::Watch { once: true, interval: Some(60) }
+            })
+        ));
+    }
+
+    #[test]
+    fn portfolio_import_parses_preset_and_dry_run() {
+        let cli = Cli::try_parse_from([
+            "stockterm", "portfolio", "import", "positions.csv", "--preset", "Fidelity", "--dry-run",
+        ])
+        .expect("parse");
+        assert!(matches!(
+            cli.command,
+            Some(Command::Portfolio {
+                command: PortfolioCommand::Import { preset: CsvPreset::Fidelity, dry_run: true, .. }
+            })
+        ));
+        assert!(Cli::try_parse_from(["stockterm", "portfolio", "import", "x.csv", "--preset", "etrade"]).is_err());
+        let cli = Cli::try_parse_from(["stockterm", "portfolio", "export"]).expect("parse");
+        assert!(matches!(
+            cli.command,
+            Some(Command::Portfolio { command: PortfolioCommand::Export { file: None, account: None } })
+        ));
+    }
+
+    #[test]
+    fn portfolio_import_rejects_a_sell_beyond_held_shares() {
+        let path = std::env::temp_dir().join(format!("stockterm-cli-import-{}.csv", std::process::id()));
+        std::fs::write(
+            &path,
+            "symbol,side,date,quantity,price,fees\nAAPL,buy,2024-01-02,2,150,0\nAAPL,sell,2024-02-01,3,160,0\n",
+        )
+        .unwrap();
+        let import = PortfolioCommand::Import {
+            file: path.clone(),
+            preset: CsvPreset::Auto,
+            dry_run: true,
+            account: None,
+        };
+        let code = run_portfolio(Config::default(), import, false);
+        std::fs::remove_file(&path).ok();
+        assert_eq!(code, EXIT_DATA_ERROR);
+    }
+
+    #[test]
+    fn quote_row_change_is_against_prev_close() {
+        let resp = TickerResponse {
+            ticker: String::new(),
+            results: vec![TickerResult {
+                o: 100.0,
+                h: 112.0,
+                l: 99.0,
+                c: 110.0,
+                v: 5.0,
+                t: 1,
+                prev_close: Some(88.0),
+                market_state: Some(MarketState::Regular),
+                ext_price: None,
+                week52_low: None,
+                week52_high: None,
+                market_cap: None,
+                currency: None,
+            }],
+            status: "OK".into(),
+            error: None,
+        };
+        let row = QuoteRow::from_response("aapl", &resp).expect("row");
+        assert_eq!(row.symbol, "AAPL");
+        assert!((row.change - 22.0).abs() < 1e-9);
+        assert!((row.change_percent - 25.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn render_table_pads_and_right_aligns() {
+        let rows = vec![vec!["AAPL".to_string(), "1.00".to_string()], vec!["T".to_string(), "123.45".to_string()]];
+        let s = render_table(&["SYMBOL", "LAST"], &[false, true], &rows);
+        assert_eq!(s, "SYMBOL    LAST\nAAPL      1.00\nT       123.45\n");
+    }
+}
@@ -7,41 +7,88 @@ use std::path::{Path, PathBuf};
 use super::layout::Layout;
 use super::theme::Theme;
 use std::collections::HashMap;
+use crate::models::alert_sink::AlertSink;
 use crate::models::alerts::Alert;
-use crate::models::portfolio::PortfolioItem;
+use crate::models::currency::DEFAULT_BASE_CURRENCY;
+use crate::models::indicators::Indicator;
+use crate::models::portfolio::{CostBasisMethod, Portfolio, PortfolioItem, DEFAULT_PORTFOLIO_NAME};
+use crate::models::time_range::{CustomRange, TimeRange};
+use crate::models::watchlist::{Watchlist, WatchlistSort, DEFAULT_WATCHLIST_NAME};
 use thiserror::Error;
 
-/// Market data backend. Yahoo is the default (no API key). Polygon requires `api_key` / `STOCKTERM_API_KEY`.
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// Market data backend. Yahoo is the default (no API key). Polygon requires `api_key` / `STOCKTERM_API_KEY`;
+/// Alpha Vantage requires `alpha_vantage_api_key` / `STOCKTERM_ALPHA_VANTAGE_API_KEY`; Finnhub requires
+/// `finnhub_api_key` / `STOCKTERM_FINNHUB_API_KEY`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum MarketProviderKind {
     #[default]
     Yahoo,
     Polygon,
+    #[serde(alias = "alpha_vantage")]
+    AlphaVantage,
+    Finnhub,
+}
+
+impl MarketProviderKind {
+    /// Config spelling (`yahoo`, `polygon`, `alphavantage`, `finnhub`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MarketProviderKind::Yahoo => "yahoo",
+            MarketProviderKind::Polygon => "polygon",
+            MarketProviderKind::AlphaVantage => "alphavantage",
+            MarketProviderKind::Finnhub => "finnhub",
+        }
+    }
 }
 
 /// `~/.stockterm.json` — persisted preferences, portfolio, watchlist, alerts, and session hints.
 ///
 /// | Field | Role |
 /// |-------|------|
-/// | `portfolio` | Holdings (symbol, shares, cost). Default: empty. |
-/// | `watchlist` | Stock View symbols (uppercase). Default: empty. |
+/// | `portfolios` | Named accounts: `{"name": "IRA", "holdings": [...]}`; each holding is a symbol + buy/sell `transactions` (legacy `shares` / `purchase_price` rows load as one buy). Default: one empty `Main` account. |
+/// | `portfolio` | Pre-accounts flat holdings list; read once into a `Main` account, never written. |
+/// | `last_portfolio` | Last Portfolio tab account name, or `"*"` for All accounts. Default: omitted → first account. |
+/// | `watchlists` | Named Stock View lists: `{"name": "Tech", "symbols": ["AAPL", …]}` (uppercase tickers). Default: one empty `Main` list. |
+/// | `watchlist` | Pre-named-lists flat symbol array; read once into a `Main` list, never written. |
+/// | `last_watchlist` | Last active Stock View list name. Default: omitted → first list. |
+/// | `watchlist_sort` | Stock View sort: `{"column": "change_percent", "descending": true}`. Default: omitted → insertion order. |
 /// | `refresh_rate` | Quote poll interval (seconds; app may enforce a minimum). Default: `0` → app default. |
 /// | `api_key` | Polygon API key stored in JSON only (see [`effective_api_key`](Config::effective_api_key); env is not copied here on load). Default: empty. |
+/// | `alpha_vantage_api_key` | Alpha Vantage API key (see [`effective_alpha_vantage_api_key`](Config::effective_alpha_vantage_api_key)). Default: omitted. |
+/// | `finnhub_api_key` | Finnhub API key (see [`effective_finnhub_api_key`](Config::effective_finnhub_api_key)). Default: omitted. |
 /// | `alerts` | Price alerts. Default: empty. |
-/// | `default_symbol` | Startup symbol when `watchlist` is empty. Default: empty → app uses `AAPL`. |
+/// | `archived_alerts` | Alerts moved out of `alerts` when their `expires_at` passed. Default: empty. |
+/// | `alert_sinks` | Webhook / shell-command destinations for fired alerts ([`AlertSink`]). Default: empty. |
+/// | `default_symbol` | Startup symbol when the active watchlist is empty. Default: empty → app uses `AAPL`. |
 /// | `theme` | Optional theme preset + hex overrides (see §21). Default: `null`. |
-/// | `provider` | `yahoo` or `polygon`. Default: `yahoo`. |
+/// | `provider` | `yahoo`, `polygon`, `alphavantage` or `finnhub`. Default: `yahoo`. |
+/// | `fallback_providers` | Backends tried in order when `provider` is rate limited, times out or returns 5xx (see [`FailoverProvider`](crate::api::failover::FailoverProvider)); ones without a key are skipped. Default: empty. |
+/// | `rate_limits` | Requests per minute per provider (`{"polygon": 5}`), `0` = unlimited; see [`requests_per_minute`](Config::requests_per_minute). Default: omitted → built-in free-tier limits. |
+/// | `stream_quotes` | Live prices between polls via the provider's [`QuoteStream`](crate::api::quote_stream::QuoteStream) (`finnhub` only). Default: `false`. |
 /// | `notifications_enabled` | Desktop toasts for alerts. Default: `true`. |
 /// | `last_tab` | Last focused tab id (`stock_view`, `portfolio`, …). Default: omitted. |
-/// | `last_symbol` | Last active ticker (uppercase) when `watchlist` was empty at launch. Default: omitted. |
+/// | `last_symbol` | Last active ticker (uppercase) when the active watchlist was empty at launch. Default: omitted. |
 /// | `keymap` | Optional chord → action overrides (see **README** “Keymap” and [`keymap`](crate::config::keymap)). Default: omitted → built-in defaults. |
 /// | `layout` | Shell chrome + pane splits (see §31 / [`layout`](crate::config::layout)). Default: omitted → built-in defaults. |
+/// | `chart_indicators` | Charts tab studies per range (`{"m1": ["sma", "rsi"]}`). Default: empty → none. |
+/// | `last_time_range` | Last Charts range (`d1`, `m3`, `ytd`, `max`, `custom`, …). Default: omitted → `m1`. |
+/// | `custom_range` | Last custom chart window (`{"from": "2024-01-02", "to": "2024-06-28"}`). Default: omitted. |
+/// | `base_currency` | ISO code portfolio totals are converted into (`USD`, `GBP`, `CAD`, …). Default: `USD`. |
+/// | `cost_basis_method` | Portfolio lot matching: `fifo`, `lifo`, or `average`. Default: `fifo`. |
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Config {
+    /// Named accounts; never empty after [`Config::try_load`] ([`Config::normalize_portfolios`]).
+    #[serde(default)]
+    pub portfolios: Vec<Portfolio>,
+    /// Legacy single-account holdings; moved into `portfolios` on load.
+    #[serde(default, skip_serializing)]
     pub portfolio: Vec<PortfolioItem>,
-    /// Symbols to show in the Stock View watchlist table (uppercase tickers).
+    /// Named Stock View lists; never empty after [`Config::try_load`] ([`Config::normalize_watchlists`]).
     #[serde(default)]
+    pub watchlists: Vec<Watchlist>,
+    /// Legacy single watchlist; moved into `watchlists` on load.
+    #[serde(default, skip_serializing)]
     pub watchlist: Vec<String>,
     pub refresh_rate: u64,
     /// Polygon API key as persisted in `~/.stockterm.json` (plaintext).
@@ -51,12 +98,34 @@ pub struct Config {
     /// merged into this field on [`try_load`](Self::try_load) or written by [`try_save`](Self::try_save)
     /// unless the user (or future in-app editor) sets it explicitly (Issue #28 / SPEC §42.2).
     pub api_key: String,
+    /// Alpha Vantage key; resolved like [`api_key`](Self::api_key) via
+    /// [`effective_alpha_vantage_api_key`](Self::effective_alpha_vantage_api_key).
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub alpha_vantage_api_key: String,
+    /// Finnhub key, resolved via [`effective_finnhub_api_key`](Self::effective_finnhub_api_key).
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub finnhub_api_key: String,
     pub alerts: Vec<Alert>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub archived_alerts: Vec<Alert>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alert_sinks: Vec<AlertSink>,
     pub default_symbol: String,
     pub theme: Option<Theme>,
-    /// When `Polygon`, [`effective_api_key`](Config::effective_api_key) must be non-empty for API calls.
+    /// When `Polygon`, [`effective_api_key`](Config::effective_api_key) must be non-empty for API calls;
+    /// when `AlphaVantage`, [`effective_alpha_vantage_api_key`](Config::effective_alpha_vantage_api_key);
+    /// when `Finnhub`, [`effective_finnhub_api_key`](Config::effective_finnhub_api_key).
     #[serde(default)]
     pub provider: MarketProviderKind,
+    /// Backends after [`provider`](Self::provider) in the failover chain ([`provider_chain`](Self::provider_chain)).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_providers: Vec<MarketProviderKind>,
+    /// Per-provider overrides of [`requests_per_minute`](Self::requests_per_minute).
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub rate_limits: HashMap<MarketProviderKind, u32>,
+    /// Stream live prices between REST polls when the provider has a [`QuoteStream`](crate::api::quote_stream::QuoteStream).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub stream_quotes: bool,
     /// Desktop toast when a price alert fires (bell always rings per SPEC §18.5).
     #[serde(default = "default_notifications_enabled")]
     pub notifications_enabled: bool,
@@ -72,28 +141,77 @@ pub struct Config {
     /// Layout visibility and pane sizing (Issue #15 / §31).
     #[serde(default)]
     pub layout: Layout,
+    /// Indicators shown on the Charts tab, keyed by [`TimeRange`] (toggled with `s`/`e`/`b`/`r`/`m`).
+    #[serde(default)]
+    pub chart_indicators: HashMap<TimeRange, Vec<Indicator>>,
+    /// Last Charts tab range; restored at launch like [`last_tab`](Self::last_tab).
+    #[serde(default)]
+    pub last_time_range: Option<TimeRange>,
+    /// Window for [`TimeRange::Custom`], entered in the Charts date dialog (`t`).
+    #[serde(default)]
+    pub custom_range: Option<CustomRange>,
+    /// Which lots portfolio sells close (cycled with `m` on the Portfolio tab).
+    #[serde(default)]
+    pub cost_basis_method: CostBasisMethod,
+    /// Portfolio tab account selector (`[` / `]`); [`ALL_PORTFOLIOS_KEY`] for the aggregate view.
+    #[serde(default)]
+    pub last_portfolio: Option<String>,
+    /// Active Stock View list (`[` / `]`), matched by name at launch.
+    #[serde(default)]
+    pub last_watchlist: Option<String>,
+    /// Currency for portfolio totals; holdings quoted in other currencies convert at the latest
+    /// FX quote (see [`MarketDataProvider::fx_symbol`](crate::api::provider::MarketDataProvider::fx_symbol)).
+    #[serde(default = "default_base_currency")]
+    pub base_currency: String,
+    /// Stock View watchlist sort (`.` / `,` pick the column, `-` reverses); `None` = insertion order.
+    #[serde(default)]
+    pub watchlist_sort: Option<WatchlistSort>,
 }
 
+/// [`Config::last_portfolio`] value for the "All accounts" view.
+pub const ALL_PORTFOLIOS_KEY: &str = "*";
+
 fn default_notifications_enabled() -> bool {
     true
 }
 
+fn default_base_currency() -> String {
+    DEFAULT_BASE_CURRENCY.to_string()
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
+            portfolios: vec![Portfolio::new(DEFAULT_PORTFOLIO_NAME)],
             portfolio: Vec::new(),
+            watchlists: vec![Watchlist::new(DEFAULT_WATCHLIST_NAME)],
             watchlist: Vec::new(),
             refresh_rate: 0,
             api_key: String::new(),
+            alpha_vantage_api_key: String::new(),
+            finnhub_api_key: String::new(),
             alerts: Vec::new(),
+            archived_alerts: Vec::new(),
+            alert_sinks: Vec::new(),
             default_symbol: String::new(),
             theme: None,
             provider: MarketProviderKind::default(),
+            fallback_providers: Vec::new(),
+            rate_limits: HashMap::new(),
+            stream_quotes: false,
             notifications_enabled: default_notifications_enabled(),
             last_tab: None,
             last_symbol: None,
             keymap: None,
             layout: Layout::default(),
+            chart_indicators: HashMap::new(),
+            last_time_range: None,
+            custom_range: None,
+            cost_basis_method: CostBasisMethod::default(),
+            last_portfolio: None,
+            last_watchlist: None,
+            base_currency: default_base_currency(),
+            watchlist_sort: None,
         }
     }
 }
@@ -113,11 +231,48 @@ fn load_config_from_path(path: &Path) -> Result<Config, ConfigError> {
     match fs::read_to_string(path) {
         Err(e) if e.kind() == ErrorKind::NotFound => Ok(Config::default()),
         Err(e) => Err(ConfigError::Io(e)),
-        Ok(s) => serde_json::from_str(&s).map_err(ConfigError::Serde),
+        Ok(s) => {
+            let mut config: Config = serde_json::from_str(&s).map_err(ConfigError::Serde)?;
+            config.normalize_portfolios();
+            config.normalize_watchlists();
+            Ok(config)
+        }
     }
 }
 
 impl Config {
+    /// Moves a legacy `portfolio` list into a `Main` account (merged into the first account when
+    /// `portfolios` also exists) and guarantees at least one account.
+    pub fn normalize_portfolios(&mut self) {
+        let legacy = std::mem::take(&mut self.portfolio);
+        match self.portfolios.first_mut() {
+            Some(first) => first.holdings.extend(legacy),
+            None => self.portfolios.push(Portfolio {
+                name: DEFAULT_PORTFOLIO_NAME.to_string(),
+                holdings: legacy,
+            }),
+        }
+    }
+
+    /// Moves a legacy `watchlist` array into a `Main` list (appended to the first list when
+    /// `watchlists` also exists, skipping duplicates) and guarantees at least one list.
+    pub fn normalize_watchlists(&mut self) {
+        let legacy = std::mem::take(&mut self.watchlist);
+        match self.watchlists.first_mut() {
+            Some(first) => {
+                for sym in legacy {
+                    if !first.symbols.contains(&sym) {
+                        first.symbols.push(sym);
+                    }
+                }
+            }
+            None => self.watchlists.push(Watchlist {
+                name: DEFAULT_WATCHLIST_NAME.to_string(),
+                symbols: legacy,
+            }),
+        }
+    }
+
     /// API key resolution (used for **Polygon** only; Issue #28 / SPEC §42.2):
     ///
     /// 1. Non-empty [`api_key`](Self::api_key) from config file (`~/.stockterm.json`).
@@ -135,6 +290,65 @@ impl Config {
         }
     }
 
+    /// Alpha Vantage key: non-empty [`alpha_vantage_api_key`](Self::alpha_vantage_api_key), else
+    /// non-empty `STOCKTERM_ALPHA_VANTAGE_API_KEY`, else empty. Same rules as
+    /// [`effective_api_key`](Self::effective_api_key).
+    pub fn effective_alpha_vantage_api_key(&self) -> Cow<'_, str> {
+        if !self.alpha_vantage_api_key.is_empty() {
+            return Cow::Borrowed(self.alpha_vantage_api_key.as_str());
+        }
+        match std::env::var("STOCKTERM_ALPHA_VANTAGE_API_KEY") {
+            Ok(s) if !s.is_empty() => Cow::Owned(s),
+            _ => Cow::Borrowed(""),
+        }
+    }
+
+    /// Finnhub key: non-empty [`finnhub_api_key`](Self::finnhub_api_key), else non-empty
+    /// `STOCKTERM_FINNHUB_API_KEY`, else empty.
+    pub fn effective_finnhub_api_key(&self) -> Cow<'_, str> {
+        if !self.finnhub_api_key.is_empty() {
+            return Cow::Borrowed(self.finnhub_api_key.as_str());
+        }
+        match std::env::var("STOCKTERM_FINNHUB_API_KEY") {
+            Ok(s) if !s.is_empty() => Cow::Owned(s),
+            _ => Cow::Borrowed(""),
+        }
+    }
+
+    /// Whether `kind` has the API key it needs (Yahoo needs none).
+    pub fn has_credentials_for(&self, kind: MarketProviderKind) -> bool {
+        match kind {
+            MarketProviderKind::Yahoo => true,
+            MarketProviderKind::Polygon => !self.effective_api_key().is_empty(),
+            MarketProviderKind::AlphaVantage => !self.effective_alpha_vantage_api_key().is_empty(),
+            MarketProviderKind::Finnhub => !self.effective_finnhub_api_key().is_empty(),
+        }
+    }
+
+    /// Client-side request budget for `kind`: the [`rate_limits`](Self::rate_limits) entry, else
+    /// the free tier (Polygon and Alpha Vantage 5/min, Finnhub 60/min, Yahoo unlimited). `None` =
+    /// unlimited, including an explicit `0`.
+    pub fn requests_per_minute(&self, kind: MarketProviderKind) -> Option<u32> {
+        let default = match kind {
+            MarketProviderKind::Yahoo => 0,
+            MarketProviderKind::Polygon | MarketProviderKind::AlphaVantage => 5,
+            MarketProviderKind::Finnhub => 60,
+        };
+        Some(self.rate_limits.get(&kind).copied().unwrap_or(default)).filter(|&rpm| rpm > 0)
+    }
+
+    /// [`provider`](Self::provider) followed by each [`fallback_providers`](Self::fallback_providers)
+    /// entry that has credentials, without duplicates.
+    pub fn provider_chain(&self) -> Vec<MarketProviderKind> {
+        let mut chain = vec![self.provider];
+        for &kind in &self.fallback_providers {
+            if !chain.contains(&kind) && self.has_credentials_for(kind) {
+                chain.push(kind);
+            }
+        }
+        chain
+    }
+
     /// Load config from disk, or [`Config::default`] on any error.
     ///
     /// **Prefer [`try_load`](Self::try_load)** for interactive applications: failures are invisible
@@ -182,6 +396,13 @@ impl Config {
         path.push(".stockterm.json");
         Ok(path)
     }
+
+    /// `~/.stockterm_alert_history.jsonl` — every alert firing, next to the config file.
+    pub fn alert_history_path() -> Result<PathBuf, ConfigError> {
+        let mut path = dirs::home_dir().ok_or(ConfigError::NoHomeDir)?;
+        path.push(".stockterm_alert_history.jsonl");
+        Ok(path)
+    }
 }
 
 #[cfg(test)]
@@ -240,6 +461,33 @@ mod tests {
         assert_eq!(c.provider, MarketProviderKind::Yahoo);
     }
 
+    #[test]
+    fn provider_chain_dedupes_and_skips_fallbacks_without_keys() {
+        let _lock = ENV_TEST_LOCK.lock().expect("env test lock");
+        let _guard = EnvVarGuard::set("STOCKTERM_ALPHA_VANTAGE_API_KEY", "");
+        let j = r#"{"refresh_rate":0,"api_key":"","alerts":[],"default_symbol":"","provider":"yahoo","finnhub_api_key":"k","fallback_providers":["finnhub","yahoo","alphavantage","finnhub"]}"#;
+        let c: Config = serde_json::from_str(j).expect("parse");
+        assert_eq!(
+            c.provider_chain(),
+            [MarketProviderKind::Yahoo, MarketProviderKind::Finnhub]
+        );
+        assert!(Config::default().fallback_providers.is_empty());
+    }
+
+    #[test]
+    fn requests_per_minute_defaults_to_free_tiers_and_honors_overrides() {
+        let c = Config::default();
+        assert_eq!(c.requests_per_minute(MarketProviderKind::Polygon), Some(5));
+        assert_eq!(c.requests_per_minute(MarketProviderKind::Finnhub), Some(60));
+        assert_eq!(c.requests_per_minute(MarketProviderKind::Yahoo), None);
+
+        let j = r#"{"refresh_rate":0,"api_key":"","alerts":[],"default_symbol":"","rate_limits":{"polygon":100,"alpha_vantage":0,"yahoo":30}}"#;
+        let c: Config = serde_json::from_str(j).expect("parse");
+        assert_eq!(c.requests_per_minute(MarketProviderKind::Polygon), Some(100));
+        assert_eq!(c.requests_per_minute(MarketProviderKind::AlphaVantage), None);
+        assert_eq!(c.requests_per_minute(MarketProviderKind::Yahoo), Some(30));
+    }
+
     #[test]
     fn serde_notifications_enabled_defaults_when_omitted() {
         let j = r#"{"portfolio":[],"watchlist":[],"refresh_rate":0,"api_key":"","alerts":[],"default_symbol":"","provider":"yahoo"}"#;
@@ -252,6 +500,15 @@ mod tests {
         let j = r#"{"portfolio":[],"watchlist":[],"refresh_rate":0,"api_key":"","alerts":[],"default_symbol":"","provider":"polygon"}"#;
         let c: Config = serde_json::from_str(j).expect("parse");
         assert_eq!(c.provider, MarketProviderKind::Polygon);
+        for spelling in ["alphavantage", "alpha_vantage"] {
+            let j = format!(r#"{{"refresh_rate":0,"api_key":"","alerts":[],"default_symbol":"","provider":"{spelling}"}}"#);
+            let c: Config = serde_json::from_str(&j).expect("parse");
+            assert_eq!(c.provider, MarketProviderKind::AlphaVantage);
+        }
+        assert_eq!(
+            serde_json::to_value(MarketProviderKind::AlphaVantage).unwrap(),
+            MarketProviderKind::AlphaVantage.as_str()
+        );
     }
 
     #[test]
@@ -279,6 +536,67 @@ mod tests {
         );
     }
 
+    #[test]
+    fn serde_chart_indicators_keyed_by_time_range() {
+        let j = r#"{"portfolio":[],"watchlist":[],"refresh_rate":0,"api_key":"","alerts":[],"default_symbol":"","provider":"yahoo","chart_indicators":{"y1":["bollinger","macd"]}}"#;
+        let c: Config = serde_json::from_str(j).expect("parse");
+        assert_eq!(
+            c.chart_indicators.get(&TimeRange::Y1).map(Vec::as_slice),
+            Some(&[Indicator::Bollinger, Indicator::Macd][..])
+        );
+        assert!(!c.chart_indicators.contains_key(&TimeRange::M1));
+    }
+
+    #[test]
+    fn serde_last_time_range_and_custom_range() {
+        let j = r#"{"portfolio":[],"watchlist":[],"refresh_rate":0,"api_key":"","alerts":[],"default_symbol":"","provider":"yahoo","last_time_range":"custom","custom_range":{"from":"2024-01-02","to":"2024-06-28"}}"#;
+        let c: Config = serde_json::from_str(j).expect("parse");
+        assert_eq!(c.last_time_range, Some(TimeRange::Custom));
+        let custom = c.custom_range.expect("custom_range");
+        assert_eq!(custom.from.to_string(), "2024-01-02");
+        assert_eq!(custom.to.to_string(), "2024-06-28");
+        let back = serde_json::to_string(&c).expect("serialize");
+        assert!(back.contains(r#""custom_range":{"from":"2024-01-02","to":"2024-06-28"}"#));
+    }
+
+    #[test]
+    fn legacy_portfolio_moves_into_main_account() {
+        let j = r#"{"portfolio":[{"symbol":"AAPL","shares":2,"purchase_price":100}],"watchlist":[],"refresh_rate":0,"api_key":"","alerts":[],"default_symbol":""}"#;
+        let mut c: Config = serde_json::from_str(j).expect("parse");
+        c.normalize_portfolios();
+        assert_eq!(c.portfolios.len(), 1);
+        assert_eq!(c.portfolios[0].name, "Main");
+        assert_eq!(c.portfolios[0].holdings[0].symbol, "AAPL");
+        let back = serde_json::to_string(&c).expect("serialize");
+        assert!(!back.contains(r#""portfolio":"#));
+
+        let j = r#"{"portfolios":[{"name":"IRA"},{"name":"Taxable","holdings":[]}],"refresh_rate":0,"api_key":"","alerts":[],"default_symbol":""}"#;
+        let mut c: Config = serde_json::from_str(j).expect("parse");
+        c.normalize_portfolios();
+        let names: Vec<&str> = c.portfolios.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["IRA", "Taxable"]);
+    }
+
+    #[test]
+    fn legacy_watchlist_moves_into_main_list() {
+        let j = r#"{"watchlist":["AAPL","MSFT"],"refresh_rate":0,"api_key":"","alerts":[],"default_symbol":""}"#;
+        let mut c: Config = serde_json::from_str(j).expect("parse");
+        c.normalize_watchlists();
+        assert_eq!(c.watchlists, vec![Watchlist {
+            name: "Main".to_string(),
+            symbols: vec!["AAPL".to_string(), "MSFT".to_string()],
+        }]);
+        let back = serde_json::to_string(&c).expect("serialize");
+        assert!(!back.contains(r#""watchlist":"#));
+
+        let j = r#"{"watchlists":[{"name":"Tech","symbols":["AAPL"]},{"name":"Energy"}],"last_watchlist":"Energy","refresh_rate":0,"api_key":"","alerts":[],"default_symbol":""}"#;
+        let mut c: Config = serde_json::from_str(j).expect("parse");
+        c.normalize_watchlists();
+        let names: Vec<&str> = c.watchlists.iter().map(|w| w.name.as_str()).collect();
+        assert_eq!(names, ["Tech", "Energy"]);
+        assert_eq!(c.last_watchlist.as_deref(), Some("Energy"));
+    }
+
     #[test]
     fn load_config_from_path_invalid_json_returns_serde_error() {
         let dir =
@@ -35,6 +35,20 @@ pub enum BindingLayer {
     AlertDialog,
     /// Table filter edit mode on Stock View / Portfolio (Issue #137 / SPEC §28).
     FilterInput,
+    /// Charts compare-symbol prompt (symbol characters stay wildcard, like Search).
+    ChartCompareInput,
+    /// Charts custom from/to date dialog (digits and `-` stay wildcard).
+    ChartRangeDialog,
+    /// Portfolio CSV import / export path prompt and import preview (path characters stay wildcard).
+    PortfolioCsvDialog,
+    /// Portfolio new-account name prompt (name characters stay wildcard).
+    PortfolioAccountInput,
+    /// Stock View new / rename / delete watchlist prompt (name characters stay wildcard).
+    WatchlistPrompt,
+    /// Alerts history sub-view (browse, filter, export).
+    AlertHistory,
+    /// Alerts history CSV export path prompt (path characters stay wildcard).
+    AlertHistoryExportPrompt,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -71,16 +85,66 @@ pub enum Action {
     StockRowUp,
     StockBackspace,
     StockEnter,
+    /// Stock View: next named watchlist (default `]`).
+    WatchlistNext,
+    /// Stock View: previous named watchlist (default `[`).
+    WatchlistPrev,
+    /// Stock View: create a named watchlist (default `Ctrl+N`).
+    WatchlistNew,
+    /// Stock View: rename the active watchlist (default `Ctrl+T`).
+    WatchlistRename,
+    /// Stock View: delete the active watchlist after confirming (default `Ctrl+X`).
+    WatchlistDelete,
+    /// Stock View: sort by the next visible column, then insertion order (default `.`).
+    WatchlistSortNext,
+    /// Stock View: sort by the previous visible column (default `,`).
+    WatchlistSortPrev,
+    /// Stock View: flip ascending / descending (default `-`).
+    WatchlistSortReverse,
+    WatchlistPromptEsc,
+    WatchlistPromptEnter,
+    WatchlistPromptBackspace,
     ChartRangeD1,
     ChartRangeW1,
     ChartRangeM1,
+    ChartRangeM3,
+    ChartRangeM6,
+    ChartRangeYtd,
     ChartRangeY1,
+    ChartRangeY5,
+    ChartRangeMax,
+    /// Charts: open the custom from/to date dialog (default `t`).
+    ChartRangeCustom,
     ChartResetViewport,
     ChartZoomIn,
     ChartZoomOut,
     ChartPanLeft,
     ChartPanRight,
     ChartToggleCandle,
+    /// Charts: toggle the SMA overlay for the current range (default `s`).
+    ChartToggleSma,
+    ChartToggleEma,
+    ChartToggleBollinger,
+    /// Charts: toggle the RSI sub-panel (default `r`).
+    ChartToggleRsi,
+    ChartToggleMacd,
+    /// Charts: show/hide the crosshair (default `x`).
+    ChartCursorToggle,
+    /// Charts: move the crosshair one bar (defaults `,` / `.`, Shift+←/→).
+    ChartCursorLeft,
+    ChartCursorRight,
+    /// Charts: open the compare-symbol prompt (default `a`).
+    ChartCompareAdd,
+    /// Charts: drop the last compare symbol (default `d`).
+    ChartCompareRemove,
+    ChartCompareInputEsc,
+    ChartCompareInputEnter,
+    ChartCompareInputBackspace,
+    ChartRangeDialogEsc,
+    ChartRangeDialogEnter,
+    ChartRangeDialogBackspace,
+    /// Custom range dialog: switch between From and To (defaults Tab / Shift+Tab).
+    ChartRangeDialogNextField,
     SearchEsc,
     SearchBackspace,
     SearchEnter,
@@ -106,6 +170,25 @@ pub enum Action {
     SettingsEditSymbolChar,
     PortfolioFilterToggle,
     PortfolioAdd,
+    /// Portfolio: record a sell for the selected holding (default `s`).
+    PortfolioSell,
+    /// Portfolio: show/hide the open-lots panel (default `l`).
+    PortfolioToggleLots,
+    /// Portfolio: cycle FIFO / LIFO / average cost basis (default `m`).
+    PortfolioCycleCostBasis,
+    /// Portfolio: import trades or positions from a CSV file (default `i`).
+    PortfolioImportCsv,
+    /// Portfolio: export the ledger to a CSV file (default `e`).
+    PortfolioExportCsv,
+    /// Portfolio: next account, then All accounts (default `]`).
+    PortfolioAccountNext,
+    /// Portfolio: previous account (default `[`).
+    PortfolioAccountPrev,
+    /// Portfolio: create a named account (default `n`).
+    PortfolioAccountNew,
+    PortfolioAccountInputEsc,
+    PortfolioAccountInputEnter,
+    PortfolioAccountInputBackspace,
     PortfolioRemoveArm,
     PortfolioRowDown,
     PortfolioRowUp,
@@ -118,10 +201,33 @@ pub enum Action {
     PortfolioDialogBackspace,
     PortfolioDialogEnter,
     PortfolioDialogDigitOrDot,
+    PortfolioCsvEsc,
+    /// Path prompt: parse (import) or write (export); preview: merge the parsed rows.
+    PortfolioCsvEnter,
+    PortfolioCsvBackspace,
+    /// Import path prompt: cycle the broker column preset (defaults Tab / Shift+Tab).
+    PortfolioCsvNextPreset,
+    PortfolioCsvScrollDown,
+    PortfolioCsvScrollUp,
     AlertAdd,
     AlertRemove,
     AlertRowUp,
     AlertRowDown,
+    /// Alerts: show / hide expired alerts from `archived_alerts` (default `h`).
+    AlertToggleArchived,
+    /// Alerts: open the alert history log (default `l`).
+    AlertHistoryOpen,
+    /// Alert history: back to the alerts table (defaults `esc`, `l`).
+    AlertHistoryClose,
+    AlertHistoryRowUp,
+    AlertHistoryRowDown,
+    /// Alert history: filter by symbol (default `slash`).
+    AlertHistoryFilter,
+    /// Alert history: export the shown rows to CSV (default `e`).
+    AlertHistoryExport,
+    AlertHistoryExportEsc,
+    AlertHistoryExportEnter,
+    AlertHistoryExportBackspace,
     AlertDialogEsc,
     AlertDialogTab,
     AlertDialogShiftTab,
@@ -159,11 +265,23 @@ pub fn action_binding_layer(a: Action) -> BindingLayer {
             BindingLayer::ErrorOverlay
         }
         StockFilterToggle | WatchlistAdd | WatchlistRemove | WatchlistRemoveShift | StockRowDown
-        | StockRowUp | StockBackspace | StockEnter => BindingLayer::StockView,
-        ChartRangeD1 | ChartRangeW1 | ChartRangeM1 | ChartRangeY1 | ChartResetViewport
-        | ChartZoomIn | ChartZoomOut | ChartPanLeft | ChartPanRight | ChartToggleCandle => {
-            BindingLayer::Charts
+        | StockRowUp | StockBackspace | StockEnter | WatchlistNext | WatchlistPrev | WatchlistNew
+        | WatchlistRename | WatchlistDelete | WatchlistSortNext | WatchlistSortPrev
+        | WatchlistSortReverse => BindingLayer::StockView,
+        WatchlistPromptEsc | WatchlistPromptEnter | WatchlistPromptBackspace => {
+            BindingLayer::WatchlistPrompt
         }
+        ChartRangeD1 | ChartRangeW1 | ChartRangeM1 | ChartRangeM3 | ChartRangeM6
+        | ChartRangeYtd | ChartRangeY1 | ChartRangeY5 | ChartRangeMax | ChartRangeCustom
+        | ChartResetViewport | ChartZoomIn | ChartZoomOut | ChartPanLeft | ChartPanRight | ChartToggleCandle
+        | ChartToggleSma | ChartToggleEma | ChartToggleBollinger | ChartToggleRsi
+        | ChartToggleMacd | ChartCursorToggle | ChartCursorLeft | ChartCursorRight
+        | ChartCompareAdd | ChartCompareRemove => BindingLayer::Charts,
+        ChartCompareInputEsc | ChartCompareInputEnter | ChartCompareInputBackspace => {
+            BindingLayer::ChartCompareInput
+        }
+        ChartRangeDialogEsc | ChartRangeDialogEnter | ChartRangeDialogBackspace
+        | ChartRangeDialogNextField => BindingLayer::ChartRangeDialog,
         SearchEsc | SearchBackspace | SearchEnter | SearchRowDown | SearchRowUp => {
             BindingLayer::Search
         }
@@ -172,14 +290,27 @@ pub fn action_binding_layer(a: Action) -> BindingLayer {
         | SettingsRowUp | SettingsEnter => BindingLayer::SettingsBrowse,
         SettingsEditEsc | SettingsEditEnter | SettingsEditBackspace | SettingsEditDigit
         | SettingsEditSymbolChar => BindingLayer::SettingsEdit,
-        PortfolioFilterToggle | PortfolioAdd | PortfolioRemoveArm | PortfolioRowDown
+        PortfolioFilterToggle | PortfolioAdd | PortfolioSell | PortfolioToggleLots
+        | PortfolioCycleCostBasis | PortfolioImportCsv | PortfolioExportCsv | PortfolioAccountNext
+        | PortfolioAccountPrev | PortfolioAccountNew | PortfolioRemoveArm | PortfolioRowDown
         | PortfolioRowUp | PortfolioEnterStock => BindingLayer::Portfolio,
+        PortfolioAccountInputEsc | PortfolioAccountInputEnter | PortfolioAccountInputBackspace => {
+            BindingLayer::PortfolioAccountInput
+        }
         PortfolioRemoveCancel | PortfolioRemoveDecline | PortfolioRemoveConfirm => {
             BindingLayer::PortfolioRemoveArmed
         }
         PortfolioDialogEsc | PortfolioDialogFocusNext | PortfolioDialogBackspace
         | PortfolioDialogEnter | PortfolioDialogDigitOrDot => BindingLayer::PortfolioDialog,
-        AlertAdd | AlertRemove | AlertRowUp | AlertRowDown => BindingLayer::Alerts,
+        PortfolioCsvEsc | PortfolioCsvEnter | PortfolioCsvBackspace | PortfolioCsvNextPreset
+        | PortfolioCsvScrollDown | PortfolioCsvScrollUp => BindingLayer::PortfolioCsvDialog,
+        AlertAdd | AlertRemove | AlertRowUp | AlertRowDown | AlertToggleArchived
+        | AlertHistoryOpen => BindingLayer::Alerts,
+        AlertHistoryClose | AlertHistoryRowUp | AlertHistoryRowDown | AlertHistoryFilter
+        | AlertHistoryExport => BindingLayer::AlertHistory,
+        AlertHistoryExportEsc | AlertHistoryExportEnter | AlertHistoryExportBackspace => {
+            BindingLayer::AlertHistoryExportPrompt
+        }
         AlertDialogEsc | AlertDialogTab | AlertDialogShiftTab | AlertDialogLeft
         | AlertDialogRight | AlertDialogConditionCycleOrFocusNext | AlertDialogEnter
         | AlertDialogBackspace | AlertDialogDigitOrDot | AlertDialogSymbolChar
@@ -470,10 +601,27 @@ const DEFAULT_BINDINGS: &[(BindingLayer, &'static str, Action)] = {
         (StockView, "up", StockRowUp),
         (StockView, "backspace", StockBackspace),
         (StockView, "enter", StockEnter),
+        (StockView, "char:]", WatchlistNext),
+        (StockView, "char:[", WatchlistPrev),
+        (StockView, "ctrl+n", WatchlistNew),
+        (StockView, "ctrl+t", WatchlistRename),
+        (StockView, "ctrl+x", WatchlistDelete),
+        (StockView, "char:.", WatchlistSortNext),
+        (StockView, "char:,", WatchlistSortPrev),
+        (StockView, "minus", WatchlistSortReverse),
+        (WatchlistPrompt, "esc", WatchlistPromptEsc),
+        (WatchlistPrompt, "enter", WatchlistPromptEnter),
+        (WatchlistPrompt, "backspace", WatchlistPromptBackspace),
         (Charts, "1", ChartRangeD1),
         (Charts, "2", ChartRangeW1),
         (Charts, "3", ChartRangeM1),
         (Charts, "4", ChartRangeY1),
+        (Charts, "5", ChartRangeM3),
+        (Charts, "6", ChartRangeM6),
+        (Charts, "7", ChartRangeYtd),
+        (Charts, "8", ChartRangeY5),
+        (Charts, "9", ChartRangeMax),
+        (Charts, "char:t", ChartRangeCustom),
         (Charts, "0", ChartResetViewport),
         (Charts, "plus", ChartZoomIn),
         (Charts, "shift+=", ChartZoomIn),
@@ -483,6 +631,26 @@ const DEFAULT_BINDINGS: &[(BindingLayer, &'static str, Action)] = {
         (Charts, "left", ChartPanLeft),
         (Charts, "right", ChartPanRight),
         (Charts, "char:c", ChartToggleCandle),
+        (Charts, "char:s", ChartToggleSma),
+        (Charts, "char:e", ChartToggleEma),
+        (Charts, "char:b", ChartToggleBollinger),
+        (Charts, "char:r", ChartToggleRsi),
+        (Charts, "char:m", ChartToggleMacd),
+        (Charts, "char:x", ChartCursorToggle),
+        (Charts, "char:,", ChartCursorLeft),
+        (Charts, "shift+left", ChartCursorLeft),
+        (Charts, "char:.", ChartCursorRight),
+        (Charts, "shift+right", ChartCursorRight),
+        (Charts, "char:a", ChartCompareAdd),
+        (Charts, "char:d", ChartCompareRemove),
+        (ChartCompareInput, "esc", ChartCompareInputEsc),
+        (ChartCompareInput, "enter", ChartCompareInputEnter),
+        (ChartCompareInput, "backspace", ChartCompareInputBackspace),
+        (ChartRangeDialog, "esc", ChartRangeDialogEsc),
+        (ChartRangeDialog, "enter", ChartRangeDialogEnter),
+        (ChartRangeDialog, "backspace", ChartRangeDialogBackspace),
+        (ChartRangeDialog, "tab", ChartRangeDialogNextField),
+        (ChartRangeDialog, "backtab", ChartRangeDialogNextField),
         (Search, "esc", SearchEsc),
         (Search, "backspace", SearchBackspace),
         (Search, "enter", SearchEnter),
@@ -511,6 +679,17 @@ const DEFAULT_BINDINGS: &[(BindingLayer, &'static str, Action)] = {
         (SettingsEdit, "backspace", SettingsEditBackspace),
         (Portfolio, "slash", PortfolioFilterToggle),
         (Portfolio, "char:a", PortfolioAdd),
+        (Portfolio, "char:s", PortfolioSell),
+        (Portfolio, "char:l", PortfolioToggleLots),
+        (Portfolio, "char:m", PortfolioCycleCostBasis),
+        (Portfolio, "char:i", PortfolioImportCsv),
+        (Portfolio, "char:e", PortfolioExportCsv),
+        (Portfolio, "char:]", PortfolioAccountNext),
+        (Portfolio, "char:[", PortfolioAccountPrev),
+        (Portfolio, "char:n", PortfolioAccountNew),
+        (PortfolioAccountInput, "esc", PortfolioAccountInputEsc),
+        (PortfolioAccountInput, "enter", PortfolioAccountInputEnter),
+        (PortfolioAccountInput, "backspace", PortfolioAccountInputBackspace),
         (Portfolio, "char:d", PortfolioRemoveArm),
         (Portfolio, "char:j", PortfolioRowDown),
         (Portfolio, "down", PortfolioRowDown),
@@ -525,6 +704,13 @@ const DEFAULT_BINDINGS: &[(BindingLayer, &'static str, Action)] = {
         (PortfolioRemoveArmed, "down", PortfolioRowDown),
         (PortfolioRemoveArmed, "char:j", PortfolioRowDown),
         (PortfolioRemoveArmed, "char:k", PortfolioRowUp),
+        (PortfolioCsvDialog, "esc", PortfolioCsvEsc),
+        (PortfolioCsvDialog, "enter", PortfolioCsvEnter),
+        (PortfolioCsvDialog, "backspace", PortfolioCsvBackspace),
+        (PortfolioCsvDialog, "tab", PortfolioCsvNextPreset),
+        (PortfolioCsvDialog, "backtab", PortfolioCsvNextPreset),
+        (PortfolioCsvDialog, "down", PortfolioCsvScrollDown),
+        (PortfolioCsvDialog, "up", PortfolioCsvScrollUp),
         (PortfolioDialog, "esc", PortfolioDialogEsc),
         (PortfolioDialog, "semicolon", PortfolioDialogFocusNext),
         (PortfolioDialog, "backspace", PortfolioDialogBackspace),
@@ -533,6 +719,17 @@ const DEFAULT_BINDINGS: &[(BindingLayer, &'static str, Action)] = {
         (Alerts, "char:d", AlertRemove),
         (Alerts, "up", AlertRowUp),
         (Alerts, "down", AlertRowDown),
+        (Alerts, "char:h", AlertToggleArchived),
+        (Alerts, "char:l", AlertHistoryOpen),
+        (AlertHistory, "esc", AlertHistoryClose),
+        (AlertHistory, "char:l", AlertHistoryClose),
+        (AlertHistory, "up", AlertHistoryRowUp),
+        (AlertHistory, "down", AlertHistoryRowDown),
+        (AlertHistory, "slash", AlertHistoryFilter),
+        (AlertHistory, "char:e", AlertHistoryExport),
+        (AlertHistoryExportPrompt, "esc", AlertHistoryExportEsc),
+        (AlertHistoryExportPrompt, "enter", AlertHistoryExportEnter),
+        (AlertHistoryExportPrompt, "backspace", AlertHistoryExportBackspace),
         (AlertDialog, "esc", AlertDialogEsc),
         (AlertDialog, "tab", AlertDialogTab),
         (AlertDialog, "backtab", AlertDialogShiftTab),
@@ -546,6 +743,7 @@ const DEFAULT_BINDINGS: &[(BindingLayer, &'static str, Action)] = {
         (FilterInput, "backspace", FilterBackspace),
         (FilterInput, "slash", FilterSlash),
         (PortfolioDialog, "char:.", PortfolioDialogDigitOrDot),
+        (PortfolioDialog, "char:-", PortfolioDialogDigitOrDot),
         (AlertDialog, "char:.", AlertDialogDigitOrDot),
         (AlertDialog, "char:a", AlertDialogConditionAbove),
         (AlertDialog, "char:b", AlertDialogConditionBelow),
@@ -877,11 +1075,17 @@ mod tests {
             km.action(BindingLayer::PortfolioDialog, &dot),
             Some(Action::PortfolioDialogDigitOrDot)
         );
+        // `-` separates trade-date parts in the Date field.
+        let dash = KeyEvent::new(KeyCode::Char('-'), KeyModifiers::NONE);
+        assert_eq!(
+            km.action(BindingLayer::PortfolioDialog, &dash),
+            Some(Action::PortfolioDialogDigitOrDot)
+        );
         let n = default_bindings()
             .iter()
             .filter(|&&(_, _, a)| a == Action::PortfolioDialogDigitOrDot)
             .count();
-        assert_eq!(n, 11);
+        assert_eq!(n, 12);
     }
 
     #[test]
@@ -921,6 +1125,20 @@ mod tests {
         assert_eq!(n_digit, 10);
     }
 
+    #[test]
+    fn chart_range_digits_follow_time_range_presets() {
+        let km = ResolvedKeymap::build(None).0;
+        for (digit, range) in ('1'..='9').zip(crate::models::time_range::TimeRange::PRESETS) {
+            let key = KeyEvent::new(KeyCode::Char(digit), KeyModifiers::NONE);
+            let action = km.action(BindingLayer::Charts, &key);
+            assert_eq!(
+                action.map(|a| format!("{a:?}")),
+                Some(format!("ChartRange{range:?}")),
+                "key {digit}"
+            );
+        }
+    }
+
     #[test]
     fn default_map_binds_news_copy_url() {
         use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
@@ -1063,7 +1281,7 @@ mod tests {
 
     #[test]
     fn default_bindings_total_row_count() {
-        assert_eq!(default_bindings().len(), 220);
+        assert_eq!(default_bindings().len(), 287);
     }
 
     #[test]
@@ -1,5 +1,6 @@
 //! Issue #15 / [`docs/SPEC.md`](../../docs/SPEC.md) §31 — layout preferences.
 
+use crate::models::watchlist::WatchlistColumn;
 use serde::{Deserialize, Serialize};
 
 const STOCK_VIEW_WATCHLIST_PCT_DEFAULT: u8 = 42;
@@ -8,6 +9,8 @@ const STOCK_VIEW_WATCHLIST_PCT_MIN: u8 = 20;
 const STOCK_VIEW_WATCHLIST_PCT_MAX: u8 = 80;
 const CHARTS_CHART_PCT_MIN: u8 = 30;
 const CHARTS_CHART_PCT_MAX: u8 = 100;
+const CHARTS_VOLUME_PCT_DEFAULT: u8 = 20;
+const CHARTS_VOLUME_PCT_MAX: u8 = 50;
 
 /// Built-in layout presets (serde snake_case).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -56,18 +59,21 @@ impl LayoutPreset {
                 show_status_bar: false,
                 stock_view_watchlist_pct: 35,
                 charts_chart_pct: 100,
+                charts_volume_pct: 15,
             },
             LayoutPreset::Wide => LayoutValues {
                 show_tab_bar: true,
                 show_status_bar: true,
                 stock_view_watchlist_pct: 30,
                 charts_chart_pct: 100,
+                charts_volume_pct: CHARTS_VOLUME_PCT_DEFAULT,
             },
             LayoutPreset::ChartFocused => LayoutValues {
                 show_tab_bar: true,
                 show_status_bar: true,
                 stock_view_watchlist_pct: 35,
                 charts_chart_pct: 85,
+                charts_volume_pct: 25,
             },
         }
     }
@@ -79,6 +85,7 @@ struct LayoutValues {
     show_status_bar: bool,
     stock_view_watchlist_pct: u8,
     charts_chart_pct: u8,
+    charts_volume_pct: u8,
 }
 
 impl LayoutValues {
@@ -88,6 +95,7 @@ impl LayoutValues {
             show_status_bar: true,
             stock_view_watchlist_pct: STOCK_VIEW_WATCHLIST_PCT_DEFAULT,
             charts_chart_pct: CHARTS_CHART_PCT_DEFAULT,
+            charts_volume_pct: CHARTS_VOLUME_PCT_DEFAULT,
         }
     }
 }
@@ -105,8 +113,14 @@ pub struct Layout {
     pub stock_view_watchlist_pct: Option<u8>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub charts_chart_pct: Option<u8>,
+    /// Share of the chart body given to the volume histogram (`0` hides it).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub charts_volume_pct: Option<u8>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub preset: Option<LayoutPreset>,
+    /// Stock View watchlist columns in display order (not part of presets).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub watchlist_columns: Option<Vec<WatchlistColumn>>,
 }
 
 /// Clamped layout inputs for one frame (Issue #15).
@@ -116,6 +130,7 @@ pub struct ResolvedLayout {
     pub show_status_bar: bool,
     pub stock_view_watchlist_pct: u16,
     pub charts_chart_pct: u16,
+    pub charts_volume_pct: u16,
 }
 
 impl Layout {
@@ -126,8 +141,27 @@ impl Layout {
             show_status_bar: Some(b.show_status_bar),
             stock_view_watchlist_pct: Some(b.stock_view_watchlist_pct),
             charts_chart_pct: Some(b.charts_chart_pct),
+            charts_volume_pct: Some(b.charts_volume_pct),
             preset: Some(preset),
+            watchlist_columns: None,
+        }
+    }
+
+    /// Watchlist columns to draw: [`WatchlistColumn::Symbol`] first, duplicates dropped, and
+    /// [`WatchlistColumn::DEFAULT`] when unset or empty.
+    pub fn watchlist_columns(&self) -> Vec<WatchlistColumn> {
+        let configured = self
+            .watchlist_columns
+            .as_deref()
+            .filter(|c| !c.is_empty())
+            .unwrap_or(&WatchlistColumn::DEFAULT);
+        let mut out = vec![WatchlistColumn::Symbol];
+        for &c in configured {
+            if !out.contains(&c) {
+                out.push(c);
+            }
         }
+        out
     }
 
     pub fn effective_preset(&self) -> LayoutPreset {
@@ -156,11 +190,13 @@ impl Layout {
             .stock_view_watchlist_pct
             .unwrap_or(base.stock_view_watchlist_pct);
         let charts = self.charts_chart_pct.unwrap_or(base.charts_chart_pct);
+        let volume = self.charts_volume_pct.unwrap_or(base.charts_volume_pct);
         ResolvedLayout {
             show_tab_bar,
             show_status_bar,
             stock_view_watchlist_pct: clamp_stock_view_watchlist_pct(stock),
             charts_chart_pct: clamp_charts_chart_pct(charts),
+            charts_volume_pct: u16::from(volume.min(CHARTS_VOLUME_PCT_MAX)),
         }
     }
 }
@@ -187,6 +223,7 @@ mod tests {
         assert!(r.show_status_bar);
         assert_eq!(r.stock_view_watchlist_pct, 42);
         assert_eq!(r.charts_chart_pct, 100);
+        assert_eq!(r.charts_volume_pct, 20);
     }
 
     #[test]
@@ -221,6 +258,22 @@ mod tests {
         assert_eq!(r.charts_chart_pct, 100);
     }
 
+    #[test]
+    fn charts_volume_pct_zero_hides_and_caps_at_half() {
+        let r = Layout {
+            charts_volume_pct: Some(0),
+            ..Layout::default()
+        }
+        .resolve();
+        assert_eq!(r.charts_volume_pct, 0);
+        let r = Layout {
+            charts_volume_pct: Some(90),
+            ..Layout::default()
+        }
+        .resolve();
+        assert_eq!(r.charts_volume_pct, 50);
+    }
+
     #[test]
     fn preset_compact_hides_status_bar() {
         let r = Layout {
@@ -252,6 +305,22 @@ mod tests {
         assert_eq!(r.stock_view_watchlist_pct, 42);
     }
 
+    #[test]
+    fn watchlist_columns_keep_symbol_first_and_drop_duplicates() {
+        assert_eq!(Layout::default().watchlist_columns(), WatchlistColumn::DEFAULT);
+        let j = r#"{"watchlist_columns":["market_cap","last","symbol","last","week52_range"]}"#;
+        let l: Layout = serde_json::from_str(j).unwrap();
+        assert_eq!(
+            l.watchlist_columns(),
+            [
+                WatchlistColumn::Symbol,
+                WatchlistColumn::MarketCap,
+                WatchlistColumn::Last,
+                WatchlistColumn::Week52Range,
+            ]
+        );
+    }
+
     #[test]
     fn preset_chart_focused_charts_pct() {
         let r = Layout::from_preset(LayoutPreset::ChartFocused).resolve();
@@ -4,7 +4,7 @@ pub mod keymap;
 pub mod layout;
 pub mod theme;
 
-pub use self::config::{Config, ConfigError, MarketProviderKind};
+pub use self::config::{Config, ConfigError, MarketProviderKind, ALL_PORTFOLIOS_KEY};
 pub use self::keymap::{Action, BindingLayer, Chord, KeymapParseError, ResolvedKeymap};
 pub use self::layout::{Layout, LayoutPreset, ResolvedLayout};
 pub use self::theme::{parse_hex_rgb, PaletteRgb, Theme, ThemePalette, ThemePreset};
@@ -1,5 +1,6 @@
 pub mod app;
 pub mod api;
+pub mod cli;
 pub mod config;
 pub mod logging;
 pub mod models;
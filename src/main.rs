@@ -5,17 +5,24 @@ use crossterm::{
     },
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
+use clap::Parser;
 use std::io;
 use stockterm::app::App;
+use stockterm::cli::Cli;
 
 #[tokio::main]
 async fn main() -> Result<(), io::Error> {
+    let cli = Cli::parse();
     stockterm::init();
     if let Err(e) = stockterm::api::http::init_shared_client() {
         eprintln!("stockterm: {e}");
         std::process::exit(1);
     }
 
+    if let Some(command) = cli.command {
+        std::process::exit(stockterm::cli::run(command, cli.json).await);
+    }
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
@@ -0,0 +1,268 @@
+//! Append-only log of fired alerts: `~/.stockterm_alert_history.jsonl`, one JSON object per line.
+//!
+//! A firing is written when it fires, with the channels still delivering listed as `pending`; a
+//! later `outcome` line for the same firing settles them and is folded in on read.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, ErrorKind, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::alert_sink::AlertEvent;
+
+/// Channel name recorded for the terminal bell.
+pub const BELL_CHANNEL: &str = "bell";
+/// Channel name recorded for the desktop toast.
+pub const DESKTOP_CHANNEL: &str = "desktop";
+
+/// One firing: the [`AlertEvent`] sent to sinks plus how delivery went.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AlertHistoryEntry {
+    #[serde(flatten)]
+    pub event: AlertEvent,
+    /// Channels that accepted the alert: `bell`, `desktop`, then sink names.
+    #[serde(default)]
+    pub delivered: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<AlertDeliveryError>,
+    /// Channels still delivering when the firing was recorded (unsettled if the app quit first).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pending: Vec<String>,
+    /// Delivery result for the earlier line with the same `symbol` and `fired_at`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub outcome: bool,
+}
+
+impl AlertHistoryEntry {
+    /// Settles this firing's `pending` channels with an `outcome` line.
+    fn settle(&mut self, outcome: AlertHistoryEntry) {
+        let settled = outcome
+            .delivered
+            .iter()
+            .chain(outcome.errors.iter().map(|e| &e.channel));
+        for channel in settled {
+            self.pending.retain(|p| p != channel);
+        }
+        self.delivered.extend(outcome.delivered);
+        self.errors.extend(outcome.errors);
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AlertDeliveryError {
+    pub channel: String,
+    pub error: String,
+}
+
+/// Parsed history file; lines that are not valid entries are counted, not fatal.
+#[derive(Debug, Default)]
+pub struct AlertHistory {
+    /// File order (oldest first).
+    pub entries: Vec<AlertHistoryEntry>,
+    pub bad_lines: usize,
+}
+
+/// Appends `entries` to the history file at `path`, creating it when missing.
+pub fn append_alert_history(path: &Path, entries: &[AlertHistoryEntry]) -> io::Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+    let mut buf = Vec::new();
+    for entry in entries {
+        serde_json::to_writer(&mut buf, entry)?;
+        buf.push(b'\n');
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    OpenOptions::new().create(true).append(true).open(path)?.write_all(&buf)
+}
+
+/// Reads the history file, folding `outcome` lines into their firing; a missing file is an empty
+/// history.
+pub fn read_alert_history(path: &Path) -> io::Result<AlertHistory> {
+    let text = match fs::read_to_string(path) {
+        Ok(t) => t,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(AlertHistory::default()),
+        Err(e) => return Err(e),
+    };
+    let mut history = AlertHistory::default();
+    for line in text.lines().filter(|l| !l.trim().is_empty()) {
+        let entry: AlertHistoryEntry = match serde_json::from_str(line) {
+            Ok(entry) => entry,
+            Err(_) => {
+                history.bad_lines += 1;
+                continue;
+            }
+        };
+        let firing = entry.outcome.then(|| {
+            history.entries.iter_mut().rev().find(|e| {
+                e.event.symbol == entry.event.symbol && e.event.fired_at == entry.event.fired_at
+            })
+        });
+        match firing.flatten() {
+            Some(firing) => firing.settle(entry),
+            None => history.entries.push(AlertHistoryEntry {
+                outcome: false,
+                ..entry
+            }),
+        }
+    }
+    Ok(history)
+}
+
+const EXPORT_HEADER: [&str; 9] = [
+    "fired_at",
+    "symbol",
+    "condition",
+    "threshold",
+    "price",
+    "message",
+    "delivered",
+    "errors",
+    "pending",
+];
+
+/// CSV with one row per firing; `delivered`, `errors` and `pending` are `; `-joined.
+pub fn write_alert_history_csv<'a>(
+    entries: impl IntoIterator<Item = &'a AlertHistoryEntry>,
+    out: impl Write,
+) -> csv::Result<()> {
+    let mut w = csv::Writer::from_writer(out);
+    w.write_record(EXPORT_HEADER)?;
+    for entry in entries {
+        let ev = &entry.event;
+        let errors: Vec<String> = entry
+            .errors
+            .iter()
+            .map(|e| format!("{}: {}", e.channel, e.error))
+            .collect();
+        w.write_record([
+            ev.fired_at.to_rfc3339(),
+            ev.symbol.clone(),
+            format!("{:?}", ev.condition),
+            ev.threshold.to_string(),
+            ev.last.map(|p| p.to_string()).unwrap_or_default(),
+            ev.message.clone(),
+            entry.delivered.join("; "),
+            errors.join("; "),
+            entry.pending.join("; "),
+        ])?;
+    }
+    w.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::alerts::AlertCondition;
+    use chrono::DateTime;
+
+    fn entry(symbol: &str, errors: Vec<AlertDeliveryError>) -> AlertHistoryEntry {
+        AlertHistoryEntry {
+            event: AlertEvent {
+                symbol: symbol.into(),
+                condition: AlertCondition::Above,
+                threshold: 200.0,
+                last: Some(201.5),
+                currency: None,
+                message: format!("{symbol} above $200.00 · last $201.50"),
+                fired_at: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+            },
+            delivered: vec![BELL_CHANNEL.into(), "team".into()],
+            errors,
+            pending: Vec::new(),
+            outcome: false,
+        }
+    }
+
+    #[test]
+    fn append_then_read_round_trips_and_skips_bad_lines() {
+        let path = std::env::temp_dir().join(format!(
+            "stockterm-alert-history-{}.jsonl",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        assert!(read_alert_history(&path).unwrap().entries.is_empty());
+
+        let failed = vec![AlertDeliveryError {
+            channel: "log".into(),
+            error: "command exited with exit status: 3".into(),
+        }];
+        append_alert_history(&path, &[entry("AAPL", Vec::new())]).unwrap();
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"not json\n")
+            .unwrap();
+        append_alert_history(&path, &[entry("MSFT", failed.clone())]).unwrap();
+
+        let history = read_alert_history(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        assert_eq!(history.bad_lines, 1);
+        assert_eq!(history.entries, vec![entry("AAPL", Vec::new()), entry("MSFT", failed)]);
+    }
+
+    #[test]
+    fn outcome_lines_settle_the_pending_channels_of_their_firing() {
+        let path = std::env::temp_dir().join(format!(
+            "stockterm-alert-history-outcome-{}.jsonl",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        let firing = |symbol: &str| AlertHistoryEntry {
+            delivered: vec![BELL_CHANNEL.into()],
+            pending: vec![DESKTOP_CHANNEL.into(), "team".into(), "log".into()],
+            ..entry(symbol, Vec::new())
+        };
+        let failed = AlertDeliveryError {
+            channel: "log".into(),
+            error: "timed out".into(),
+        };
+        let outcome = AlertHistoryEntry {
+            delivered: vec![DESKTOP_CHANNEL.into(), "team".into()],
+            errors: vec![failed.clone()],
+            outcome: true,
+            ..entry("AAPL", Vec::new())
+        };
+        // MSFT's delivery never finished (the app quit), so its sinks stay pending.
+        append_alert_history(&path, &[firing("AAPL"), firing("MSFT"), outcome]).unwrap();
+
+        let history = read_alert_history(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        assert_eq!(history.entries.len(), 2);
+        let aapl = &history.entries[0];
+        assert_eq!(aapl.delivered, [BELL_CHANNEL, DESKTOP_CHANNEL, "team"]);
+        assert_eq!(aapl.errors, [failed]);
+        assert!(aapl.pending.is_empty());
+        assert_eq!(history.entries[1].pending, [DESKTOP_CHANNEL, "team", "log"]);
+    }
+
+    #[test]
+    fn csv_export_joins_channels_and_errors() {
+        let e = entry(
+            "AAPL",
+            vec![AlertDeliveryError {
+                channel: "log".into(),
+                error: "timed out".into(),
+            }],
+        );
+        let mut buf = Vec::new();
+        write_alert_history_csv([&e], &mut buf).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+        let mut lines = text.lines();
+        assert_eq!(
+            lines.next(),
+            Some("fired_at,symbol,condition,threshold,price,message,delivered,errors,pending")
+        );
+        assert_eq!(
+            lines.next(),
+            Some(
+                "2023-11-14T22:13:20+00:00,AAPL,Above,200,201.5,AAPL above $200.00 · last $201.50,bell; team,log: timed out,"
+            )
+        );
+    }
+}
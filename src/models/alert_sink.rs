@@ -0,0 +1,129 @@
+//! Notification sinks for fired alerts (`alert_sinks` in `~/.stockterm.json`).
+//!
+//! Data only; delivery lives in [`crate::app::alert_delivery`].
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::alerts::{Alert, AlertCondition};
+
+/// One named destination for fired alerts, e.g.
+/// `{"name": "team", "type": "webhook", "url": "https://hooks.slack.com/…", "template": "slack"}`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AlertSink {
+    /// Referenced from [`Alert::sinks`].
+    pub name: String,
+    #[serde(flatten)]
+    pub target: AlertSinkTarget,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AlertSinkTarget {
+    /// HTTP POST of the alert rendered with `template`.
+    Webhook {
+        url: String,
+        #[serde(default)]
+        template: WebhookTemplate,
+    },
+    /// Shell command (`sh -c`, `cmd /C` on Windows) with the [`AlertEvent`] JSON on stdin.
+    Command { command: String },
+}
+
+/// Body of an [`AlertSinkTarget::Webhook`] POST.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookTemplate {
+    /// The [`AlertEvent`] itself.
+    #[default]
+    Json,
+    /// `{"text": message}` (Slack / Mattermost incoming webhooks).
+    Slack,
+    /// `{"content": message}` (Discord webhooks).
+    Discord,
+    /// Plain-text message with a `Title` header (ntfy topic URL).
+    Ntfy,
+}
+
+/// JSON payload for one fired alert (webhook `json` template and command stdin).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AlertEvent {
+    pub symbol: String,
+    pub condition: AlertCondition,
+    /// [`Alert::price`], or the SMA period for SMA alerts.
+    pub threshold: f64,
+    /// Price the alert compared, when a quote was loaded.
+    pub last: Option<f64>,
+    /// Quote currency as the provider reports it (`GBp`, `EUR`); omitted reads as USD.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// One-line summary, e.g. `AAPL above $200.00 · last $201.15`.
+    pub message: String,
+    pub fired_at: DateTime<Utc>,
+}
+
+/// Sinks `alert` notifies, in config order. Names match case-insensitively; unknown names are
+/// ignored.
+pub fn sinks_for_alert<'a>(
+    alert: &'a Alert,
+    sinks: &'a [AlertSink],
+) -> impl Iterator<Item = &'a AlertSink> + 'a {
+    sinks.iter().filter(move |sink| match &alert.sinks {
+        None => true,
+        Some(names) => names.iter().any(|n| n.eq_ignore_ascii_case(&sink.name)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sinks_parse_from_config_json() {
+        let sinks: Vec<AlertSink> = serde_json::from_str(
+            r#"[
+                {"name": "team", "type": "webhook", "url": "https://example.com/hook", "template": "slack"},
+                {"name": "raw", "type": "webhook", "url": "https://example.com/raw"},
+                {"name": "log", "type": "command", "command": "cat >> ~/alerts.log"}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            sinks[0].target,
+            AlertSinkTarget::Webhook {
+                url: "https://example.com/hook".into(),
+                template: WebhookTemplate::Slack,
+            }
+        );
+        assert!(matches!(
+            sinks[1].target,
+            AlertSinkTarget::Webhook { template: WebhookTemplate::Json, .. }
+        ));
+        assert_eq!(
+            sinks[2].target,
+            AlertSinkTarget::Command { command: "cat >> ~/alerts.log".into() }
+        );
+        let round: Vec<AlertSink> =
+            serde_json::from_str(&serde_json::to_string(&sinks).unwrap()).unwrap();
+        assert_eq!(round, sinks);
+    }
+
+    #[test]
+    fn alerts_pick_all_none_or_named_sinks() {
+        let sinks: Vec<AlertSink> = ["team", "log"]
+            .iter()
+            .map(|n| AlertSink {
+                name: n.to_string(),
+                target: AlertSinkTarget::Command { command: "true".into() },
+            })
+            .collect();
+        let mut alert = Alert::new("AAPL".into(), AlertCondition::Above, 1.0);
+        let names = |a: &Alert| sinks_for_alert(a, &sinks).map(|s| s.name.clone()).collect::<Vec<_>>();
+
+        assert_eq!(names(&alert), ["team", "log"]);
+        alert.sinks = Some(vec![]);
+        assert!(names(&alert).is_empty());
+        alert.sinks = Some(vec!["LOG".into(), "missing".into()]);
+        assert_eq!(names(&alert), ["log"]);
+    }
+}
@@ -1,17 +1,292 @@
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDateTime, NaiveTime, Utc, Weekday};
 use serde::{Serialize, Deserialize};
 
+use crate::models::ticker::MarketState;
+
+/// What an alert compares; [`Alert::price`] holds the threshold in the condition's
+/// [`AlertThresholdUnit`] (SMA alerts use [`Alert::sma_days`] instead).
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AlertCondition {
     Above,
     Below,
+    /// Up at least `price` percent from the previous close.
+    DayUpPercent,
+    /// Down at least `price` percent from the previous close.
+    DayDownPercent,
+    /// Up at least `price` percent from [`Alert::reference_price`].
+    UpPercentSinceSet,
+    /// Down at least `price` percent from [`Alert::reference_price`].
+    DownPercentSinceSet,
+    /// Last closes at or below the SMA and the price is now above it.
+    CrossAboveSma,
+    /// Last closes at or above the SMA and the price is now below it.
+    CrossBelowSma,
+    /// Regular-session volume above `price` shares.
+    VolumeAbove,
+}
+
+/// Unit of the add-dialog threshold for an [`AlertCondition`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertThresholdUnit {
+    Price,
+    Percent,
+    Days,
+    Shares,
+}
+
+/// Shortest and longest SMA period accepted for SMA alerts.
+pub const MIN_ALERT_SMA_DAYS: u32 = 2;
+pub const MAX_ALERT_SMA_DAYS: u32 = 200;
+
+impl AlertCondition {
+    /// Add-dialog cycle order.
+    pub const ALL: [AlertCondition; 9] = [
+        AlertCondition::Above,
+        AlertCondition::Below,
+        AlertCondition::DayUpPercent,
+        AlertCondition::DayDownPercent,
+        AlertCondition::UpPercentSinceSet,
+        AlertCondition::DownPercentSinceSet,
+        AlertCondition::CrossAboveSma,
+        AlertCondition::CrossBelowSma,
+        AlertCondition::VolumeAbove,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AlertCondition::Above => "Above",
+            AlertCondition::Below => "Below",
+            AlertCondition::DayUpPercent => "Day up",
+            AlertCondition::DayDownPercent => "Day down",
+            AlertCondition::UpPercentSinceSet => "Up since set",
+            AlertCondition::DownPercentSinceSet => "Down since set",
+            AlertCondition::CrossAboveSma => "Cross above",
+            AlertCondition::CrossBelowSma => "Cross below",
+            AlertCondition::VolumeAbove => "Volume above",
+        }
+    }
+
+    pub fn unit(self) -> AlertThresholdUnit {
+        match self {
+            AlertCondition::Above | AlertCondition::Below => AlertThresholdUnit::Price,
+            AlertCondition::DayUpPercent
+            | AlertCondition::DayDownPercent
+            | AlertCondition::UpPercentSinceSet
+            | AlertCondition::DownPercentSinceSet => AlertThresholdUnit::Percent,
+            AlertCondition::CrossAboveSma | AlertCondition::CrossBelowSma => AlertThresholdUnit::Days,
+            AlertCondition::VolumeAbove => AlertThresholdUnit::Shares,
+        }
+    }
+
+    /// Next (`forward`) or previous condition in [`ALL`](Self::ALL), wrapping.
+    pub fn cycle(self, forward: bool) -> Self {
+        let n = Self::ALL.len();
+        let i = Self::ALL.iter().position(|&c| c == self).unwrap_or(0);
+        Self::ALL[if forward { (i + 1) % n } else { (i + n - 1) % n }]
+    }
+
+    pub fn uses_reference_price(self) -> bool {
+        matches!(
+            self,
+            AlertCondition::UpPercentSinceSet | AlertCondition::DownPercentSinceSet
+        )
+    }
+}
+
+/// What happens after an alert fires.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum AlertRepeat {
+    /// Stays triggered until removed.
+    #[default]
+    Once,
+    /// Re-arms once the compared value is back past the threshold by `hysteresis` (dollars for
+    /// price and SMA conditions, percentage points for percent conditions, shares for volume).
+    Rearm { hysteresis: f64 },
+    /// Re-arms `minutes` after it last fired; fires again if the condition still holds.
+    Cooldown { minutes: u32 },
+}
+
+impl AlertRepeat {
+    pub fn is_once(&self) -> bool {
+        *self == AlertRepeat::Once
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AlertRepeat::Once => "Once",
+            AlertRepeat::Rearm { .. } => "Re-arm",
+            AlertRepeat::Cooldown { .. } => "Cooldown",
+        }
+    }
+
+    /// Once → Re-arm → Cooldown (values reset to zero).
+    pub fn cycle(self, forward: bool) -> Self {
+        match (self, forward) {
+            (AlertRepeat::Once, true) | (AlertRepeat::Cooldown { .. }, false) => {
+                AlertRepeat::Rearm { hysteresis: 0.0 }
+            }
+            (AlertRepeat::Rearm { .. }, true) | (AlertRepeat::Once, false) => {
+                AlertRepeat::Cooldown { minutes: 0 }
+            }
+            (AlertRepeat::Cooldown { .. }, true) | (AlertRepeat::Rearm { .. }, false) => {
+                AlertRepeat::Once
+            }
+        }
+    }
+}
+
+/// When an alert is evaluated; the default is always. Times are local wall clock.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AlertSchedule {
+    /// Only while the quote's session is regular (unknown session counts as regular).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub regular_session_only: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub weekdays_only: bool,
+    /// Start of a daily window (`"09:30"`); wraps midnight when after `active_until`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_from: Option<NaiveTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_until: Option<NaiveTime>,
+}
+
+impl AlertSchedule {
+    pub fn is_always(&self) -> bool {
+        *self == AlertSchedule::default()
+    }
+
+    /// Add-dialog presets: any time → weekdays → regular session (custom hours are config-only).
+    pub fn cycle(self, forward: bool) -> Self {
+        let weekdays = AlertSchedule {
+            weekdays_only: true,
+            ..AlertSchedule::default()
+        };
+        let regular = AlertSchedule {
+            regular_session_only: true,
+            ..AlertSchedule::default()
+        };
+        let presets = [AlertSchedule::default(), weekdays, regular];
+        let i = presets.iter().position(|p| *p == self);
+        let n = presets.len();
+        match (i, forward) {
+            (Some(i), true) => presets[(i + 1) % n],
+            (Some(i), false) => presets[(i + n - 1) % n],
+            (None, _) => AlertSchedule::default(),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        if self.regular_session_only {
+            parts.push("Regular session".into());
+        }
+        if self.weekdays_only {
+            parts.push("Weekdays".into());
+        }
+        if self.active_from.is_some() || self.active_until.is_some() {
+            let t = |t: Option<NaiveTime>| t.map(|t| t.format("%H:%M").to_string());
+            parts.push(format!(
+                "{}–{}",
+                t(self.active_from).unwrap_or_else(|| "00:00".into()),
+                t(self.active_until).unwrap_or_else(|| "24:00".into())
+            ));
+        }
+        if parts.is_empty() {
+            "Any time".into()
+        } else {
+            parts.join(", ")
+        }
+    }
+
+    /// Whether `local` (and the quote's `session`, when known) falls inside the schedule.
+    pub fn allows(&self, local: NaiveDateTime, session: Option<MarketState>) -> bool {
+        if self.weekdays_only && matches!(local.weekday(), Weekday::Sat | Weekday::Sun) {
+            return false;
+        }
+        if self.regular_session_only && session.is_some_and(|s| s != MarketState::Regular) {
+            return false;
+        }
+        let t = local.time();
+        match (self.active_from, self.active_until) {
+            (Some(from), Some(until)) if from <= until => from <= t && t < until,
+            (Some(from), Some(until)) => t >= from || t < until,
+            (Some(from), None) => t >= from,
+            (None, Some(until)) => t < until,
+            (None, None) => true,
+        }
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Alert {
     pub symbol: String,
     pub condition: AlertCondition,
     pub price: f64,
     pub triggered: bool,
+    /// Evaluate against the pre/post-market price when one is available (regular last otherwise).
+    #[serde(default)]
+    pub extended_hours: bool,
+    /// Price when the alert was set, for the "since set" conditions. Filled from the first quote
+    /// when the alert was added without one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference_price: Option<f64>,
+    /// Period of the daily SMA for [`CrossAboveSma`](AlertCondition::CrossAboveSma) /
+    /// [`CrossBelowSma`](AlertCondition::CrossBelowSma).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sma_days: Option<u32>,
+    #[serde(default, skip_serializing_if = "AlertRepeat::is_once")]
+    pub repeat: AlertRepeat,
+    /// When the alert last fired (kept across re-arms).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_fired: Option<DateTime<Utc>>,
+    /// After this instant the alert is moved to [`Config::archived_alerts`](crate::config::Config::archived_alerts).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "AlertSchedule::is_always")]
+    pub schedule: AlertSchedule,
+    /// Names of the [`AlertSink`](crate::models::alert_sink::AlertSink)s to notify; omitted → every
+    /// configured sink, `[]` → bell / desktop toast only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sinks: Option<Vec<String>>,
+}
+
+/// Latest prices for one symbol as seen by [`process_alert_crossings`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertQuote {
+    pub symbol: String,
+    /// Regular-session last.
+    pub last: f64,
+    /// Pre/post-market last while the session is extended.
+    pub extended: Option<f64>,
+    pub prev_close: Option<f64>,
+    /// Regular-session volume so far.
+    pub volume: Option<f64>,
+    pub market_state: Option<MarketState>,
+    /// Closes of completed daily sessions, oldest first; only loaded for symbols with SMA alerts.
+    pub daily_closes: Vec<f64>,
+    /// Currency as the provider reports it (`GBp`, `EUR`); `None` reads as USD.
+    pub currency: Option<String>,
+}
+
+impl AlertQuote {
+    pub fn new(symbol: impl Into<String>, last: f64) -> Self {
+        Self {
+            symbol: symbol.into(),
+            last,
+            extended: None,
+            prev_close: None,
+            volume: None,
+            market_state: None,
+            daily_closes: Vec::new(),
+            currency: None,
+        }
+    }
+
+    /// [`currency`](Self::currency), or `USD` when the provider does not report one.
+    pub fn quote_currency(&self) -> &str {
+        self.currency.as_deref().unwrap_or("USD")
+    }
 }
 
 impl Alert {
@@ -21,41 +296,194 @@ impl Alert {
             condition,
             price,
             triggered: false,
+            extended_hours: false,
+            reference_price: None,
+            sma_days: None,
+            repeat: AlertRepeat::Once,
+            last_fired: None,
+            expires_at: None,
+            schedule: AlertSchedule::default(),
+            sinks: None,
+        }
+    }
+
+    /// Price this alert compares against: extended-hours last for opted-in alerts when known.
+    pub fn evaluation_price(&self, quote: &AlertQuote) -> f64 {
+        match quote.extended {
+            Some(ext) if self.extended_hours => ext,
+            _ => quote.last,
+        }
+    }
+
+    /// Whether the condition holds for `quote`. Conditions missing their inputs (no previous
+    /// close, no reference price, too few daily closes) never hold.
+    pub fn is_triggered(&self, quote: &AlertQuote) -> bool {
+        let price = self.evaluation_price(quote);
+        let percent_from = |base| percent_change(base, price);
+        match self.condition {
+            AlertCondition::Above => price > self.price,
+            AlertCondition::Below => price < self.price,
+            AlertCondition::DayUpPercent => {
+                percent_from(quote.prev_close).is_some_and(|p| p >= self.price)
+            }
+            AlertCondition::DayDownPercent => {
+                percent_from(quote.prev_close).is_some_and(|p| p <= -self.price)
+            }
+            AlertCondition::UpPercentSinceSet => {
+                percent_from(self.reference_price).is_some_and(|p| p >= self.price)
+            }
+            AlertCondition::DownPercentSinceSet => {
+                percent_from(self.reference_price).is_some_and(|p| p <= -self.price)
+            }
+            AlertCondition::CrossAboveSma => match (self.sma(quote), quote.daily_closes.last()) {
+                (Some(sma), Some(&prior)) => prior <= sma && price > sma,
+                _ => false,
+            },
+            AlertCondition::CrossBelowSma => match (self.sma(quote), quote.daily_closes.last()) {
+                (Some(sma), Some(&prior)) => prior >= sma && price < sma,
+                _ => false,
+            },
+            AlertCondition::VolumeAbove => quote.volume.is_some_and(|v| v > self.price),
+        }
+    }
+
+    /// `sma_days`-day simple moving average of the completed sessions in `quote`.
+    pub fn sma(&self, quote: &AlertQuote) -> Option<f64> {
+        let n = self.sma_days? as usize;
+        let closes = &quote.daily_closes;
+        if n == 0 || closes.len() < n {
+            return None;
         }
+        Some(closes[closes.len() - n..].iter().sum::<f64>() / n as f64)
     }
 
-    pub fn is_triggered(&self, current_price: f64) -> bool {
+    /// Whether the compared value is back past the threshold by `hysteresis` (re-arm test for
+    /// [`AlertRepeat::Rearm`]).
+    pub fn moved_back(&self, quote: &AlertQuote, hysteresis: f64) -> bool {
+        let price = self.evaluation_price(quote);
+        let back_below = |v: Option<f64>, level: f64| v.is_some_and(|v| v <= level - hysteresis);
+        let back_above = |v: Option<f64>, level: f64| v.is_some_and(|v| v >= level + hysteresis);
         match self.condition {
-            AlertCondition::Above => current_price > self.price,
-            AlertCondition::Below => current_price < self.price,
+            AlertCondition::Above => back_below(Some(price), self.price),
+            AlertCondition::Below => back_above(Some(price), self.price),
+            AlertCondition::DayUpPercent => {
+                back_below(percent_change(quote.prev_close, price), self.price)
+            }
+            AlertCondition::DayDownPercent => {
+                back_above(percent_change(quote.prev_close, price), -self.price)
+            }
+            AlertCondition::UpPercentSinceSet => {
+                back_below(percent_change(self.reference_price, price), self.price)
+            }
+            AlertCondition::DownPercentSinceSet => {
+                back_above(percent_change(self.reference_price, price), -self.price)
+            }
+            AlertCondition::CrossAboveSma => {
+                self.sma(quote).is_some_and(|sma| back_below(Some(price), sma))
+            }
+            AlertCondition::CrossBelowSma => {
+                self.sma(quote).is_some_and(|sma| back_above(Some(price), sma))
+            }
+            AlertCondition::VolumeAbove => back_below(quote.volume, self.price),
+        }
+    }
+
+    /// Fired alert whose [`AlertRepeat`] lets it fire again at `now`.
+    fn ready_to_rearm(&self, quote: &AlertQuote, now: DateTime<Utc>) -> bool {
+        match self.repeat {
+            AlertRepeat::Once => false,
+            AlertRepeat::Rearm { hysteresis } => self.moved_back(quote, hysteresis),
+            AlertRepeat::Cooldown { minutes } => self
+                .last_fired
+                .is_none_or(|t| now - t >= Duration::minutes(i64::from(minutes))),
         }
     }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|t| now >= t)
+    }
+
+    /// Not expired and inside its [`AlertSchedule`] at `local` for a quote in `session`.
+    pub fn is_active_at(
+        &self,
+        now: DateTime<Utc>,
+        local: NaiveDateTime,
+        session: Option<MarketState>,
+    ) -> bool {
+        !self.is_expired(now) && self.schedule.allows(local, session)
+    }
+
+    /// A "since set" alert that has not seen a price yet.
+    pub fn awaiting_reference(&self) -> bool {
+        self.condition.uses_reference_price() && self.reference_price.is_none()
+    }
 }
 
-/// Applies threshold crossings using known last prices. Returns indices of alerts that **newly**
-/// set `triggered` in this call (SPEC §18.9 — test hook).
-pub fn process_alert_crossings(alerts: &mut [Alert], prices: &[(String, f64)]) -> Vec<usize> {
-    let mut newly = Vec::new();
+fn percent_change(base: Option<f64>, price: f64) -> Option<f64> {
+    base.filter(|b| *b > 0.0).map(|b| (price - b) / b * 100.0)
+}
+
+/// Result of [`evaluate_alerts`].
+#[derive(Debug, Default, PartialEq)]
+pub struct AlertPass {
+    /// Indices of alerts that fired in this pass.
+    pub fired: Vec<usize>,
+    /// Some alert re-armed or took its reference price, so state needs saving even when
+    /// nothing fired.
+    pub changed: bool,
+}
+
+/// Removes and returns alerts whose `expires_at` has passed (for archiving).
+pub fn take_expired_alerts(alerts: &mut Vec<Alert>, now: DateTime<Utc>) -> Vec<Alert> {
+    let (expired, kept): (Vec<Alert>, Vec<Alert>) =
+        std::mem::take(alerts).into_iter().partition(|a| a.is_expired(now));
+    *alerts = kept;
+    expired
+}
+
+/// One evaluation pass at `now`: skips alerts that are expired or outside their schedule
+/// (local time, quote session), re-arms fired alerts per their [`AlertRepeat`], fills missing
+/// reference prices (without firing), then fires alerts whose condition holds and stamps
+/// `last_fired`. A [`Rearm`](AlertRepeat::Rearm) alert that re-arms must cross again in a later
+/// pass; a [`Cooldown`](AlertRepeat::Cooldown) alert may fire again in the same pass.
+pub fn evaluate_alerts(alerts: &mut [Alert], quotes: &[AlertQuote], now: DateTime<Utc>) -> AlertPass {
+    let mut pass = AlertPass::default();
+    let local = now.with_timezone(&Local).naive_local();
     for (i, alert) in alerts.iter_mut().enumerate() {
-        if alert.triggered {
+        let Some(quote) = quotes.iter().find(|q| q.symbol == alert.symbol) else {
+            continue;
+        };
+        if !alert.is_active_at(now, local, quote.market_state) {
             continue;
         }
-        let Some((_, price)) = prices
-            .iter()
-            .find(|(symbol, _)| symbol == &alert.symbol)
-        else {
+        if alert.triggered {
+            if !alert.ready_to_rearm(quote, now) {
+                continue;
+            }
+            alert.triggered = false;
+            pass.changed = true;
+            if matches!(alert.repeat, AlertRepeat::Rearm { .. }) {
+                continue;
+            }
+        }
+        if alert.awaiting_reference() {
+            alert.reference_price = Some(alert.evaluation_price(quote));
+            pass.changed = true;
             continue;
-        };
-        let crossed = match alert.condition {
-            AlertCondition::Above => *price > alert.price,
-            AlertCondition::Below => *price < alert.price,
-        };
-        if crossed {
+        }
+        if alert.is_triggered(quote) {
             alert.triggered = true;
-            newly.push(i);
+            alert.last_fired = Some(now);
+            pass.fired.push(i);
         }
     }
-    newly
+    pass
+}
+
+/// Applies threshold crossings using known last prices. Returns indices of alerts that **newly**
+/// set `triggered` in this call (SPEC §18.9 — test hook); see [`evaluate_alerts`].
+pub fn process_alert_crossings(alerts: &mut [Alert], quotes: &[AlertQuote]) -> Vec<usize> {
+    evaluate_alerts(alerts, quotes, Utc::now()).fired
 }
 
 #[cfg(test)]
@@ -68,7 +496,7 @@ mod tests {
             Alert::new("AAPL".into(), AlertCondition::Above, 1.0),
             Alert::new("MSFT".into(), AlertCondition::Below, 500.0),
         ];
-        let prices = vec![("AAPL".into(), 150.0), ("MSFT".into(), 400.0)];
+        let prices = vec![AlertQuote::new("AAPL", 150.0), AlertQuote::new("MSFT", 400.0)];
         let n1 = process_alert_crossings(&mut alerts, &prices);
         assert_eq!(n1, vec![0, 1]);
         assert!(alerts[0].triggered);
@@ -77,4 +505,159 @@ mod tests {
         let n2 = process_alert_crossings(&mut alerts, &prices);
         assert!(n2.is_empty());
     }
+
+    #[test]
+    fn extended_hours_alert_uses_extended_price_only_when_opted_in() {
+        let regular = Alert::new("AAPL".into(), AlertCondition::Above, 105.0);
+        let mut ext = regular.clone();
+        ext.extended_hours = true;
+        let mut alerts = vec![regular, ext.clone()];
+        let quotes = vec![AlertQuote {
+            extended: Some(110.0),
+            ..AlertQuote::new("AAPL", 100.0)
+        }];
+        assert_eq!(process_alert_crossings(&mut alerts, &quotes), vec![1]);
+
+        let no_ext = AlertQuote::new("AAPL", 100.0);
+        assert!((ext.evaluation_price(&no_ext) - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn relative_conditions_use_prev_close_reference_and_volume() {
+        let quote = AlertQuote {
+            prev_close: Some(100.0),
+            volume: Some(2_000_000.0),
+            ..AlertQuote::new("AAPL", 94.0)
+        };
+        let day_down = Alert::new("AAPL".into(), AlertCondition::DayDownPercent, 5.0);
+        let day_up = Alert::new("AAPL".into(), AlertCondition::DayUpPercent, 5.0);
+        let volume = Alert::new("AAPL".into(), AlertCondition::VolumeAbove, 1_000_000.0);
+        assert!(day_down.is_triggered(&quote));
+        assert!(!day_up.is_triggered(&quote));
+        assert!(volume.is_triggered(&quote));
+        assert!(!day_up.is_triggered(&AlertQuote::new("AAPL", 200.0)), "no previous close");
+
+        let mut alerts = vec![Alert::new("AAPL".into(), AlertCondition::UpPercentSinceSet, 10.0)];
+        assert!(process_alert_crossings(&mut alerts, &[AlertQuote::new("AAPL", 50.0)]).is_empty());
+        assert_eq!(alerts[0].reference_price, Some(50.0));
+        assert!(process_alert_crossings(&mut alerts, &[AlertQuote::new("AAPL", 54.0)]).is_empty());
+        assert_eq!(process_alert_crossings(&mut alerts, &[AlertQuote::new("AAPL", 55.0)]), vec![0]);
+    }
+
+    #[test]
+    fn sma_cross_needs_prior_close_on_the_other_side() {
+        let mut above = Alert::new("AAPL".into(), AlertCondition::CrossAboveSma, 0.0);
+        above.sma_days = Some(3);
+        let mut below = above.clone();
+        below.condition = AlertCondition::CrossBelowSma;
+        // SMA(3) of 10, 11, 9 = 10; last close 9 is below it.
+        let quote = |last: f64| AlertQuote {
+            daily_closes: vec![50.0, 10.0, 11.0, 9.0],
+            ..AlertQuote::new("AAPL", last)
+        };
+        assert!((above.sma(&quote(0.0)).unwrap() - 10.0).abs() < 1e-9);
+        assert!(above.is_triggered(&quote(10.5)));
+        assert!(!above.is_triggered(&quote(9.5)));
+        assert!(!below.is_triggered(&quote(9.5)), "already below before today");
+
+        let short = AlertQuote {
+            daily_closes: vec![9.0, 9.0],
+            ..AlertQuote::new("AAPL", 12.0)
+        };
+        assert!(!above.is_triggered(&short), "too few sessions for the period");
+    }
+
+    #[test]
+    fn rearm_waits_for_hysteresis_band() {
+        let mut alert = Alert::new("AAPL".into(), AlertCondition::Above, 100.0);
+        alert.repeat = AlertRepeat::Rearm { hysteresis: 2.0 };
+        let mut alerts = vec![alert];
+        let now = Utc::now();
+        let pass = |alerts: &mut Vec<Alert>, last: f64| {
+            evaluate_alerts(alerts, &[AlertQuote::new("AAPL", last)], now)
+        };
+        assert_eq!(pass(&mut alerts, 101.0).fired, vec![0]);
+        assert_eq!(alerts[0].last_fired, Some(now));
+        assert_eq!(pass(&mut alerts, 99.0), AlertPass::default(), "inside the band");
+        assert!(pass(&mut alerts, 101.0).fired.is_empty());
+        let rearmed = pass(&mut alerts, 98.0);
+        assert!(rearmed.changed && rearmed.fired.is_empty());
+        assert!(!alerts[0].triggered);
+        assert_eq!(pass(&mut alerts, 100.5).fired, vec![0]);
+    }
+
+    #[test]
+    fn cooldown_repeats_while_condition_holds() {
+        let mut alert = Alert::new("AAPL".into(), AlertCondition::Below, 50.0);
+        alert.repeat = AlertRepeat::Cooldown { minutes: 30 };
+        let mut alerts = vec![alert];
+        let quotes = [AlertQuote::new("AAPL", 45.0)];
+        let t0 = Utc::now();
+        assert_eq!(evaluate_alerts(&mut alerts, &quotes, t0).fired, vec![0]);
+        let early = t0 + Duration::minutes(29);
+        assert!(evaluate_alerts(&mut alerts, &quotes, early).fired.is_empty());
+        let later = t0 + Duration::minutes(30);
+        assert_eq!(evaluate_alerts(&mut alerts, &quotes, later).fired, vec![0]);
+        assert_eq!(alerts[0].last_fired, Some(later));
+    }
+
+    #[test]
+    fn repeat_mode_round_trips_and_once_is_omitted() {
+        let mut alert = Alert::new("AAPL".into(), AlertCondition::Above, 1.0);
+        assert!(!serde_json::to_string(&alert).unwrap().contains("repeat"));
+        alert.repeat = AlertRepeat::Cooldown { minutes: 15 };
+        let json = serde_json::to_string(&alert).unwrap();
+        assert!(json.contains(r#""repeat":{"mode":"cooldown","minutes":15}"#), "{json}");
+        let back: Alert = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.repeat, alert.repeat);
+    }
+
+    #[test]
+    fn schedule_limits_days_hours_and_session() {
+        let at = |d: u32, h: u32, m: u32| {
+            chrono::NaiveDate::from_ymd_opt(2024, 7, d)
+                .unwrap()
+                .and_hms_opt(h, m, 0)
+                .unwrap()
+        };
+        // 2024-07-10 is a Wednesday, 2024-07-13 a Saturday.
+        let weekdays = AlertSchedule {
+            weekdays_only: true,
+            ..AlertSchedule::default()
+        };
+        assert!(weekdays.allows(at(10, 19, 0), None));
+        assert!(!weekdays.allows(at(13, 12, 0), None));
+
+        let regular = AlertSchedule::default().cycle(false);
+        assert!(regular.regular_session_only);
+        assert!(!regular.allows(at(10, 19, 0), Some(MarketState::Post)));
+        assert!(regular.allows(at(10, 11, 0), Some(MarketState::Regular)));
+        assert!(regular.allows(at(10, 11, 0), None), "unknown session is not suppressed");
+
+        let overnight = AlertSchedule {
+            active_from: NaiveTime::from_hms_opt(22, 0, 0),
+            active_until: NaiveTime::from_hms_opt(6, 0, 0),
+            ..AlertSchedule::default()
+        };
+        assert!(overnight.allows(at(10, 23, 0), None));
+        assert!(overnight.allows(at(10, 5, 59), None));
+        assert!(!overnight.allows(at(10, 12, 0), None));
+        assert_eq!(overnight.label(), "22:00–06:00");
+    }
+
+    #[test]
+    fn expired_alerts_are_skipped_and_taken_for_archive() {
+        let now = Utc::now();
+        let mut expired = Alert::new("AAPL".into(), AlertCondition::Above, 1.0);
+        expired.expires_at = Some(now - Duration::minutes(1));
+        let live = Alert::new("MSFT".into(), AlertCondition::Above, 1.0);
+        let mut alerts = vec![expired, live];
+        let quotes = [AlertQuote::new("AAPL", 5.0), AlertQuote::new("MSFT", 5.0)];
+        assert_eq!(evaluate_alerts(&mut alerts, &quotes, now).fired, vec![1]);
+
+        let archived = take_expired_alerts(&mut alerts, now);
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].symbol, "AAPL");
+        assert_eq!(alerts.len(), 1);
+    }
 }
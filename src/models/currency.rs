@@ -0,0 +1,73 @@
+//! Quote currencies, FX pair symbols, and money formatting for non-USD listings.
+//!
+//! Yahoo quotes some exchanges in minor units: LSE in pence (`GBp`), JSE in cents (`ZAc`) and
+//! TASE in agorot (`ILA`). [`major_currency`] maps those to the ISO code plus a factor so a
+//! `GBp` price converts through the `GBP` rate.
+
+/// [`Config::base_currency`](crate::config::Config::base_currency) when omitted.
+pub const DEFAULT_BASE_CURRENCY: &str = "USD";
+
+/// `(ISO code, factor)` such that `amount * factor` is in the ISO currency.
+pub fn major_currency(code: &str) -> (String, f64) {
+    match code.trim() {
+        "GBp" | "GBX" => ("GBP".to_string(), 0.01),
+        "ZAc" | "ZAC" => ("ZAR".to_string(), 0.01),
+        "ILA" => ("ILS".to_string(), 0.01),
+        other => (other.to_ascii_uppercase(), 1.0),
+    }
+}
+
+/// Yahoo-style FX pair for converting `from` into `to` (`EURUSD=X`); both ISO codes.
+pub fn yahoo_fx_symbol(from: &str, to: &str) -> String {
+    format!("{from}{to}=X")
+}
+
+fn currency_prefix(iso: &str) -> Option<&'static str> {
+    Some(match iso {
+        "USD" => "$",
+        "EUR" => "€",
+        "GBP" => "£",
+        "JPY" => "¥",
+        "CAD" => "C$",
+        "AUD" => "A$",
+        "HKD" => "HK$",
+        "CHF" => "CHF ",
+        "INR" => "₹",
+        _ => return None,
+    })
+}
+
+/// `$12.34`, `£12.34`, `C$12.34`, `1234.50p` for pence, else `SEK 12.34`.
+pub fn format_money(amount: f64, currency: &str) -> String {
+    let currency = currency.trim();
+    if matches!(currency, "GBp" | "GBX") {
+        return format!("{amount:.2}p");
+    }
+    let iso = currency.to_ascii_uppercase();
+    match currency_prefix(&iso) {
+        Some(prefix) => format!("{prefix}{amount:.2}"),
+        None if iso.is_empty() => format!("${amount:.2}"),
+        None => format!("{iso} {amount:.2}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minor_units_map_to_iso_with_factor() {
+        assert_eq!(major_currency("GBp"), ("GBP".to_string(), 0.01));
+        assert_eq!(major_currency("cad"), ("CAD".to_string(), 1.0));
+        assert_eq!(yahoo_fx_symbol("GBP", "USD"), "GBPUSD=X");
+    }
+
+    #[test]
+    fn money_uses_symbol_or_code() {
+        assert_eq!(format_money(12.5, "USD"), "$12.50");
+        assert_eq!(format_money(12.5, "CAD"), "C$12.50");
+        assert_eq!(format_money(1234.5, "GBp"), "1234.50p");
+        assert_eq!(format_money(3.0, "SEK"), "SEK 3.00");
+        assert_eq!(format_money(-1.0, "EUR"), "€-1.00");
+    }
+}
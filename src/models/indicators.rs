@@ -0,0 +1,273 @@
+//! Technical indicators over [`HistoricalResponse::results`](crate::models::historical::HistoricalResponse)
+//! closes (Charts tab overlays and sub-panels).
+//!
+//! Every function returns one entry per input bar; `None` marks the warm-up bars before the
+//! indicator has enough history. Callers compute over the full series and then slice to the chart
+//! viewport so zooming in does not restart the warm-up.
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::historical::HistoricalData;
+
+pub const SMA_PERIOD: usize = 20;
+pub const EMA_PERIOD: usize = 50;
+pub const BOLLINGER_PERIOD: usize = 20;
+pub const BOLLINGER_STD_DEVS: f64 = 2.0;
+pub const RSI_PERIOD: usize = 14;
+pub const MACD_FAST: usize = 12;
+pub const MACD_SLOW: usize = 26;
+pub const MACD_SIGNAL: usize = 9;
+
+/// Chart study toggled from the Charts tab (persisted per [`TimeRange`](crate::models::time_range::TimeRange)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Indicator {
+    Sma,
+    Ema,
+    Bollinger,
+    Rsi,
+    Macd,
+}
+
+impl Indicator {
+    pub const ALL: [Indicator; 5] = [
+        Indicator::Sma,
+        Indicator::Ema,
+        Indicator::Bollinger,
+        Indicator::Rsi,
+        Indicator::Macd,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Indicator::Sma => "SMA20",
+            Indicator::Ema => "EMA50",
+            Indicator::Bollinger => "BB20",
+            Indicator::Rsi => "RSI14",
+            Indicator::Macd => "MACD",
+        }
+    }
+
+    /// Drawn on the price axis (`true`) or in a lower sub-panel (`false`).
+    pub fn is_overlay(self) -> bool {
+        matches!(self, Indicator::Sma | Indicator::Ema | Indicator::Bollinger)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BollingerBand {
+    pub lower: f64,
+    pub middle: f64,
+    pub upper: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MacdPoint {
+    pub macd: f64,
+    pub signal: f64,
+    pub histogram: f64,
+}
+
+pub fn closes(results: &[HistoricalData]) -> Vec<f64> {
+    results.iter().map(|b| b.c).collect()
+}
+
+/// Simple moving average over the trailing `period` values.
+pub fn sma(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut out = vec![None; values.len()];
+    if period == 0 || values.len() < period {
+        return out;
+    }
+    let mut sum: f64 = values[..period].iter().sum();
+    out[period - 1] = Some(sum / period as f64);
+    for i in period..values.len() {
+        sum += values[i] - values[i - period];
+        out[i] = Some(sum / period as f64);
+    }
+    out
+}
+
+/// Exponential moving average (`α = 2 / (period + 1)`), seeded with the SMA of the first `period` values.
+pub fn ema(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let wrapped: Vec<Option<f64>> = values.iter().copied().map(Some).collect();
+    ema_of_defined(&wrapped, period)
+}
+
+/// [`ema`] over a series with leading `None`s (e.g. the MACD line before the slow EMA warms up).
+fn ema_of_defined(values: &[Option<f64>], period: usize) -> Vec<Option<f64>> {
+    let mut out = vec![None; values.len()];
+    if period == 0 {
+        return out;
+    }
+    let Some(first) = values.iter().position(Option::is_some) else {
+        return out;
+    };
+    if values.len() - first < period {
+        return out;
+    }
+    let alpha = 2.0 / (period as f64 + 1.0);
+    let seed_end = first + period;
+    let mut prev = values[first..seed_end].iter().flatten().sum::<f64>() / period as f64;
+    out[seed_end - 1] = Some(prev);
+    for i in seed_end..values.len() {
+        let Some(v) = values[i] else {
+            continue;
+        };
+        prev = alpha * v + (1.0 - alpha) * prev;
+        out[i] = Some(prev);
+    }
+    out
+}
+
+/// SMA middle band ± `std_devs` population standard deviations.
+pub fn bollinger(values: &[f64], period: usize, std_devs: f64) -> Vec<Option<BollingerBand>> {
+    sma(values, period)
+        .into_iter()
+        .enumerate()
+        .map(|(i, mid)| {
+            let middle = mid?;
+            let window = &values[i + 1 - period..=i];
+            let var = window.iter().map(|v| (v - middle).powi(2)).sum::<f64>() / period as f64;
+            let dev = var.sqrt() * std_devs;
+            Some(BollingerBand {
+                lower: middle - dev,
+                middle,
+                upper: middle + dev,
+            })
+        })
+        .collect()
+}
+
+/// Wilder's relative strength index (0–100).
+pub fn rsi(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut out = vec![None; values.len()];
+    if period == 0 || values.len() <= period {
+        return out;
+    }
+    let mut gain = 0.0;
+    let mut loss = 0.0;
+    for i in 1..=period {
+        let d = values[i] - values[i - 1];
+        if d >= 0.0 {
+            gain += d;
+        } else {
+            loss -= d;
+        }
+    }
+    gain /= period as f64;
+    loss /= period as f64;
+    out[period] = Some(rsi_value(gain, loss));
+    for i in period + 1..values.len() {
+        let d = values[i] - values[i - 1];
+        gain = (gain * (period as f64 - 1.0) + d.max(0.0)) / period as f64;
+        loss = (loss * (period as f64 - 1.0) + (-d).max(0.0)) / period as f64;
+        out[i] = Some(rsi_value(gain, loss));
+    }
+    out
+}
+
+fn rsi_value(avg_gain: f64, avg_loss: f64) -> f64 {
+    if avg_loss.abs() < f64::EPSILON {
+        if avg_gain.abs() < f64::EPSILON {
+            50.0
+        } else {
+            100.0
+        }
+    } else {
+        100.0 - 100.0 / (1.0 + avg_gain / avg_loss)
+    }
+}
+
+/// MACD line (`EMA(fast) − EMA(slow)`), its `signal` EMA, and the histogram between them.
+pub fn macd(values: &[f64], fast: usize, slow: usize, signal: usize) -> Vec<Option<MacdPoint>> {
+    let fast_ema = ema(values, fast);
+    let slow_ema = ema(values, slow);
+    let line: Vec<Option<f64>> = fast_ema
+        .iter()
+        .zip(&slow_ema)
+        .map(|(f, s)| Some((*f)? - (*s)?))
+        .collect();
+    let signal_line = ema_of_defined(&line, signal);
+    line.iter()
+        .zip(&signal_line)
+        .map(|(m, s)| {
+            let (macd, signal) = ((*m)?, (*s)?);
+            Some(MacdPoint {
+                macd,
+                signal,
+                histogram: macd - signal,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn sma_warms_up_then_averages_window() {
+        let got = sma(&[1.0, 2.0, 3.0, 4.0, 5.0], 3);
+        assert_eq!(got, vec![None, None, Some(2.0), Some(3.0), Some(4.0)]);
+        assert!(sma(&[1.0, 2.0], 3).iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn ema_seeds_with_sma_then_smooths() {
+        let got = ema(&[2.0, 4.0, 6.0, 8.0], 3);
+        assert_eq!(got[..2], [None, None]);
+        assert!(approx(got[2].unwrap(), 4.0));
+        assert!(approx(got[3].unwrap(), 0.5 * 8.0 + 0.5 * 4.0));
+    }
+
+    #[test]
+    fn bollinger_flat_series_has_zero_width() {
+        let got = bollinger(&[5.0; 4], 3, 2.0);
+        assert!(got[1].is_none());
+        let b = got[3].unwrap();
+        assert!(approx(b.lower, 5.0) && approx(b.middle, 5.0) && approx(b.upper, 5.0));
+    }
+
+    #[test]
+    fn bollinger_band_is_symmetric_around_sma() {
+        let b = bollinger(&[1.0, 2.0, 3.0], 3, 2.0)[2].unwrap();
+        let dev = (2.0f64 / 3.0).sqrt() * 2.0;
+        assert!(approx(b.middle, 2.0));
+        assert!(approx(b.upper, 2.0 + dev));
+        assert!(approx(b.lower, 2.0 - dev));
+    }
+
+    #[test]
+    fn rsi_extremes_and_warm_up() {
+        let rising: Vec<f64> = (0..20).map(f64::from).collect();
+        let got = rsi(&rising, 14);
+        assert!(got[13].is_none());
+        assert_eq!(got[14], Some(100.0));
+        let falling: Vec<f64> = rising.iter().rev().copied().collect();
+        assert_eq!(rsi(&falling, 14)[19], Some(0.0));
+        assert_eq!(rsi(&[3.0; 16], 14)[15], Some(50.0));
+    }
+
+    #[test]
+    fn macd_waits_for_slow_and_signal_warm_up() {
+        let values: Vec<f64> = (0..40).map(|i| 100.0 + f64::from(i)).collect();
+        let got = macd(&values, 12, 26, 9);
+        let first = got.iter().position(Option::is_some).unwrap();
+        assert_eq!(first, 26 - 1 + 9 - 1);
+        let p = got[39].unwrap();
+        assert!(p.macd > 0.0, "rising series → fast EMA above slow");
+        assert!(approx(p.histogram, p.macd - p.signal));
+    }
+
+    #[test]
+    fn indicator_serde_snake_case() {
+        let j = serde_json::to_string(&Indicator::Bollinger).unwrap();
+        assert_eq!(j, "\"bollinger\"");
+        assert!(Indicator::Sma.is_overlay());
+        assert!(!Indicator::Macd.is_overlay());
+    }
+}
@@ -1,7 +1,13 @@
 pub mod ticker;
 pub mod historical;
+pub mod indicators;
 pub mod time_range;
 pub mod search;
 pub mod news;
 pub mod portfolio;
+pub mod portfolio_csv;
 pub mod alerts;
+pub mod alert_sink;
+pub mod alert_history;
+pub mod watchlist;
+pub mod currency;
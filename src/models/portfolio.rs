@@ -1,40 +1,517 @@
-use serde::{Serialize, Deserialize};
+//! Portfolio holdings as per-symbol transaction ledgers, grouped into named accounts.
+//!
+//! Each [`PortfolioItem`] keeps every buy and sell; open lots, cost basis, and realized P/L are
+//! derived on demand with a [`CostBasisMethod`]. Configs written before the ledger (one averaged
+//! `shares` / `purchase_price` per symbol) load as a single buy lot. A [`Portfolio`] is one named
+//! account (taxable, IRA, …) with its own holdings; ledgers never span accounts.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TradeSide {
+    #[default]
+    Buy,
+    Sell,
+}
+
+impl TradeSide {
+    pub fn label(self) -> &'static str {
+        match self {
+            TradeSide::Buy => "Buy",
+            TradeSide::Sell => "Sell",
+        }
+    }
+}
+
+/// One ledger entry. `fees` add to a buy's cost and come out of a sell's proceeds.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Transaction {
+    pub side: TradeSide,
+    /// Trade date; `None` for holdings migrated without a purchase date and undated entries
+    /// (replayed where they were entered, see [`replay_ledger`]).
+    #[serde(default)]
+    pub date: Option<NaiveDate>,
+    pub quantity: f64,
+    pub price: f64,
+    #[serde(default)]
+    pub fees: f64,
+}
+
+impl Transaction {
+    pub fn buy(date: Option<NaiveDate>, quantity: f64, price: f64, fees: f64) -> Self {
+        Self {
+            side: TradeSide::Buy,
+            date,
+            quantity,
+            price,
+            fees,
+        }
+    }
+
+    pub fn sell(date: Option<NaiveDate>, quantity: f64, price: f64, fees: f64) -> Self {
+        Self {
+            side: TradeSide::Sell,
+            date,
+            quantity,
+            price,
+            fees,
+        }
+    }
+}
+
+/// Which open lots a sell consumes.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CostBasisMethod {
+    /// Oldest lots first.
+    #[default]
+    Fifo,
+    /// Newest lots first.
+    Lifo,
+    /// Every open lot shrinks pro rata, so the per-share cost stays the running average.
+    Average,
+}
+
+impl CostBasisMethod {
+    pub fn label(self) -> &'static str {
+        match self {
+            CostBasisMethod::Fifo => "FIFO",
+            CostBasisMethod::Lifo => "LIFO",
+            CostBasisMethod::Average => "Average",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            CostBasisMethod::Fifo => CostBasisMethod::Lifo,
+            CostBasisMethod::Lifo => CostBasisMethod::Average,
+            CostBasisMethod::Average => CostBasisMethod::Fifo,
+        }
+    }
+}
+
+/// Remaining quantity of one buy after sells were matched against it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OpenLot {
+    pub date: Option<NaiveDate>,
+    pub quantity: f64,
+    /// Per-share cost including the buy's fees.
+    pub unit_cost: f64,
+}
+
+impl OpenLot {
+    pub fn cost(&self) -> f64 {
+        self.quantity * self.unit_cost
+    }
+}
+
+/// Ledger replayed with one [`CostBasisMethod`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Position {
+    pub open_lots: Vec<OpenLot>,
+    /// Sell proceeds (after fees) minus the cost of the shares they closed.
+    pub realized: f64,
+}
+
+impl Position {
+    pub fn shares(&self) -> f64 {
+        self.open_lots.iter().map(|l| l.quantity).sum()
+    }
+
+    pub fn cost_basis(&self) -> f64 {
+        self.open_lots.iter().map(OpenLot::cost).sum()
+    }
+
+    pub fn average_cost(&self) -> Option<f64> {
+        let shares = self.shares();
+        (shares > f64::EPSILON).then(|| self.cost_basis() / shares)
+    }
+}
+
+/// Replays `transactions` in date order (stable for equal dates). An undated entry sorts with the
+/// last dated entry before it, so it stays after the trades entered ahead of it; leading undated
+/// entries (migrated holdings) come first. Sells beyond the open quantity only close what is held,
+/// and only that share of their fees counts.
+pub fn replay_ledger(transactions: &[Transaction], method: CostBasisMethod) -> Position {
+    let mut pos = Position::default();
+    for t in replay_order(transactions) {
+        match t.side {
+            TradeSide::Buy => {
+                if t.quantity <= 0.0 {
+                    continue;
+                }
+                pos.open_lots.push(OpenLot {
+                    date: t.date,
+                    quantity: t.quantity,
+                    unit_cost: (t.quantity * t.price + t.fees) / t.quantity,
+                });
+            }
+            TradeSide::Sell => {
+                let qty = t.quantity.min(pos.shares());
+                if qty <= 0.0 {
+                    continue;
+                }
+                let closed_cost = close_lots(&mut pos.open_lots, qty, method);
+                let fees = t.fees * qty / t.quantity;
+                pos.realized += qty * t.price - fees - closed_cost;
+            }
+        }
+    }
+    pos
+}
+
+/// `transactions` in the order [`replay_ledger`] applies them.
+fn replay_order(transactions: &[Transaction]) -> Vec<&Transaction> {
+    let mut last_date = None;
+    let mut ordered: Vec<(Option<NaiveDate>, &Transaction)> = transactions
+        .iter()
+        .map(|t| {
+            last_date = t.date.or(last_date);
+            (last_date, t)
+        })
+        .collect();
+    ordered.sort_by_key(|&(date, _)| date);
+    ordered.into_iter().map(|(_, t)| t).collect()
+}
+
+/// Shares held just before the first sell that exceeds them in replay order, if any.
+pub fn first_oversell(transactions: &[Transaction]) -> Option<f64> {
+    let mut held: f64 = 0.0;
+    for t in replay_order(transactions) {
+        match t.side {
+            TradeSide::Buy => held += t.quantity.max(0.0),
+            TradeSide::Sell if t.quantity > held + f64::EPSILON => return Some(held),
+            TradeSide::Sell => held -= t.quantity,
+        }
+    }
+    None
+}
+
+/// Removes `qty` shares from `lots` and returns their cost.
+fn close_lots(lots: &mut Vec<OpenLot>, qty: f64, method: CostBasisMethod) -> f64 {
+    let mut cost = 0.0;
+    match method {
+        CostBasisMethod::Average => {
+            let held: f64 = lots.iter().map(|l| l.quantity).sum();
+            let keep = 1.0 - qty / held;
+            for lot in lots.iter_mut() {
+                cost += lot.cost() * (1.0 - keep);
+                lot.quantity *= keep;
+            }
+        }
+        CostBasisMethod::Fifo | CostBasisMethod::Lifo => {
+            let mut left = qty;
+            while left > f64::EPSILON && !lots.is_empty() {
+                let i = if method == CostBasisMethod::Fifo { 0 } else { lots.len() - 1 };
+                let take = left.min(lots[i].quantity);
+                cost += take * lots[i].unit_cost;
+                lots[i].quantity -= take;
+                left -= take;
+                if lots[i].quantity <= f64::EPSILON {
+                    lots.remove(i);
+                }
+            }
+        }
+    }
+    lots.retain(|l| l.quantity > f64::EPSILON);
+    cost
+}
+
+/// Upper sanity bound for shares (paste / typo); SPEC §15.5.
+pub const MAX_HOLDING_SHARES: f64 = 1_000_000_000.0;
+/// Upper sanity bound for price per share; SPEC §15.5.
+pub const MAX_HOLDING_PRICE_PER_SHARE: f64 = 1e12;
+
+/// Rejects trades above [`MAX_HOLDING_SHARES`] or [`MAX_HOLDING_PRICE_PER_SHARE`] (trade dialog and CSV import).
+pub fn validate_holding_limits(shares: f64, price: f64) -> Result<(), &'static str> {
+    if shares > MAX_HOLDING_SHARES {
+        return Err("Shares exceed the allowed maximum");
+    }
+    if price > MAX_HOLDING_PRICE_PER_SHARE {
+        return Err("Price per share exceeds the allowed maximum");
+    }
+    Ok(())
+}
+
+/// Account created for configs without `portfolios` (and for the legacy flat `portfolio` list).
+pub const DEFAULT_PORTFOLIO_NAME: &str = "Main";
+
+/// One named account.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Portfolio {
+    pub name: String,
+    #[serde(default)]
+    pub holdings: Vec<PortfolioItem>,
+}
+
+impl Portfolio {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            holdings: Vec::new(),
+        }
+    }
+}
+
+/// Appends `tx` to the ledger of `symbol` (already normalized), creating the holding when absent.
+pub fn merge_trade(portfolio: &mut Vec<PortfolioItem>, symbol: &str, tx: Transaction) {
+    match portfolio
+        .iter_mut()
+        .find(|i| i.symbol.trim().eq_ignore_ascii_case(symbol))
+    {
+        Some(item) => item.transactions.push(tx),
+        None => portfolio.push(PortfolioItem::with_transactions(symbol.to_string(), vec![tx])),
+    }
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(from = "PortfolioItemRepr")]
 pub struct PortfolioItem {
     pub symbol: String,
-    pub shares: f64,
-    pub purchase_price: f64,
+    /// Buys and sells in entry order; see [`replay_ledger`].
+    pub transactions: Vec<Transaction>,
     pub current_price: Option<f64>,
-    pub purchase_date: Option<String>,
     pub notes: Option<String>,
+    /// Quote currency of prices and trades (`GBp`, `CAD`, …), copied from the latest quote so
+    /// totals convert before the first refresh. `None` = the base currency.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Day-change base from the latest quote (previous close, else session open). Runtime only.
+    #[serde(skip)]
+    pub previous_close: Option<f64>,
+}
+
+/// On-disk shape accepted by [`PortfolioItem`]: the ledger, or the pre-ledger averaged fields.
+#[derive(Deserialize)]
+struct PortfolioItemRepr {
+    symbol: String,
+    #[serde(default)]
+    transactions: Option<Vec<Transaction>>,
+    #[serde(default)]
+    shares: Option<f64>,
+    #[serde(default)]
+    purchase_price: Option<f64>,
+    #[serde(default)]
+    purchase_date: Option<String>,
+    #[serde(default)]
+    current_price: Option<f64>,
+    #[serde(default)]
+    notes: Option<String>,
+    #[serde(default)]
+    currency: Option<String>,
+}
+
+impl From<PortfolioItemRepr> for PortfolioItem {
+    fn from(r: PortfolioItemRepr) -> Self {
+        let transactions = match (r.transactions, r.shares) {
+            (Some(t), _) => t,
+            (None, Some(shares)) if shares > 0.0 => {
+                // Accept `YYYY-MM-DD` with or without a trailing time.
+                let date = r.purchase_date.as_deref().and_then(|d| {
+                    NaiveDate::parse_from_str(d.get(..10).unwrap_or(d), "%Y-%m-%d").ok()
+                });
+                vec![Transaction::buy(date, shares, r.purchase_price.unwrap_or(0.0), 0.0)]
+            }
+            (None, _) => Vec::new(),
+        };
+        Self {
+            symbol: r.symbol,
+            transactions,
+            current_price: r.current_price,
+            notes: r.notes,
+            currency: r.currency,
+            previous_close: None,
+        }
+    }
 }
 
 impl PortfolioItem {
+    /// Holding with one undated buy (`shares` at `purchase_price`).
     pub fn new(symbol: String, shares: f64, purchase_price: f64) -> Self {
+        Self::with_transactions(symbol, vec![Transaction::buy(None, shares, purchase_price, 0.0)])
+    }
+
+    pub fn with_transactions(symbol: String, transactions: Vec<Transaction>) -> Self {
         Self {
             symbol,
-            shares,
-            purchase_price,
+            transactions,
             current_price: None,
-            purchase_date: None,
             notes: None,
+            currency: None,
+            previous_close: None,
         }
     }
 
+    pub fn position(&self, method: CostBasisMethod) -> Position {
+        replay_ledger(&self.transactions, method)
+    }
+
+    /// Open quantity (independent of the cost-basis method).
+    pub fn shares(&self) -> f64 {
+        self.position(CostBasisMethod::Fifo).shares()
+    }
+
     pub fn market_value(&self) -> Option<f64> {
-        self.current_price.map(|price| price * self.shares)
+        self.current_price.map(|price| price * self.shares())
     }
 
-    pub fn cost_basis(&self) -> f64 {
-        self.purchase_price * self.shares
+    pub fn cost_basis(&self, method: CostBasisMethod) -> f64 {
+        self.position(method).cost_basis()
+    }
+
+    /// Unrealized P/L on the open lots.
+    pub fn profit_loss(&self, method: CostBasisMethod) -> Option<f64> {
+        self.market_value().map(|value| value - self.cost_basis(method))
+    }
+
+    pub fn profit_loss_percent(&self, method: CostBasisMethod) -> Option<f64> {
+        let cost = self.cost_basis(method);
+        if cost.abs() < f64::EPSILON {
+            return None;
+        }
+        self.profit_loss(method).map(|pl| (pl / cost) * 100.0)
+    }
+
+    pub fn realized_profit_loss(&self, method: CostBasisMethod) -> f64 {
+        self.position(method).realized
+    }
+
+    /// Today's P/L: `(current − previous close) × shares`.
+    pub fn day_change(&self) -> Option<f64> {
+        Some((self.current_price? - self.previous_close?) * self.shares())
+    }
+
+    pub fn day_change_percent(&self) -> Option<f64> {
+        let prev = self.previous_close.filter(|p| p.abs() > f64::EPSILON)?;
+        Some((self.current_price? - prev) / prev * 100.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(s: &str) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()
+    }
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    fn ledger() -> Vec<Transaction> {
+        vec![
+            Transaction::buy(d("2024-01-02"), 10.0, 100.0, 0.0),
+            Transaction::sell(d("2024-03-01"), 5.0, 150.0, 0.0),
+            Transaction::buy(d("2024-02-01"), 10.0, 120.0, 0.0),
+        ]
+    }
+
+    #[test]
+    fn day_change_needs_both_prices() {
+        let mut item = PortfolioItem::new("AAPL".into(), 10.0, 50.0);
+        assert_eq!(item.day_change(), None);
+        item.current_price = Some(110.0);
+        assert_eq!(item.day_change(), None);
+        item.previous_close = Some(100.0);
+        assert_eq!(item.day_change(), Some(100.0));
+        assert_eq!(item.day_change_percent(), Some(10.0));
+    }
+
+    #[test]
+    fn fifo_and_lifo_close_different_lots() {
+        let fifo = replay_ledger(&ledger(), CostBasisMethod::Fifo);
+        assert!(approx(fifo.shares(), 15.0));
+        assert!(approx(fifo.realized, 5.0 * 50.0));
+        assert!(approx(fifo.cost_basis(), 5.0 * 100.0 + 10.0 * 120.0));
+
+        let lifo = replay_ledger(&ledger(), CostBasisMethod::Lifo);
+        assert!(approx(lifo.realized, 5.0 * 30.0));
+        assert!(approx(lifo.cost_basis(), 10.0 * 100.0 + 5.0 * 120.0));
+        assert_eq!(lifo.open_lots.len(), 2);
+    }
+
+    #[test]
+    fn average_keeps_running_unit_cost() {
+        let pos = replay_ledger(&ledger(), CostBasisMethod::Average);
+        assert!(approx(pos.average_cost().unwrap(), 110.0));
+        assert!(approx(pos.realized, 5.0 * 40.0));
+        assert!(approx(pos.shares(), 15.0));
+    }
+
+    #[test]
+    fn fees_raise_cost_and_reduce_proceeds() {
+        let txs = vec![
+            Transaction::buy(None, 10.0, 10.0, 5.0),
+            Transaction::sell(None, 10.0, 12.0, 3.0),
+        ];
+        let pos = replay_ledger(&txs, CostBasisMethod::Fifo);
+        assert!(pos.open_lots.is_empty());
+        assert!(approx(pos.realized, 120.0 - 3.0 - 105.0));
+    }
+
+    #[test]
+    fn undated_entries_replay_after_the_trades_entered_before_them() {
+        let txs = vec![
+            Transaction::buy(d("2024-01-02"), 10.0, 100.0, 0.0),
+            Transaction::sell(None, 4.0, 150.0, 0.0),
+            Transaction::buy(d("2024-02-01"), 5.0, 120.0, 0.0),
+            Transaction::sell(None, 3.0, 130.0, 0.0),
+        ];
+        let pos = replay_ledger(&txs, CostBasisMethod::Fifo);
+        assert!(approx(pos.shares(), 8.0));
+        assert!(approx(pos.realized, 4.0 * 50.0 + 3.0 * 30.0));
+        assert!(approx(pos.cost_basis(), 3.0 * 100.0 + 5.0 * 120.0));
+    }
+
+    #[test]
+    fn oversell_only_closes_held_quantity() {
+        let txs = vec![
+            Transaction::buy(None, 2.0, 10.0, 0.0),
+            Transaction::sell(None, 5.0, 11.0, 0.0),
+        ];
+        let pos = replay_ledger(&txs, CostBasisMethod::Fifo);
+        assert!(approx(pos.shares(), 0.0));
+        assert!(approx(pos.realized, 2.0));
+    }
+
+    #[test]
+    fn first_oversell_follows_replay_order() {
+        let txs = vec![
+            Transaction::sell(d("2024-03-01"), 5.0, 150.0, 0.0),
+            Transaction::buy(d("2024-01-02"), 10.0, 100.0, 0.0),
+        ];
+        assert_eq!(first_oversell(&txs), None);
+        // The undated sell replays with the buy entered before it, ahead of the March sell.
+        let mut over = txs.clone();
+        over.push(Transaction::sell(None, 6.0, 150.0, 0.0));
+        assert_eq!(first_oversell(&over), Some(4.0));
     }
 
-    pub fn profit_loss(&self) -> Option<f64> {
-        self.market_value().map(|value| value - self.cost_basis())
+    #[test]
+    fn oversell_prorates_fees_to_the_closed_quantity() {
+        let txs = vec![
+            Transaction::buy(None, 2.0, 10.0, 0.0),
+            Transaction::sell(None, 5.0, 11.0, 10.0),
+        ];
+        let pos = replay_ledger(&txs, CostBasisMethod::Fifo);
+        assert!(approx(pos.realized, 2.0 * 11.0 - 4.0 - 20.0));
     }
 
-    pub fn profit_loss_percent(&self) -> Option<f64> {
-        self.profit_loss().map(|pl| (pl / self.cost_basis()) * 100.0)
+    #[test]
+    fn legacy_averaged_holding_migrates_to_one_buy() {
+        let j = r#"{"symbol":"AAPL","shares":4.0,"purchase_price":150.0,"current_price":null,"purchase_date":"2023-05-01","notes":null}"#;
+        let item: PortfolioItem = serde_json::from_str(j).expect("parse");
+        assert_eq!(
+            item.transactions,
+            vec![Transaction::buy(d("2023-05-01"), 4.0, 150.0, 0.0)]
+        );
+        let out = serde_json::to_string(&item).expect("serialize");
+        assert!(out.contains(r#""transactions":[{"side":"buy","date":"2023-05-01""#));
+        assert!(!out.contains("purchase_price"));
+        let back: PortfolioItem = serde_json::from_str(&out).expect("round trip");
+        assert_eq!(back.transactions, item.transactions);
     }
 }
@@ -0,0 +1,491 @@
+//! Portfolio CSV export and broker-format import.
+//!
+//! Export writes one row per ledger entry in the native layout (`symbol,side,date,quantity,price,fees`),
+//! which imports back losslessly. Import maps header names through a [`CsvPreset`]; broker position
+//! exports without a side column become one buy per row at their cost basis.
+
+use std::io::Write;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::models::portfolio::{validate_holding_limits, PortfolioItem, TradeSide, Transaction};
+
+/// Header of [`write_portfolio_csv`]; also the [`CsvPreset::Stockterm`] column names.
+pub const EXPORT_HEADER: [&str; 6] = ["symbol", "side", "date", "quantity", "price", "fees"];
+
+/// Rows scanned for a header line before giving up (brokers prepend account titles).
+const HEADER_SCAN_ROWS: usize = 10;
+
+/// Column mapping used by import. [`CsvPreset::Auto`] picks the first preset whose header matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CsvPreset {
+    #[default]
+    Auto,
+    /// `stockterm portfolio export` / Portfolio tab export.
+    Stockterm,
+    /// Fidelity "Portfolio Positions" (total cost basis; `Date Acquired` on lot exports).
+    Fidelity,
+    /// Schwab "Positions" (title line before the header, total cost basis).
+    Schwab,
+    /// Interactive Brokers Flex open positions / lots.
+    Ibkr,
+    /// Robinhood account activity (`Trans Code` Buy / Sell).
+    Robinhood,
+}
+
+/// Accepted header names per field (compared trimmed, case-insensitively).
+struct ColumnMap {
+    symbol: &'static [&'static str],
+    quantity: &'static [&'static str],
+    /// Per-share cost or trade price.
+    price: &'static [&'static str],
+    /// Total cost; divided by quantity when no per-share column is present.
+    total_cost: &'static [&'static str],
+    date: &'static [&'static str],
+    side: &'static [&'static str],
+    fees: &'static [&'static str],
+}
+
+impl CsvPreset {
+    /// Cycle order in the import dialog.
+    pub const ALL: [CsvPreset; 6] = [
+        CsvPreset::Auto,
+        CsvPreset::Stockterm,
+        CsvPreset::Fidelity,
+        CsvPreset::Schwab,
+        CsvPreset::Ibkr,
+        CsvPreset::Robinhood,
+    ];
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            CsvPreset::Auto => "auto",
+            CsvPreset::Stockterm => "stockterm",
+            CsvPreset::Fidelity => "fidelity",
+            CsvPreset::Schwab => "schwab",
+            CsvPreset::Ibkr => "ibkr",
+            CsvPreset::Robinhood => "robinhood",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|p| *p == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    fn columns(self) -> ColumnMap {
+        match self {
+            CsvPreset::Auto | CsvPreset::Stockterm => ColumnMap {
+                symbol: &["symbol"],
+                quantity: &["quantity", "shares"],
+                price: &["price"],
+                total_cost: &[],
+                date: &["date"],
+                side: &["side"],
+                fees: &["fees"],
+            },
+            CsvPreset::Fidelity => ColumnMap {
+                symbol: &["symbol"],
+                quantity: &["quantity"],
+                price: &["average cost basis"],
+                total_cost: &["cost basis total", "cost basis"],
+                date: &["date acquired"],
+                side: &[],
+                fees: &[],
+            },
+            CsvPreset::Schwab => ColumnMap {
+                symbol: &["symbol"],
+                quantity: &["qty (quantity)", "quantity"],
+                price: &["cost/share"],
+                total_cost: &["cost basis"],
+                date: &["date acquired", "open date"],
+                side: &[],
+                fees: &[],
+            },
+            CsvPreset::Ibkr => ColumnMap {
+                symbol: &["symbol"],
+                quantity: &["quantity", "position"],
+                price: &["costbasisprice", "cost price"],
+                total_cost: &["costbasismoney", "cost basis"],
+                date: &["opendatetime", "open date"],
+                side: &[],
+                fees: &[],
+            },
+            CsvPreset::Robinhood => ColumnMap {
+                symbol: &["instrument"],
+                quantity: &["quantity"],
+                price: &["price"],
+                total_cost: &[],
+                date: &["activity date", "trade date"],
+                side: &["trans code"],
+                fees: &[],
+            },
+        }
+    }
+}
+
+/// Parses a [`CsvPreset::label`] case-insensitively; used by `stockterm portfolio import --preset`.
+impl FromStr for CsvPreset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let t = s.trim();
+        CsvPreset::ALL
+            .into_iter()
+            .find(|p| p.label().eq_ignore_ascii_case(t))
+            .ok_or_else(|| {
+                format!("unknown preset {t:?} (expected auto, stockterm, fidelity, schwab, ibkr or robinhood)")
+            })
+    }
+}
+
+/// One parsed row ready for `App::add_to_portfolio` / [`crate::models::portfolio::merge_trade`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ImportedTrade {
+    /// 1-based line in the source file.
+    pub line: u64,
+    pub symbol: String,
+    #[serde(flatten)]
+    pub transaction: Transaction,
+}
+
+/// A row that could not be turned into a trade.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RowError {
+    pub line: u64,
+    pub message: String,
+}
+
+/// Result of [`parse_portfolio_csv`]: good rows and bad rows, both in file order.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CsvImport {
+    /// Preset whose columns matched (never [`CsvPreset::Auto`]).
+    pub preset: CsvPreset,
+    pub trades: Vec<ImportedTrade>,
+    pub errors: Vec<RowError>,
+}
+
+/// Column indices resolved against one header row.
+struct ResolvedColumns {
+    symbol: usize,
+    quantity: usize,
+    price: Option<usize>,
+    total_cost: Option<usize>,
+    date: Option<usize>,
+    side: Option<usize>,
+    fees: Option<usize>,
+}
+
+impl ResolvedColumns {
+    fn resolve(map: &ColumnMap, header: &csv::StringRecord) -> Option<Self> {
+        let find = |names: &[&str]| {
+            header
+                .iter()
+                .position(|h| names.iter().any(|n| h.trim().eq_ignore_ascii_case(n)))
+        };
+        let cols = Self {
+            symbol: find(map.symbol)?,
+            quantity: find(map.quantity)?,
+            price: find(map.price),
+            total_cost: find(map.total_cost),
+            date: find(map.date),
+            side: find(map.side),
+            fees: find(map.fees),
+        };
+        (cols.price.is_some() || cols.total_cost.is_some()).then_some(cols)
+    }
+}
+
+/// Parses `input` with `preset` (or the first matching preset for [`CsvPreset::Auto`]).
+///
+/// Fails only when no header row matches; every data row ends up in `trades` or `errors`. Blank
+/// rows and rows without a symbol and quantity (totals, disclaimers) are skipped silently.
+pub fn parse_portfolio_csv(input: &str, preset: CsvPreset) -> Result<CsvImport, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(input.trim_start_matches('\u{feff}').as_bytes());
+    let mut records = reader.records();
+    let candidates: Vec<CsvPreset> = match preset {
+        CsvPreset::Auto => CsvPreset::ALL[1..].to_vec(),
+        p => vec![p],
+    };
+    let mut header = None;
+    for record in records.by_ref().take(HEADER_SCAN_ROWS) {
+        let record = record.map_err(|e| format!("Could not read CSV: {e}"))?;
+        if let Some(found) = candidates
+            .iter()
+            .find_map(|p| ResolvedColumns::resolve(&p.columns(), &record).map(|c| (*p, c)))
+        {
+            header = Some(found);
+            break;
+        }
+    }
+    let Some((preset, cols)) = header else {
+        return Err(format!(
+            "No {} header found (need symbol, quantity and price or cost basis columns)",
+            match preset {
+                CsvPreset::Auto => "known",
+                p => p.label(),
+            }
+        ));
+    };
+    let mut out = CsvImport {
+        preset,
+        ..CsvImport::default()
+    };
+    for record in records {
+        let record = match record {
+            Ok(r) => r,
+            Err(e) => {
+                out.errors.push(RowError {
+                    line: e.position().map_or(0, |p| p.line()),
+                    message: e.to_string(),
+                });
+                continue;
+            }
+        };
+        let line = record.position().map_or(0, |p| p.line());
+        let field = |i: Option<usize>| i.and_then(|i| record.get(i)).map(str::trim).unwrap_or("");
+        if field(Some(cols.symbol)).is_empty() && field(Some(cols.quantity)).is_empty() {
+            continue;
+        }
+        match parse_row(&cols, field) {
+            Ok((symbol, transaction)) => out.trades.push(ImportedTrade {
+                line,
+                symbol,
+                transaction,
+            }),
+            Err(message) => out.errors.push(RowError { line, message }),
+        }
+    }
+    Ok(out)
+}
+
+fn parse_row<'a>(
+    cols: &ResolvedColumns,
+    field: impl Fn(Option<usize>) -> &'a str,
+) -> Result<(String, Transaction), String> {
+    let symbol = parse_symbol(field(Some(cols.symbol)))?;
+    let signed_qty = parse_amount(field(Some(cols.quantity))).ok_or("Invalid quantity")?;
+    let side = match cols.side {
+        Some(_) => parse_side(field(cols.side))?,
+        None if signed_qty < 0.0 => TradeSide::Sell,
+        None => TradeSide::Buy,
+    };
+    let quantity = signed_qty.abs();
+    if quantity <= 0.0 {
+        return Err("Quantity must be greater than zero".into());
+    }
+    let price = match (field(cols.price), field(cols.total_cost)) {
+        (p, _) if !p.is_empty() => parse_amount(p).ok_or("Invalid price")?.abs(),
+        (_, total) if !total.is_empty() => parse_amount(total).ok_or("Invalid cost basis")?.abs() / quantity,
+        _ => return Err("Price or cost basis missing".into()),
+    };
+    if price <= 0.0 {
+        return Err("Price must be greater than zero".into());
+    }
+    validate_holding_limits(quantity, price)?;
+    let fees = match field(cols.fees) {
+        "" => 0.0,
+        f => parse_amount(f).ok_or("Invalid fees")?.abs(),
+    };
+    let date = parse_date(field(cols.date))?;
+    Ok((symbol, Transaction {
+        side,
+        date,
+        quantity,
+        price,
+        fees,
+    }))
+}
+
+/// Upper-cases and drops Fidelity's trailing `*` on money-market rows.
+fn parse_symbol(s: &str) -> Result<String, String> {
+    let t = s.trim().trim_end_matches('*').to_uppercase();
+    if t.is_empty() {
+        return Err("Symbol missing".into());
+    }
+    if !t.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '^' | '=')) {
+        return Err(format!("Invalid symbol {s:?}"));
+    }
+    Ok(t)
+}
+
+/// Broker number formatting: `$1,234.50`, `(12.00)` for negatives, leading `+`.
+fn parse_amount(s: &str) -> Option<f64> {
+    let t = s.trim();
+    let (negative, t) = match t.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+        Some(inner) => (true, inner),
+        None => (false, t),
+    };
+    let cleaned: String = t.chars().filter(|c| !matches!(c, '$' | ',' | '+' | ' ')).collect();
+    let v: f64 = cleaned.parse().ok()?;
+    v.is_finite().then_some(if negative { -v } else { v })
+}
+
+fn parse_side(s: &str) -> Result<TradeSide, String> {
+    let t = s.trim().to_ascii_lowercase();
+    if matches!(t.as_str(), "buy" | "b" | "bought") || t.starts_with("you bought") {
+        Ok(TradeSide::Buy)
+    } else if matches!(t.as_str(), "sell" | "s" | "sold") || t.starts_with("you sold") {
+        Ok(TradeSide::Sell)
+    } else {
+        Err(format!("Unsupported side {s:?}"))
+    }
+}
+
+/// `YYYY-MM-DD`, `MM/DD/YYYY`, `MM/DD/YY` or IBKR `YYYYMMDD`; a time after a space, `,` or `;` is ignored.
+fn parse_date(s: &str) -> Result<Option<NaiveDate>, String> {
+    let t = s.trim();
+    if t.is_empty() || t == "--" {
+        return Ok(None);
+    }
+    let day = t.split([' ', ',', ';']).next().unwrap_or(t);
+    ["%Y-%m-%d", "%m/%d/%Y", "%m/%d/%y", "%Y%m%d"]
+        .iter()
+        .find_map(|fmt| NaiveDate::parse_from_str(day, fmt).ok())
+        .map(Some)
+        .ok_or_else(|| format!("Invalid date {t:?}"))
+}
+
+/// Writes every ledger entry (holdings in list order, trades in entry order) with [`EXPORT_HEADER`].
+pub fn write_portfolio_csv(portfolio: &[PortfolioItem], out: impl Write) -> csv::Result<()> {
+    let mut w = csv::Writer::from_writer(out);
+    w.write_record(EXPORT_HEADER)?;
+    for item in portfolio {
+        for t in &item.transactions {
+            w.write_record([
+                item.symbol.as_str(),
+                match t.side {
+                    TradeSide::Buy => "buy",
+                    TradeSide::Sell => "sell",
+                },
+                &t.date.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default(),
+                &t.quantity.to_string(),
+                &t.price.to_string(),
+                &t.fees.to_string(),
+            ])?;
+        }
+    }
+    w.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::portfolio::{merge_trade, replay_ledger, CostBasisMethod};
+
+    fn date(s: &str) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()
+    }
+
+    #[test]
+    fn export_round_trips_through_auto_import() {
+        let portfolio = vec![PortfolioItem::with_transactions(
+            "AAPL".into(),
+            vec![
+                Transaction::buy(date("2024-01-02"), 10.0, 150.25, 1.0),
+                Transaction::sell(None, 4.0, 190.0, 0.0),
+            ],
+        )];
+        let mut buf = Vec::new();
+        write_portfolio_csv(&portfolio, &mut buf).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+        assert!(text.starts_with("symbol,side,date,quantity,price,fees\n"));
+        let parsed = parse_portfolio_csv(&text, CsvPreset::Auto).unwrap();
+        assert_eq!(parsed.preset, CsvPreset::Stockterm);
+        assert!(parsed.errors.is_empty());
+        let txs: Vec<Transaction> = parsed.trades.iter().map(|t| t.transaction.clone()).collect();
+        assert_eq!(txs, portfolio[0].transactions);
+        assert_eq!(parsed.trades[1].line, 3);
+
+        let mut reimported = Vec::new();
+        for t in parsed.trades {
+            merge_trade(&mut reimported, &t.symbol, t.transaction);
+        }
+        let pos = replay_ledger(&reimported[0].transactions, CostBasisMethod::Fifo);
+        assert!((pos.shares() - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn fidelity_positions_use_total_cost_and_report_bad_rows() {
+        let csv = "\u{feff}Account Number,Account Name,Symbol,Description,Quantity,Last Price,Cost Basis Total\n\
+                   X1,Brokerage,SPAXX**,MONEY MARKET,\"1,000.00\",$1.00,--\n\
+                   X1,Brokerage,MSFT,MICROSOFT,20,$410.00,\"$6,000.00\"\n\
+                   X1,Brokerage,Pending Activity,,,,\n\
+                   \n\
+                   \"Date downloaded 10/17/2026\"\n";
+        let parsed = parse_portfolio_csv(csv, CsvPreset::Auto).unwrap();
+        assert_eq!(parsed.preset, CsvPreset::Fidelity);
+        assert_eq!(parsed.trades.len(), 1);
+        assert_eq!(parsed.trades[0].symbol, "MSFT");
+        assert!((parsed.trades[0].transaction.price - 300.0).abs() < 1e-9);
+        let lines: Vec<u64> = parsed.errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![2, 4]);
+        assert!(parsed.errors[1].message.contains("Invalid symbol"));
+    }
+
+    #[test]
+    fn rows_above_the_holding_limits_are_line_numbered_errors() {
+        let csv = "symbol,side,date,quantity,price,fees\n\
+                   AAPL,buy,2024-01-02,10,150,0\n\
+                   MSFT,buy,2024-01-02,2000000000,10,0\n\
+                   NVDA,buy,2024-01-02,1,2000000000000,0\n";
+        let parsed = parse_portfolio_csv(csv, CsvPreset::Auto).unwrap();
+        assert_eq!(parsed.trades.len(), 1);
+        assert_eq!(parsed.errors, vec![
+            RowError {
+                line: 3,
+                message: "Shares exceed the allowed maximum".into(),
+            },
+            RowError {
+                line: 4,
+                message: "Price per share exceeds the allowed maximum".into(),
+            },
+        ]);
+    }
+
+    #[test]
+    fn schwab_title_line_and_robinhood_sides() {
+        let schwab = "\"Positions for account Individual ...123 as of 10/17/2026\"\n\
+                      \n\
+                      \"Symbol\",\"Description\",\"Qty (Quantity)\",\"Cost Basis\"\n\
+                      \"VTI\",\"VANGUARD TOTAL\",\"12\",\"$2,400.00\"\n";
+        let parsed = parse_portfolio_csv(schwab, CsvPreset::Schwab).unwrap();
+        assert_eq!(parsed.trades.len(), 1);
+        assert_eq!(parsed.trades[0].line, 4);
+        assert!((parsed.trades[0].transaction.price - 200.0).abs() < 1e-9);
+
+        let robinhood = "Activity Date,Instrument,Trans Code,Quantity,Price,Amount\n\
+                         3/4/2024,TSLA,Buy,5,$180.00,($900.00)\n\
+                         4/1/2024,TSLA,Sell,2,$170.00,$340.00\n\
+                         4/2/2024,TSLA,CDIV,,,$1.00\n";
+        let parsed = parse_portfolio_csv(robinhood, CsvPreset::Auto).unwrap();
+        assert_eq!(parsed.preset, CsvPreset::Robinhood);
+        let sides: Vec<TradeSide> = parsed.trades.iter().map(|t| t.transaction.side).collect();
+        assert_eq!(sides, vec![TradeSide::Buy, TradeSide::Sell]);
+        assert_eq!(parsed.trades[0].transaction.date, date("2024-03-04"));
+        assert_eq!(parsed.errors.len(), 1);
+        assert_eq!(parsed.errors[0].line, 4);
+
+        let mut portfolio = Vec::new();
+        for t in parsed.trades {
+            merge_trade(&mut portfolio, &t.symbol, t.transaction);
+        }
+        assert_eq!(portfolio.len(), 1);
+        assert!((portfolio[0].shares() - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn unknown_header_is_an_error() {
+        assert!(parse_portfolio_csv("a,b,c\n1,2,3\n", CsvPreset::Auto).is_err());
+        assert!(parse_portfolio_csv("symbol,quantity,price\n", CsvPreset::Fidelity).is_err());
+        assert_eq!("IBKR".parse::<CsvPreset>(), Ok(CsvPreset::Ibkr));
+        assert!("etrade".parse::<CsvPreset>().is_err());
+    }
+}
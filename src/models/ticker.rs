@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Polygon `/v2/aggs/ticker/.../range/...` body. The `ticker` field is omitted on some responses
 /// (empty results, delayed/error payloads), so it must not be required for deserialization.
@@ -14,6 +14,38 @@ pub struct TickerResponse {
     pub error: Option<String>,
 }
 
+/// US equity session phase when a quote was taken (Yahoo `marketState`; derived from the clock for Polygon).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MarketState {
+    Pre,
+    Regular,
+    Post,
+    Closed,
+}
+
+impl MarketState {
+    /// Yahoo `marketState` (`PREPRE`, `PRE`, `REGULAR`, `POST`, `POSTPOST`, `CLOSED`); unknown → `None`.
+    pub fn from_yahoo(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "PRE" | "PREPRE" => Some(MarketState::Pre),
+            "REGULAR" => Some(MarketState::Regular),
+            "POST" | "POSTPOST" => Some(MarketState::Post),
+            "CLOSED" => Some(MarketState::Closed),
+            _ => None,
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            MarketState::Pre => "PRE",
+            MarketState::Regular => "REG",
+            MarketState::Post => "POST",
+            MarketState::Closed => "CLOSED",
+        }
+    }
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct TickerResult {
     pub o: f64,
@@ -24,6 +56,93 @@ pub struct TickerResult {
     #[serde(default)]
     pub v: f64,
     pub t: u64,
+    /// Prior session close (Yahoo `regularMarketPreviousClose`; Polygon prior-day bar). Not on the wire
+    /// for raw Polygon aggregates — filled by the provider after parsing.
+    #[serde(default)]
+    pub prev_close: Option<f64>,
+    #[serde(default)]
+    pub market_state: Option<MarketState>,
+    /// Latest pre/post-market trade (Yahoo `preMarketPrice` / `postMarketPrice`) while
+    /// `market_state` is [`MarketState::Pre`] or [`MarketState::Post`].
+    #[serde(default)]
+    pub ext_price: Option<f64>,
+    /// 52-week low / high (Yahoo `fiftyTwoWeekLow` / `fiftyTwoWeekHigh`; `None` on Polygon).
+    #[serde(default)]
+    pub week52_low: Option<f64>,
+    #[serde(default)]
+    pub week52_high: Option<f64>,
+    /// Market capitalization in the quote currency (Yahoo v7 `marketCap` only).
+    #[serde(default)]
+    pub market_cap: Option<f64>,
+    /// Quote currency as reported (Yahoo `currency`; may be a minor unit such as `GBp`).
+    /// `None` on Polygon, which only quotes USD listings.
+    #[serde(default)]
+    pub currency: Option<String>,
+}
+
+impl TickerResult {
+    /// Reference price for day change: previous close, else session open (payloads without a prior close).
+    pub fn change_base(&self) -> f64 {
+        self.prev_close
+            .filter(|p| p.abs() > f64::EPSILON)
+            .unwrap_or(self.o)
+    }
+
+    /// Last minus [`change_base`](Self::change_base).
+    pub fn day_change(&self) -> f64 {
+        self.c - self.change_base()
+    }
+
+    /// [`day_change`](Self::day_change) as a percentage of the base; `0` when the base is zero.
+    pub fn day_change_percent(&self) -> f64 {
+        let base = self.change_base();
+        if base.abs() > f64::EPSILON {
+            (self.day_change() / base) * 100.0
+        } else {
+            0.0
+        }
+    }
+
+    /// `(PRE|POST, price)` when an extended-hours trade is known for the current session.
+    pub fn extended_quote(&self) -> Option<(MarketState, f64)> {
+        let state = self.market_state?;
+        if !matches!(state, MarketState::Pre | MarketState::Post) {
+            return None;
+        }
+        self.ext_price.map(|p| (state, p))
+    }
+
+    /// Extended-hours move versus the regular-session last `c`: `(change, percent)`.
+    pub fn extended_change(&self) -> Option<(f64, f64)> {
+        let (_, price) = self.extended_quote()?;
+        let change = price - self.c;
+        let pct = if self.c.abs() > f64::EPSILON {
+            (change / self.c) * 100.0
+        } else {
+            0.0
+        };
+        Some((change, pct))
+    }
+
+    /// [`currency`](Self::currency), or `USD` when the provider does not report one.
+    pub fn quote_currency(&self) -> &str {
+        self.currency.as_deref().unwrap_or("USD")
+    }
+
+    /// Where `c` sits in the day's low–high range, `0.0`–`1.0` (`None` for a zero-width range).
+    pub fn day_range_position(&self) -> Option<f64> {
+        range_position(self.c, self.l, self.h)
+    }
+
+    /// Where `c` sits in the 52-week range, `0.0`–`1.0`.
+    pub fn week52_position(&self) -> Option<f64> {
+        range_position(self.c, self.week52_low?, self.week52_high?)
+    }
+}
+
+fn range_position(price: f64, low: f64, high: f64) -> Option<f64> {
+    let width = high - low;
+    (width > f64::EPSILON).then(|| ((price - low) / width).clamp(0.0, 1.0))
 }
 
 impl TickerResponse {
@@ -32,6 +151,11 @@ impl TickerResponse {
         self.results.iter().max_by_key(|r| r.t)
     }
 
+    /// Mutable [`latest_result`](Self::latest_result), for folding streamed trades into the bar.
+    pub fn latest_result_mut(&mut self) -> Option<&mut TickerResult> {
+        self.results.iter_mut().max_by_key(|r| r.t)
+    }
+
     pub fn symbol_or<'a>(&'a self, requested: &'a str) -> &'a str {
         if self.ticker.is_empty() {
             requested
@@ -104,6 +228,40 @@ mod tests {
         assert!((r.results[0].v - 52692761.275784).abs() < 1e-6);
     }
 
+    #[test]
+    fn day_change_prefers_prev_close_over_open() {
+        let json = r#"{"results":[{"o":100.0,"h":112.0,"l":99.0,"c":110.0,"v":1,"t":1}]}"#;
+        let mut r: TickerResponse = serde_json::from_str(json).expect("parse");
+        let bar = &mut r.results[0];
+        assert!((bar.day_change() - 10.0).abs() < 1e-9);
+        bar.prev_close = Some(88.0);
+        assert!((bar.day_change() - 22.0).abs() < 1e-9);
+        assert!((bar.day_change_percent() - 25.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn extended_quote_only_in_pre_or_post() {
+        let json = r#"{"results":[{"o":1.0,"h":1.0,"l":1.0,"c":100.0,"v":1,"t":1,"ext_price":102.0}]}"#;
+        let mut r: TickerResponse = serde_json::from_str(json).expect("parse");
+        let bar = &mut r.results[0];
+        assert_eq!(bar.extended_quote(), None);
+        bar.market_state = Some(MarketState::Regular);
+        assert_eq!(bar.extended_quote(), None);
+        bar.market_state = Some(MarketState::Post);
+        assert_eq!(bar.extended_quote(), Some((MarketState::Post, 102.0)));
+        let (chg, pct) = bar.extended_change().expect("change");
+        assert!((chg - 2.0).abs() < 1e-9);
+        assert!((pct - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn market_state_from_yahoo_folds_extended_variants() {
+        assert_eq!(MarketState::from_yahoo("PREPRE"), Some(MarketState::Pre));
+        assert_eq!(MarketState::from_yahoo("postpost"), Some(MarketState::Post));
+        assert_eq!(MarketState::from_yahoo("REGULAR"), Some(MarketState::Regular));
+        assert_eq!(MarketState::from_yahoo("???"), None);
+    }
+
     #[test]
     fn ticker_response_matches_symbol_empty_ticker() {
         let resp = TickerResponse {
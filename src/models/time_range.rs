@@ -1,26 +1,56 @@
-//! Chart time window selection (Issues #9 / M4).
+//! Chart time window selection (Issues #9 / M4), extended presets, and custom date windows.
 
-use chrono::{DateTime, Duration, Local};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate};
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
-/// User-selected historical window on the Charts tab (`1`–`4` keys).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+/// How far back Yahoo serves intraday bars (about 60 days); older custom windows use daily bars.
+const INTRADAY_HORIZON_DAYS: i64 = 60;
+
+/// User-selected historical window on the Charts tab (`1`–`9` keys; `t` opens the custom dialog).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum TimeRange {
     D1,
     W1,
     #[default]
     M1,
+    M3,
+    M6,
+    Ytd,
     Y1,
+    Y5,
+    Max,
+    /// Calendar window from [`CustomRange`] (stored next to the range, not inside it).
+    Custom,
 }
 
 impl TimeRange {
+    /// Preset ranges in key order (`1`–`9`); excludes [`TimeRange::Custom`].
+    pub const PRESETS: [TimeRange; 9] = [
+        TimeRange::D1,
+        TimeRange::W1,
+        TimeRange::M1,
+        TimeRange::Y1,
+        TimeRange::M3,
+        TimeRange::M6,
+        TimeRange::Ytd,
+        TimeRange::Y5,
+        TimeRange::Max,
+    ];
+
     pub const fn label(self) -> &'static str {
         match self {
             TimeRange::D1 => "1D",
             TimeRange::W1 => "1W",
             TimeRange::M1 => "1M",
+            TimeRange::M3 => "3M",
+            TimeRange::M6 => "6M",
+            TimeRange::Ytd => "YTD",
             TimeRange::Y1 => "1Y",
+            TimeRange::Y5 => "5Y",
+            TimeRange::Max => "MAX",
+            TimeRange::Custom => "Custom",
         }
     }
 
@@ -31,54 +61,129 @@ impl TimeRange {
     /// | D1 | 1d | 5m | 5 trading days back → today | 5 × minute |
     /// | W1 | 5d | 30m | 8 days → today | 30 × minute |
     /// | M1 | 1mo | 1d | 32 days → today | 1 × day |
+    /// | M3 | 3mo | 1d | 95 days → today | 1 × day |
+    /// | M6 | 6mo | 1d | 185 days → today | 1 × day |
+    /// | Ytd | ytd | 1d | Jan 1 → today | 1 × day |
     /// | Y1 | 1y | 1wk | 400 days → today | 1 × week |
-    pub fn historical_params(self, now: DateTime<Local>) -> HistoricalQueryParams {
+    /// | Y5 | 5y | 1wk | 1830 days → today | 1 × week |
+    /// | Max | max | 1mo | 1970-01-01 → today | 1 × month |
+    /// | Custom | — (`period1`/`period2`) | by span | `custom.from` → `custom.to` | by span |
+    ///
+    /// `custom` is only read for [`TimeRange::Custom`]; when it is `None` the last 30 days are used.
+    pub fn historical_params(
+        self,
+        now: DateTime<Local>,
+        custom: Option<CustomRange>,
+    ) -> HistoricalQueryParams {
         let today = now.date_naive();
+        let back = |days: i64| (today - Duration::days(days)).format("%Y-%m-%d").to_string();
+        let to = today.format("%Y-%m-%d").to_string();
+        let preset = |from: String,
+                      bar_interval: &'static str,
+                      yahoo_range: &'static str,
+                      polygon_multiplier: u32,
+                      polygon_timespan: &'static str| HistoricalQueryParams {
+            from,
+            to: to.clone(),
+            bar_interval,
+            yahoo_range: Some(yahoo_range),
+            polygon_multiplier,
+            polygon_timespan,
+        };
         match self {
-            TimeRange::D1 => HistoricalQueryParams {
-                from: (today - Duration::days(5))
-                    .format("%Y-%m-%d")
-                    .to_string(),
-                to: today.format("%Y-%m-%d").to_string(),
-                bar_interval: "5m",
-                yahoo_range: Some("1d"),
-                polygon_multiplier: 5,
-                polygon_timespan: "minute",
-            },
-            TimeRange::W1 => HistoricalQueryParams {
-                from: (today - Duration::days(8))
-                    .format("%Y-%m-%d")
-                    .to_string(),
-                to: today.format("%Y-%m-%d").to_string(),
-                bar_interval: "30m",
-                yahoo_range: Some("5d"),
-                polygon_multiplier: 30,
-                polygon_timespan: "minute",
-            },
-            TimeRange::M1 => HistoricalQueryParams {
-                from: (today - Duration::days(32))
-                    .format("%Y-%m-%d")
-                    .to_string(),
-                to: today.format("%Y-%m-%d").to_string(),
-                bar_interval: "1d",
-                yahoo_range: Some("1mo"),
-                polygon_multiplier: 1,
-                polygon_timespan: "day",
-            },
-            TimeRange::Y1 => HistoricalQueryParams {
-                from: (today - Duration::days(400))
-                    .format("%Y-%m-%d")
-                    .to_string(),
-                to: today.format("%Y-%m-%d").to_string(),
-                bar_interval: "1wk",
-                yahoo_range: Some("1y"),
-                polygon_multiplier: 1,
-                polygon_timespan: "week",
-            },
+            TimeRange::D1 => preset(back(5), "5m", "1d", 5, "minute"),
+            TimeRange::W1 => preset(back(8), "30m", "5d", 30, "minute"),
+            TimeRange::M1 => preset(back(32), "1d", "1mo", 1, "day"),
+            TimeRange::M3 => preset(back(95), "1d", "3mo", 1, "day"),
+            TimeRange::M6 => preset(back(185), "1d", "6mo", 1, "day"),
+            TimeRange::Ytd => {
+                let jan1 = NaiveDate::from_ymd_opt(today.year(), 1, 1).unwrap_or(today);
+                preset(jan1.format("%Y-%m-%d").to_string(), "1d", "ytd", 1, "day")
+            }
+            TimeRange::Y1 => preset(back(400), "1wk", "1y", 1, "week"),
+            TimeRange::Y5 => preset(back(1830), "1wk", "5y", 1, "week"),
+            TimeRange::Max => preset("1970-01-01".to_string(), "1mo", "max", 1, "month"),
+            TimeRange::Custom => custom
+                .unwrap_or(CustomRange {
+                    from: today - Duration::days(30),
+                    to: today,
+                })
+                .historical_params(today),
         }
     }
 }
 
+/// Parses the chart label (`1d`, `ytd`, `MAX`, …) case-insensitively; used by `stockterm history --range`.
+impl FromStr for TimeRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let t = s.trim();
+        TimeRange::PRESETS
+            .into_iter()
+            .find(|tr| tr.label().eq_ignore_ascii_case(t))
+            .ok_or_else(|| {
+                format!("unknown range {t:?} (expected 1d, 1w, 1m, 3m, 6m, ytd, 1y, 5y or max)")
+            })
+    }
+}
+
+/// User-entered calendar window for [`TimeRange::Custom`] (inclusive dates, `from <= to`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomRange {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+impl CustomRange {
+    /// Parse `YYYY-MM-DD` bounds; rejects reversed windows and dates after `today`.
+    pub fn parse(from: &str, to: &str, today: NaiveDate) -> Result<Self, String> {
+        let date = |s: &str, name: &str| {
+            NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d")
+                .map_err(|_| format!("{name} must be YYYY-MM-DD"))
+        };
+        Self::new(date(from, "From")?, date(to, "To")?, today)
+    }
+
+    /// Rejects reversed windows and windows starting after `today`.
+    pub fn new(from: NaiveDate, to: NaiveDate, today: NaiveDate) -> Result<Self, String> {
+        if from > to {
+            return Err("From must be on or before To".to_string());
+        }
+        if from > today {
+            return Err("From must not be in the future".to_string());
+        }
+        Ok(Self { from, to })
+    }
+
+    /// `from`/`to` go to the provider as-is; bar size follows the window length so long spans stay
+    /// within a few hundred bars. Yahoo only serves intraday bars for roughly the last 60 days, so
+    /// short windows starting before that (relative to `today`) get daily bars.
+    pub fn historical_params(self, today: NaiveDate) -> HistoricalQueryParams {
+        let days = (self.to - self.from).num_days();
+        let intraday = (today - self.from).num_days() <= INTRADAY_HORIZON_DAYS;
+        let (bar_interval, polygon_multiplier, polygon_timespan) = match days {
+            ..=2 if intraday => ("5m", 5, "minute"),
+            ..=10 if intraday => ("30m", 30, "minute"),
+            ..=370 => ("1d", 1, "day"),
+            371..=1900 => ("1wk", 1, "week"),
+            _ => ("1mo", 1, "month"),
+        };
+        HistoricalQueryParams {
+            from: self.from.format("%Y-%m-%d").to_string(),
+            to: self.to.format("%Y-%m-%d").to_string(),
+            bar_interval,
+            yahoo_range: None,
+            polygon_multiplier,
+            polygon_timespan,
+        }
+    }
+
+    pub fn label(self) -> String {
+        format!("{} → {}", self.from.format("%Y-%m-%d"), self.to.format("%Y-%m-%d"))
+    }
+}
+
 /// Owned parameters for a historical request; converted to [`crate::api::HistoricalQuery`] at the call site.
 #[derive(Debug, Clone)]
 pub struct HistoricalQueryParams {
@@ -90,9 +195,21 @@ pub struct HistoricalQueryParams {
     pub polygon_timespan: &'static str,
 }
 
+impl HistoricalQueryParams {
+    /// Bars shorter than a day (axis labels and readouts include the time).
+    pub fn is_intraday(&self) -> bool {
+        self.polygon_timespan == "minute"
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
 
     #[test]
     fn default_time_range_is_m1() {
@@ -102,11 +219,89 @@ mod tests {
     #[test]
     fn historical_params_non_empty_dates() {
         let now = Local::now();
-        for tr in [TimeRange::D1, TimeRange::W1, TimeRange::M1, TimeRange::Y1] {
-            let p = tr.historical_params(now);
+        for tr in TimeRange::PRESETS {
+            let p = tr.historical_params(now, None);
             assert!(!p.from.is_empty());
             assert!(!p.to.is_empty());
             assert!(p.yahoo_range.is_some());
         }
     }
+
+    #[test]
+    fn extended_presets_map_to_provider_windows() {
+        let now = Local.with_ymd_and_hms(2026, 3, 15, 12, 0, 0).unwrap();
+        let ytd = TimeRange::Ytd.historical_params(now, None);
+        assert_eq!(ytd.from, "2026-01-01");
+        assert_eq!(ytd.yahoo_range, Some("ytd"));
+        assert_eq!((ytd.polygon_multiplier, ytd.polygon_timespan), (1, "day"));
+        let m3 = TimeRange::M3.historical_params(now, None);
+        assert_eq!((m3.yahoo_range, m3.bar_interval), (Some("3mo"), "1d"));
+        let y5 = TimeRange::Y5.historical_params(now, None);
+        assert_eq!((y5.yahoo_range, y5.polygon_timespan), (Some("5y"), "week"));
+        let max = TimeRange::Max.historical_params(now, None);
+        assert_eq!((max.yahoo_range, max.bar_interval), (Some("max"), "1mo"));
+        assert_eq!(max.polygon_timespan, "month");
+    }
+
+    #[test]
+    fn custom_range_uses_calendar_window_and_span_interval() {
+        let now = Local.with_ymd_and_hms(2026, 3, 15, 12, 0, 0).unwrap();
+        let custom = CustomRange {
+            from: date("2025-01-02"),
+            to: date("2025-03-31"),
+        };
+        let p = TimeRange::Custom.historical_params(now, Some(custom));
+        assert_eq!((p.from.as_str(), p.to.as_str()), ("2025-01-02", "2025-03-31"));
+        assert_eq!(p.yahoo_range, None);
+        assert_eq!(p.bar_interval, "1d");
+        let short = CustomRange {
+            from: date("2026-03-10"),
+            to: date("2026-03-11"),
+        };
+        assert!(short.historical_params(now.date_naive()).is_intraday());
+        let fallback = TimeRange::Custom.historical_params(now, None);
+        assert_eq!(fallback.from, "2026-02-13");
+    }
+
+    #[test]
+    fn short_custom_range_past_the_intraday_horizon_uses_daily_bars() {
+        let today = date("2026-03-15");
+        let recent = CustomRange {
+            from: date("2026-01-15"),
+            to: date("2026-01-20"),
+        };
+        assert_eq!(recent.historical_params(today).bar_interval, "30m");
+        let old = CustomRange {
+            from: date("2025-06-02"),
+            to: date("2025-06-03"),
+        };
+        let p = old.historical_params(today);
+        assert!(!p.is_intraday());
+        assert_eq!((p.bar_interval, p.polygon_timespan), ("1d", "day"));
+    }
+
+    #[test]
+    fn custom_range_parse_validates_order_and_format() {
+        let today = date("2026-03-15");
+        assert_eq!(
+            CustomRange::parse("2026-01-01", " 2026-02-01 ", today),
+            Ok(CustomRange {
+                from: date("2026-01-01"),
+                to: date("2026-02-01"),
+            })
+        );
+        assert!(CustomRange::parse("2026-02-01", "2026-01-01", today).is_err());
+        assert!(CustomRange::parse("2026/01/01", "2026-02-01", today).is_err());
+        assert!(CustomRange::parse("2026-04-01", "2026-05-01", today).is_err());
+    }
+
+    #[test]
+    fn from_str_accepts_labels_case_insensitively() {
+        assert_eq!("1y".parse::<TimeRange>(), Ok(TimeRange::Y1));
+        assert_eq!(" 1W ".parse::<TimeRange>(), Ok(TimeRange::W1));
+        assert_eq!("ytd".parse::<TimeRange>(), Ok(TimeRange::Ytd));
+        assert_eq!("Max".parse::<TimeRange>(), Ok(TimeRange::Max));
+        assert!("2y".parse::<TimeRange>().is_err());
+        assert!("custom".parse::<TimeRange>().is_err());
+    }
 }
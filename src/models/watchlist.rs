@@ -0,0 +1,208 @@
+//! Named Stock View watchlists ("Tech", "Energy", "Earnings this week", …) and their table
+//! columns / sort order.
+//!
+//! Only the active list is shown and polled; the others keep their symbols in config until
+//! selected with `[` / `]`.
+
+use crate::models::ticker::TickerResult;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// List created for configs without `watchlists` (and for the legacy flat `watchlist` array).
+pub const DEFAULT_WATCHLIST_NAME: &str = "Main";
+
+/// Longest accepted watchlist name (characters).
+pub const MAX_WATCHLIST_NAME_LEN: usize = 32;
+
+/// One named list of uppercase tickers.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Watchlist {
+    pub name: String,
+    #[serde(default)]
+    pub symbols: Vec<String>,
+}
+
+impl Watchlist {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            symbols: Vec::new(),
+        }
+    }
+}
+
+/// Trims `name` and checks it against `existing` (case-insensitive); `skip` is the index being
+/// renamed, which may keep its own name.
+pub fn validate_watchlist_name(
+    name: &str,
+    existing: &[Watchlist],
+    skip: Option<usize>,
+) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Name cannot be empty".to_string());
+    }
+    if name.chars().count() > MAX_WATCHLIST_NAME_LEN {
+        return Err(format!("Name is longer than {MAX_WATCHLIST_NAME_LEN} characters"));
+    }
+    let taken = existing
+        .iter()
+        .enumerate()
+        .any(|(i, w)| Some(i) != skip && w.name.eq_ignore_ascii_case(name));
+    if taken {
+        return Err(format!("A watchlist named {name:?} already exists"));
+    }
+    Ok(name.to_string())
+}
+
+/// One Stock View watchlist table column (`layout.watchlist_columns`).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchlistColumn {
+    /// Always shown first, whether or not it is listed.
+    Symbol,
+    Last,
+    Change,
+    ChangePercent,
+    Volume,
+    DayRange,
+    Week52Range,
+    MarketCap,
+    PrevClose,
+    ExtHours,
+}
+
+impl WatchlistColumn {
+    /// Columns used when `layout.watchlist_columns` is omitted or empty.
+    pub const DEFAULT: [WatchlistColumn; 6] = [
+        WatchlistColumn::Symbol,
+        WatchlistColumn::Last,
+        WatchlistColumn::Change,
+        WatchlistColumn::ChangePercent,
+        WatchlistColumn::Volume,
+        WatchlistColumn::ExtHours,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            WatchlistColumn::Symbol => "Symbol",
+            WatchlistColumn::Last => "Last",
+            WatchlistColumn::Change => "Change",
+            WatchlistColumn::ChangePercent => "%Chg",
+            WatchlistColumn::Volume => "Volume",
+            WatchlistColumn::DayRange => "Day Range",
+            WatchlistColumn::Week52Range => "52W Range",
+            WatchlistColumn::MarketCap => "Mkt Cap",
+            WatchlistColumn::PrevClose => "Prev Close",
+            WatchlistColumn::ExtHours => "Ext Hours",
+        }
+    }
+
+    /// Numeric sort key from the latest bar. Range columns sort by where the last price sits in
+    /// the range (near the low first). `None` for [`Symbol`](Self::Symbol) and missing data.
+    fn sort_value(self, bar: &TickerResult) -> Option<f64> {
+        match self {
+            WatchlistColumn::Symbol => None,
+            WatchlistColumn::Last => Some(bar.c),
+            WatchlistColumn::Change => Some(bar.day_change()),
+            WatchlistColumn::ChangePercent => Some(bar.day_change_percent()),
+            WatchlistColumn::Volume => Some(bar.v),
+            WatchlistColumn::DayRange => bar.day_range_position(),
+            WatchlistColumn::Week52Range => bar.week52_position(),
+            WatchlistColumn::MarketCap => bar.market_cap,
+            WatchlistColumn::PrevClose => bar.prev_close,
+            WatchlistColumn::ExtHours => bar.extended_change().map(|(_, pct)| pct),
+        }
+    }
+}
+
+/// Persisted Stock View sort (`watchlist_sort`); `None` in config keeps insertion order.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchlistSort {
+    pub column: WatchlistColumn,
+    #[serde(default)]
+    pub descending: bool,
+}
+
+/// Backing indices of `symbols` in `sort` order. Rows without a value (no quote yet, no 52-week
+/// data, …) go last in either direction; ties keep insertion order.
+pub fn sorted_watchlist_indices<'a>(
+    symbols: &[String],
+    sort: Option<WatchlistSort>,
+    mut bar_for: impl FnMut(&str) -> Option<&'a TickerResult>,
+) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..symbols.len()).collect();
+    let Some(sort) = sort else {
+        return order;
+    };
+    let directed = |o: Ordering| if sort.descending { o.reverse() } else { o };
+    if sort.column == WatchlistColumn::Symbol {
+        order.sort_by(|&a, &b| directed(symbols[a].cmp(&symbols[b])));
+        return order;
+    }
+    let values: Vec<Option<f64>> = symbols
+        .iter()
+        .map(|s| bar_for(s).and_then(|bar| sort.column.sort_value(bar)))
+        .collect();
+    order.sort_by(|&a, &b| match (values[a], values[b]) {
+        (Some(x), Some(y)) => directed(x.total_cmp(&y)),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    });
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn watchlist_names_are_trimmed_and_unique() {
+        let lists = vec![Watchlist::new("Tech"), Watchlist::new("Energy")];
+        assert_eq!(validate_watchlist_name("  Banks ", &lists, None), Ok("Banks".to_string()));
+        assert!(validate_watchlist_name("   ", &lists, None).is_err());
+        assert!(validate_watchlist_name("tech", &lists, None).is_err());
+        assert_eq!(validate_watchlist_name("TECH", &lists, Some(0)), Ok("TECH".to_string()));
+        assert!(validate_watchlist_name(&"x".repeat(33), &lists, None).is_err());
+    }
+
+    #[test]
+    fn sort_puts_missing_quotes_last_in_both_directions() {
+        let bar = |c: f64| TickerResult {
+            o: c,
+            h: c,
+            l: c,
+            c,
+            v: 0.0,
+            t: 1,
+            prev_close: None,
+            market_state: None,
+            ext_price: None,
+            week52_low: None,
+            week52_high: None,
+            market_cap: None,
+            currency: None,
+        };
+        let symbols: Vec<String> = ["MSFT", "AAPL", "NEW", "XOM"].map(String::from).into();
+        let bars = [bar(400.0), bar(190.0), bar(0.0), bar(110.0)];
+        let lookup = |s: &str| match s {
+            "NEW" => None,
+            _ => symbols.iter().position(|x| x == s).map(|i| &bars[i]),
+        };
+        let by_last = |descending| {
+            Some(WatchlistSort {
+                column: WatchlistColumn::Last,
+                descending,
+            })
+        };
+        assert_eq!(sorted_watchlist_indices(&symbols, None, lookup), [0, 1, 2, 3]);
+        assert_eq!(sorted_watchlist_indices(&symbols, by_last(false), lookup), [3, 1, 0, 2]);
+        assert_eq!(sorted_watchlist_indices(&symbols, by_last(true), lookup), [0, 1, 3, 2]);
+        let by_symbol = Some(WatchlistSort {
+            column: WatchlistColumn::Symbol,
+            descending: false,
+        });
+        assert_eq!(sorted_watchlist_indices(&symbols, by_symbol, lookup), [1, 0, 2, 3]);
+    }
+}